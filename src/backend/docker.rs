@@ -0,0 +1,478 @@
+use super::{Backend, Kind, RawEntry};
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    ffi::{OsStr, OsString},
+    fs::File,
+    io::{self, BufReader, Error, ErrorKind, Read, Seek, SeekFrom},
+    path::{Component, Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Environment variable to override the program used to export images with, which is invoked
+/// like `docker save -o <file> <image>`; `podman` works as well.
+const DOCKER_VAR: &str = "ERDTREE_DOCKER";
+
+/// Program used to export images unless [DOCKER_VAR] is set.
+const DOCKER: &str = "docker";
+
+/// Size of the blocks that make up tarballs.
+const BLOCK: u64 = 512;
+
+/// Size of the header preceding each entry of a tarball, which is a single block.
+const HEADER_LEN: usize = 512;
+
+/// Prefix of the names of files that mark a file of a lower layer as deleted.
+const WHITEOUT: &str = ".wh.";
+
+/// Name of the file that marks all of the entries lower layers put in its directory as deleted.
+const OPAQUE_WHITEOUT: &str = ".wh..wh..opq";
+
+/// Instructions that created layers are truncated to this many characters.
+const MAX_INSTRUCTION_LEN: usize = 60;
+
+/// Magic numbers of compressed layers, which `docker save` doesn't produce.
+const COMPRESSED_MAGIC: [&[u8]; 2] = [&[0x1f, 0x8b], &[0x28, 0xb5, 0x2f, 0xfd]];
+
+/// An image as described by the `manifest.json` of `docker save`.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Manifest {
+    config: String,
+    layers: Vec<String>,
+}
+
+/// The parts of the configuration of an image that tell how its layers were created.
+#[derive(Default, Deserialize)]
+struct Config {
+    #[serde(default)]
+    history: Vec<History>,
+}
+
+#[derive(Deserialize)]
+struct History {
+    #[serde(default)]
+    created_by: String,
+    #[serde(default)]
+    empty_layer: bool,
+}
+
+/// An entry of a tarball, with the offset of its contents from the start of the file.
+struct TarEntry {
+    path: String,
+    kind: u8,
+    size: u64,
+    link: String,
+    offset: u64,
+}
+
+/// An entry of the filesystem of the image, along with the index of the layer it's from.
+struct Item {
+    kind: Kind,
+    size: u64,
+    layer: usize,
+}
+
+/// Path and link target overrides of the next entry of a tarball, from GNU and PAX extensions.
+#[derive(Default)]
+struct Overrides {
+    path: Option<String>,
+    link: Option<String>,
+    size: Option<u64>,
+}
+
+/// A container image whose layers are merged into a single filesystem as a container would see
+/// it. Files are annotated with the layer that last put them there along with the instruction that
+/// created it.
+pub struct Image {
+    image: String,
+}
+
+impl Image {
+    pub fn new(image: &str) -> Self {
+        Self {
+            image: image.to_owned(),
+        }
+    }
+}
+
+impl Backend for Image {
+    fn label(&self) -> String {
+        self.image.clone()
+    }
+
+    fn walk(&self) -> io::Result<Box<dyn Iterator<Item = RawEntry> + '_>> {
+        // The export is written to a directory only accessible to the user so that no one else
+        // can have it written anywhere else by planting a symlink in its place.
+        let dir = tempfile::Builder::new()
+            .prefix("erdtree-docker-")
+            .tempdir()?;
+        let tarball = tempfile::Builder::new()
+            .suffix(".tar")
+            .tempfile_in(dir.path())?;
+
+        save(&self.image, tarball.path())?;
+
+        let merged = merge(&self.image, tarball.path());
+
+        // The export is as large as the image so it's removed as soon as it has been read.
+        drop(tarball);
+
+        let path = dir.path().to_owned();
+
+        if let Err(e) = dir.close() {
+            tracing::warn!(error = %e, path = %path.display(), "failed to remove export");
+        }
+
+        let (items, instructions) = merged?;
+
+        let entries = items.into_iter().map(move |(rel_path, item)| {
+            let Item { kind, size, layer } = item;
+
+            let note = (!matches!(kind, Kind::Directory)).then(|| {
+                instructions.get(layer).map_or_else(
+                    || format!("layer {}", layer + 1),
+                    |instruction| format!("layer {}: {instruction}", layer + 1),
+                )
+            });
+
+            RawEntry {
+                kind,
+                size,
+                rel_path,
+                note,
+            }
+        });
+
+        Ok(Box::new(entries))
+    }
+}
+
+/// Merges the layers of the export of `image` at `tarball`, returning the resulting entries by
+/// path along with the instruction that created each layer, if known.
+fn merge(image: &str, tarball: &Path) -> io::Result<(BTreeMap<PathBuf, Item>, Vec<String>)> {
+    let mut reader = BufReader::new(File::open(tarball)?);
+    let len = reader.get_ref().metadata()?.len();
+
+    let index = read_tar(&mut reader, 0, len)?
+        .into_iter()
+        .map(|entry| (normalize(&entry.path), entry))
+        .collect::<HashMap<_, _>>();
+
+    let member = |name: &str| {
+        index.get(&normalize(name)).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("{name} is missing from the export of {image}"),
+            )
+        })
+    };
+
+    let manifest = read_json::<Vec<Manifest>>(&mut reader, member("manifest.json")?)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Image manifest is empty"))?;
+
+    // The configuration is only needed for the instructions, so don't fail over it.
+    let config = member(&manifest.config)
+        .and_then(|entry| read_json::<Config>(&mut reader, entry))
+        .unwrap_or_default();
+
+    let instructions = instructions(&config, manifest.layers.len());
+
+    let mut items = BTreeMap::new();
+
+    for (layer, name) in manifest.layers.iter().enumerate() {
+        let tar = member(name)?;
+        merge_layer(&mut reader, tar, layer, &mut items)?;
+    }
+
+    Ok((items, instructions))
+}
+
+/// Exports `image` to `tarball`.
+fn save(image: &str, tarball: &Path) -> io::Result<()> {
+    let docker = env::var_os(DOCKER_VAR).unwrap_or_else(|| OsString::from(DOCKER));
+
+    let status = Command::new(&docker)
+        .arg("save")
+        .arg("-o")
+        .arg(tarball)
+        .arg(image)
+        .stdin(Stdio::null())
+        .status()
+        .map_err(|e| {
+            Error::new(
+                e.kind(),
+                format!("Failed to run {}: {e}", docker.to_string_lossy()),
+            )
+        })?;
+
+    if !status.success() {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("Failed to export {image}: {status}"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// The instruction that created each layer, if the history of the image accounts for all of them.
+/// Instructions that didn't create a layer, such as `ENV`, are left out.
+fn instructions(config: &Config, layers: usize) -> Vec<String> {
+    let instructions = config
+        .history
+        .iter()
+        .filter(|history| !history.empty_layer)
+        .map(|history| shorten(&history.created_by))
+        .collect::<Vec<_>>();
+
+    if instructions.len() == layers {
+        instructions
+    } else {
+        vec![]
+    }
+}
+
+/// Strips the shell builders prefix instructions with, along with the marker BuildKit suffixes
+/// them with, and truncates them. The classic builder only records the command of `RUN`.
+fn shorten(created_by: &str) -> String {
+    let created_by = created_by.trim().trim_end_matches("# buildkit").trim();
+
+    let instruction = created_by.strip_prefix("/bin/sh -c #(nop)").map_or_else(
+        || {
+            created_by
+                .strip_prefix("/bin/sh -c ")
+                .or_else(|| created_by.strip_prefix("RUN /bin/sh -c "))
+                .map_or_else(
+                    || created_by.to_owned(),
+                    |cmd| format!("RUN {}", cmd.trim()),
+                )
+        },
+        |nop| nop.trim().to_owned(),
+    );
+
+    if instruction.chars().count() <= MAX_INSTRUCTION_LEN {
+        return instruction;
+    }
+
+    let truncated = instruction
+        .chars()
+        .take(MAX_INSTRUCTION_LEN - 1)
+        .collect::<String>();
+
+    format!("{truncated}\u{2026}")
+}
+
+/// Applies the entries of the layer stored as `tar` to `items`, which holds the entries of the
+/// layers beneath it, honoring whiteouts.
+fn merge_layer<R: Read + Seek>(
+    reader: &mut R,
+    tar: &TarEntry,
+    layer: usize,
+    items: &mut BTreeMap<PathBuf, Item>,
+) -> io::Result<()> {
+    let mut magic = [0; 4];
+    reader.seek(SeekFrom::Start(tar.offset))?;
+    reader.read_exact(&mut magic)?;
+
+    if COMPRESSED_MAGIC.iter().any(|m| magic.starts_with(m)) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Layer {} is compressed which isn't supported", tar.path),
+        ));
+    }
+
+    for entry in read_tar(reader, tar.offset, tar.offset + tar.size)? {
+        let path = normalize(&entry.path);
+
+        let Some(name) = path.file_name().and_then(OsStr::to_str) else {
+            continue;
+        };
+
+        if name == OPAQUE_WHITEOUT {
+            let dir = path.parent().unwrap_or_else(|| Path::new(""));
+            remove_descendants(items, dir, |item| item.layer < layer);
+            continue;
+        }
+
+        if let Some(deleted) = name.strip_prefix(WHITEOUT) {
+            let deleted = path.with_file_name(deleted);
+            remove_descendants(items, &deleted, |_| true);
+            items.remove(&deleted);
+            continue;
+        }
+
+        let (kind, size) = match entry.kind {
+            b'5' => (Kind::Directory, 0),
+            b'2' => (Kind::Symlink(OsString::from(entry.link)), 0),
+            b'0' | b'7' | 0 => (Kind::File, entry.size),
+
+            // Hardlinks, devices, and FIFOs take up no space of their own.
+            _ => (Kind::File, 0),
+        };
+
+        items.insert(path, Item { kind, size, layer });
+    }
+
+    Ok(())
+}
+
+/// Removes the entries beneath `dir` from `items` that satisfy `predicate`.
+fn remove_descendants(
+    items: &mut BTreeMap<PathBuf, Item>,
+    dir: &Path,
+    predicate: impl Fn(&Item) -> bool,
+) {
+    // Paths are ordered by component so that descendants directly follow their ancestor.
+    let descendants = items
+        .range(dir.to_path_buf()..)
+        .skip_while(|(path, _)| path.as_path() == dir)
+        .take_while(|(path, _)| path.starts_with(dir))
+        .filter(|(_, item)| predicate(item))
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<_>>();
+
+    for path in descendants {
+        items.remove(&path);
+    }
+}
+
+/// Reads the headers of the tarball stored between `start` and `end` of `reader`.
+fn read_tar<R: Read + Seek>(reader: &mut R, start: u64, end: u64) -> io::Result<Vec<TarEntry>> {
+    let mut entries = vec![];
+    let mut overrides = Overrides::default();
+    let mut header = [0; HEADER_LEN];
+    let mut pos = start;
+
+    while pos + BLOCK <= end {
+        reader.seek(SeekFrom::Start(pos))?;
+        reader.read_exact(&mut header)?;
+
+        // Archives end with blocks of zeroes.
+        if header.iter().all(|byte| *byte == 0) {
+            break;
+        }
+
+        let kind = header[156];
+        let offset = pos + BLOCK;
+        let size = overrides.size.take().unwrap_or(number(&header[124..136])?);
+
+        pos = offset + (size + BLOCK - 1) / BLOCK * BLOCK;
+
+        match kind {
+            b'L' => overrides.path = Some(read_string(reader, offset, size)?),
+            b'K' => overrides.link = Some(read_string(reader, offset, size)?),
+            b'x' => read_pax(&read_string(reader, offset, size)?, &mut overrides),
+            b'g' => (),
+            _ => entries.push(TarEntry {
+                path: overrides.path.take().unwrap_or_else(|| ustar_path(&header)),
+                kind,
+                size,
+                link: overrides
+                    .link
+                    .take()
+                    .unwrap_or_else(|| string(&header[157..257])),
+                offset,
+            }),
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Path of the entry of `header`, joined with its prefix in the ustar format.
+fn ustar_path(header: &[u8]) -> String {
+    let name = string(&header[..100]);
+
+    if &header[257..262] != b"ustar" {
+        return name;
+    }
+
+    let prefix = string(&header[345..500]);
+
+    if prefix.is_empty() {
+        name
+    } else {
+        format!("{prefix}/{name}")
+    }
+}
+
+/// Applies the records of a PAX extended header, being `<length> <key>=<value>\n` each.
+fn read_pax(records: &str, overrides: &mut Overrides) {
+    for record in records.lines() {
+        let Some((key, value)) = record
+            .split_once(' ')
+            .and_then(|(_, record)| record.split_once('='))
+        else {
+            continue;
+        };
+
+        match key {
+            "path" => overrides.path = Some(value.to_owned()),
+            "linkpath" => overrides.link = Some(value.to_owned()),
+            "size" => overrides.size = value.parse().ok(),
+            _ => (),
+        }
+    }
+}
+
+/// Parses a numeric field of a header, which is octal unless its high bit is set in which case
+/// it's big-endian binary.
+fn number(field: &[u8]) -> io::Result<u64> {
+    if field[0] & 0x80 != 0 {
+        let bytes = field[1..]
+            .iter()
+            .fold(0_u64, |n, byte| (n << 8) | u64::from(*byte));
+
+        return Ok(bytes);
+    }
+
+    let digits = string(field);
+    let digits = digits.trim();
+
+    if digits.is_empty() {
+        return Ok(0);
+    }
+
+    u64::from_str_radix(digits, 8).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+/// Field of a header up to the first NUL.
+fn string(field: &[u8]) -> String {
+    let len = field
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..len]).into_owned()
+}
+
+/// Contents of the entry of a tarball at `offset`, sans trailing NULs.
+fn read_string<R: Read + Seek>(reader: &mut R, offset: u64, size: u64) -> io::Result<String> {
+    let mut contents = vec![0; usize::try_from(size).unwrap_or(usize::MAX)];
+
+    reader.seek(SeekFrom::Start(offset))?;
+    reader.read_exact(&mut contents)?;
+
+    Ok(string(&contents))
+}
+
+/// Parses the JSON stored as `entry`.
+fn read_json<T: for<'de> Deserialize<'de>>(
+    reader: &mut (impl Read + Seek),
+    entry: &TarEntry,
+) -> io::Result<T> {
+    let contents = read_string(reader, entry.offset, entry.size)?;
+
+    serde_json::from_str(&contents).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+/// Drops `.` components as well as any that would escape the root, and leading slashes.
+fn normalize(path: &str) -> PathBuf {
+    Path::new(path)
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect()
+}
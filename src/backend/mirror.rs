@@ -0,0 +1,151 @@
+use super::{Backend, Kind, RawEntry};
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    fs::{self, File},
+    io,
+    path::{Component, Path, PathBuf},
+};
+use tempfile::TempDir;
+
+/// Local stand-in for the entries of a [Backend], consisting of empty directories, sparse files as
+/// large as the entries they stand for, and symlinks. It occupies next to no disk space and is
+/// removed once dropped.
+pub struct Mirror {
+    dir: Option<TempDir>,
+    root: PathBuf,
+    annotations: Option<PathBuf>,
+}
+
+impl Mirror {
+    /// Reconstructs the entries of `backend` in a temporary directory. Entries that can't be
+    /// created, such as files whose paths clash with directories implied by others, are skipped
+    /// with a warning. Symlinks are created last and nothing is created beneath one so that a
+    /// listing can't have entries written through them to anywhere outside of the mirror.
+    pub fn of(backend: &dyn Backend) -> io::Result<Self> {
+        let dir = tempfile::Builder::new()
+            .prefix("erdtree-mirror-")
+            .tempdir()?;
+        let root = dir.path().join("root");
+
+        // Constructed up front so that the directory is removed should listing fail.
+        let mut mirror = Self {
+            dir: Some(dir),
+            root,
+            annotations: None,
+        };
+
+        let (links, entries): (Vec<_>, Vec<_>) = backend
+            .walk()?
+            .partition(|entry| matches!(entry.kind, Kind::Symlink(_)));
+
+        let link_paths = links
+            .iter()
+            .map(|entry| entry.rel_path.as_path())
+            .collect::<HashSet<_>>();
+
+        let mut notes = HashMap::new();
+
+        for entry in entries.iter().chain(&links) {
+            if entry
+                .rel_path
+                .ancestors()
+                .skip(1)
+                .any(|ancestor| link_paths.contains(ancestor))
+            {
+                let path = entry.rel_path.display();
+                tracing::warn!(%path, "skipping entry beneath symlink of {}", backend.label());
+                continue;
+            }
+
+            if let Err(e) = mirror.create(entry) {
+                let path = entry.rel_path.display();
+                tracing::warn!(error = %e, %path, "skipping entry of {}", backend.label());
+                continue;
+            }
+
+            if let Some(ref note) = entry.note {
+                notes.insert(entry.rel_path.to_string_lossy().into_owned(), note.clone());
+            }
+        }
+
+        // An empty listing would leave nothing to traverse.
+        if !mirror.root.exists() {
+            fs::create_dir(&mirror.root)?;
+        }
+
+        if !notes.is_empty() {
+            let annotations = mirror.root.with_file_name("annotations.json");
+            fs::write(&annotations, serde_json::to_vec(&notes)?)?;
+            mirror.annotations = Some(annotations);
+        }
+
+        Ok(mirror)
+    }
+
+    /// Path to the local stand-in of the root of the [Backend].
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path to a file of the notes of entries in the format of `--annotate`, if there are any.
+    pub fn annotations(&self) -> Option<&Path> {
+        self.annotations.as_deref()
+    }
+
+    /// Creates the local stand-in of `entry`. Paths that would escape the root are skipped lest
+    /// the source write anywhere else.
+    fn create(&self, entry: &RawEntry) -> io::Result<()> {
+        let rel_path = entry.rel_path.as_path();
+
+        if !rel_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            tracing::warn!(path = %rel_path.display(), "skipping entry outside of root");
+            return Ok(());
+        }
+
+        // The root itself is listed with an empty relative path.
+        let path = if rel_path.as_os_str().is_empty() {
+            self.root.clone()
+        } else {
+            self.root.join(rel_path)
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        match entry.kind {
+            Kind::Directory => fs::create_dir_all(path),
+            Kind::Symlink(ref target) => symlink(target, &path),
+            Kind::File => File::create(path)?.set_len(entry.size),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn symlink(target: &OsString, path: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, path)
+}
+
+/// Creating symlinks requires privileges elsewhere so they're stood in for by empty files.
+#[cfg(not(unix))]
+fn symlink(_target: &OsString, path: &Path) -> io::Result<()> {
+    File::create(path).map(|_| ())
+}
+
+impl Drop for Mirror {
+    fn drop(&mut self) {
+        let Some(dir) = self.dir.take() else {
+            return;
+        };
+
+        let path = dir.path().to_owned();
+
+        if let Err(e) = dir.close() {
+            tracing::warn!(error = %e, dir = %path.display(), "failed to remove mirror");
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::render::context::Context;
+use std::{ffi::OsString, io, path::PathBuf};
+
+/// Merged filesystems of container images.
+pub mod docker;
+
+/// Local stand-ins for the entries of a [Backend].
+pub mod mirror;
+
+/// Listing of S3 buckets.
+pub mod s3;
+
+/// Directories on remote hosts reached over SSH.
+pub mod ssh;
+
+/// A source of entries other than the local filesystem, such as a remote host or an archive.
+/// Their entries are reconstructed locally by a [`mirror::Mirror`] which is then traversed like
+/// any other directory, so that every feature works the same regardless of where entries come
+/// from.
+///
+/// The local filesystem itself isn't a [Backend]: it's walked in parallel with every node built
+/// straight from the `DirEntry` and metadata of its entry, neither of which a [RawEntry] can
+/// carry. Sources whose listings can only be had as a whole are what this trait is for.
+pub trait Backend {
+    /// Text to display in place of the name of the root, such as the URL the source was provided
+    /// as.
+    fn label(&self) -> String;
+
+    /// Lists the entries of the source. The root itself may be listed with an empty relative path
+    /// in which case it can be a file; it's a directory otherwise. Ancestors needn't be listed
+    /// before their descendants, nor at all.
+    fn walk(&self) -> io::Result<Box<dyn Iterator<Item = RawEntry> + '_>>;
+}
+
+/// An entry as listed by a [Backend].
+pub struct RawEntry {
+    pub kind: Kind,
+
+    /// Logical size in bytes.
+    pub size: u64,
+
+    /// Path relative to the root of the source.
+    pub rel_path: PathBuf,
+
+    /// Note to display alongside the entry as with `--annotate`.
+    pub note: Option<String>,
+}
+
+/// Type of a [RawEntry]. Special files such as sockets are represented as empty files.
+pub enum Kind {
+    Directory,
+    File,
+    Symlink(OsString),
+}
+
+/// The [Backend] of the root provided to `ctx`, if it's not the local filesystem.
+pub fn of(ctx: &Context) -> Option<Box<dyn Backend>> {
+    if let Some(url) = ctx.object_store() {
+        return Some(Box::new(s3::Bucket::new(url)));
+    }
+
+    if let Some(ref remote) = ctx.remote {
+        return Some(Box::new(ssh::Remote::new(remote)));
+    }
+
+    ctx.docker
+        .as_deref()
+        .map(|image| Box::new(docker::Image::new(image)) as Box<dyn Backend>)
+}
@@ -0,0 +1,129 @@
+use super::{Backend, Kind, RawEntry};
+use serde::Deserialize;
+use std::{
+    env,
+    ffi::OsString,
+    io::{self, Error, ErrorKind},
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+/// Scheme of the URLs of S3 buckets.
+pub const SCHEME: &str = "s3://";
+
+/// Environment variable to override the AWS CLI with, which is invoked like
+/// `aws s3api list-objects-v2 --bucket <bucket> --prefix <prefix> --output json`.
+const AWS_VAR: &str = "ERDTREE_AWS";
+
+/// Program used to list buckets unless [AWS_VAR] is set.
+const AWS: &str = "aws";
+
+/// Output of `aws s3api list-objects-v2`, which the CLI merges across pages. It is empty for a
+/// prefix without objects.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Listing {
+    #[serde(default)]
+    contents: Vec<Object>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Object {
+    key: String,
+    size: u64,
+}
+
+/// An S3 bucket, provided as `s3://bucket[/prefix]`, whose objects are listed with the AWS CLI.
+/// The hierarchy is implied by the slashes in their keys. The prefix is treated like a directory
+/// so that e.g. `logs` doesn't also take in `logs-old/`.
+pub struct Bucket {
+    url: String,
+}
+
+impl Bucket {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_owned(),
+        }
+    }
+}
+
+impl Backend for Bucket {
+    fn label(&self) -> String {
+        self.url.clone()
+    }
+
+    fn walk(&self) -> io::Result<Box<dyn Iterator<Item = RawEntry> + '_>> {
+        let location = self.url.strip_prefix(SCHEME).unwrap_or(&self.url);
+        let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+        let prefix = prefix.trim_end_matches('/');
+        let prefix = if prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{prefix}/")
+        };
+
+        let listing = list(&self.url, bucket, &prefix)?;
+
+        let entries = listing
+            .contents
+            .into_iter()
+            .filter_map(move |Object { key, size }| {
+                // The placeholder of the prefix itself, if any, stands for the root.
+                let rel_path = key.strip_prefix(&prefix).filter(|rel| !rel.is_empty())?;
+
+                // Keys ending in a slash are placeholders for empty directories, as created by
+                // the console.
+                let (kind, rel_path) = rel_path
+                    .strip_suffix('/')
+                    .map_or((Kind::File, rel_path), |dir| (Kind::Directory, dir));
+
+                Some(RawEntry {
+                    kind,
+                    size,
+                    rel_path: PathBuf::from(rel_path),
+                    note: None,
+                })
+            });
+
+        Ok(Box::new(entries))
+    }
+}
+
+/// Lists the objects in `bucket` whose keys start with `prefix` using the AWS CLI, which takes care
+/// of credentials and pagination.
+fn list(url: &str, bucket: &str, prefix: &str) -> io::Result<Listing> {
+    let aws = env::var_os(AWS_VAR).unwrap_or_else(|| OsString::from(AWS));
+
+    let output = Command::new(&aws)
+        .args(["s3api", "list-objects-v2", "--bucket", bucket])
+        .args(["--prefix", prefix, "--output", "json"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| {
+            Error::new(
+                e.kind(),
+                format!("Failed to run {}: {e}", aws.to_string_lossy()),
+            )
+        })?;
+
+    if !output.status.success() {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("Failed to list {url}: {}", output.status),
+        ));
+    }
+
+    if output.stdout.iter().all(u8::is_ascii_whitespace) {
+        return Ok(Listing::default());
+    }
+
+    serde_json::from_slice(&output.stdout).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("Failed to parse the listing of {url}: {e}"),
+        )
+    })
+}
@@ -0,0 +1,135 @@
+use super::{Backend, Kind, RawEntry};
+use std::{
+    borrow::Cow,
+    env,
+    ffi::{OsStr, OsString},
+    io::{self, Error, ErrorKind},
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+/// Environment variable to override the program used to connect to remote hosts with, which is
+/// invoked like `ssh -- <host> <command>`.
+const SSH_VAR: &str = "ERDTREE_SSH";
+
+/// Program used to connect to remote hosts unless [SSH_VAR] is set.
+const SSH: &str = "ssh";
+
+/// Format of the entries printed by the remote `find`: type, size in bytes, target of symlinks,
+/// and path relative to the root, each terminated by a NUL.
+const FIND_FORMAT: &str = r"%y\0%s\0%l\0%P\0";
+
+/// A directory on a remote host, provided as `[user@]host[:path]`, that is listed with `find`
+/// over SSH. The path defaults to the home directory of the user.
+pub struct Remote {
+    remote: String,
+}
+
+impl Remote {
+    pub fn new(remote: &str) -> Self {
+        Self {
+            remote: remote.to_owned(),
+        }
+    }
+}
+
+impl Backend for Remote {
+    fn label(&self) -> String {
+        self.remote.clone()
+    }
+
+    fn walk(&self) -> io::Result<Box<dyn Iterator<Item = RawEntry> + '_>> {
+        let (host, path) = self.remote.split_once(':').unwrap_or((&self.remote, ""));
+
+        // A path that starts with a dash would otherwise be taken for an option by `find`.
+        let path = match path {
+            "" => Cow::from("."),
+            _ if path.starts_with('-') => Cow::from(format!("./{path}")),
+            _ => Cow::from(path),
+        };
+
+        let listing = list(host, &path)?;
+
+        let mut fields = listing.split(|byte| *byte == 0);
+        let mut entries = vec![];
+
+        while let Some(entry) = next(&mut fields) {
+            entries.push(entry);
+        }
+
+        Ok(Box::new(entries.into_iter()))
+    }
+}
+
+/// Reads the next entry from the NUL-separated fields of the listing.
+fn next<'a>(fields: &mut impl Iterator<Item = &'a [u8]>) -> Option<RawEntry> {
+    let kind = fields.next()?.first().copied()?;
+    let size = String::from_utf8_lossy(fields.next()?).parse().ok()?;
+    let target = os_string(fields.next()?);
+    let rel_path = PathBuf::from(os_string(fields.next()?));
+
+    let kind = match kind {
+        b'd' => Kind::Directory,
+        b'l' => Kind::Symlink(target),
+        _ => Kind::File,
+    };
+
+    Some(RawEntry {
+        kind,
+        size,
+        rel_path,
+        note: None,
+    })
+}
+
+/// Runs `find` on `host` within `path`, returning its output as per [FIND_FORMAT]. Entries that
+/// couldn't be read remotely, such as directories without permission, are left out with a
+/// warning as long as anything was listed at all.
+fn list(host: &str, path: &str) -> io::Result<Vec<u8>> {
+    let ssh = env::var_os(SSH_VAR).unwrap_or_else(|| OsString::from(SSH));
+
+    // The command is interpreted by the remote shell, hence the quoting.
+    let command = format!("find -H {} -printf '{FIND_FORMAT}'", quote(path));
+
+    let output = Command::new(&ssh)
+        .args(["--", host, &command])
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| {
+            Error::new(
+                e.kind(),
+                format!("Failed to run {}: {e}", ssh.to_string_lossy()),
+            )
+        })?;
+
+    if !output.status.success() {
+        if output.stdout.is_empty() {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("Failed to list {path} on {host}: {}", output.status),
+            ));
+        }
+
+        tracing::warn!(%host, %path, status = %output.status, "remote listing is incomplete");
+    }
+
+    Ok(output.stdout)
+}
+
+/// Quotes `arg` for a POSIX shell.
+fn quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+#[cfg(unix)]
+fn os_string(bytes: &[u8]) -> OsString {
+    use std::os::unix::ffi::OsStrExt;
+
+    OsStr::from_bytes(bytes).to_owned()
+}
+
+#[cfg(not(unix))]
+fn os_string(bytes: &[u8]) -> OsString {
+    OsString::from(String::from_utf8_lossy(bytes).into_owned())
+}
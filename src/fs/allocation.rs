@@ -0,0 +1,138 @@
+use std::{fs::Metadata, io, path::Path};
+
+/// Explains why a regular file occupies fewer bytes on disk than its apparent size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Allocation {
+    /// The file contains holes i.e. ranges that were never written to and aren't backed by disk.
+    Sparse,
+
+    /// The file is stored transparently compressed or otherwise shares storage with other files.
+    Compressed,
+
+    /// The file is a placeholder of a cloud sync provider such as OneDrive, iCloud Drive, or
+    /// Dropbox whose contents haven't been downloaded.
+    Placeholder,
+}
+
+impl Allocation {
+    /// Determines whether the file is a cloud placeholder, which unlike telling sparse files apart
+    /// from compressed ones takes nothing but its metadata.
+    pub fn placeholder(md: &Metadata) -> Option<Self> {
+        (md.is_file() && is_placeholder(md)).then_some(Self::Placeholder)
+    }
+
+    /// Determines the [Allocation] of a file whose allocated size is smaller than its apparent
+    /// size. Returns `None` if that isn't the case. If the platform can't tell whether the file
+    /// has holes it is assumed to be sparse. Cloud placeholders are detected regardless of what
+    /// the platform reports as allocated.
+    pub fn of(path: &Path, md: &Metadata) -> Option<Self> {
+        if !md.is_file() {
+            return None;
+        }
+
+        if is_placeholder(md) {
+            return Some(Self::Placeholder);
+        }
+
+        let allocated = allocated_bytes(md)?;
+
+        if allocated >= md.len() {
+            return None;
+        }
+
+        match has_holes(path, md.len()) {
+            // Small files whose contents are stored inline with their inode occupy no blocks.
+            Ok(false) if allocated == 0 => None,
+            Ok(false) => Some(Self::Compressed),
+            _ => Some(Self::Sparse),
+        }
+    }
+}
+
+/// Returns the number of bytes actually allocated on disk for a file as reported by the number of
+/// 512-byte blocks it occupies. Returns `None` on platforms that don't expose this.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_wraps)]
+pub fn allocated_bytes(md: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(md.blocks() * 512)
+}
+
+/// Returns the number of bytes actually allocated on disk for a file as reported by the number of
+/// 512-byte blocks it occupies. Returns `None` on platforms that don't expose this.
+#[cfg(not(unix))]
+pub fn allocated_bytes(_md: &Metadata) -> Option<u64> {
+    None
+}
+
+/// Whether the file is a placeholder whose contents are stored remotely and only downloaded when
+/// accessed, which Windows indicates with file attributes.
+#[cfg(windows)]
+fn is_placeholder(md: &Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    use windows_sys::Win32::Storage::FileSystem::{
+        FILE_ATTRIBUTE_OFFLINE, FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS, FILE_ATTRIBUTE_RECALL_ON_OPEN,
+    };
+
+    let remote = FILE_ATTRIBUTE_OFFLINE
+        | FILE_ATTRIBUTE_RECALL_ON_OPEN
+        | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS;
+
+    md.file_attributes() & remote != 0
+}
+
+/// Whether the file is a placeholder whose contents are stored remotely and only downloaded when
+/// accessed, which macOS indicates by marking the file as dataless.
+#[cfg(target_os = "macos")]
+fn is_placeholder(md: &Metadata) -> bool {
+    use std::os::macos::fs::MetadataExt;
+
+    /// `SF_DATALESS` from `sys/stat.h`.
+    const SF_DATALESS: u32 = 0x4000_0000;
+
+    md.st_flags() & SF_DATALESS != 0
+}
+
+/// Placeholders can't be told apart from regular files on this platform.
+#[cfg(not(any(windows, target_os = "macos")))]
+const fn is_placeholder(_md: &Metadata) -> bool {
+    false
+}
+
+/// Whether the file at `path` that is `len` bytes long contains any holes, determined by seeking
+/// to the first hole which is at `len` if there are none.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+))]
+fn has_holes(path: &Path, len: u64) -> io::Result<bool> {
+    use std::{fs::File, os::unix::io::AsRawFd};
+
+    let _handle = super::handles::acquire();
+    let file = File::open(path)?;
+
+    // SAFETY: `file` is an open file descriptor for the duration of the call.
+    let offset = unsafe { libc::lseek(file.as_raw_fd(), 0, libc::SEEK_HOLE) };
+
+    if offset < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(u64::try_from(offset).map_or(true, |offset| offset < len))
+}
+
+/// Holes can't be detected on this platform.
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+)))]
+fn has_holes(_path: &Path, _len: u64) -> io::Result<bool> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
@@ -0,0 +1,28 @@
+use std::path::Path;
+
+/// Extensions of directories that macOS presents as a single file.
+const BUNDLE_EXTENSIONS: [&str; 12] = [
+    "app",
+    "appex",
+    "bundle",
+    "framework",
+    "kext",
+    "musiclibrary",
+    "photoslibrary",
+    "plugin",
+    "prefPane",
+    "qlgenerator",
+    "saver",
+    "xpc",
+];
+
+/// Whether the directory at `path` is a bundle judging by its extension.
+pub fn is_bundle(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| {
+            BUNDLE_EXTENSIONS
+                .iter()
+                .any(|bundle_ext| bundle_ext.eq_ignore_ascii_case(ext))
+        })
+}
@@ -0,0 +1,208 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::{
+    fmt::{self, Display},
+    fs,
+    ops::AddAssign,
+    path::Path,
+};
+
+/// Number of lines of a source file or of all source files beneath a directory by kind.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct LineCount {
+    pub code: u64,
+    pub comments: u64,
+    pub blanks: u64,
+}
+
+/// Comment syntax of a programming language.
+struct Language {
+    extensions: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comments: &'static [(&'static str, &'static str)],
+}
+
+const C_STYLE_BLOCK: &[(&str, &str)] = &[("/*", "*/")];
+
+/// Languages whose source files are recognized by their extension.
+const LANGUAGES: &[Language] = &[
+    Language {
+        extensions: &[
+            "c", "h", "cc", "cpp", "cxx", "hpp", "hh", "cs", "go", "java", "js", "jsx", "mjs",
+            "cjs", "ts", "tsx", "kt", "kts", "rs", "scala", "swift", "dart", "zig",
+        ],
+        line_comments: &["//"],
+        block_comments: C_STYLE_BLOCK,
+    },
+    Language {
+        extensions: &["css", "scss", "less"],
+        line_comments: &[],
+        block_comments: C_STYLE_BLOCK,
+    },
+    Language {
+        extensions: &["php"],
+        line_comments: &["//", "#"],
+        block_comments: C_STYLE_BLOCK,
+    },
+    Language {
+        extensions: &[
+            "py", "rb", "sh", "bash", "zsh", "fish", "pl", "r", "toml", "yaml", "yml", "nix", "ex",
+            "exs", "cmake",
+        ],
+        line_comments: &["#"],
+        block_comments: &[],
+    },
+    Language {
+        extensions: &["lua"],
+        line_comments: &["--"],
+        block_comments: &[("--[[", "]]")],
+    },
+    Language {
+        extensions: &["hs"],
+        line_comments: &["--"],
+        block_comments: &[("{-", "-}")],
+    },
+    Language {
+        extensions: &["sql"],
+        line_comments: &["--"],
+        block_comments: C_STYLE_BLOCK,
+    },
+    Language {
+        extensions: &["html", "htm", "xml", "svg", "vue"],
+        line_comments: &[],
+        block_comments: &[("<!--", "-->")],
+    },
+    Language {
+        extensions: &["el", "clj", "lisp", "scm"],
+        line_comments: &[";"],
+        block_comments: &[],
+    },
+];
+
+/// Counts the lines of code, comments, and blanks of the file at `path`. Returns `None` if it
+/// isn't a source file of a recognized language or if it can't be read as UTF-8.
+pub fn count(path: &Path) -> Option<LineCount> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+
+    let language = LANGUAGES
+        .iter()
+        .find(|language| language.extensions.contains(&ext.as_str()))?;
+
+    let _handle = super::handles::acquire();
+    let source = fs::read_to_string(path).ok()?;
+
+    Some(language.count(&source))
+}
+
+impl Language {
+    /// Classifies each line of `source`. A line is a comment if it only holds a comment, and code
+    /// if it holds anything else that isn't whitespace. Comment markers within string literals
+    /// aren't accounted for.
+    fn count(&self, source: &str) -> LineCount {
+        let mut lines = LineCount::default();
+
+        // End marker of the block comment the current line is within, if any.
+        let mut block_end: Option<&str> = None;
+
+        for line in source.lines() {
+            let line = line.trim();
+
+            if let Some(end) = block_end {
+                lines.comments += 1;
+
+                if let Some(i) = line.find(end) {
+                    // Code following the end of the comment on the same line.
+                    let rest = line[i + end.len()..].trim();
+                    if !rest.is_empty() && !self.is_comment(rest) {
+                        lines.comments -= 1;
+                        lines.code += 1;
+                    }
+
+                    block_end = self.unterminated_block(rest);
+                }
+
+                continue;
+            }
+
+            if line.is_empty() {
+                lines.blanks += 1;
+            } else if self.is_comment(line) {
+                lines.comments += 1;
+            } else {
+                lines.code += 1;
+            }
+
+            block_end = self.unterminated_block(line);
+        }
+
+        lines
+    }
+
+    /// Whether `line` begins with a comment.
+    fn is_comment(&self, line: &str) -> bool {
+        self.line_comments
+            .iter()
+            .chain(self.block_comments.iter().map(|(start, _)| start))
+            .any(|start| line.starts_with(start))
+    }
+
+    /// If `line` opens a block comment that it doesn't close, returns the marker that closes it.
+    fn unterminated_block(&self, line: &str) -> Option<&'static str> {
+        // Anything following a line comment is commented out, block comment markers included.
+        let line = self
+            .line_comments
+            .iter()
+            .filter(|start| {
+                !self
+                    .block_comments
+                    .iter()
+                    .any(|(b, _)| b.starts_with(*start))
+            })
+            .filter_map(|start| line.find(start))
+            .min()
+            .map_or(line, |i| &line[..i]);
+
+        let (start, end, i) = self
+            .block_comments
+            .iter()
+            .filter_map(|(start, end)| line.find(start).map(|i| (start, end, i)))
+            .min_by_key(|(_, _, i)| *i)?;
+
+        let rest = &line[i + start.len()..];
+
+        let Some(j) = rest.find(end) else {
+            return Some(end);
+        };
+
+        // The comment may be closed on the same line only for another one to be opened.
+        self.unterminated_block(&rest[j + end.len()..])
+    }
+}
+
+impl AddAssign for LineCount {
+    fn add_assign(&mut self, rhs: Self) {
+        self.code += rhs.code;
+        self.comments += rhs.comments;
+        self.blanks += rhs.blanks;
+    }
+}
+
+impl Display for LineCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |count: u64, singular: &str, plural: &str| {
+            if count == 1 {
+                format!("{count} {singular}")
+            } else {
+                format!("{count} {plural}")
+            }
+        };
+
+        write!(
+            f,
+            "{}, {}, {}",
+            plural(self.code, "line of code", "lines of code"),
+            plural(self.comments, "comment", "comments"),
+            plural(self.blanks, "blank", "blanks")
+        )
+    }
+}
@@ -0,0 +1,92 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    ops::AddAssign,
+    path::Path,
+};
+
+/// Number of bytes in each sample of a file that is compressed to estimate how well it compresses.
+const SAMPLE_LEN: u64 = 64 * 1024;
+
+/// Number of samples taken of files too large to be compressed in full, spread evenly from the
+/// start to the end of the file.
+const SAMPLES: u64 = 4;
+
+/// Level of zstd that samples are compressed at, being one of the fastest.
+const ZSTD_LEVEL: i32 = 1;
+
+/// Size of a file or of all files beneath a directory along with how large they're estimated to be
+/// once compressed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct Compressibility {
+    pub bytes: u64,
+    pub compressed_bytes: u64,
+}
+
+impl Compressibility {
+    /// Estimates how large the regular file at `path`, which is `len` bytes long, would be once
+    /// compressed by compressing samples of it with zstd.
+    pub fn of(path: &Path, len: u64) -> io::Result<Self> {
+        let _handle = super::handles::acquire();
+
+        let mut file = File::open(path)?;
+
+        let offsets = if len <= SAMPLE_LEN * SAMPLES {
+            vec![0]
+        } else {
+            (0..SAMPLES)
+                .map(|i| (len - SAMPLE_LEN) * i / (SAMPLES - 1))
+                .collect()
+        };
+
+        let sample_len = if offsets.len() == 1 { len } else { SAMPLE_LEN };
+
+        let mut sampled = 0;
+        let mut compressed = 0;
+        let mut buf = Vec::new();
+
+        for offset in offsets {
+            buf.clear();
+
+            file.seek(SeekFrom::Start(offset))?;
+            (&mut file).take(sample_len).read_to_end(&mut buf)?;
+
+            sampled += buf.len() as u64;
+            compressed += zstd::bulk::compress(&buf, ZSTD_LEVEL)?.len() as u64;
+        }
+
+        // Incompressible samples grow slightly from framing, which storing them as is avoids, so
+        // estimates are capped at the original size.
+        let compressed_bytes = if sampled == 0 {
+            0
+        } else {
+            let estimate =
+                u128::from(len) * u128::from(compressed.min(sampled)) / u128::from(sampled);
+            u64::try_from(estimate).unwrap_or(len)
+        };
+
+        Ok(Self {
+            bytes: len,
+            compressed_bytes,
+        })
+    }
+
+    /// How many times smaller the files are once compressed. Files that are empty altogether
+    /// don't compress at all.
+    pub fn ratio(&self) -> f64 {
+        if self.compressed_bytes == 0 {
+            return 1.0;
+        }
+
+        self.bytes as f64 / self.compressed_bytes as f64
+    }
+}
+
+impl AddAssign for Compressibility {
+    fn add_assign(&mut self, rhs: Self) {
+        self.bytes += rhs.bytes;
+        self.compressed_bytes += rhs.compressed_bytes;
+    }
+}
@@ -0,0 +1,190 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+/// Number of bytes at the start of a file that are inspected to classify it, akin to `grep` and
+/// `git`.
+const SNIFF_LEN: u64 = 8192;
+
+/// Largest file in bytes that is considered small enough to be previewed with `--preview`.
+const PREVIEW_MAX_LEN: u64 = 64 * 1024;
+
+/// Number of spaces a tab is expanded to in a preview so that it lines up regardless of where the
+/// preview is indented to.
+const PREVIEW_TAB_WIDTH: usize = 4;
+
+/// Whether a file holds text or binary data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Content {
+    Text,
+    Binary,
+}
+
+/// Character encoding of a text file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16,
+    Latin1,
+}
+
+/// Which line breaks a text file uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+
+    /// Both LF and CRLF.
+    Mixed,
+
+    /// The file has no line breaks at all.
+    None,
+}
+
+/// Encoding and line endings of a text file as detected from its first block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextProfile {
+    pub encoding: Encoding,
+    pub line_ending: LineEnding,
+}
+
+impl Content {
+    /// Classifies the regular file at `path` as binary if its first block contains a null byte,
+    /// otherwise as text. Empty files are considered text.
+    pub fn of(path: &Path) -> io::Result<Self> {
+        let block = sniff(path)?;
+
+        if block.contains(&0) {
+            Ok(Self::Binary)
+        } else {
+            Ok(Self::Text)
+        }
+    }
+}
+
+impl TextProfile {
+    /// Detects the encoding and line endings of the regular file at `path` from its first block.
+    /// Returns `None` for binary files. Files starting with a UTF-16 byte order mark are text
+    /// despite the null bytes they're bound to contain, whereas text that isn't valid UTF-8 is
+    /// assumed to be Latin-1.
+    pub fn of(path: &Path) -> io::Result<Option<Self>> {
+        let block = sniff(path)?;
+
+        if let Some(body) = block
+            .strip_prefix(b"\xff\xfe")
+            .or_else(|| block.strip_prefix(b"\xfe\xff"))
+        {
+            let little_endian = block.starts_with(b"\xff\xfe");
+
+            let units = body
+                .chunks_exact(2)
+                .map(|pair| {
+                    if little_endian {
+                        u16::from_le_bytes([pair[0], pair[1]])
+                    } else {
+                        u16::from_be_bytes([pair[0], pair[1]])
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let line_ending = line_ending(&units, u16::from(b'\r'), u16::from(b'\n'));
+
+            return Ok(Some(Self {
+                encoding: Encoding::Utf16,
+                line_ending,
+            }));
+        }
+
+        if block.contains(&0) {
+            return Ok(None);
+        }
+
+        let encoding = match std::str::from_utf8(&block) {
+            Ok(_) => Encoding::Utf8,
+
+            // The block may end partway through a multi-byte character.
+            Err(e) if e.error_len().is_none() => Encoding::Utf8,
+            Err(_) => Encoding::Latin1,
+        };
+
+        Ok(Some(Self {
+            encoding,
+            line_ending: line_ending(&block, b'\r', b'\n'),
+        }))
+    }
+}
+
+/// The first `lines` lines of the regular file at `path` that is `len` bytes large, to be printed
+/// beneath it with `--preview`. Returns `None` for binary files and those larger than
+/// [PREVIEW_MAX_LEN]. Lines are read from the first block only, tabs are expanded, other control
+/// characters are left out so as not to mess up the terminal, and text that isn't valid UTF-8 is
+/// decoded lossily.
+pub fn preview(path: &Path, len: u64, lines: usize) -> io::Result<Option<Vec<String>>> {
+    if len > PREVIEW_MAX_LEN {
+        return Ok(None);
+    }
+
+    let block = sniff(path)?;
+
+    if block.contains(&0) {
+        return Ok(None);
+    }
+
+    let preview = String::from_utf8_lossy(&block)
+        .lines()
+        .take(lines)
+        .map(|line| {
+            line.chars()
+                .flat_map(|ch| match ch {
+                    '\t' => vec![' '; PREVIEW_TAB_WIDTH],
+                    ch if ch.is_control() => vec![],
+                    ch => vec![ch],
+                })
+                .collect::<String>()
+                .trim_end()
+                .to_owned()
+        })
+        .collect();
+
+    Ok(Some(preview))
+}
+
+/// Reads the first block of the file at `path`.
+fn sniff(path: &Path) -> io::Result<Vec<u8>> {
+    let _handle = super::handles::acquire();
+
+    let mut block = Vec::new();
+    File::open(path)?.take(SNIFF_LEN).read_to_end(&mut block)?;
+
+    Ok(block)
+}
+
+/// Determines which line breaks are used in `text` which is made up of code units of type `T`.
+fn line_ending<T: PartialEq + Copy>(text: &[T], cr: T, lf: T) -> LineEnding {
+    let mut lfs = 0;
+    let mut crlfs = 0;
+
+    for (i, unit) in text.iter().enumerate() {
+        if *unit != lf {
+            continue;
+        }
+
+        if i > 0 && text[i - 1] == cr {
+            crlfs += 1;
+        } else {
+            lfs += 1;
+        }
+    }
+
+    match (lfs, crlfs) {
+        (0, 0) => LineEnding::None,
+        (_, 0) => LineEnding::Lf,
+        (0, _) => LineEnding::Crlf,
+        _ => LineEnding::Mixed,
+    }
+}
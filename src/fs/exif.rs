@@ -0,0 +1,180 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+    time::SystemTime,
+};
+
+/// Extensions of image files that may carry EXIF metadata, compared case-insensitively.
+const EXTENSIONS: [&str; 11] = [
+    "jpg", "jpeg", "tif", "tiff", "dng", "nef", "cr2", "arw", "orf", "rw2", "pef",
+];
+
+/// Number of bytes read from the start of an image to find its EXIF metadata in, which cameras
+/// write well within.
+const READ_LEN: u64 = 64 * 1024;
+
+/// Tag of IFD0 pointing to the EXIF sub-IFD.
+const EXIF_IFD: u16 = 0x8769;
+
+/// Tag of the EXIF sub-IFD holding when the photo was taken.
+const DATE_TIME_ORIGINAL: u16 = 0x9003;
+
+/// Tag of the EXIF sub-IFD holding when the photo was digitized.
+const DATE_TIME_DIGITIZED: u16 = 0x9004;
+
+/// Tag of IFD0 holding when the image was last changed.
+const DATE_TIME: u16 = 0x0132;
+
+/// Gets when the image at `path` was taken as per its EXIF metadata. Returns `None` for files that
+/// aren't JPEG or TIFF-based images or that have no date in their metadata; dates are taken to be
+/// in UTC as EXIF doesn't reliably record the time zone.
+pub fn taken(path: &Path) -> Option<SystemTime> {
+    let is_image = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| {
+            EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        });
+
+    if !is_image {
+        return None;
+    }
+
+    let buf = read_head(path)
+        .map_err(|e| tracing::debug!(path = %path.display(), error = %e, "failed to read image"))
+        .ok()?;
+
+    tiff(&buf).and_then(date_time)
+}
+
+/// Reads up to [READ_LEN] bytes from the start of the file at `path`.
+fn read_head(path: &Path) -> io::Result<Vec<u8>> {
+    let _handle = super::handles::acquire();
+
+    let mut buf = Vec::new();
+    File::open(path)?.take(READ_LEN).read_to_end(&mut buf)?;
+
+    Ok(buf)
+}
+
+/// Locates the TIFF structure that holds the metadata, which makes up the whole of TIFF-based raw
+/// files and sits in the APP1 segment of JPEGs.
+fn tiff(buf: &[u8]) -> Option<&[u8]> {
+    if buf.starts_with(b"II*\0") || buf.starts_with(b"MM\0*") {
+        return Some(buf);
+    }
+
+    if !buf.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+
+    let mut pos = 2;
+
+    while let Some(&[0xFF, marker, hi, lo]) = buf.get(pos..pos + 4) {
+        // Start of scan is followed by image data rather than more segments.
+        if marker == 0xDA {
+            break;
+        }
+
+        let len = usize::from(u16::from_be_bytes([hi, lo]));
+        let segment = buf.get(pos + 4..pos + 2 + len)?;
+
+        if marker == 0xE1 {
+            if let Some(tiff) = segment.strip_prefix(b"Exif\0\0") {
+                return Some(tiff);
+            }
+        }
+
+        pos += 2 + len;
+    }
+
+    None
+}
+
+/// Reads the date the photo was taken from the TIFF structure, preferring when it was taken over
+/// when it was digitized over when it was last changed.
+fn date_time(tiff: &[u8]) -> Option<SystemTime> {
+    let reader = Reader::new(tiff)?;
+    let ifd0 = reader.u32(4)?;
+
+    let exif_ifd = reader
+        .entry(ifd0, EXIF_IFD)
+        .and_then(|entry| reader.u32(entry + 8));
+
+    let from_exif = |tag| exif_ifd.and_then(|ifd| reader.entry(ifd, tag));
+
+    from_exif(DATE_TIME_ORIGINAL)
+        .and_then(|entry| reader.date_time(entry))
+        .or_else(|| from_exif(DATE_TIME_DIGITIZED).and_then(|entry| reader.date_time(entry)))
+        .or_else(|| {
+            reader
+                .entry(ifd0, DATE_TIME)
+                .and_then(|entry| reader.date_time(entry))
+        })
+}
+
+/// Reads values out of a TIFF structure in its byte order, with offsets relative to its start.
+struct Reader<'a> {
+    buf: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Option<Self> {
+        let little_endian = match buf.get(..2)? {
+            b"II" => true,
+            b"MM" => false,
+            _ => return None,
+        };
+
+        Some(Self { buf, little_endian })
+    }
+
+    fn u16(&self, offset: usize) -> Option<u16> {
+        let bytes = self.buf.get(offset..offset + 2)?.try_into().ok()?;
+
+        Some(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, offset: usize) -> Option<usize> {
+        let bytes = self.buf.get(offset..offset + 4)?.try_into().ok()?;
+
+        let value = if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        };
+
+        usize::try_from(value).ok()
+    }
+
+    /// Offset of the 12-byte entry for `tag` in the IFD at `ifd`.
+    fn entry(&self, ifd: usize, tag: u16) -> Option<usize> {
+        let count = usize::from(self.u16(ifd)?);
+
+        (0..count)
+            .map(|i| ifd + 2 + i * 12)
+            .find(|&entry| self.u16(entry) == Some(tag))
+    }
+
+    /// Reads the ASCII value of the entry at `entry` as a date formatted `YYYY:MM:DD HH:MM:SS`.
+    fn date_time(&self, entry: usize) -> Option<SystemTime> {
+        // Dates are 20 bytes including the terminating NUL so never fit inline in the entry.
+        let offset = self.u32(entry + 8)?;
+        let raw = self.buf.get(offset..offset + 19)?;
+        let raw = std::str::from_utf8(raw).ok()?;
+
+        let mut numbers = raw.split([':', ' ']).map(|n| n.trim().parse::<u64>().ok());
+
+        let mut next = || numbers.next().flatten();
+
+        crate::utils::from_civil(next()?, next()?, next()?, next()?, next()?, next()?)
+    }
+}
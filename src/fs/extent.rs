@@ -0,0 +1,172 @@
+use std::{fs::Metadata, io, path::Path};
+
+/// A contiguous range of disk that a file shares with at least one other file, as is the case with
+/// files cloned via reflinks on copy-on-write filesystems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SharedExtent {
+    pub dev: u64,
+    pub physical: u64,
+    pub length: u64,
+}
+
+/// How many bytes of a regular file are stored in extents that are shared with other files, such
+/// as those of clones, and in extents that are encoded, such as those compressed by btrfs whose
+/// size on disk is nonetheless reported as if they weren't.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Storage {
+    pub shared: u64,
+    pub encoded: u64,
+}
+
+/// Determines the [Storage] of the regular file at `path`. Only supported on Linux by way of the
+/// `FIEMAP` ioctl; elsewhere nothing is ever reported as shared or encoded.
+#[cfg(target_os = "linux")]
+pub fn storage(path: &Path, md: &Metadata) -> io::Result<Storage> {
+    use std::fs::File;
+
+    if !md.is_file() {
+        return Ok(Storage::default());
+    }
+
+    let _handle = super::handles::acquire();
+    let file = File::open(path)?;
+
+    let storage =
+        fiemap::extents(&file)?
+            .into_iter()
+            .fold(Storage::default(), |mut storage, extent| {
+                if extent.fe_flags & fiemap::FIEMAP_EXTENT_SHARED != 0 {
+                    storage.shared += extent.fe_length;
+                }
+
+                if extent.fe_flags & fiemap::FIEMAP_EXTENT_ENCODED != 0 {
+                    storage.encoded += extent.fe_length;
+                }
+
+                storage
+            });
+
+    Ok(storage)
+}
+
+/// Determines the [Storage] of the regular file at `path`. Only supported on Linux by way of the
+/// `FIEMAP` ioctl; elsewhere nothing is ever reported as shared or encoded.
+#[cfg(not(target_os = "linux"))]
+#[allow(clippy::unnecessary_wraps)]
+pub fn storage(_path: &Path, _md: &Metadata) -> io::Result<Storage> {
+    Ok(Storage::default())
+}
+
+/// Collects the extents of the regular file at `path` that are shared with other files. Only
+/// supported on Linux by way of the `FIEMAP` ioctl; elsewhere no extents are ever reported.
+#[cfg(target_os = "linux")]
+pub fn shared_extents(path: &Path, md: &Metadata) -> io::Result<Vec<SharedExtent>> {
+    use std::{fs::File, os::unix::fs::MetadataExt};
+
+    if !md.is_file() {
+        return Ok(vec![]);
+    }
+
+    let _handle = super::handles::acquire();
+    let file = File::open(path)?;
+    let dev = md.dev();
+
+    let shared = fiemap::extents(&file)?
+        .into_iter()
+        .filter(|extent| extent.fe_flags & fiemap::FIEMAP_EXTENT_SHARED != 0)
+        .map(|extent| SharedExtent {
+            dev,
+            physical: extent.fe_physical,
+            length: extent.fe_length,
+        })
+        .collect();
+
+    Ok(shared)
+}
+
+/// Collects the extents of the regular file at `path` that are shared with other files. Only
+/// supported on Linux by way of the `FIEMAP` ioctl; elsewhere no extents are ever reported.
+#[cfg(not(target_os = "linux"))]
+#[allow(clippy::unnecessary_wraps)]
+pub fn shared_extents(_path: &Path, _md: &Metadata) -> io::Result<Vec<SharedExtent>> {
+    Ok(vec![])
+}
+
+/// Bindings to the `FIEMAP` ioctl described in `linux/fiemap.h`; field names mirror the header.
+#[cfg(target_os = "linux")]
+#[allow(clippy::struct_field_names)]
+mod fiemap {
+    use std::{fs::File, io, os::unix::io::AsRawFd};
+
+    const FS_IOC_FIEMAP: libc::c_ulong = 0xC020_660B;
+    const FIEMAP_FLAG_SYNC: u32 = 0x0001;
+    const FIEMAP_EXTENT_LAST: u32 = 0x0001;
+    pub const FIEMAP_EXTENT_ENCODED: u32 = 0x0008;
+    pub const FIEMAP_EXTENT_SHARED: u32 = 0x2000;
+
+    /// Number of extents requested per call.
+    const EXTENT_COUNT: u32 = 64;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    pub struct Extent {
+        pub fe_logical: u64,
+        pub fe_physical: u64,
+        pub fe_length: u64,
+        fe_reserved64: [u64; 2],
+        pub fe_flags: u32,
+        fe_reserved: [u32; 3],
+    }
+
+    #[repr(C)]
+    struct Request {
+        fm_start: u64,
+        fm_length: u64,
+        fm_flags: u32,
+        fm_mapped_extents: u32,
+        fm_extent_count: u32,
+        fm_reserved: u32,
+        fm_extents: [Extent; EXTENT_COUNT as usize],
+    }
+
+    /// Every extent of `file` in order of logical offset.
+    pub fn extents(file: &File) -> io::Result<Vec<Extent>> {
+        let mut extents = vec![];
+        let mut start = 0;
+
+        loop {
+            let mut request = Request {
+                fm_start: start,
+                fm_length: u64::MAX - start,
+                fm_flags: FIEMAP_FLAG_SYNC,
+                fm_mapped_extents: 0,
+                fm_extent_count: EXTENT_COUNT,
+                fm_reserved: 0,
+                fm_extents: [Extent::default(); EXTENT_COUNT as usize],
+            };
+
+            // SAFETY: `request` is laid out as `struct fiemap` followed by room for exactly
+            // `fm_extent_count` instances of `struct fiemap_extent`.
+            let res = unsafe { libc::ioctl(file.as_raw_fd(), FS_IOC_FIEMAP, &mut request) };
+
+            if res < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mapped = &request.fm_extents[..request.fm_mapped_extents as usize];
+
+            let Some(last) = mapped.last() else {
+                return Ok(extents);
+            };
+
+            let done = last.fe_flags & FIEMAP_EXTENT_LAST != 0;
+            start = last.fe_logical + last.fe_length;
+
+            extents.extend_from_slice(mapped);
+
+            if done {
+                return Ok(extents);
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::{self, BufRead, BufReader, Error, ErrorKind, Read},
+    path::{Component, Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+};
+
+/// Marks the lines of the output of `git log` that hold the author of the commit whose files
+/// follow.
+const AUTHOR_MARKER: char = '\0';
+
+/// The author of the most recent commit to touch each of the files in `wanted`, keyed by their
+/// paths relative to `root` which must be within a git repository. The history is read once from
+/// newest to oldest, and only until every tracked file has been accounted for. Authors are as per
+/// the mailmap of the repository, if any. Untracked files are left out.
+pub fn last_authors(
+    root: &Path,
+    wanted: &HashSet<PathBuf>,
+) -> io::Result<HashMap<PathBuf, String>> {
+    let mut authors = HashMap::new();
+
+    if wanted.is_empty() {
+        return Ok(authors);
+    }
+
+    // Untracked files never show up in the history which would otherwise be read to the end.
+    let tracked = git_output(root, &["ls-files", "-z"])?
+        .split(|byte| *byte == 0)
+        .map(|path| PathBuf::from(String::from_utf8_lossy(path).into_owned()))
+        .filter(|path| wanted.contains(path))
+        .collect::<HashSet<_>>();
+
+    if tracked.is_empty() {
+        return Ok(authors);
+    }
+
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["-c", "core.quotePath=false", "log", "--format=%x00%aN"])
+        .args(["--name-only", "--no-renames", "--relative", "--", "."])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| Error::new(e.kind(), format!("Failed to run git: {e}")))?;
+
+    // Read on its own thread so that git can't block on a full stderr pipe while stdout is read.
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let stderr = thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stderr.read_to_string(&mut buf);
+        buf
+    });
+
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let mut author = String::new();
+
+    for line in BufReader::new(stdout).split(b'\n') {
+        let line = String::from_utf8_lossy(&line?).into_owned();
+
+        if let Some(name) = line.strip_prefix(AUTHOR_MARKER) {
+            name.clone_into(&mut author);
+            continue;
+        }
+
+        let path = PathBuf::from(line);
+
+        if tracked.contains(&path) && !authors.contains_key(&path) {
+            authors.insert(path, author.clone());
+
+            if authors.len() == tracked.len() {
+                // The remainder of the history is of no interest.
+                let _ = child.kill();
+                break;
+            }
+        }
+    }
+
+    let status = child.wait()?;
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() && authors.len() < tracked.len() && !stderr.trim().is_empty() {
+        return Err(Error::new(ErrorKind::Other, stderr.trim().to_owned()));
+    }
+
+    Ok(authors)
+}
+
+/// Paths relative to `root`, which must be within a git repository, of the files in the index
+/// that are flagged skip-worktree and thus not checked out, which is what sparse-checkout does
+/// to the files its patterns exclude.
+pub fn skip_worktree(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let output = git_output(root, &["ls-files", "-v", "-z"])?;
+
+    // Entries are tagged by a letter followed by a space, `S` being that of skip-worktree.
+    let paths = output
+        .split(|byte| *byte == 0)
+        .filter_map(|entry| entry.strip_prefix(b"S "))
+        .map(|path| PathBuf::from(String::from_utf8_lossy(path).into_owned()))
+        .collect();
+
+    Ok(paths)
+}
+
+/// Standard output of git run with `args` in `root`, or its standard error if it failed.
+fn git_output(root: &Path, args: &[&str]) -> io::Result<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| Error::new(e.kind(), format!("Failed to run git: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::new(ErrorKind::Other, stderr.trim().to_owned()));
+    }
+
+    Ok(output.stdout)
+}
+
+/// Whether the directory at `path` is the working tree of a git submodule, which has a `.git`
+/// file pointing to the repository within the `.git/modules` directory of its superproject rather
+/// than a `.git` directory. Linked worktrees also have a `.git` file but it points elsewhere.
+pub fn is_submodule(path: &Path) -> bool {
+    let Ok(contents) = fs::read_to_string(path.join(".git")) else {
+        return false;
+    };
+
+    let Some(gitdir) = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))
+    else {
+        return false;
+    };
+
+    let components = Path::new(gitdir.trim())
+        .components()
+        .map(Component::as_os_str)
+        .collect::<Vec<_>>();
+
+    components
+        .windows(2)
+        .any(|pair| pair[0] == ".git" && pair[1] == "modules")
+}
@@ -0,0 +1,110 @@
+use once_cell::sync::OnceCell;
+use std::sync::{Condvar, Mutex};
+
+/// File descriptors set aside for everything other than inspecting files e.g. the handles of the
+/// directories being read and stdio.
+const RESERVED_HANDLES: u64 = 64;
+
+/// Limit on the number of files held open at once while inspecting them, set by [init].
+static HANDLES: OnceCell<Handles> = OnceCell::new();
+
+/// Counting semaphore over file handles.
+struct Handles {
+    available: Mutex<u64>,
+    released: Condvar,
+}
+
+/// A handle reserved by [acquire] that is given back once dropped.
+pub struct Handle(&'static Handles);
+
+/// Limits the number of files held open at once to `max`, or if `None` to what the process may
+/// have open less [RESERVED_HANDLES] if that is known. Must be called at most once.
+pub fn init(max: Option<u64>) {
+    let Some(max) = max.or_else(|| soft_limit().map(|n| n.saturating_sub(RESERVED_HANDLES).max(1)))
+    else {
+        return;
+    };
+
+    let handles = Handles {
+        available: Mutex::new(max),
+        released: Condvar::new(),
+    };
+
+    HANDLES
+        .set(handles)
+        .unwrap_or_else(|_| panic!("File handle limit already initialized"));
+}
+
+/// Reserves a handle prior to opening a file, blocking until one is given back if the limit has
+/// been reached. Without a limit there's nothing to reserve.
+pub fn acquire() -> Option<Handle> {
+    let handles = HANDLES.get()?;
+
+    let mut available = handles
+        .released
+        .wait_while(handles.available.lock().unwrap(), |available| {
+            *available == 0
+        })
+        .unwrap();
+
+    *available -= 1;
+    drop(available);
+
+    Some(Handle(handles))
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        *self.0.available.lock().unwrap() += 1;
+        self.0.released.notify_one();
+    }
+}
+
+/// Raises the soft limit on open file descriptors to the hard limit. This is best-effort as some
+/// platforms cap the soft limit below the hard limit.
+#[cfg(unix)]
+pub fn raise_limit() {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+
+    // SAFETY: `limit` is a valid pointer for the duration of both calls.
+    unsafe {
+        if libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) != 0 || limit.rlim_cur >= limit.rlim_max
+        {
+            return;
+        }
+
+        limit.rlim_cur = limit.rlim_max;
+        libc::setrlimit(libc::RLIMIT_NOFILE, &limit);
+    }
+}
+
+/// There is no limit on open file descriptors to raise on this platform.
+#[cfg(not(unix))]
+pub const fn raise_limit() {}
+
+/// Current soft limit on open file descriptors.
+#[cfg(unix)]
+fn soft_limit() -> Option<u64> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+
+    // SAFETY: `limit` is a valid pointer for the duration of the call.
+    let res = unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) };
+
+    // `rlim_t` is signed on some platforms.
+    #[allow(clippy::useless_conversion)]
+    (res == 0 && limit.rlim_cur != libc::RLIM_INFINITY)
+        .then(|| u64::try_from(limit.rlim_cur).ok())
+        .flatten()
+}
+
+/// Open file descriptors aren't limited on this platform.
+#[cfg(not(unix))]
+const fn soft_limit() -> Option<u64> {
+    None
+}
@@ -1,9 +1,54 @@
 use ignore::DirEntry;
 use std::{fs, path::PathBuf};
 
+/// How the contents of files are stored on disk.
+pub mod allocation;
+
+/// Detection of macOS bundles.
+pub mod bundle;
+
+/// Counting lines of source code.
+pub mod code;
+
+/// Estimating how well files compress.
+pub mod compression;
+
+/// Telling apart text from binary files.
+pub mod content;
+
+/// Reading when photos were taken from their EXIF metadata.
+pub mod exif;
+
+/// Looking up the history of files tracked by git.
+pub mod git;
+
+/// Detection of disk space shared between files.
+pub mod extent;
+
+/// Limits on the number of files held open at once.
+pub mod handles;
+
 /// Operations pertaining to underlying inodes of files.
 pub mod inode;
 
+/// Looking up the owners of files.
+pub mod owner;
+
+/// Normalization of Windows paths.
+pub mod path;
+
+/// Detection of risky file permissions.
+pub mod permissions;
+
+/// Detection of NTFS reparse points such as junctions.
+pub mod reparse;
+
+/// Raw metadata of files for machine-readable output.
+pub mod stat;
+
+/// Detection of the kind of device files reside on.
+pub mod storage;
+
 /// Returns the path to the target of the soft link. Returns `None` if provided `dir_entry` isn't a
 /// symlink.
 pub fn symlink_target(dir_entry: &DirEntry) -> Option<PathBuf> {
@@ -0,0 +1,138 @@
+#[cfg(unix)]
+use once_cell::sync::Lazy;
+#[cfg(unix)]
+use std::{collections::HashMap, sync::Mutex};
+use std::{fs::Metadata, path::PathBuf};
+
+/// Size of the buffer for the strings of a password database entry should the system not suggest
+/// one.
+#[cfg(unix)]
+const PASSWD_BUF_LEN: usize = 1024;
+
+/// Names of the users looked up so far by user ID, as the owners of a tree are usually few.
+#[cfg(unix)]
+static NAMES: Lazy<Mutex<HashMap<u32, String>>> = Lazy::new(Mutex::default);
+
+/// Looks up the name of the user that owns the file with the given [Metadata], falling back to
+/// the numeric user ID if the user isn't known to the system.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_wraps)]
+pub fn owner(md: &Metadata) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+
+    let uid = md.uid();
+
+    let name = NAMES
+        .lock()
+        .unwrap()
+        .entry(uid)
+        .or_insert_with(|| user_name(uid))
+        .clone();
+
+    Some(name)
+}
+
+/// Looks up the name of the user with the ID `uid` in the password database, falling back to the
+/// ID itself if the user isn't known to the system.
+#[cfg(unix)]
+fn user_name(uid: u32) -> String {
+    use std::{ffi::CStr, mem::MaybeUninit, ptr};
+
+    // SAFETY: `sysconf` has no preconditions.
+    let suggested = unsafe { libc::sysconf(libc::_SC_GETPW_R_SIZE_MAX) };
+    let buf_len = usize::try_from(suggested).unwrap_or(PASSWD_BUF_LEN);
+
+    let mut buf = vec![0; buf_len];
+    let mut pwd = MaybeUninit::<libc::passwd>::uninit();
+    let mut result = ptr::null_mut();
+
+    // SAFETY: `pwd` and `buf` outlive the call and `buf_len` is the length of `buf`.
+    let rc = unsafe {
+        libc::getpwuid_r(
+            uid,
+            pwd.as_mut_ptr(),
+            buf.as_mut_ptr(),
+            buf_len,
+            &mut result,
+        )
+    };
+
+    if rc != 0 || result.is_null() {
+        return uid.to_string();
+    }
+
+    // SAFETY: `result` points to `pwd` which was initialized along with the name within `buf`.
+    let name = unsafe { CStr::from_ptr((*result).pw_name) };
+
+    name.to_string_lossy().into_owned()
+}
+
+/// Owners are identified by security descriptors rather than user IDs on this platform.
+#[cfg(not(unix))]
+pub const fn owner(_md: &Metadata) -> Option<String> {
+    None
+}
+
+/// The numeric ID of the user that owns the file with the given [Metadata].
+#[cfg(unix)]
+#[allow(clippy::unnecessary_wraps)]
+pub fn uid(md: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(md.uid())
+}
+
+/// Owners are identified by security descriptors rather than user IDs on this platform.
+#[cfg(not(unix))]
+pub const fn uid(_md: &Metadata) -> Option<u32> {
+    None
+}
+
+/// Looks up the home directory of the user named `name`, as in `~name`.
+#[cfg(unix)]
+pub fn home_of(name: &str) -> Option<PathBuf> {
+    use std::{
+        ffi::{CStr, CString, OsStr},
+        mem::MaybeUninit,
+        os::unix::ffi::OsStrExt,
+        ptr,
+    };
+
+    let name = CString::new(name).ok()?;
+
+    // SAFETY: `sysconf` has no preconditions.
+    let suggested = unsafe { libc::sysconf(libc::_SC_GETPW_R_SIZE_MAX) };
+    let buf_len = usize::try_from(suggested).unwrap_or(PASSWD_BUF_LEN);
+
+    let mut buf = vec![0; buf_len];
+    let mut pwd = MaybeUninit::<libc::passwd>::uninit();
+    let mut result = ptr::null_mut();
+
+    // SAFETY: `name` is NUL-terminated, and `pwd` and `buf` outlive the call with `buf_len` being
+    // the length of `buf`.
+    let rc = unsafe {
+        libc::getpwnam_r(
+            name.as_ptr(),
+            pwd.as_mut_ptr(),
+            buf.as_mut_ptr(),
+            buf_len,
+            &mut result,
+        )
+    };
+
+    if rc != 0 || result.is_null() {
+        return None;
+    }
+
+    // SAFETY: `result` points to `pwd` which was initialized along with the directory within
+    // `buf`.
+    let dir = unsafe { CStr::from_ptr((*result).pw_dir) };
+
+    Some(PathBuf::from(OsStr::from_bytes(dir.to_bytes())))
+}
+
+/// Home directories of other users aren't looked up on this platform.
+#[cfg(not(unix))]
+pub const fn home_of(_name: &str) -> Option<PathBuf> {
+    None
+}
@@ -0,0 +1,135 @@
+use super::owner;
+use std::{
+    borrow::Cow,
+    env,
+    path::{self, Path, PathBuf},
+};
+
+/// Unit tests for path normalization.
+#[cfg(test)]
+mod test;
+
+/// Environment variables that hold the home directory of the current user, in order of preference.
+const HOME_VARS: [&str; 2] = ["HOME", "USERPROFILE"];
+
+/// Prefix of Windows paths that are passed to the file-system verbatim, allowing them to exceed
+/// `MAX_PATH`. Canonicalized paths always have it.
+const VERBATIM_PREFIX: &str = r"\\?\";
+
+/// Prefix of verbatim UNC paths i.e. paths to network shares.
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+/// Strips the verbatim prefix from a Windows path, turning `\\?\C:\dir` into `C:\dir` and
+/// `\\?\UNC\server\share\dir` into `\\server\share\dir`. Verbatim paths to anything but a drive
+/// or a UNC share, such as `\\?\Volume{...}\`, have no equivalent without the prefix and are
+/// returned as is, as is any other path.
+pub fn strip_verbatim(path: &Path) -> Cow<'_, Path> {
+    let Some(raw) = path.to_str() else {
+        return Cow::Borrowed(path);
+    };
+
+    if let Some(unc) = raw.strip_prefix(VERBATIM_UNC_PREFIX) {
+        return Cow::Owned(format!(r"\\{unc}").into());
+    }
+
+    match raw.strip_prefix(VERBATIM_PREFIX) {
+        Some(rest) if is_drive(rest) => Cow::Borrowed(Path::new(rest)),
+        _ => Cow::Borrowed(path),
+    }
+}
+
+/// Whether `path` begins with a drive letter such as `C:`.
+fn is_drive(path: &str) -> bool {
+    let mut chars = path.chars();
+
+    matches!(
+        (chars.next(), chars.next()),
+        (Some(letter), Some(':')) if letter.is_ascii_alphabetic()
+    )
+}
+
+/// Expands a leading `~` or `~user` to the home directory of the current or given user, and
+/// `$VAR` or `${VAR}` to the value of the environment variable, as the shell would for paths that
+/// didn't go through one. Users and variables that aren't known are left as is, as are paths that
+/// aren't valid UTF-8.
+pub fn expand(path: &Path) -> Cow<'_, Path> {
+    expand_with(path, |name| env::var(name).ok(), home)
+}
+
+/// Home directory of `user`, or of the current user if `None`.
+fn home(user: Option<&str>) -> Option<PathBuf> {
+    user.map_or_else(
+        || {
+            HOME_VARS
+                .into_iter()
+                .find_map(env::var_os)
+                .map(PathBuf::from)
+        },
+        owner::home_of,
+    )
+}
+
+/// Does the work of [expand] looking up variables with `var` and home directories with `home`.
+fn expand_with<V, H>(path: &Path, var: V, home: H) -> Cow<'_, Path>
+where
+    V: Fn(&str) -> Option<String>,
+    H: Fn(Option<&str>) -> Option<PathBuf>,
+{
+    let Some(raw) = path.to_str() else {
+        return Cow::Borrowed(path);
+    };
+
+    if !raw.starts_with('~') && !raw.contains('$') {
+        return Cow::Borrowed(path);
+    }
+
+    let mut expanded = String::with_capacity(raw.len());
+    let mut rest = raw;
+
+    if let Some(after_tilde) = raw.strip_prefix('~') {
+        let end = after_tilde
+            .find(path::is_separator)
+            .unwrap_or(after_tilde.len());
+
+        let user = Some(&after_tilde[..end]).filter(|user| !user.is_empty());
+
+        if let Some(dir) = home(user).as_deref().and_then(Path::to_str) {
+            expanded.push_str(dir);
+            rest = &after_tilde[end..];
+        }
+    }
+
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+
+        let after_dollar = &rest[dollar + 1..];
+
+        // The name along with the length of what it's spelled with, braces included.
+        let (name, len) = after_dollar.strip_prefix('{').map_or_else(
+            || {
+                let end = after_dollar
+                    .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '_')
+                    .unwrap_or(after_dollar.len());
+
+                (&after_dollar[..end], end)
+            },
+            |braced| {
+                braced
+                    .find('}')
+                    .map_or(("", 0), |end| (&braced[..end], end + 2))
+            },
+        );
+
+        if let Some(value) = Some(name).filter(|name| !name.is_empty()).and_then(&var) {
+            expanded.push_str(&value);
+            rest = &after_dollar[len..];
+        } else {
+            expanded.push('$');
+            rest = after_dollar;
+        }
+    }
+
+    expanded.push_str(rest);
+
+    Cow::Owned(PathBuf::from(expanded))
+}
@@ -0,0 +1,87 @@
+use super::{expand_with, strip_verbatim};
+use std::path::{Path, PathBuf};
+
+#[test]
+fn strip_verbatim_drive() {
+    assert_eq!(
+        strip_verbatim(Path::new(r"\\?\C:\Users\erdtree")),
+        Path::new(r"C:\Users\erdtree")
+    );
+}
+
+#[test]
+fn strip_verbatim_unc() {
+    assert_eq!(
+        strip_verbatim(Path::new(r"\\?\UNC\server\share\dir")),
+        Path::new(r"\\server\share\dir")
+    );
+}
+
+#[test]
+fn strip_verbatim_long_path() {
+    let long = format!(r"C:\{}", ["a".repeat(200), "b".repeat(200)].join(r"\"));
+    let verbatim = format!(r"\\?\{long}");
+
+    assert_eq!(strip_verbatim(Path::new(&verbatim)), Path::new(&long));
+}
+
+#[test]
+fn strip_verbatim_volume() {
+    let volume = r"\\?\Volume{b75e2c83-0000-0000-0000-602f00000000}\dir";
+    assert_eq!(strip_verbatim(Path::new(volume)), Path::new(volume));
+}
+
+#[test]
+fn strip_verbatim_other() {
+    for path in [r"C:\dir", r"\\server\share", "/home/erdtree", "relative"] {
+        assert_eq!(strip_verbatim(Path::new(path)), Path::new(path));
+    }
+}
+
+/// Expands `path` with `$HOME` and `$DATA` set, and `erdtree` being the only other user.
+fn expand(path: &str) -> PathBuf {
+    let var = |name: &str| match name {
+        "HOME" => Some(String::from("/home/me")),
+        "DATA" => Some(String::from("/srv/data")),
+        _ => None,
+    };
+
+    let home = |user: Option<&str>| match user {
+        None => Some(PathBuf::from("/home/me")),
+        Some("erdtree") => Some(PathBuf::from("/home/erdtree")),
+        Some(_) => None,
+    };
+
+    expand_with(Path::new(path), var, home).into_owned()
+}
+
+#[test]
+fn expand_tilde() {
+    assert_eq!(expand("~"), Path::new("/home/me"));
+    assert_eq!(expand("~/projects"), Path::new("/home/me/projects"));
+    assert_eq!(expand("~erdtree/src"), Path::new("/home/erdtree/src"));
+}
+
+#[test]
+fn expand_tilde_unknown_user() {
+    assert_eq!(expand("~nobody/src"), Path::new("~nobody/src"));
+}
+
+#[test]
+fn expand_tilde_not_leading() {
+    assert_eq!(expand("backup~/~"), Path::new("backup~/~"));
+}
+
+#[test]
+fn expand_vars() {
+    assert_eq!(expand("$DATA/logs"), Path::new("/srv/data/logs"));
+    assert_eq!(expand("${DATA}_old"), Path::new("/srv/data_old"));
+    assert_eq!(expand("$HOME/$DATA"), Path::new("/home/me//srv/data"));
+}
+
+#[test]
+fn expand_vars_unset() {
+    for path in ["$UNSET/logs", "${UNSET}", "cost$", "${DATA", "$/x"] {
+        assert_eq!(expand(path), Path::new(path));
+    }
+}
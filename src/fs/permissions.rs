@@ -0,0 +1,129 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::{
+    fmt::{self, Display},
+    fs::Metadata,
+    path::Path,
+};
+
+/// Permissions of a file that warrant a closer look during a security review.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Issue {
+    /// Anyone may modify the file.
+    WorldWritable,
+
+    /// The file is executed with the privileges of its owner.
+    Setuid,
+
+    /// The file is executed with the privileges of its group.
+    Setgid,
+
+    /// The file is owned by root but resides in a directory owned by another user.
+    RootOwned,
+}
+
+/// Determines the [Issue]s of the file at `path` whose own metadata is `md`. Directories and
+/// symlinks, whose permission bits are meaningless on most platforms, never have any.
+#[cfg(unix)]
+pub fn issues(path: &Path, md: &Metadata) -> Vec<Issue> {
+    use std::os::unix::fs::MetadataExt;
+
+    /// Mode bits from `sys/stat.h` which are the same across Unix platforms.
+    const S_IWOTH: u32 = 0o2;
+    const S_ISGID: u32 = 0o2000;
+    const S_ISUID: u32 = 0o4000;
+
+    if md.is_dir() || md.is_symlink() {
+        return vec![];
+    }
+
+    let mode = md.mode();
+    let mut issues = vec![];
+
+    if mode & S_IWOTH != 0 {
+        issues.push(Issue::WorldWritable);
+    }
+
+    if mode & S_ISUID != 0 {
+        issues.push(Issue::Setuid);
+    }
+
+    if mode & S_ISGID != 0 {
+        issues.push(Issue::Setgid);
+    }
+
+    let in_user_dir = || {
+        path.parent()
+            .and_then(|parent| std::fs::metadata(parent).ok())
+            .map_or(false, |parent_md| parent_md.uid() != 0)
+    };
+
+    if md.uid() == 0 && in_user_dir() {
+        issues.push(Issue::RootOwned);
+    }
+
+    issues
+}
+
+/// Renders the permission bits of a file as in `ls -l` e.g. `rwxr-sr-x`.
+#[cfg(unix)]
+pub fn symbolic(md: &Metadata) -> String {
+    use std::os::unix::fs::MetadataExt;
+
+    let mode = md.mode();
+
+    // Execute bits are replaced by their special counterparts, uppercase if not executable.
+    let specials = [(0o4000, 's'), (0o2000, 's'), (0o1000, 't')];
+
+    let mut symbolic = String::with_capacity(9);
+
+    for (i, (special, special_char)) in specials.into_iter().enumerate() {
+        let shift = 6 - 3 * i;
+        let bits = (mode >> shift) & 0o7;
+
+        symbolic.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        symbolic.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+
+        let exec = bits & 0o1 != 0;
+
+        symbolic.push(match (mode & special != 0, exec) {
+            (true, true) => special_char,
+            (true, false) => special_char.to_ascii_uppercase(),
+            (false, true) => 'x',
+            (false, false) => '-',
+        });
+    }
+
+    symbolic
+}
+
+/// Renders the permissions of a file which are limited to whether it's read-only on this
+/// platform.
+#[cfg(not(unix))]
+pub fn symbolic(md: &Metadata) -> String {
+    if md.permissions().readonly() {
+        String::from("r--r--r--")
+    } else {
+        String::from("rw-rw-rw-")
+    }
+}
+
+/// Permissions aren't expressed as mode bits on this platform.
+#[cfg(not(unix))]
+pub const fn issues(_path: &Path, _md: &Metadata) -> Vec<Issue> {
+    vec![]
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::WorldWritable => "world-writable",
+            Self::Setuid => "setuid",
+            Self::Setgid => "setgid",
+            Self::RootOwned => "root-owned",
+        };
+
+        write!(f, "{label}")
+    }
+}
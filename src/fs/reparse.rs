@@ -0,0 +1,77 @@
+use std::{fs::Metadata, path::Path};
+
+/// Kinds of NTFS reparse points that change how an entry is treated. Reparse points only exist
+/// on Windows.
+#[cfg_attr(not(windows), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReparsePoint {
+    /// A directory junction i.e. a mount point that redirects to another directory.
+    Junction,
+
+    /// A symbolic link to either a file or a directory.
+    Symlink,
+
+    /// A file or directory managed by a cloud sync provider such as OneDrive that may not be
+    /// stored locally. These are regular files and directories as far as traversal goes.
+    Cloud,
+
+    /// Any other reparse point such as those used by deduplication.
+    Other,
+}
+
+/// Determines what kind of reparse point the entry at `path` is, if it is one at all.
+#[cfg(windows)]
+pub fn reparse_point(path: &Path, md: &Metadata) -> Option<ReparsePoint> {
+    use std::os::windows::{ffi::OsStrExt, fs::MetadataExt};
+    use windows_sys::Win32::{
+        Foundation::INVALID_HANDLE_VALUE,
+        Storage::FileSystem::{
+            FindClose, FindFirstFileW, FILE_ATTRIBUTE_REPARSE_POINT, WIN32_FIND_DATAW,
+        },
+        System::SystemServices::{
+            IO_REPARSE_TAG_CLOUD, IO_REPARSE_TAG_MOUNT_POINT, IO_REPARSE_TAG_SYMLINK,
+        },
+    };
+
+    /// Cloud reparse tags differ from one another only in these bits.
+    const CLOUD_TAG_MASK: u32 = 0xFFFF_0FFF;
+
+    if md.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT == 0 {
+        return None;
+    }
+
+    let wide_path = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect::<Vec<u16>>();
+
+    // SAFETY: `WIN32_FIND_DATAW` is plain old data for which all zeroes is a valid value.
+    let mut find_data = unsafe { std::mem::zeroed::<WIN32_FIND_DATAW>() };
+
+    // SAFETY: `wide_path` is nul-terminated and `find_data` is valid for the duration of the call.
+    let handle = unsafe { FindFirstFileW(wide_path.as_ptr(), &mut find_data) };
+
+    if handle == INVALID_HANDLE_VALUE {
+        return Some(ReparsePoint::Other);
+    }
+
+    // SAFETY: `handle` was just returned by `FindFirstFileW` and is closed exactly once.
+    unsafe { FindClose(handle) };
+
+    // The reparse tag is reported in an otherwise reserved field if the entry is a reparse point.
+    let reparse_point = match find_data.dwReserved0 {
+        IO_REPARSE_TAG_MOUNT_POINT => ReparsePoint::Junction,
+        IO_REPARSE_TAG_SYMLINK => ReparsePoint::Symlink,
+        tag if tag & CLOUD_TAG_MASK == IO_REPARSE_TAG_CLOUD => ReparsePoint::Cloud,
+        _ => ReparsePoint::Other,
+    };
+
+    Some(reparse_point)
+}
+
+/// Reparse points only exist on Windows.
+#[cfg(not(windows))]
+pub const fn reparse_point(_path: &Path, _md: &Metadata) -> Option<ReparsePoint> {
+    None
+}
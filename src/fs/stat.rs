@@ -0,0 +1,77 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fs::Metadata;
+
+/// Raw metadata of a file as returned by `stat`, for tools downstream that would otherwise have to
+/// stat every file again. Fields not available on the platform are left out. Timestamps are in
+/// seconds since the epoch.
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct Stat {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dev: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ino: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nlink: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    atime: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mtime: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ctime: Option<i64>,
+}
+
+impl From<&Metadata> for Stat {
+    #[cfg(unix)]
+    fn from(md: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        Self {
+            dev: Some(md.dev()),
+            ino: Some(md.ino()),
+            nlink: Some(md.nlink()),
+            uid: Some(md.uid()),
+            gid: Some(md.gid()),
+            mode: Some(md.mode()),
+            atime: Some(md.atime()),
+            mtime: Some(md.mtime()),
+            ctime: Some(md.ctime()),
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn from(md: &Metadata) -> Self {
+        use super::inode::Inode;
+        use std::convert::TryFrom;
+
+        let inode = Inode::try_from(md).ok();
+
+        Self {
+            dev: inode.as_ref().map(|inode| inode.dev),
+            ino: inode.as_ref().map(|inode| inode.ino),
+            nlink: inode.as_ref().map(|inode| inode.nlink),
+            atime: md.accessed().ok().and_then(epoch_secs),
+            mtime: md.modified().ok().and_then(epoch_secs),
+            ..Self::default()
+        }
+    }
+}
+
+/// Seconds between the epoch and `time`, negative if `time` predates it.
+#[cfg(not(unix))]
+fn epoch_secs(time: std::time::SystemTime) -> Option<i64> {
+    use std::{convert::TryFrom, time::UNIX_EPOCH};
+
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => i64::try_from(since.as_secs()).ok(),
+        Err(before) => i64::try_from(before.duration().as_secs())
+            .ok()
+            .map(|secs| -secs),
+    }
+}
@@ -0,0 +1,86 @@
+use std::{num::NonZeroUsize, path::Path, thread};
+
+/// Number of threads used when nothing is known about the storage.
+pub const DEFAULT_THREADS: usize = 3;
+
+/// Number of threads used for spinning disks, where seeking between files requested by many
+/// threads at once costs more than the parallelism gains.
+const ROTATIONAL_THREADS: usize = 2;
+
+/// Upper bound on the number of threads used for NVMe drives, beyond which gains are negligible.
+const MAX_NVME_THREADS: usize = 16;
+
+/// Kind of device a file resides on, insofar as it matters for how many threads should read from
+/// it at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Storage {
+    /// Hard disk drives.
+    Rotational,
+
+    /// Solid state drives attached by means other than NVMe, such as SATA.
+    Ssd,
+
+    /// Solid state drives attached via NVMe, which service many requests in parallel.
+    Nvme,
+
+    /// Pseudo filesystems, network shares, or platforms that don't expose the kind of device.
+    Unknown,
+}
+
+impl Storage {
+    /// Determines the kind of device `path` resides on.
+    #[cfg(target_os = "linux")]
+    pub fn of(path: &Path) -> Self {
+        use std::{fs, os::unix::fs::MetadataExt};
+
+        let Ok(md) = fs::metadata(path) else {
+            return Self::Unknown;
+        };
+
+        let dev = md.dev();
+
+        // SAFETY: `major` and `minor` merely extract bits from `dev`.
+        let (major, minor) = unsafe { (libc::major(dev), libc::minor(dev)) };
+
+        let Ok(mut device) = fs::canonicalize(format!("/sys/dev/block/{major}:{minor}")) else {
+            return Self::Unknown;
+        };
+
+        // Partitions don't have a queue of their own; the disk they're a part of does.
+        if device.join("partition").exists() {
+            device.pop();
+        }
+
+        let Ok(rotational) = fs::read_to_string(device.join("queue").join("rotational")) else {
+            return Self::Unknown;
+        };
+
+        let is_nvme = device
+            .file_name()
+            .map_or(false, |name| name.to_string_lossy().starts_with("nvme"));
+
+        match rotational.trim() {
+            "1" => Self::Rotational,
+            "0" if is_nvme => Self::Nvme,
+            "0" => Self::Ssd,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Determines the kind of device `path` resides on.
+    #[cfg(not(target_os = "linux"))]
+    pub fn of(_path: &Path) -> Self {
+        Self::Unknown
+    }
+
+    /// A sensible number of threads to traverse a directory on this kind of device with.
+    pub fn default_threads(self) -> usize {
+        match self {
+            Self::Rotational => ROTATIONAL_THREADS,
+            Self::Ssd | Self::Unknown => DEFAULT_THREADS,
+            Self::Nvme => thread::available_parallelism()
+                .map_or(DEFAULT_THREADS, NonZeroUsize::get)
+                .clamp(DEFAULT_THREADS, MAX_NVME_THREADS),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+## Summaries printed with --count and --stats
+
+count-directories =
+    { $count ->
+        [one] { $digits } Verzeichnis
+       *[other] { $digits } Verzeichnisse
+    }
+count-files =
+    { $count ->
+        [one] { $digits } Datei
+       *[other] { $digits } Dateien
+    }
+count-links =
+    { $count ->
+        [one] { $digits } Link
+       *[other] { $digits } Links
+    }
+stats-entries = { $entries } Einträge
+stats-throughput = { $entries } Einträge in { $elapsed }, { $entries-per-sec } Einträge/s, { $bytes-per-sec }/s
+
+## Markers printed beneath the tree
+
+output-truncated =
+    { $count ->
+        [one] { $ellipsis } Ausgabe gekürzt ({ $digits } weiterer Eintrag)
+       *[other] { $ellipsis } Ausgabe gekürzt ({ $digits } weitere Einträge)
+    }
+delta-gone = entfernt
+
+## Labels of --output html
+
+html-title = Speicherbelegung von { $root }
+html-generated = Erstellt am { $timestamp } UTC
+html-summary = Zusammenfassung
+html-disk-usage = Speicherbelegung
+html-files = Dateien
+html-directories = Verzeichnisse
+html-traversal = Durchlauf
+html-tree = Baum
+html-largest-files = Größte Dateien
+html-size = Größe
+html-path = Pfad
+html-by-extension = Nach Dateiendung
+html-extension = Dateiendung
+html-share = Anteil
+
+## Errors
+
+error-config-parse = Eine Konfigurationsdatei wurde gefunden, konnte aber nicht verarbeitet werden: { $error }
+error-missing-profile = In der Konfigurationsdatei wurde kein Profil namens '{ $profile }' gefunden
+error-read-patterns = Muster aus { $path } konnten nicht gelesen werden: { $error }
+error-missing-parent = Datei ohne übergeordnetes Verzeichnis
+error-invalid-glob = Ungültige Glob-Muster: { $error }
+error-missing-root = Wurzelknoten konnte nicht ermittelt werden
+error-missing-data-dir = Kein Datenverzeichnis gefunden; versuchen Sie es mit --history-dir
+error-missing-history = Kein Verlauf für { $path } aufgezeichnet
+error-missing-cache-dir = Kein Cache-Verzeichnis gefunden; versuchen Sie $ERDTREE_CACHE_DIR zu setzen
+error-snapshot = Snapshot konnte nicht gelesen oder geschrieben werden: { $error }
+error-checkpoint = Checkpoint konnte nicht gelesen oder geschrieben werden: { $error }
+error-git-history = Git-Verlauf konnte nicht gelesen werden: { $error }
+error-annotations-read = Anmerkungen konnten nicht gelesen werden: { $error }
+error-annotations-parse = Anmerkungen konnten nicht verarbeitet werden: { $error }
+error-icon-map-read = Symbolzuordnung konnte nicht gelesen werden: { $error }
+error-icon-map-parse = Symbolzuordnung konnte nicht verarbeitet werden: { $error }
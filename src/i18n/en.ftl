@@ -0,0 +1,76 @@
+## Units of disk usage
+
+unit-b = B
+unit-kb = KB
+unit-kib = KiB
+unit-mb = MB
+unit-mib = MiB
+unit-gb = GB
+unit-gib = GiB
+unit-tb = TB
+unit-tib = TiB
+
+## Summaries printed with --count and --stats
+
+count-directories =
+    { $count ->
+        [one] { $digits } directory
+       *[other] { $digits } directories
+    }
+count-files =
+    { $count ->
+        [one] { $digits } file
+       *[other] { $digits } files
+    }
+count-links =
+    { $count ->
+        [one] { $digits } link
+       *[other] { $digits } links
+    }
+stats-entries = { $entries } entries
+stats-throughput = { $entries } entries in { $elapsed }, { $entries-per-sec } entries/s, { $bytes-per-sec }/s
+
+## Markers printed beneath the tree
+
+output-truncated =
+    { $count ->
+        [one] { $ellipsis } output truncated ({ $digits } more entry)
+       *[other] { $ellipsis } output truncated ({ $digits } more entries)
+    }
+delta-gone = gone
+
+## Labels of --output html
+
+html-title = Disk usage of { $root }
+html-generated = Generated on { $timestamp } UTC
+html-summary = Summary
+html-disk-usage = Disk usage
+html-files = Files
+html-directories = Directories
+html-traversal = Traversal
+html-tree = Tree
+html-largest-files = Largest files
+html-size = Size
+html-path = Path
+html-by-extension = By extension
+html-extension = Extension
+html-share = Share
+
+## Errors
+
+error-config-parse = A configuration file was found but failed to parse: { $error }
+error-missing-profile = No profile named '{ $profile }' was found in the configuration file
+error-read-patterns = Failed to read patterns from { $path }: { $error }
+error-missing-parent = File expected to have parent
+error-invalid-glob = Invalid glob patterns: { $error }
+error-missing-root = Failed to compute root node
+error-missing-data-dir = Failed to locate a data directory; try providing --history-dir
+error-missing-history = No history recorded for { $path }
+error-missing-cache-dir = Failed to locate a cache directory; try setting $ERDTREE_CACHE_DIR
+error-snapshot = Failed to read or write snapshot: { $error }
+error-checkpoint = Failed to read or write checkpoint: { $error }
+error-git-history = Failed to read git history: { $error }
+error-annotations-read = Failed to read annotations: { $error }
+error-annotations-parse = Failed to parse annotations: { $error }
+error-icon-map-read = Failed to read icon map: { $error }
+error-icon-map-parse = Failed to parse icon map: { $error }
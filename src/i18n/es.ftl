@@ -0,0 +1,64 @@
+## Summaries printed with --count and --stats
+
+count-directories =
+    { $count ->
+        [one] { $digits } directorio
+       *[other] { $digits } directorios
+    }
+count-files =
+    { $count ->
+        [one] { $digits } archivo
+       *[other] { $digits } archivos
+    }
+count-links =
+    { $count ->
+        [one] { $digits } enlace
+       *[other] { $digits } enlaces
+    }
+stats-entries = { $entries } entradas
+stats-throughput = { $entries } entradas en { $elapsed }, { $entries-per-sec } entradas/s, { $bytes-per-sec }/s
+
+## Markers printed beneath the tree
+
+output-truncated =
+    { $count ->
+        [one] { $ellipsis } salida truncada ({ $digits } entrada más)
+       *[other] { $ellipsis } salida truncada ({ $digits } entradas más)
+    }
+delta-gone = eliminado
+
+## Labels of --output html
+
+html-title = Uso de disco de { $root }
+html-generated = Generado el { $timestamp } UTC
+html-summary = Resumen
+html-disk-usage = Uso de disco
+html-files = Archivos
+html-directories = Directorios
+html-traversal = Recorrido
+html-tree = Árbol
+html-largest-files = Archivos más grandes
+html-size = Tamaño
+html-path = Ruta
+html-by-extension = Por extensión
+html-extension = Extensión
+html-share = Proporción
+
+## Errors
+
+error-config-parse = Se encontró un archivo de configuración pero no se pudo analizar: { $error }
+error-missing-profile = No se encontró ningún perfil llamado '{ $profile }' en el archivo de configuración
+error-read-patterns = No se pudieron leer los patrones de { $path }: { $error }
+error-missing-parent = Se esperaba que el archivo tuviera un directorio padre
+error-invalid-glob = Patrones glob no válidos: { $error }
+error-missing-root = No se pudo determinar el nodo raíz
+error-missing-data-dir = No se encontró un directorio de datos; pruebe a indicar --history-dir
+error-missing-history = No hay historial registrado para { $path }
+error-missing-cache-dir = No se encontró un directorio de caché; pruebe a definir $ERDTREE_CACHE_DIR
+error-snapshot = No se pudo leer o escribir la instantánea: { $error }
+error-checkpoint = No se pudo leer o escribir el punto de control: { $error }
+error-git-history = No se pudo leer el historial de git: { $error }
+error-annotations-read = No se pudieron leer las anotaciones: { $error }
+error-annotations-parse = No se pudieron analizar las anotaciones: { $error }
+error-icon-map-read = No se pudo leer el mapa de iconos: { $error }
+error-icon-map-parse = No se pudo analizar el mapa de iconos: { $error }
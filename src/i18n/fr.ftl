@@ -0,0 +1,76 @@
+## Units of disk usage, bytes being octets in French
+
+unit-b = o
+unit-kb = Ko
+unit-kib = Kio
+unit-mb = Mo
+unit-mib = Mio
+unit-gb = Go
+unit-gib = Gio
+unit-tb = To
+unit-tib = Tio
+
+## Summaries printed with --count and --stats
+
+count-directories =
+    { $count ->
+        [one] { $digits } répertoire
+       *[other] { $digits } répertoires
+    }
+count-files =
+    { $count ->
+        [one] { $digits } fichier
+       *[other] { $digits } fichiers
+    }
+count-links =
+    { $count ->
+        [one] { $digits } lien
+       *[other] { $digits } liens
+    }
+stats-entries = { $entries } entrées
+stats-throughput = { $entries } entrées en { $elapsed }, { $entries-per-sec } entrées/s, { $bytes-per-sec }/s
+
+## Markers printed beneath the tree
+
+output-truncated =
+    { $count ->
+        [one] { $ellipsis } sortie tronquée ({ $digits } entrée de plus)
+       *[other] { $ellipsis } sortie tronquée ({ $digits } entrées de plus)
+    }
+delta-gone = supprimé
+
+## Labels of --output html
+
+html-title = Utilisation du disque de { $root }
+html-generated = Généré le { $timestamp } UTC
+html-summary = Résumé
+html-disk-usage = Utilisation du disque
+html-files = Fichiers
+html-directories = Répertoires
+html-traversal = Parcours
+html-tree = Arborescence
+html-largest-files = Plus gros fichiers
+html-size = Taille
+html-path = Chemin
+html-by-extension = Par extension
+html-extension = Extension
+html-share = Part
+
+## Errors
+
+error-config-parse = Un fichier de configuration a été trouvé mais n'a pas pu être analysé : { $error }
+error-missing-profile = Aucun profil nommé '{ $profile }' n'a été trouvé dans le fichier de configuration
+error-read-patterns = Impossible de lire les motifs depuis { $path } : { $error }
+error-missing-parent = Le fichier devrait avoir un répertoire parent
+error-invalid-glob = Motifs glob invalides : { $error }
+error-missing-root = Impossible de déterminer le nœud racine
+error-missing-data-dir = Aucun répertoire de données trouvé ; essayez d'indiquer --history-dir
+error-missing-history = Aucun historique enregistré pour { $path }
+error-missing-cache-dir = Aucun répertoire de cache trouvé ; essayez de définir $ERDTREE_CACHE_DIR
+error-snapshot = Impossible de lire ou d'écrire l'instantané : { $error }
+error-checkpoint = Impossible de lire ou d'écrire le point de reprise : { $error }
+error-git-history = Impossible de lire l'historique git : { $error }
+error-annotations-read = Impossible de lire les annotations : { $error }
+error-annotations-parse = Impossible d'analyser les annotations : { $error }
+error-icon-map-read = Impossible de lire la table d'icônes : { $error }
+error-icon-map-parse = Impossible d'analyser la table d'icônes : { $error }
@@ -0,0 +1,91 @@
+use crate::render::context::lang::Lang;
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use once_cell::sync::{Lazy, OnceCell};
+use std::borrow::Cow;
+
+/// Catalog of every message keyed by its stable message ID, which other catalogs fall back to.
+const EN: &str = include_str!("en.ftl");
+
+/// Catalog of messages translated into German.
+const DE: &str = include_str!("de.ftl");
+
+/// Catalog of messages translated into Spanish.
+const ES: &str = include_str!("es.ftl");
+
+/// Catalog of messages translated into French.
+const FR: &str = include_str!("fr.ftl");
+
+/// Language that messages are printed in, set once `--lang` is known.
+static LANG: OnceCell<Lang> = OnceCell::new();
+
+/// Messages in [lang], set the first time a message is translated.
+static BUNDLE: OnceCell<Bundle> = OnceCell::new();
+
+/// Messages in English for those that aren't translated into [lang].
+static FALLBACK: Lazy<Bundle> = Lazy::new(|| bundle(Lang::En));
+
+type Bundle = FluentBundle<FluentResource>;
+
+/// Sets the language that messages are printed in, that of the environment if `lang` is `None`.
+/// Must be called before [crate::render::styles::init] as disk usage is colored by unit name.
+pub fn init(lang: Option<Lang>) {
+    let _ = LANG.set(lang.unwrap_or_else(Lang::from_env));
+}
+
+/// Language that messages are printed in. Messages printed before [init], such as errors in
+/// reading the configuration file, are in the language of the environment.
+pub fn lang() -> Lang {
+    *LANG.get_or_init(Lang::from_env)
+}
+
+/// Message with the ID `id` in [lang]. Messages that aren't translated are left in English.
+pub fn tr(id: &str) -> Cow<'static, str> {
+    format(id, None)
+}
+
+/// Message with the ID `id` in [lang] with its variables, such as `$path`, set to `args`.
+pub fn tr_args<'a, V>(id: &str, args: impl IntoIterator<Item = (&'a str, V)>) -> String
+where
+    V: Into<FluentValue<'a>>,
+{
+    let args = args.into_iter().collect::<FluentArgs>();
+
+    format(id, Some(&args)).into_owned()
+}
+
+/// Formats the message with the ID `id` from the catalog of [lang] if it has it or else from that
+/// of English. Messages with an ID that's in neither are printed as their ID.
+fn format<'a>(id: &str, args: Option<&'a FluentArgs<'a>>) -> Cow<'a, str> {
+    let bundle = BUNDLE.get_or_init(|| bundle(lang()));
+
+    let Some((bundle, pattern)) = [bundle, &*FALLBACK]
+        .into_iter()
+        .find_map(|bundle| Some((bundle, bundle.get_message(id)?.value()?)))
+    else {
+        return Cow::Owned(id.to_owned());
+    };
+
+    // Errors are for references to missing variables which are printed as is.
+    bundle.format_pattern(pattern, args, &mut vec![])
+}
+
+/// Messages in `lang` from its catalog, which is shipped with the binary and so always parses.
+fn bundle(lang: Lang) -> Bundle {
+    let source = match lang {
+        Lang::En => EN,
+        Lang::De => DE,
+        Lang::Es => ES,
+        Lang::Fr => FR,
+    };
+
+    let resource = FluentResource::try_new(source.to_owned()).expect("catalog to parse");
+    let mut bundle = Bundle::new_concurrent(vec![lang.code().parse().unwrap_or_default()]);
+
+    // Isolation marks around variables would end up in the terminal and in error messages.
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .expect("catalog to have unique message IDs");
+
+    bundle
+}
@@ -1,12 +1,66 @@
-use crate::hash;
+use crate::{hash, i18n::tr_args};
 use ansi_term::Color;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Deserialize;
 use std::{
     collections::HashMap,
     ffi::{OsStr, OsString},
-    fs::FileType,
+    fs::{self, FileType},
+    io,
+    path::Path,
 };
 
+/// User-provided icons that take precedence over the built-in ones, read from the file provided
+/// via `--icon-map`. See [init].
+static ICON_OVERRIDES: OnceCell<IconOverrides> = OnceCell::new();
+
+/// Icons keyed by file extension, file name, and directory name as defined in an `--icon-map`
+/// TOML file e.g.:
+///
+/// ```toml
+/// [extensions]
+/// proto = { glyph = "\ue60b", color = 33 }
+///
+/// [file_names]
+/// Justfile = "\uf489"
+///
+/// [dir_names]
+/// migrations = "\uf1c0"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct IconMap {
+    extensions: HashMap<String, IconSpec>,
+    file_names: HashMap<String, IconSpec>,
+    dir_names: HashMap<String, IconSpec>,
+}
+
+/// A single icon of [IconMap]. Icons without a color take on the color of their file.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum IconSpec {
+    Glyph(String),
+    Styled { glyph: String, color: Option<u8> },
+}
+
+/// [IconMap] with every glyph painted in its color, if any.
+#[derive(Debug)]
+struct IconOverrides {
+    extensions: HashMap<OsString, String>,
+    file_names: HashMap<OsString, String>,
+    dir_names: HashMap<OsString, String>,
+}
+
+/// Errors that may occur while reading the file provided via `--icon-map`.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{}", tr_args("error-icon-map-read", [("error", .0.to_string())]))]
+    Io(#[from] io::Error),
+
+    #[error("{}", tr_args("error-icon-map-parse", [("error", .0.to_string())]))]
+    Parse(#[from] toml::de::Error),
+}
+
 /// Lazily evaluated static hash-map of special file-types and their corresponding styled icons.
 /// These icons will take on the color properties of their associated file which is based on
 /// `LS_COLORS`.
@@ -14,8 +68,33 @@ use std::{
 /// Dev icons sourced from [`exa`](https://github.com/ogham/exa/blob/master/src/output/icons.rs)
 static FILE_TYPE_ICON_MAP: Lazy<HashMap<&str, &str>> = Lazy::new(|| {
     hash!(
-        "dir"     => "\u{f413}", // 
-        "symlink" => "\u{f482}"  // 
+        "dir"            => "\u{f413}", // 
+        "symlink"        => "\u{f482}", // 
+        "symlink_broken" => "\u{f127}", // 
+        "symlink_dir"    => "\u{f482}", // 
+        "symlink_file"   => "\u{f481}"  // 
+    )
+});
+
+/// Lazily evaluated static hash-map of well-known directory names and their corresponding icons
+/// which are used in place of the generic directory icon. These icons will take on the color
+/// properties of their associated directory which is based on `LS_COLORS`.
+static DIR_NAME_ICON_MAP: Lazy<HashMap<OsString, &str>> = Lazy::new(|| {
+    hash!(
+        OsString::from(".config")      => "\u{e5fc}", // 
+        OsString::from(".git")         => "\u{e5fb}", // 
+        OsString::from(".github")      => "\u{e5fd}", // 
+        OsString::from(".vscode")      => "\u{e70c}", // 
+        OsString::from("Desktop")      => "\u{f108}", // 
+        OsString::from("Documents")    => "\u{f0c5}", // 
+        OsString::from("Downloads")    => "\u{f019}", // 
+        OsString::from("Music")        => "\u{f001}", // 
+        OsString::from("Pictures")     => "\u{f03e}", // 
+        OsString::from("Videos")       => "\u{f03d}", // 
+        OsString::from("node_modules") => "\u{e5fa}", // 
+        OsString::from("src")          => "\u{f121}", // 
+        OsString::from("test")         => "\u{f0c3}", // 
+        OsString::from("tests")        => "\u{f0c3}"  // 
     )
 });
 
@@ -147,6 +226,7 @@ static EXT_ICON_MAP: Lazy<HashMap<OsString, String>> = Lazy::new(|| {
         OsString::from("haml")          => col(188, "\u{e60e}"),   // 
         OsString::from("hbs")           => col(208, "\u{e60f}"),   // 
         OsString::from("h")             => col(140, "\u{f0fd}"),   // 
+        OsString::from("hcl")           => col(57, "\u{e2a6}"),    // 
         OsString::from("heex")          => col(140, "\u{e62d}"),   // 
         OsString::from("hh")            => col(140, "\u{f0fd}"),   // 
         OsString::from("hpp")           => col(140, "\u{f0fd}"),   // 
@@ -158,6 +238,7 @@ static EXT_ICON_MAP: Lazy<HashMap<OsString, String>> = Lazy::new(|| {
         OsString::from("ico")           => col(185, "\u{e60d}"),   // 
         OsString::from("import")        => col(231, "\u{f0c6}"),   // 
         OsString::from("ini")           => col(66, "\u{e615}"),    // 
+        OsString::from("ipynb")         => col(214, "\u{e606}"),   // 
         OsString::from("java")          => col(167, "\u{e738}"),   // 
         OsString::from("jl")            => col(133, "\u{e624}"),   // 
         OsString::from("jpeg")          => col(140, "\u{e60d}"),   // 
@@ -205,6 +286,7 @@ static EXT_ICON_MAP: Lazy<HashMap<OsString, String>> = Lazy::new(|| {
         OsString::from("ppt")           => col(167, "\u{f726}"),   // 
         OsString::from("prisma")        => col(255, "\u{5351}"),   // 卑
         OsString::from("pro")           => col(179, "\u{e7a1}"),   // 
+        OsString::from("proto")         => col(67, "\u{f1c9}"),    // 
         OsString::from("ps1")           => col(69, "\u{f0a0a}"),   // 󰨊
         OsString::from("psb")           => col(67, "\u{e7b8}"),    // 
         OsString::from("psd1")          => col(105, "\u{f0a0a}"),  // 󰨊
@@ -285,9 +367,32 @@ static EXT_ICON_MAP: Lazy<HashMap<OsString, String>> = Lazy::new(|| {
 /// Default fallback icon.
 static DEFAULT_ICON: Lazy<String> = Lazy::new(|| col(66, "\u{f15b}"));
 
+/// Reads the icon map at `path`, merging it over the built-in icons. Must be called at most once
+/// and before any icons are looked up.
+pub fn init(path: &Path) -> Result<(), Error> {
+    let contents = fs::read_to_string(path)?;
+    let icon_map = toml::from_str::<IconMap>(&contents)?;
+
+    let overrides = IconOverrides {
+        extensions: IconSpec::paint_all(icon_map.extensions),
+        file_names: IconSpec::paint_all(icon_map.file_names),
+        dir_names: IconSpec::paint_all(icon_map.dir_names),
+    };
+
+    ICON_OVERRIDES
+        .set(overrides)
+        .expect("Icon overrides already initialized");
+
+    Ok(())
+}
+
 /// Attempts to return an icon given a file extension.
 pub fn icon_from_ext(ext: &OsStr) -> Option<&str> {
-    EXT_ICON_MAP.get(ext).map(String::as_str)
+    ICON_OVERRIDES
+        .get()
+        .and_then(|overrides| overrides.extensions.get(ext))
+        .map(String::as_str)
+        .or_else(|| EXT_ICON_MAP.get(ext).map(String::as_str))
 }
 
 /// Attempts to return an icon based on file type.
@@ -301,9 +406,34 @@ pub fn icon_from_file_type(ft: &FileType) -> Option<&str> {
     None
 }
 
+/// Returns the icon of a symlink given whether its target is a directory or `None` if the target
+/// doesn't exist.
+pub fn icon_from_symlink(target_is_dir: Option<bool>) -> &'static str {
+    let kind = match target_is_dir {
+        Some(true) => "symlink_dir",
+        Some(false) => "symlink_file",
+        None => "symlink_broken",
+    };
+
+    FILE_TYPE_ICON_MAP[kind]
+}
+
+/// Attempts to get the icon associated with a well-known directory name.
+pub fn icon_from_dir_name(name: &OsStr) -> Option<&str> {
+    ICON_OVERRIDES
+        .get()
+        .and_then(|overrides| overrides.dir_names.get(name))
+        .map(String::as_str)
+        .or_else(|| DIR_NAME_ICON_MAP.get(name).copied())
+}
+
 /// Attempts to get the icon associated with the special file kind.
 pub fn icon_from_file_name(name: &OsStr) -> Option<&str> {
-    FILE_NAME_ICON_MAP.get(name).copied()
+    ICON_OVERRIDES
+        .get()
+        .and_then(|overrides| overrides.file_names.get(name))
+        .map(String::as_str)
+        .or_else(|| FILE_NAME_ICON_MAP.get(name).copied())
 }
 
 /// Returns the default fallback icon.
@@ -311,6 +441,26 @@ pub fn get_default_icon<'a>() -> &'a str {
     DEFAULT_ICON.as_str()
 }
 
+impl IconSpec {
+    /// Paints each glyph of `specs` in its color, if any.
+    fn paint_all(specs: HashMap<String, Self>) -> HashMap<OsString, String> {
+        specs
+            .into_iter()
+            .map(|(key, spec)| {
+                let icon = match spec {
+                    Self::Glyph(glyph) | Self::Styled { glyph, color: None } => glyph,
+                    Self::Styled {
+                        glyph,
+                        color: Some(num),
+                    } => col(num, &glyph),
+                };
+
+                (OsString::from(key), icon)
+            })
+            .collect()
+    }
+}
+
 /// Convenience method to paint fixed colors.
 fn col(num: u8, code: &str) -> String {
     Color::Fixed(num).paint(code).to_string()
@@ -0,0 +1,30 @@
+use crate::render::context::Context;
+use std::{fs::File, io, sync::Mutex};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// Logs traversals to the file provided via `--log-file`, or stderr otherwise, if either it or
+/// `--log-level` is provided. Spans are logged along with how long they took once they close.
+pub fn init(ctx: &Context) -> io::Result<()> {
+    if ctx.log_level.is_none() && ctx.log_file.is_none() {
+        return Ok(());
+    }
+
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::from(ctx.log_level.unwrap_or_default()))
+        .with_span_events(FmtSpan::CLOSE)
+        .with_thread_names(true);
+
+    match ctx.log_file {
+        Some(ref path) => {
+            let file = File::create(path)?;
+
+            subscriber
+                .with_ansi(false)
+                .with_writer(Mutex::new(file))
+                .init();
+        }
+        None => subscriber.with_writer(io::stderr).init(),
+    }
+
+    Ok(())
+}
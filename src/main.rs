@@ -19,19 +19,43 @@
     clippy::type_complexity,
     clippy::fallible_impl_from
 )]
+use backend::mirror::Mirror;
 use clap::CommandFactory;
-use render::{context::Context, tree::Tree};
-use std::{io::stdout, process::ExitCode};
+use render::{
+    canvas::{self, svg::Svg},
+    context::{output::Output, subcommand::Subcommand, Context},
+    tree::{bench, doctor, document::Document, history, ignore_check, Tree},
+};
+use std::{
+    fmt::Display,
+    io::{self, stdout, BufWriter, Write},
+    process::ExitCode,
+};
+
+/// Sources of entries other than the local filesystem.
+mod backend;
 
 /// Filesystem operations.
 mod fs;
 
+/// Translations of messages, summaries, and unit names.
+mod i18n;
+
 /// Dev icons.
 mod icons;
 
+/// Structured logging of traversals.
+mod logging;
+
 /// Tools and operations to display root-directory.
 mod render;
 
+/// Scheduling of the threads that traverse the filesystem.
+mod sched;
+
+/// Queries about the terminal.
+mod tty;
+
 /// Common utilities.
 mod utils;
 
@@ -45,18 +69,120 @@ fn main() -> ExitCode {
 }
 
 fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let ctx = Context::init()?;
+    fs::handles::raise_limit();
+
+    let mut ctx = Context::init()?;
 
     if let Some(shell) = ctx.completions {
         clap_complete::generate(shell, &mut Context::command(), "et", &mut stdout().lock());
         return Ok(());
     }
 
-    render::styles::init();
+    if ctx.schema {
+        writeln!(
+            stdout().lock(),
+            "{}",
+            render::tree::report::schema(ctx.report)?
+        )?;
+        return Ok(());
+    }
+
+    i18n::init(ctx.lang);
+
+    render::styles::init(ctx.accessible);
+
+    fs::handles::init(ctx.max_open);
+
+    logging::init(&ctx)?;
+
+    if let Some(ref icon_map) = ctx.icon_map {
+        icons::init(icon_map)?;
+    }
+
+    match ctx.subcommand {
+        Some(Subcommand::Daemon(ref args)) => return Ok(history::run_daemon(&ctx, args)?),
+        Some(Subcommand::History(ref args)) => {
+            writeln!(stdout().lock(), "{}", history::plot(&ctx, args)?)?;
+            return Ok(());
+        }
+        Some(Subcommand::Bench(ref args)) => {
+            writeln!(stdout().lock(), "{}", bench::run(&ctx, args)?)?;
+            return Ok(());
+        }
+        Some(Subcommand::IgnoreCheck(ref args)) => {
+            writeln!(stdout().lock(), "{}", ignore_check::run(&ctx, args)?)?;
+            return Ok(());
+        }
+        Some(Subcommand::Doctor) => {
+            writeln!(stdout().lock(), "{}", doctor::run())?;
+            return Ok(());
+        }
+        None => (),
+    }
+
+    // Kept around until the tree is printed as the mirror is removed once dropped.
+    let _mirror = mirror(&mut ctx)?;
 
     let tree = Tree::init(ctx)?;
 
-    println!("{tree}");
+    let _render = tracing::info_span!("render").entered();
+
+    let ctx = tree.context();
+
+    // The tree is rendered to a string only when it has to be processed further.
+    let text = || {
+        let output = tree.to_string();
+
+        if ctx.deterministic {
+            render::styles::strip_ansi(&output)
+        } else {
+            output
+        }
+    };
+
+    let output = match ctx.output {
+        Output::TreeSvg => {
+            let mut svg = Svg::new();
+            canvas::paint(&text(), &mut svg);
+            Some(svg.to_string())
+        }
+        Output::Html => Some(Document::new(&tree, &text()).to_string()),
+        _ if ctx.deterministic => Some(text()),
+        _ => None,
+    };
+
+    let output: &dyn Display = output.as_ref().map_or(&tree, |output| output);
+
+    let mut stdout = stdout().lock();
+
+    match ctx.render_buffer {
+        Some(kb) => {
+            let capacity = usize::try_from(kb.saturating_mul(1024)).unwrap_or(usize::MAX);
+            let mut writer = BufWriter::with_capacity(capacity, stdout);
+            writeln!(writer, "{output}")?;
+            writer.flush()?;
+        }
+        None => writeln!(stdout, "{output}")?,
+    }
 
     Ok(())
 }
+
+/// Swaps the root for a local mirror if it's provided by a [backend] other than the local
+/// filesystem.
+fn mirror(ctx: &mut Context) -> io::Result<Option<Mirror>> {
+    let Some(backend) = backend::of(ctx) else {
+        return Ok(None);
+    };
+
+    let mirror = Mirror::of(backend.as_ref())?;
+
+    // Notes provided explicitly take precedence over those of the backend.
+    if let Some(annotations) = mirror.annotations() {
+        ctx.annotate.get_or_insert_with(|| annotations.to_owned());
+    }
+
+    ctx.mirror(mirror.root(), &backend.label());
+
+    Ok(Some(mirror))
+}
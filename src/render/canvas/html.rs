@@ -0,0 +1,73 @@
+use super::{escape, hex, Canvas, BACKGROUND};
+use ansi_term::Style;
+use std::fmt::{self, Display, Write};
+
+/// A [Canvas] that draws text as the contents of an HTML `<pre>` element, styling spans of it
+/// inline so that it can be embedded without a stylesheet. It's meant to be shown on
+/// [BACKGROUND] as in a terminal.
+#[derive(Default)]
+pub struct Html {
+    html: String,
+}
+
+impl Canvas for Html {
+    fn text(&mut self, text: &str, style: Style) {
+        let (foreground, background) = if style.is_reverse {
+            (style.background, style.foreground)
+        } else {
+            (style.foreground, style.background)
+        };
+
+        let mut css = vec![];
+
+        match foreground {
+            Some(color) => css.push(format!("color:{}", hex(color))),
+            None if style.is_reverse => css.push(format!("color:{BACKGROUND}")),
+            None => (),
+        }
+
+        if let Some(color) = background {
+            css.push(format!("background:{}", hex(color)));
+        }
+
+        if style.is_bold {
+            css.push("font-weight:bold".to_owned());
+        }
+
+        if style.is_dimmed {
+            css.push("opacity:0.6".to_owned());
+        }
+
+        if style.is_italic {
+            css.push("font-style:italic".to_owned());
+        }
+
+        match (style.is_underline, style.is_strikethrough) {
+            (true, true) => css.push("text-decoration:underline line-through".to_owned()),
+            (true, false) => css.push("text-decoration:underline".to_owned()),
+            (false, true) => css.push("text-decoration:line-through".to_owned()),
+            (false, false) => (),
+        }
+
+        if css.is_empty() {
+            self.html.push_str(&escape(text));
+        } else {
+            let _ = write!(
+                self.html,
+                r#"<span style="{}">{}</span>"#,
+                css.join(";"),
+                escape(text)
+            );
+        }
+    }
+
+    fn newline(&mut self) {
+        self.html.push('\n');
+    }
+}
+
+impl Display for Html {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.html)
+    }
+}
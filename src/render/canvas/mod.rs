@@ -0,0 +1,168 @@
+use super::styles::{self, Segment};
+use ansi_term::{Color, Style};
+
+/// Drawing of output as HTML.
+pub mod html;
+
+/// Drawing of output as an SVG image.
+pub mod svg;
+
+/// Colors of the background and of text without one of its own, as in a dark terminal theme.
+pub const BACKGROUND: &str = "#1e1e1e";
+pub const FOREGROUND: &str = "#d4d4d4";
+
+/// The 16 colors of the basic palette, the basic eight then their bright variants, as in the dark
+/// theme of VS Code whose [BACKGROUND] keeps them legible.
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 49, 49),
+    (13, 188, 121),
+    (229, 229, 16),
+    (36, 114, 200),
+    (188, 63, 188),
+    (17, 168, 205),
+    (229, 229, 229),
+    (102, 102, 102),
+    (241, 76, 76),
+    (35, 209, 139),
+    (245, 245, 67),
+    (59, 142, 234),
+    (214, 112, 214),
+    (41, 184, 219),
+    (229, 229, 229),
+];
+
+/// Levels of each of red, green, and blue in the 6x6x6 color cube of the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// A surface onto which lines of styled text, as laid out for the terminal, are drawn. This lets
+/// output be rendered to formats other than the terminal without laying it out anew.
+pub trait Canvas {
+    /// Draws `text` in `style` right after whatever was last drawn on the current line.
+    fn text(&mut self, text: &str, style: Style);
+
+    /// Moves on to the start of the next line.
+    fn newline(&mut self);
+}
+
+/// Draws `output`, as it would be printed to the terminal, onto `canvas`. Colors and other
+/// attributes set by SGR escape sequences carry over to the text that follows them whereas other
+/// escape sequences, such as those of hyperlinks, are dropped.
+pub fn paint(output: &str, canvas: &mut impl Canvas) {
+    let mut style = Style::new();
+
+    for (i, line) in output.trim_end_matches('\n').split('\n').enumerate() {
+        if i > 0 {
+            canvas.newline();
+        }
+
+        for segment in styles::segments(line) {
+            match segment {
+                Segment::Text(text) => canvas.text(text, style),
+                Segment::Sgr(params) => apply_sgr(&mut style, params),
+                Segment::Escape => (),
+            }
+        }
+    }
+}
+
+/// Updates `style` as per the parameters of a Select Graphic Rendition sequence e.g. `1;38;5;208`.
+fn apply_sgr(style: &mut Style, params: &str) {
+    let mut codes = params
+        .split(';')
+        .map(|code| code.parse::<u8>().unwrap_or(0));
+
+    while let Some(code) = codes.next() {
+        match code {
+            0 => *style = Style::new(),
+            1 => style.is_bold = true,
+            2 => style.is_dimmed = true,
+            3 => style.is_italic = true,
+            4 => style.is_underline = true,
+            7 => style.is_reverse = true,
+            9 => style.is_strikethrough = true,
+            22 => {
+                style.is_bold = false;
+                style.is_dimmed = false;
+            }
+            23 => style.is_italic = false,
+            24 => style.is_underline = false,
+            27 => style.is_reverse = false,
+            29 => style.is_strikethrough = false,
+            30..=37 => style.foreground = Some(basic(code - 30)),
+            38 => style.foreground = extended(&mut codes),
+            39 => style.foreground = None,
+            40..=47 => style.background = Some(basic(code - 40)),
+            48 => style.background = extended(&mut codes),
+            49 => style.background = None,
+            90..=97 => style.foreground = Some(Color::Fixed(code - 90 + 8)),
+            100..=107 => style.background = Some(Color::Fixed(code - 100 + 8)),
+            _ => (),
+        }
+    }
+}
+
+/// One of the eight basic colors by its index.
+const fn basic(index: u8) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Purple,
+        6 => Color::Cyan,
+        _ => Color::White,
+    }
+}
+
+/// A 256-color, e.g. `5;208`, or 24-bit color, e.g. `2;255;128;0`, following a `38` or `48`.
+fn extended(codes: &mut impl Iterator<Item = u8>) -> Option<Color> {
+    match codes.next()? {
+        5 => codes.next().map(Color::Fixed),
+        2 => Some(Color::RGB(codes.next()?, codes.next()?, codes.next()?)),
+        _ => None,
+    }
+}
+
+/// `color` as a hexadecimal RGB triplet e.g. `#cd3131`.
+pub fn hex(color: Color) -> String {
+    let (r, g, b) = rgb(color);
+
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Levels of red, green, and blue of `color`, with those of the basic palette as in [PALETTE].
+pub fn rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Black => PALETTE[0],
+        Color::Red => PALETTE[1],
+        Color::Green => PALETTE[2],
+        Color::Yellow => PALETTE[3],
+        Color::Blue => PALETTE[4],
+        Color::Purple => PALETTE[5],
+        Color::Cyan => PALETTE[6],
+        Color::White => PALETTE[7],
+        Color::Fixed(n) if n < 16 => PALETTE[usize::from(n)],
+        Color::Fixed(n) if n < 232 => {
+            let n = usize::from(n - 16);
+            (
+                CUBE_LEVELS[n / 36],
+                CUBE_LEVELS[(n / 6) % 6],
+                CUBE_LEVELS[n % 6],
+            )
+        }
+        Color::Fixed(n) => {
+            let gray = 8 + (n - 232) * 10;
+            (gray, gray, gray)
+        }
+        Color::RGB(r, g, b) => (r, g, b),
+    }
+}
+
+/// Escapes the characters that are special in the text of XML and HTML.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,148 @@
+use super::{escape, hex, Canvas, BACKGROUND, FOREGROUND};
+use crate::render::styles;
+use ansi_term::Style;
+use std::fmt::{self, Display, Write};
+
+/// Width of a character and height of a line of the monospace font in pixels.
+const CHAR_WIDTH: f64 = 8.4;
+const LINE_HEIGHT: f64 = 20.0;
+
+/// Distance from the top of a line to the baseline of its text in pixels.
+const BASELINE: f64 = 15.0;
+
+/// Font size in pixels, which is about twice [CHAR_WIDTH] in typical monospace fonts.
+const FONT_SIZE: u8 = 14;
+
+/// Space around the text in pixels.
+const MARGIN: f64 = 16.0;
+
+/// A [Canvas] that draws text as an SVG image in a monospace font on a dark background, as it
+/// would appear in a terminal.
+pub struct Svg {
+    lines: Vec<Vec<(String, Style)>>,
+}
+
+impl Svg {
+    pub fn new() -> Self {
+        Self {
+            lines: vec![vec![]],
+        }
+    }
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Canvas for Svg {
+    fn text(&mut self, text: &str, style: Style) {
+        if let Some(line) = self.lines.last_mut() {
+            line.push((text.to_owned(), style));
+        }
+    }
+
+    fn newline(&mut self) {
+        self.lines.push(vec![]);
+    }
+}
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let columns = self
+            .lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|(text, _)| styles::visible_width(text))
+                    .sum()
+            })
+            .max()
+            .unwrap_or(0);
+
+        let width = (columns as f64).mul_add(CHAR_WIDTH, MARGIN * 2.0);
+        let height = (self.lines.len() as f64).mul_add(LINE_HEIGHT, MARGIN * 2.0);
+
+        let mut backgrounds = String::new();
+        let mut texts = String::new();
+
+        for (i, line) in self.lines.iter().enumerate() {
+            let y = (i as f64).mul_add(LINE_HEIGHT, MARGIN);
+            let mut column = 0;
+
+            write!(
+                texts,
+                r#"<text x="{MARGIN}" y="{:.1}" xml:space="preserve">"#,
+                y + BASELINE
+            )?;
+
+            for (text, style) in line {
+                let len = styles::visible_width(text);
+
+                let (foreground, background) = if style.is_reverse {
+                    (style.background, style.foreground)
+                } else {
+                    (style.foreground, style.background)
+                };
+
+                if let Some(background) = background {
+                    writeln!(
+                        backgrounds,
+                        r#"<rect x="{:.1}" y="{y:.1}" width="{:.1}" height="{LINE_HEIGHT}" fill="{}"/>"#,
+                        (column as f64).mul_add(CHAR_WIDTH, MARGIN),
+                        len as f64 * CHAR_WIDTH,
+                        hex(background),
+                    )?;
+                }
+
+                write!(texts, "<tspan")?;
+
+                match foreground {
+                    Some(color) => write!(texts, r#" fill="{}""#, hex(color))?,
+                    None if style.is_reverse => write!(texts, r#" fill="{BACKGROUND}""#)?,
+                    None => (),
+                }
+
+                if style.is_bold {
+                    write!(texts, r#" font-weight="bold""#)?;
+                }
+
+                if style.is_dimmed {
+                    write!(texts, r#" opacity="0.6""#)?;
+                }
+
+                if style.is_italic {
+                    write!(texts, r#" font-style="italic""#)?;
+                }
+
+                match (style.is_underline, style.is_strikethrough) {
+                    (true, true) => write!(texts, r#" text-decoration="underline line-through""#)?,
+                    (true, false) => write!(texts, r#" text-decoration="underline""#)?,
+                    (false, true) => write!(texts, r#" text-decoration="line-through""#)?,
+                    (false, false) => (),
+                }
+
+                write!(texts, ">{}</tspan>", escape(text))?;
+
+                column += len;
+            }
+
+            writeln!(texts, "</text>")?;
+        }
+
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.1}" height="{height:.1}" viewBox="0 0 {width:.1} {height:.1}" font-family="ui-monospace, Menlo, Consolas, 'DejaVu Sans Mono', monospace" font-size="{FONT_SIZE}">"#
+        )?;
+        writeln!(
+            f,
+            r#"<rect width="100%" height="100%" rx="6" fill="{BACKGROUND}"/>"#
+        )?;
+        write!(f, "{backgrounds}")?;
+        writeln!(f, r#"<g fill="{FOREGROUND}">"#)?;
+        write!(f, "{texts}")?;
+        writeln!(f, "</g>")?;
+        write!(f, "</svg>")
+    }
+}
@@ -0,0 +1,13 @@
+use clap::ValueEnum;
+
+/// How to display macOS bundles such as `.app` and `.framework` directories.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum Bundles {
+    /// Show each bundle as a single entry with the combined size of its contents, as Finder does
+    #[cfg_attr(target_os = "macos", default)]
+    Collapse,
+
+    /// Show the contents of bundles like those of any other directory
+    #[cfg_attr(not(target_os = "macos"), default)]
+    Expand,
+}
@@ -0,0 +1,15 @@
+use clap::ValueEnum;
+
+/// What the names of files are colored by.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum ColorBy {
+    /// The type of file as per `LS_COLORS`
+    #[default]
+    Type,
+
+    /// How long ago the file was modified, from bright for recent to dim for old
+    Age,
+
+    /// The user that owns the file, each with a color of their own
+    Owner,
+}
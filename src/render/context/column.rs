@@ -0,0 +1,35 @@
+use clap::ValueEnum;
+
+/// Metadata that can be printed in its own column with `--columns`.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum Column {
+    /// File-type and permissions akin to `ls -l`
+    Perms,
+
+    /// Name of the user that owns the entry
+    Owner,
+
+    /// Disk usage
+    Size,
+
+    /// Time of last modification in UTC
+    Mtime,
+
+    /// What --column-cmd printed for the entry
+    Cmd,
+
+    /// The tree itself along with the name of every entry
+    Name,
+}
+
+/// Parses the command of `--column-cmd`, which is split into words as by a POSIX shell so that
+/// quoted words may contain whitespace.
+pub fn parse_command(input: &str) -> Result<String, String> {
+    let words = shell_words::split(input).map_err(|e| format!("invalid command: {e}"))?;
+
+    if words.is_empty() {
+        return Err(String::from("command cannot be empty"));
+    }
+
+    Ok(input.to_owned())
+}
@@ -1,3 +1,4 @@
+use crate::fs::path::expand;
 use std::{
     env, fs,
     path::{Path, PathBuf},
@@ -11,76 +12,107 @@ const HOME: &str = "HOME";
 const XDG_CONFIG_HOME: &str = "XDG_CONFIG_HOME";
 
 /// Reads the config file into a `String` if there is one. When `None` is provided then the config
-/// is looked for in the following locations in order:
-///
-/// - `$ERDTREE_CONFIG_PATH`
-/// - `$XDG_CONFIG_HOME/erdtree/.erdtreerc`
-/// - `$XDG_CONFIG_HOME/.erdtreerc`
-/// - `$HOME/.config/erdtree/.erdtreerc`
-/// - `$HOME/.erdtreerc`
+/// is looked for in the locations listed by [`config_path`].
 pub fn read_config_to_string<T: AsRef<Path>>(path: Option<T>) -> Option<String> {
     path.map(fs::read_to_string)
         .and_then(Result::ok)
-        .or_else(config_from_config_path)
-        .or_else(config_from_xdg_path)
-        .or_else(config_from_home)
+        .or_else(|| config_path().and_then(|path| fs::read_to_string(path).ok()))
         .map(|e| prepend_arg_prefix(&e))
 }
 
-/// Parses the config `str`, removing comments and preparing it as a format understood by
-/// [`get_matches_from`].
+/// Locates the config file, being the first of the following that can be read:
 ///
-/// [`get_matches_from`]: clap::builder::Command::get_matches_from
-pub fn parse<'a>(config: &'a str) -> Vec<&'a str> {
+/// - `$ERDTREE_CONFIG_PATH`, in which `~` and `$VARS` are expanded
+/// - `$XDG_CONFIG_HOME/erdtree/.erdtreerc`
+/// - `$XDG_CONFIG_HOME/.erdtreerc`
+/// - `$HOME/.config/erdtree/.erdtreerc`
+/// - `$HOME/.erdtreerc`
+pub fn config_path() -> Option<PathBuf> {
+    let config_path = env::var_os(ERDTREE_CONFIG_PATH)
+        .map(PathBuf::from)
+        .map(|path| expand(&path).into_owned());
+
+    let xdg_paths = env::var_os(XDG_CONFIG_HOME)
+        .map(PathBuf::from)
+        .map(|xdg_config| {
+            [
+                xdg_config.join(ERDTREE_DIR).join(ERDTREE_CONFIG_NAME),
+                xdg_config.join(ERDTREE_CONFIG_NAME),
+            ]
+        });
+
+    let home_paths = env::var_os(HOME).map(PathBuf::from).map(|home| {
+        [
+            home.join(CONFIG_DIR)
+                .join(ERDTREE_DIR)
+                .join(ERDTREE_CONFIG_NAME),
+            home.join(ERDTREE_CONFIG_NAME),
+        ]
+    });
+
+    config_path
+        .into_iter()
+        .chain(xdg_paths.into_iter().flatten())
+        .chain(home_paths.into_iter().flatten())
+        .find(|path| fs::read_to_string(path).is_ok())
+}
+
+/// Names of the profiles defined in the config `str` by `[name]` headers, in order.
+pub fn profiles(config: &str) -> Vec<&str> {
     config
         .lines()
-        .filter(|line| {
-            line.trim_start()
-                .chars()
-                .next()
-                .map_or(true, |ch| ch != '#')
+        .filter_map(|line| {
+            line.trim()
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
         })
-        .flat_map(str::split_ascii_whitespace)
-        .collect::<Vec<&'a str>>()
+        .collect()
 }
 
-/// Try to read in config from `ERDTREE_CONFIG_PATH`.
-fn config_from_config_path() -> Option<String> {
-    env::var_os(ERDTREE_CONFIG_PATH)
-        .map(PathBuf::from)
-        .map(fs::read_to_string)
-        .and_then(Result::ok)
-}
+/// Parses the config `str`, removing comments and preparing it as a format understood by
+/// [`get_matches_from`]. Lines preceding the first `[name]` header always apply whereas the lines
+/// beneath a header only apply if `profile` is `name`, in which case they follow the former so as
+/// to take precedence. Returns `None` if `profile` isn't defined.
+///
+/// [`get_matches_from`]: clap::builder::Command::get_matches_from
+pub fn parse<'a>(config: &'a str, profile: Option<&str>) -> Option<Vec<&'a str>> {
+    let mut args = vec![];
+    let mut profile_args = vec![];
+    let mut found_profile = profile.is_none();
+    let mut section = None;
 
-/// Try to read in config from either one of the following locations:
-/// - `$HOME/.config/erdtree/.erdtreerc`
-/// - `$HOME/.erdtreerc`
-fn config_from_home() -> Option<String> {
-    let home = env::var_os(HOME).map(PathBuf::from)?;
+    #[allow(clippy::unnecessary_map_or)]
+    let lines = config.lines().filter(|line| {
+        line.trim_start()
+            .chars()
+            .next()
+            .map_or(true, |ch| ch != '#')
+    });
 
-    let config_path = home
-        .join(CONFIG_DIR)
-        .join(ERDTREE_DIR)
-        .join(ERDTREE_CONFIG_NAME);
+    for line in lines {
+        let trimmed = line.trim();
 
-    fs::read_to_string(config_path).ok().or_else(|| {
-        let config_path = home.join(ERDTREE_CONFIG_NAME);
-        fs::read_to_string(config_path).ok()
-    })
-}
+        if let Some(name) = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            found_profile |= Some(name) == profile;
+            section = Some(name);
+            continue;
+        }
 
-/// Try to read in config from either one of the following locations:
-/// - `$XDG_CONFIG_HOME/erdtree/.erdtreerc`
-/// - `$XDG_CONFIG_HOME/.erdtreerc`
-fn config_from_xdg_path() -> Option<String> {
-    let xdg_config = env::var_os(XDG_CONFIG_HOME).map(PathBuf::from)?;
+        match section {
+            None => args.extend(line.split_ascii_whitespace()),
+            Some(name) if Some(name) == profile => {
+                profile_args.extend(line.split_ascii_whitespace());
+            }
+            Some(_) => (),
+        }
+    }
 
-    let config_path = xdg_config.join(ERDTREE_DIR).join(ERDTREE_CONFIG_NAME);
+    args.extend(profile_args);
 
-    fs::read_to_string(config_path).ok().or_else(|| {
-        let config_path = xdg_config.join(ERDTREE_CONFIG_NAME);
-        fs::read_to_string(config_path).ok()
-    })
+    found_profile.then_some(args)
 }
 
 /// Prepends "--\n" to the config string which is required for proper parsing by
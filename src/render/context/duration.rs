@@ -0,0 +1,141 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// Parses a human-friendly duration such as `90s`, `30m`, `1h`, `2d`, `1w`, or a combination of
+/// them such as `1h30m`. A bare number is interpreted as seconds.
+pub fn parse(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+
+    if input.is_empty() {
+        return Err(String::from("duration cannot be empty"));
+    }
+
+    if let Ok(secs) = input.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total = 0_u64;
+    let mut digits = String::new();
+
+    for ch in input.chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+
+        let unit = match ch {
+            's' => 1,
+            'm' => MINUTE,
+            'h' => HOUR,
+            'd' => DAY,
+            'w' => WEEK,
+            _ => return Err(format!("invalid duration unit '{ch}' in '{input}'")),
+        };
+
+        let amount = digits
+            .parse::<u64>()
+            .map_err(|_| format!("expected a number before '{ch}' in '{input}'"))?;
+
+        total += amount * unit;
+        digits.clear();
+    }
+
+    if !digits.is_empty() {
+        return Err(format!("missing unit after '{digits}' in '{input}'"));
+    }
+
+    Ok(Duration::from_secs(total))
+}
+
+/// Parses a UTC date such as `2023-05-01`, optionally followed by a time such as `18:30` or
+/// `18:30:15` separated by a space or `T`. Dates without a time refer to the start of the day.
+pub fn parse_date(input: &str) -> Result<SystemTime, String> {
+    let input = input.trim();
+
+    let invalid = || format!("expected a date such as 2023-05-01 or 2023-05-01 18:30 in '{input}'");
+
+    let (date, time) = input
+        .split_once([' ', 'T'])
+        .map_or((input, None), |(date, time)| (date, Some(time)));
+
+    let numbers = |raw: &str, sep: char| {
+        raw.split(sep)
+            .map(|n| n.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()
+    };
+
+    let Some(&[year, month, day]) = numbers(date, '-').as_deref() else {
+        return Err(invalid());
+    };
+
+    let (hour, minute, second) = match time.map(|time| numbers(time, ':')) {
+        None => (0, 0, 0),
+        Some(Some(hm)) if hm.len() == 2 => (hm[0], hm[1], 0),
+        Some(Some(hms)) if hms.len() == 3 => (hms[0], hms[1], hms[2]),
+        Some(_) => return Err(invalid()),
+    };
+
+    crate::utils::from_civil(year, month, day, hour, minute, second).ok_or_else(invalid)
+}
+
+/// Formats a point in time as a UTC date and time e.g. `2023-04-01 12:30:00`. Times before the
+/// epoch are clamped to it.
+pub fn timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let (days, rem) = (secs / DAY, secs % DAY);
+
+    // Days since the epoch to a civil date as per Howard Hinnant's `civil_from_days`.
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        rem / HOUR,
+        rem % HOUR / MINUTE,
+        rem % MINUTE
+    )
+}
+
+/// Formats a [Duration] using its two most significant units e.g. `3d 4h`.
+pub fn format(duration: Duration) -> String {
+    let mut secs = duration.as_secs();
+
+    let units = [
+        (WEEK, "w"),
+        (DAY, "d"),
+        (HOUR, "h"),
+        (MINUTE, "m"),
+        (1, "s"),
+    ];
+
+    let components = units
+        .iter()
+        .filter_map(|(unit_secs, unit)| {
+            let amount = secs / unit_secs;
+            secs %= unit_secs;
+            (amount > 0).then(|| format!("{amount}{unit}"))
+        })
+        .take(2)
+        .collect::<Vec<_>>();
+
+    if components.is_empty() {
+        String::from("0s")
+    } else {
+        components.join(" ")
+    }
+}
@@ -0,0 +1,324 @@
+use super::{duration, size};
+use crate::render::tree::node::Node;
+use std::{
+    iter::Peekable,
+    str::Chars,
+    time::{Duration, SystemTime},
+};
+
+/// Predicate parsed from an expression such as `size > 100M && ext == "log" && mtime < 30d`
+/// that entries other than directories must satisfy to be printed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Filter {
+    /// Both sides must hold.
+    And(Box<Self>, Box<Self>),
+
+    /// Either side must hold.
+    Or(Box<Self>, Box<Self>),
+
+    /// The inner expression must not hold.
+    Not(Box<Self>),
+
+    /// An attribute of the entry compared against a value.
+    Compare(Comparison),
+}
+
+/// Comparison of a single attribute of an entry against a value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    /// Disk usage in bytes.
+    Size(Op, u64),
+
+    /// Time elapsed since the last modification.
+    Mtime(Op, Duration),
+
+    /// Depth relative to the root directory.
+    Depth(Op, usize),
+
+    /// File name.
+    Name(Op, String),
+
+    /// File extension without the leading dot; empty if there is none.
+    Ext(Op, String),
+}
+
+/// Comparison operators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Lexical units of a filter expression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    /// Attribute names and unquoted values such as `100M` or `30d`.
+    Word(String),
+
+    /// Double-quoted values.
+    Str(String),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+/// Parses a filter expression. `&&` binds tighter than `||` and `!` negates the expression that
+/// follows it; parentheses may be used for grouping.
+pub fn parse(input: &str) -> Result<Filter, String> {
+    let tokens = tokenize(input)?;
+
+    let mut parser = Parser {
+        tokens: tokens.into_iter().peekable(),
+    };
+
+    let filter = parser.or()?;
+
+    if let Some(token) = parser.tokens.next() {
+        return Err(format!("unexpected {} in '{input}'", token.describe()));
+    }
+
+    Ok(filter)
+}
+
+impl Filter {
+    /// Whether `node` satisfies the filter.
+    pub fn matches(&self, node: &Node) -> bool {
+        match self {
+            Self::And(lhs, rhs) => lhs.matches(node) && rhs.matches(node),
+            Self::Or(lhs, rhs) => lhs.matches(node) || rhs.matches(node),
+            Self::Not(inner) => !inner.matches(node),
+            Self::Compare(comparison) => comparison.matches(node),
+        }
+    }
+}
+
+impl Comparison {
+    fn matches(&self, node: &Node) -> bool {
+        match self {
+            Self::Size(op, bytes) => {
+                let size = node.file_size().map_or(0, |fs| fs.bytes);
+                op.apply(&size, bytes)
+            }
+            Self::Mtime(op, duration) => node
+                .modified()
+                .map(|modified| {
+                    SystemTime::now()
+                        .duration_since(modified)
+                        .unwrap_or(Duration::ZERO)
+                })
+                .map_or(false, |age| op.apply(&age, duration)),
+            Self::Depth(op, depth) => op.apply(&node.depth(), depth),
+            Self::Name(op, name) => op.apply(node.file_name_lossy().as_ref(), name.as_str()),
+            Self::Ext(op, ext) => {
+                let extension = node
+                    .path()
+                    .extension()
+                    .map(|ext| ext.to_string_lossy())
+                    .unwrap_or_default();
+
+                op.apply(extension.as_ref(), ext.as_str())
+            }
+        }
+    }
+}
+
+impl Op {
+    fn apply<T: Ord + ?Sized>(self, lhs: &T, rhs: &T) -> bool {
+        match self {
+            Self::Eq => lhs == rhs,
+            Self::Ne => lhs != rhs,
+            Self::Lt => lhs < rhs,
+            Self::Le => lhs <= rhs,
+            Self::Gt => lhs > rhs,
+            Self::Ge => lhs >= rhs,
+        }
+    }
+
+    const fn symbol(self) -> &'static str {
+        match self {
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Self::Word(word) => format!("'{word}'"),
+            Self::Str(string) => format!("\"{string}\""),
+            Self::Op(op) => format!("'{}'", op.symbol()),
+            Self::And => String::from("'&&'"),
+            Self::Or => String::from("'||'"),
+            Self::Not => String::from("'!'"),
+            Self::LParen => String::from("'('"),
+            Self::RParen => String::from("')'"),
+        }
+    }
+}
+
+/// Recursive descent parser over the tokens of a filter expression.
+struct Parser {
+    tokens: Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl Parser {
+    fn or(&mut self) -> Result<Filter, String> {
+        let mut lhs = self.and()?;
+
+        while self.tokens.next_if_eq(&Token::Or).is_some() {
+            let rhs = self.and()?;
+            lhs = Filter::Or(Box::new(lhs), Box::new(rhs));
+        }
+
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Filter, String> {
+        let mut lhs = self.unary()?;
+
+        while self.tokens.next_if_eq(&Token::And).is_some() {
+            let rhs = self.unary()?;
+            lhs = Filter::And(Box::new(lhs), Box::new(rhs));
+        }
+
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Filter, String> {
+        match self.tokens.next() {
+            Some(Token::Not) => Ok(Filter::Not(Box::new(self.unary()?))),
+            Some(Token::LParen) => {
+                let inner = self.or()?;
+
+                match self.tokens.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    Some(token) => Err(format!("expected ')' but found {}", token.describe())),
+                    None => Err(String::from("missing ')'")),
+                }
+            }
+            Some(Token::Word(field)) => self.comparison(&field).map(Filter::Compare),
+            Some(token) => Err(format!(
+                "expected an attribute but found {}",
+                token.describe()
+            )),
+            None => Err(String::from("expected an attribute")),
+        }
+    }
+
+    fn comparison(&mut self, field: &str) -> Result<Comparison, String> {
+        let op = match self.tokens.next() {
+            Some(Token::Op(op)) => op,
+            Some(token) => {
+                return Err(format!(
+                    "expected an operator after '{field}' but found {}",
+                    token.describe()
+                ))
+            }
+            None => return Err(format!("expected an operator after '{field}'")),
+        };
+
+        let value = match self.tokens.next() {
+            Some(Token::Word(value) | Token::Str(value)) => value,
+            Some(token) => {
+                return Err(format!(
+                    "expected a value after '{field} {}' but found {}",
+                    op.symbol(),
+                    token.describe()
+                ))
+            }
+            None => return Err(format!("expected a value after '{field} {}'", op.symbol())),
+        };
+
+        let textual = |value: String| {
+            if matches!(op, Op::Eq | Op::Ne) {
+                Ok(value)
+            } else {
+                Err(format!("'{field}' can only be compared with '==' or '!='"))
+            }
+        };
+
+        match field {
+            "size" => size::parse(&value).map(|bytes| Comparison::Size(op, bytes)),
+            "mtime" => duration::parse(&value).map(|age| Comparison::Mtime(op, age)),
+            "depth" => value
+                .parse()
+                .map(|depth| Comparison::Depth(op, depth))
+                .map_err(|_| format!("expected a number for depth but found '{value}'")),
+            "name" => textual(value).map(|name| Comparison::Name(op, name)),
+            "ext" => textual(value).map(|ext| Comparison::Ext(op, ext)),
+            _ => Err(format!(
+                "unknown attribute '{field}'; expected one of size, mtime, depth, name, ext"
+            )),
+        }
+    }
+}
+
+/// Splits a filter expression into [Token]s.
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        let token = match ch {
+            ch if ch.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '&' if chars.next_if_eq(&'&').is_some() => Token::And,
+            '|' if chars.next_if_eq(&'|').is_some() => Token::Or,
+            '=' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Eq),
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Ne),
+            '!' => Token::Not,
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Le),
+            '<' => Token::Op(Op::Lt),
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Ge),
+            '>' => Token::Op(Op::Gt),
+            '"' => Token::Str(string(&mut chars)?),
+            ch if is_word_char(ch) => {
+                let mut word = String::from(ch);
+
+                while let Some(ch) = chars.next_if(|ch| is_word_char(*ch)) {
+                    word.push(ch);
+                }
+
+                Token::Word(word)
+            }
+            _ => return Err(format!("unexpected character '{ch}' in '{input}'")),
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Reads a double-quoted string whose opening quote has already been consumed. Quotes and
+/// backslashes within it are escaped with a backslash.
+fn string(chars: &mut Peekable<Chars<'_>>) -> Result<String, String> {
+    let mut string = String::new();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => return Ok(string),
+            '\\' => string.extend(chars.next()),
+            _ => string.push(ch),
+        }
+    }
+
+    Err(format!("unterminated string \"{string}"))
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || matches!(ch, '.' | '_' | '-')
+}
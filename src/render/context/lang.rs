@@ -0,0 +1,65 @@
+use std::env;
+
+/// Languages that messages, summaries, and unit names can be printed in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Lang {
+    #[default]
+    En,
+    De,
+    Es,
+    Fr,
+}
+
+/// Parses a language or locale such as `de`, `fr_FR`, or `es_ES.UTF-8`. `auto` uses the language
+/// of the environment as determined by `LC_ALL`, `LC_MESSAGES`, and `LANG` in that order, whereas
+/// `C` and `POSIX` mean English.
+pub fn parse(input: &str) -> Result<Lang, String> {
+    if input == "auto" {
+        return Ok(Lang::from_env());
+    }
+
+    // Strip the encoding and modifier as in `de_DE.UTF-8@euro`.
+    let name = input.split(['.', '@']).next().unwrap_or_default();
+
+    if matches!(name, "C" | "POSIX") {
+        return Ok(Lang::En);
+    }
+
+    let language = name
+        .split(['_', '-'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    match language.as_str() {
+        "en" => Ok(Lang::En),
+        "de" => Ok(Lang::De),
+        "es" => Ok(Lang::Es),
+        "fr" => Ok(Lang::Fr),
+        _ => Err(format!("unsupported language '{input}'")),
+    }
+}
+
+impl Lang {
+    /// The language of the environment. Languages of the environment that aren't supported fall
+    /// back to English.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .filter(|locale| locale != "auto")
+            .and_then(|locale| parse(&locale).ok())
+            .unwrap_or_default()
+    }
+
+    /// ISO 639-1 code of the language.
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::De => "de",
+            Self::Es => "es",
+            Self::Fr => "fr",
+        }
+    }
+}
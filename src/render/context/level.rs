@@ -0,0 +1,76 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Maximum depth to print, either throughout the tree or beneath one of its subtrees.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Level {
+    /// Number of levels to print beneath the root or `subtree`.
+    pub depth: usize,
+
+    /// Directory relative to the root that `depth` counts from, if it only applies beneath it.
+    pub subtree: Option<PathBuf>,
+}
+
+impl Level {
+    /// Depth relative to the root beyond which entries beneath `subtree` aren't printed.
+    pub fn max_depth(&self) -> usize {
+        let base = self
+            .subtree
+            .as_deref()
+            .map_or(0, |path| path.iter().count());
+
+        base.saturating_add(self.depth)
+    }
+
+    /// Whether this applies to the entry at `path` relative to the root.
+    pub fn applies_to(&self, path: &Path) -> bool {
+        self.subtree
+            .as_deref()
+            .map_or(true, |subtree| path.starts_with(subtree))
+    }
+
+    /// Whether `subtree` lies beneath the entry at `path` relative to the root, which then has
+    /// to be printed for it to be reached.
+    pub fn leads_through(&self, path: &Path) -> bool {
+        self.subtree.as_deref().map_or(false, |subtree| {
+            subtree != path && subtree.starts_with(path)
+        })
+    }
+}
+
+/// Parses a level such as `2`, which applies to the whole tree, or `4:tests`, which applies to
+/// the `tests` directory beneath the root and counts from it.
+pub fn parse(input: &str) -> Result<Level, String> {
+    let (depth, subtree) = input
+        .split_once(':')
+        .map_or((input, None), |(depth, subtree)| (depth, Some(subtree)));
+
+    let depth = depth
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| format!("expected a number in '{input}'"))?;
+
+    let subtree = subtree.map(parse_subtree).transpose()?;
+
+    Ok(Level { depth, subtree })
+}
+
+/// Parses the path of a subtree relative to the root such as `src/`. Paths are compared
+/// component-wise so `./src/` and `src` are one and the same.
+pub fn parse_subtree(input: &str) -> Result<PathBuf, String> {
+    let subtree = Path::new(input)
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect::<PathBuf>();
+
+    if subtree.as_os_str().is_empty() {
+        return Err(format!(
+            "expected a directory beneath the root in '{input}'"
+        ));
+    }
+
+    if subtree.is_absolute() {
+        return Err(format!("expected a path relative to the root in '{input}'"));
+    }
+
+    Ok(subtree)
+}
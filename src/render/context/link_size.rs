@@ -0,0 +1,16 @@
+use clap::ValueEnum;
+
+/// What a symlink that isn't followed contributes to the disk usage of its ancestors.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum LinkSize {
+    /// The size of the link itself
+    #[value(name = "self")]
+    Own,
+
+    /// The size of the file the link resolves to
+    Target,
+
+    /// Nothing at all
+    #[default]
+    None,
+}
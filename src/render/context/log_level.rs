@@ -0,0 +1,34 @@
+use clap::ValueEnum;
+use tracing::Level;
+
+/// Verbosity of the log of a traversal.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum LogLevel {
+    /// Failures that prevent an entry from being read
+    Error,
+
+    /// Entries that are skipped due to errors
+    Warn,
+
+    /// Timing of each stage of a traversal
+    #[default]
+    Info,
+
+    /// Decisions about which entries are included
+    Debug,
+
+    /// Everything including the timing of sorting each directory
+    Trace,
+}
+
+impl From<LogLevel> for Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => Self::ERROR,
+            LogLevel::Warn => Self::WARN,
+            LogLevel::Info => Self::INFO,
+            LogLevel::Debug => Self::DEBUG,
+            LogLevel::Trace => Self::TRACE,
+        }
+    }
+}
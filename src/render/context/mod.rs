@@ -1,29 +1,106 @@
-use super::disk_usage::{file_size::DiskUsage, units::PrefixKind};
+use super::disk_usage::{
+    file_size::{DiskUsage, Rounding},
+    units::{PrefixKind, Unit},
+};
+use crate::{
+    backend::s3,
+    fs::{
+        path as fs_path,
+        storage::{Storage, DEFAULT_THREADS},
+    },
+    i18n::tr_args,
+};
+use bundles::Bundles;
 use clap::{
-    parser::ValueSource, ArgMatches, CommandFactory, Error as ClapError, FromArgMatches, Id, Parser,
+    error::ErrorKind as ClapErrorKind, parser::ValueSource, ArgAction, ArgMatches, CommandFactory,
+    Error as ClapError, FromArgMatches, Id, Parser,
 };
+use color_by::ColorBy;
+use column::Column;
+use filter::Filter;
 use ignore::overrides::{Override, OverrideBuilder};
 use is_terminal::IsTerminal;
+use lang::Lang;
+use level::Level;
+use link_size::LinkSize;
+use log_level::LogLevel;
+use numeric_locale::NumericLocale;
+use output::Output;
+use report::ReportKind;
 use sort::SortType;
 use std::{
+    borrow::Cow,
     convert::From,
     ffi::{OsStr, OsString},
-    io::{stdin, BufRead},
+    fmt::Display,
+    fs,
+    io::{self, stdin, BufRead},
     path::{Path, PathBuf},
+    time::{Duration, SystemTime},
 };
+use subcommand::Subcommand;
+use thread_priority::ThreadPriority;
+
+/// How to display macOS bundles.
+pub mod bundles;
+
+/// What the names of files are colored by.
+pub mod color_by;
+
+/// Metadata columns selectable with `--columns`.
+pub mod column;
 
 /// Operations to load in defaults from configuration file.
 pub mod config;
 
+/// Parsing and formatting of human-friendly durations.
+pub mod duration;
+
+/// Expressions that select which files to print.
+pub mod filter;
+
+/// What symlinks contribute to disk usage.
+pub mod link_size;
+
+/// Language of messages, summaries, and unit names.
+pub mod lang;
+
+/// Maximum depths to print throughout the tree and beneath its subtrees.
+pub mod level;
+
+/// Verbosity of logging.
+pub mod log_level;
+
+/// Hidden `--no-<flag>` counterparts of boolean flags.
+mod negation;
+
+/// Grouping of digits in thousands.
+pub mod numeric_locale;
+
+/// Formats to print the tree in.
+pub mod output;
+
+/// Kinds of reports printed with `--report`.
+pub mod report;
+
+/// Parsing of human-friendly sizes.
+pub mod size;
+
 /// Printing order kinds.
 pub mod sort;
 
+/// Operations other than printing a single tree.
+pub mod subcommand;
+
+/// Scheduling priority of the threads that traverse the filesystem.
+pub mod thread_priority;
+
 /// Unit tests for [Context]
 #[cfg(test)]
 mod test;
 
 /// Defines the CLI.
-#[derive(Parser, Debug)]
+#[derive(Parser, Clone, Debug)]
 #[command(name = "erdtree")]
 #[command(author = "Benjamin Nguyen. <benjamin.van.nguyen@gmail.com>")]
 #[command(version = "1.7.1")]
@@ -33,17 +110,70 @@ pub struct Context {
     #[arg(short, long)]
     pub count: bool,
 
-    /// Root directory to traverse; defaults to current working directory
+    /// Include the time the traversal took along with its throughput in the summary
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Print the same output given the same files regardless of the environment: no colors, no
+    /// dependence on the width of the terminal, ties broken by path, and no timings
+    #[arg(long)]
+    pub deterministic: bool,
+
+    /// Traverse without printing the tree, only the time the traversal took along with its
+    /// throughput as with --stats
+    #[arg(long, conflicts_with_all = ["report", "du_compat", "total_only"])]
+    pub no_render: bool,
+
+    /// Root directory, file, glob pattern, or S3 URL e.g. s3://bucket/prefix to traverse; defaults
+    /// to current working directory
     dir: Option<PathBuf>,
 
-    /// Print physical or logical file size
-    #[arg(short, long, value_enum, default_value_t = DiskUsage::default())]
-    pub disk_usage: DiskUsage,
+    /// Directory on a remote host to scan over SSH e.g. user@host:/var/log; requires GNU find on
+    /// the host
+    #[arg(
+        long,
+        value_name = "HOST:PATH",
+        conflicts_with_all = ["dir", "physical", "disk_usage", "follow_links", "link_sizes", "include_dir_entry_size"]
+    )]
+    pub remote: Option<String>,
+
+    /// Container image whose merged filesystem to show, annotating files with the layer they're
+    /// from; requires docker or podman
+    #[arg(
+        long,
+        value_name = "IMAGE",
+        conflicts_with_all = ["dir", "remote", "physical", "disk_usage", "follow_links", "link_sizes", "include_dir_entry_size"]
+    )]
+    pub docker: Option<String>,
+
+    /// Print physical or logical file size; several comma-separated kinds e.g. logical,block are
+    /// printed side by side
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_delimiter = ',',
+        action = ArgAction::Set,
+        default_value = "logical"
+    )]
+    pub disk_usage: Vec<DiskUsage>,
+
+    /// Print on-disk usage which accounts for transparent compression; same as --disk-usage physical
+    #[arg(long, conflicts_with = "disk_usage")]
+    pub physical: bool,
 
     /// Include or exclude files using glob patterns
     #[arg(short, long)]
     pub glob: Vec<String>,
 
+    /// Read glob patterns, one per line, from a file; --glob patterns take precedence over them
+    #[arg(long, value_name = "FILE")]
+    pub patterns_from: Option<PathBuf>,
+
+    /// Only print files matching an expression e.g. 'size > 100M && ext == "log" && mtime < 30d'
+    #[arg(long, value_name = "EXPR", value_parser = filter::parse)]
+    pub filter: Option<Filter>,
+
     /// Include or exclude files using glob patterns; case insensitive
     #[arg(long)]
     iglob: Vec<String>,
@@ -52,6 +182,10 @@ pub struct Context {
     #[arg(long)]
     glob_case_insensitive: bool,
 
+    /// Only print files that don't match the glob patterns and --filter expression
+    #[arg(short = 'v', long)]
+    pub invert_match: bool,
+
     /// Show hidden files
     #[arg(short = 'H', long)]
     pub hidden: bool,
@@ -60,22 +194,152 @@ pub struct Context {
     #[arg(long, requires = "hidden")]
     ignore_git: bool,
 
+    /// Skip .git, .hg, and .svn directories regardless of other settings
+    #[arg(long)]
+    pub no_vcs_dirs: bool,
+
+    /// Skip build artifact directories such as target, node_modules, and __pycache__
+    #[arg(long, conflicts_with = "artifacts_only")]
+    pub skip_build_artifacts: bool,
+
+    /// Only print build artifact directories such as target, node_modules, and __pycache__
+    #[arg(long)]
+    pub artifacts_only: bool,
+
     /// Display file icons
     #[arg(short = 'I', long)]
     pub icons: bool,
 
+    /// TOML file of icons by extension and file name to use over the built-in ones
+    #[arg(long, value_name = "FILE")]
+    pub icon_map: Option<PathBuf>,
+
+    /// TOML or JSON file mapping paths relative to the root directory to notes to display
+    #[arg(long, value_name = "FILE")]
+    pub annotate: Option<PathBuf>,
+
+    /// Show the author who last committed to each file; requires the root to be in a git
+    /// repository
+    #[arg(long)]
+    pub git_authors: bool,
+
+    /// Mark directories with files that git's sparse-checkout, or a skip-worktree bit, keeps out
+    /// of the working tree; requires the root to be in a git repository
+    #[arg(long)]
+    pub sparse_checkout: bool,
+
+    /// Don't descend into git submodules, showing each as a single entry with the combined size
+    /// of its contents
+    #[arg(long)]
+    pub no_submodules: bool,
+
+    /// Progressively dim entries the deeper they are
+    #[arg(long)]
+    pub fade: bool,
+
+    /// What to color the names of files by
+    #[arg(
+        long,
+        value_enum,
+        value_name = "KIND",
+        default_value_t = ColorBy::default(),
+        conflicts_with = "fade"
+    )]
+    pub color_by: ColorBy,
+
+    /// Print the type of entries as text rather than by color alone, indent rather than draw
+    /// branches, and avoid dimmed text so that output suits screen readers and low vision
+    #[arg(long, conflicts_with_all = ["fade", "icons", "color_by"])]
+    pub accessible: bool,
+
     /// Ignore .gitignore
     #[arg(short, long)]
     pub ignore_git_ignore: bool,
 
-    /// Maximum depth to display
-    #[arg(short, long, value_name = "NUM")]
-    pub level: Option<usize>,
+    /// Maximum depth to display; NUM:PATH e.g. 4:tests applies to the given directory beneath the
+    /// root, counting from it, and may be provided several times
+    #[arg(short, long, value_name = "NUM[:PATH]", value_parser = level::parse)]
+    level: Vec<Level>,
+
+    /// Print the given directory beneath the root in full regardless of --level; may be provided
+    /// several times
+    #[arg(long, value_name = "PATH", value_parser = level::parse_subtree)]
+    expand: Vec<PathBuf>,
+
+    /// Print the given directory beneath the root as a single entry regardless of --level; a name
+    /// without a separator e.g. node_modules matches directories at any depth
+    #[arg(long, value_name = "PATH", value_parser = level::parse_subtree)]
+    collapse: Vec<PathBuf>,
+
+    /// Don't traverse deeper than the deepest --level so that directory sizes only account for what's printed
+    #[arg(long, requires = "level")]
+    pub shallow_sizes: bool,
+
+    /// Stop printing the tree after NUM lines, noting how many entries were left out
+    #[arg(long, value_name = "NUM", conflicts_with = "grid")]
+    pub max_lines: Option<usize>,
 
     /// Total number of digits after the decimal to display for disk usage
-    #[arg(short = 'n', long, default_value_t = 2, value_name = "NUM")]
+    #[arg(
+        short = 'n',
+        long,
+        visible_alias = "precision",
+        default_value_t = 2,
+        value_name = "NUM"
+    )]
     pub scale: usize,
 
+    /// How disk usage is rounded to the number of digits set by --scale
+    #[arg(long, value_enum, default_value_t = Rounding::default())]
+    pub rounding: Rounding,
+
+    /// Print disk usage in UNIT regardless of its magnitude, in place of the binary or SI prefix
+    /// that suits each size
+    #[arg(long, value_enum, value_name = "UNIT", ignore_case = true)]
+    pub unit: Option<Unit>,
+
+    /// Follow human-readable disk usage with the exact number of bytes in parentheses
+    #[arg(long)]
+    pub show_bytes: bool,
+
+    /// Group digits of byte and file counts in thousands as customary in LOCALE e.g. en_US,
+    /// fr_FR, or auto
+    #[arg(long, value_name = "LOCALE", value_parser = numeric_locale::parse)]
+    pub numeric_locale: Option<NumericLocale>,
+
+    /// Print messages, summaries, and unit names in LANG e.g. de, fr_FR, or auto which picks the
+    /// language of the environment and is the default
+    #[arg(long, value_name = "LANG", value_parser = lang::parse)]
+    pub lang: Option<Lang>,
+
+    /// Print the given metadata in columns of their own in the given order
+    #[arg(
+        long,
+        value_name = "COLS",
+        value_enum,
+        value_delimiter = ',',
+        action = ArgAction::Set
+    )]
+    pub columns: Vec<Column>,
+
+    /// Run CMD for every entry printed, quoted as in a shell and replacing {} with its path, and
+    /// print the first line of its output in a column of its own e.g. 'file -b {}'; runs as many at
+    /// once as --threads
+    #[arg(long, value_name = "CMD", value_parser = column::parse_command, conflicts_with = "grid")]
+    pub column_cmd: Option<String>,
+
+    /// Stop runs of --column-cmd that take longer than DURATION e.g. 30s [default: 10s]
+    #[arg(long, value_name = "DURATION", value_parser = duration::parse, requires = "column_cmd")]
+    pub column_cmd_timeout: Option<Duration>,
+
+    /// Lay out entries without children of their own in multiple columns across the terminal
+    #[arg(long, conflicts_with = "columns")]
+    pub grid: bool,
+
+    /// Print a header row naming each of the --columns
+    #[arg(long, requires = "columns")]
+    pub header: bool,
+
     /// Display disk usage as binary or SI units
     #[arg(short, long, value_enum, default_value_t = PrefixKind::default())]
     pub prefix: PrefixKind,
@@ -85,8 +349,29 @@ pub struct Context {
     pub prune: bool,
 
     /// Print disk usage information in plain format without ASCII tree
-    #[arg(short, long)]
-    pub report: bool,
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_name = "KIND",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "du"
+    )]
+    pub report: Option<ReportKind>,
+
+    /// Print the report as JSON
+    #[arg(long, requires = "report")]
+    pub json: bool,
+
+    /// Include the device, inode, link count, owner, group, mode, and timestamps of every entry
+    /// in the JSON report
+    #[arg(long, requires = "json")]
+    pub extended_metadata: bool,
+
+    /// Print the JSON Schema of the JSON report of the kind given to --report and exit
+    #[arg(long)]
+    pub schema: bool,
 
     /// Print human-readable disk usage in report
     #[arg(long, requires = "report")]
@@ -96,9 +381,31 @@ pub struct Context {
     #[arg(long, requires = "report")]
     pub file_name: bool,
 
-    /// Sort-order to display directory content
-    #[arg(short, long, value_enum, default_value_t = SortType::default())]
-    pub sort: SortType,
+    /// Print disk usage in bytes followed by a tab and the path for every entry, akin to `du -a`
+    #[arg(long, conflicts_with = "report")]
+    pub du_compat: bool,
+
+    /// Print only the disk usage of the root directory followed by a tab and its path, akin to
+    /// `du -s`
+    #[arg(short = 'q', long, conflicts_with_all = ["report", "du_compat"])]
+    pub total_only: bool,
+
+    /// Print every entry as a line of tab-separated fields in a format that is stable between releases
+    #[arg(long, conflicts_with_all = ["report", "du_compat", "total_only"])]
+    pub porcelain: bool,
+
+    /// Sort-order to display directory content; several comma-separated keys e.g. size-rev,name
+    /// break ties in the order given
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_name = "SORT",
+        value_delimiter = ',',
+        default_value = "none",
+        action = ArgAction::Set
+    )]
+    pub sort: Vec<SortType>,
 
     /// Always sorts directories above files
     #[arg(long)]
@@ -108,9 +415,48 @@ pub struct Context {
     #[arg(short = 'S', long)]
     pub follow_links: bool,
 
-    /// Number of threads to use
-    #[arg(short, long, default_value_t = 3)]
-    pub threads: usize,
+    /// Don't descend into symlinks to directories nested within more than NUM others that were
+    /// followed, annotating them instead
+    #[arg(long, value_name = "NUM", requires = "follow_links")]
+    pub max_link_depth: Option<usize>,
+
+    /// What symlinks that aren't followed contribute to the disk usage of their ancestors
+    #[arg(long, value_enum, value_name = "KIND", default_value_t = LinkSize::default())]
+    pub link_sizes: LinkSize,
+
+    /// Number of threads to use; defaults to 2 for hard disk drives, up to 16 for NVMe drives, and
+    /// 3 otherwise
+    #[arg(short, long)]
+    pub threads: Option<usize>,
+
+    /// Scheduling priority of the threads that traverse the filesystem
+    #[arg(long, value_enum, default_value_t = ThreadPriority::default())]
+    pub thread_priority: ThreadPriority,
+
+    /// Comma-separated CPUs to run the threads that traverse the filesystem on e.g. 0,1; Linux and
+    /// Windows only
+    #[arg(
+        long,
+        value_name = "CPUS",
+        value_delimiter = ',',
+        action = ArgAction::Set
+    )]
+    pub cpu_affinity: Vec<usize>,
+
+    /// Maximum number of files to hold open at once; defaults to what the process is allowed
+    #[arg(long, value_name = "NUM", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_open: Option<u64>,
+
+    /// Estimate the size of large directories by only reading every Nth of their files
+    #[arg(
+        long,
+        value_name = "N",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "10",
+        value_parser = clap::value_parser!(u64).range(2..)
+    )]
+    pub approx: Option<u64>,
 
     #[arg(long)]
     /// Print completions for a given shell to stdout
@@ -120,6 +466,52 @@ pub struct Context {
     #[arg(long)]
     pub dirs_only: bool,
 
+    /// Only print sparse files and the directories that contain them
+    #[arg(long)]
+    pub sparse_only: bool,
+
+    /// Only print text files i.e. those without null bytes in their first 8 KiB
+    #[arg(long, conflicts_with = "binary_only")]
+    pub text_only: bool,
+
+    /// Only print binary files i.e. those with null bytes in their first 8 KiB
+    #[arg(long)]
+    pub binary_only: bool,
+
+    /// Only print files whose contents are stored locally rather than cloud placeholders
+    #[arg(long)]
+    pub materialized_only: bool,
+
+    /// Only print files taken on or after DATE e.g. 2023-05-01, as per the EXIF data of images or
+    /// else when they were modified
+    #[arg(long, value_name = "DATE", value_parser = duration::parse_date)]
+    pub taken_after: Option<SystemTime>,
+
+    /// Only print files taken before DATE e.g. 2023-06-01 18:30, as per the EXIF data of images or
+    /// else when they were modified
+    #[arg(long, value_name = "DATE", value_parser = duration::parse_date)]
+    pub taken_before: Option<SystemTime>,
+
+    /// Show macOS bundles such as .app and .framework directories as single entries or expand them
+    #[arg(long, value_enum, value_name = "MODE", default_value_t = Bundles::default())]
+    pub bundles: Bundles,
+
+    /// Collapse sibling directories with identical contents, ignoring their own names, into one
+    #[arg(long)]
+    pub collapse_identical: bool,
+
+    /// Print chains of directories that each contain nothing but a single directory on one line
+    #[arg(long)]
+    pub collapse_single_child: bool,
+
+    /// Count data shared between files through reflinks only once; requires Linux
+    #[arg(long)]
+    pub reflinks: bool,
+
+    /// Include the size of directory entries themselves in disk usage, matching `du`
+    #[arg(long)]
+    pub include_dir_entry_size: bool,
+
     /// Omit disk usage from output
     #[arg(long)]
     pub suppress_size: bool,
@@ -128,11 +520,119 @@ pub struct Context {
     #[arg(long)]
     pub size_left: bool,
 
+    /// Show the usage of each top-level directory as a percentage of a quota e.g. 500G, 2TiB
+    #[arg(long, value_name = "SIZE", value_parser = size::parse)]
+    pub quota: Option<u64>,
+
+    /// Text to display in place of the name of the root directory
+    #[arg(long, value_name = "TEXT", conflicts_with = "relative")]
+    pub root_label: Option<String>,
+
+    /// Display the root directory as it was provided rather than by its name
+    #[arg(long)]
+    pub relative: bool,
+
+    /// Print the first NUM lines of text files of up to 64 KiB dimmed beneath them
+    #[arg(long, value_name = "NUM", value_parser = clap::value_parser!(u64).range(1..))]
+    pub preview: Option<u64>,
+
+    /// Maximum width of output; defaults to the width of the terminal if there is one
+    #[arg(long, value_name = "COLS")]
+    pub width: Option<usize>,
+
+    /// Write output in chunks of KB kibibytes, flushing after each, which speeds up printing large
+    /// trees to slow terminals such as those over SSH
+    #[arg(long, value_name = "KB", value_parser = clap::value_parser!(u64).range(1..))]
+    pub render_buffer: Option<u64>,
+
+    /// Format to print the tree in
+    #[arg(short, long, value_enum, default_value_t = Output::default())]
+    pub output: Output,
+
+    /// Number of entries to print when using an output format or report that ranks them
+    #[arg(long, default_value_t = 10, value_name = "NUM")]
+    pub top: usize,
+
+    /// Prefix the --top largest files anywhere in the tree with their rank by disk usage
+    #[arg(long)]
+    pub rank: bool,
+
+    /// Annotate entries with their change in disk usage since the previous run with this flag
+    #[arg(long, conflicts_with_all = ["report", "du_compat"])]
+    pub delta: bool,
+
+    /// Periodically save which directories were traversed in full to FILE
+    #[arg(long, value_name = "FILE", conflicts_with = "shallow_sizes")]
+    pub checkpoint: Option<PathBuf>,
+
+    /// Resume the traversal checkpointed to FILE, skipping directories it completed, and keep checkpointing to it
+    #[arg(long, value_name = "FILE", conflicts_with = "shallow_sizes")]
+    pub resume: Option<PathBuf>,
+
+    /// Mark directories with nothing modified within DURATION e.g. 30d or 1w
+    #[arg(long, value_name = "DURATION", value_parser = duration::parse)]
+    pub stale: Option<Duration>,
+
+    /// Badge to mark stale directories with [default: [stale]]
+    #[arg(long, value_name = "TEXT")]
+    pub stale_badge: Option<String>,
+
+    /// Log the traversal to stderr, or to --log-file if provided, at LEVEL [default: info]
+    #[arg(long, value_enum, value_name = "LEVEL")]
+    pub log_level: Option<LogLevel>,
+
+    /// File to log the traversal to
+    #[arg(long, value_name = "FILE")]
+    pub log_file: Option<PathBuf>,
+
     /// Don't read configuration file
     #[arg(long)]
     pub no_config: bool,
+
+    /// Root the tree at the path exactly as provided rather than resolving it to a canonical path
+    #[arg(long)]
+    pub no_canonicalize: bool,
+
+    /// Strip the \\?\ prefix from absolute paths in the output on Windows
+    #[arg(long)]
+    pub strip_verbatim: bool,
+
+    /// Apply the flags defined under [NAME] in the configuration file
+    #[arg(long, value_name = "NAME", conflicts_with = "no_config")]
+    pub profile: Option<String>,
+
+    #[command(subcommand)]
+    pub subcommand: Option<Subcommand>,
+
+    /// Pattern that entries directly beneath the root directory must match, taken from a root
+    /// argument such as `'logs/*.log'` that the shell didn't expand
+    #[arg(skip)]
+    root_glob: Option<String>,
+
+    /// Whether the root was swapped for a local mirror of a remote directory, bucket, or image
+    #[arg(skip)]
+    mirrored: bool,
+
+    /// Number of columns taken up by the largest size in `--unit`, which all sizes are padded to
+    /// so that their decimals line up
+    #[arg(skip)]
+    unit_size_width: Option<usize>,
+
+    /// Number of columns taken up by the largest exact byte count printed with `--show-bytes`
+    #[arg(skip)]
+    bytes_width: usize,
 }
 
+/// Names of directories that hold build artifacts, dependencies, and caches.
+const BUILD_ARTIFACTS: [&str; 6] = [
+    "target",
+    "node_modules",
+    "dist",
+    "build",
+    ".venv",
+    "__pycache__",
+];
+
 impl Context {
     /// Initializes [Context], optionally reading in the configuration file to override defaults.
     /// Arguments provided will take precedence over config.
@@ -141,6 +641,7 @@ impl Context {
 
         // If there's input on stdin we add each line as a separate glob pattern
         if !stdin().is_terminal() {
+            #[allow(clippy::lines_filter_map_ok)]
             stdin()
                 .lock()
                 .lines()
@@ -152,19 +653,150 @@ impl Context {
                 });
         }
 
-        let user_args = Self::command()
+        let user_args = negation::augment(Self::command())
             .args_override_self(true)
             .get_matches_from(args);
 
+        // Reconciling with the config rebuilds the arguments from scratch so the subcommand is
+        // carried over separately.
+        let subcommand = user_args
+            .subcommand()
+            .map(|_| Subcommand::from_arg_matches(&user_args))
+            .transpose()
+            .map_err(Error::ArgParse)?;
+
+        // `et doctor` checks the config itself so a broken one mustn't get in its way.
+        let mut ctx = if matches!(subcommand, Some(Subcommand::Doctor)) {
+            Self::from_arg_matches(&user_args).map_err(Error::ArgParse)?
+        } else {
+            Self::from_user_args(&user_args)?
+        };
+        ctx.subcommand = subcommand;
+        ctx.expand_paths();
+
+        if ctx.width.is_none() && !ctx.deterministic {
+            ctx.width = crate::tty::columns();
+        }
+
+        if ctx.physical {
+            ctx.disk_usage = vec![DiskUsage::Physical];
+        }
+
+        if ctx.column_cmd.is_some() {
+            ctx.add_cmd_column();
+        }
+
+        // Expanded subtrees are those without a limit on their depth.
+        let expanded = ctx.expand.iter().map(|subtree| Level {
+            depth: usize::MAX,
+            subtree: Some(subtree.clone()),
+        });
+        ctx.level.extend(expanded);
+
+        ctx.split_root_glob();
+
+        if let Some(ref path) = ctx.patterns_from {
+            // Later patterns take precedence so those of the file go first.
+            let patterns = Self::read_patterns(path)?;
+            ctx.glob.splice(0..0, patterns);
+        }
+
+        if ctx.invert_match {
+            ctx.check_invert_match()?;
+        }
+
+        if ctx.object_store().is_some() {
+            ctx.check_object_store()?;
+        }
+
+        if ctx.threads.is_none() {
+            ctx.threads = Some(Storage::of(ctx.dir()).default_threads());
+        }
+
+        Ok(ctx)
+    }
+
+    /// Expands `~`, `~user`, and `$VARS` in paths provided on the command line or in the
+    /// configuration file as the shell would, since invocations from keybindings and other programs
+    /// don't go through one.
+    fn expand_paths(&mut self) {
+        let expand = |path: &mut PathBuf| *path = fs_path::expand(path).into_owned();
+
+        [
+            &mut self.dir,
+            &mut self.patterns_from,
+            &mut self.icon_map,
+            &mut self.annotate,
+            &mut self.log_file,
+            &mut self.checkpoint,
+            &mut self.resume,
+        ]
+        .into_iter()
+        .flatten()
+        .for_each(expand);
+
+        match self.subcommand {
+            Some(Subcommand::Daemon(ref mut args)) => args
+                .roots
+                .iter_mut()
+                .chain(args.history_dir.as_mut())
+                .for_each(expand),
+            Some(Subcommand::History(ref mut args)) => args
+                .path
+                .iter_mut()
+                .chain(args.history_dir.as_mut())
+                .for_each(expand),
+            Some(Subcommand::Bench(ref mut args)) => args.path.iter_mut().for_each(expand),
+            Some(Subcommand::IgnoreCheck(ref mut args)) => args.paths.iter_mut().for_each(expand),
+            Some(Subcommand::Doctor) | None => (),
+        }
+    }
+
+    /// Makes room for the output of `--column-cmd` in the columns, just before the names unless
+    /// placed elsewhere with `--columns`. Without `--columns` it goes between sizes and names.
+    fn add_cmd_column(&mut self) {
+        if self.columns.contains(&Column::Cmd) {
+            return;
+        }
+
+        if self.columns.is_empty() {
+            self.columns = vec![Column::Size, Column::Name];
+        }
+
+        let position = self
+            .columns
+            .iter()
+            .position(|column| *column == Column::Name)
+            .unwrap_or(self.columns.len());
+
+        self.columns.insert(position, Column::Cmd);
+    }
+
+    /// Constructs [Context] from the arguments provided by the user, using the configuration file
+    /// for anything left unspecified.
+    fn from_user_args(user_args: &ArgMatches) -> Result<Self, Error> {
         let no_config = user_args.get_one("no_config").map_or(false, bool::clone);
 
         if no_config {
-            return Self::from_arg_matches(&user_args).map_err(Error::ArgParse);
+            return Self::from_arg_matches(user_args).map_err(Error::ArgParse);
         }
 
-        if let Some(ref config) = config::read_config_to_string::<&str>(None) {
-            let raw_config_args = config::parse(config);
-            let config_args = Self::command().get_matches_from(raw_config_args);
+        let profile = user_args.get_one::<String>("profile").map(String::as_str);
+
+        let config = config::read_config_to_string::<&str>(None);
+
+        if let (Some(name), None) = (profile, config.as_ref()) {
+            return Err(Error::MissingProfile(name.to_owned()));
+        }
+
+        if let Some(ref config) = config {
+            let raw_config_args = config::parse(config, profile)
+                .ok_or_else(|| Error::MissingProfile(profile.unwrap_or_default().to_owned()))?;
+
+            // Profiles may override flags set outside of them.
+            let config_args = negation::augment(Self::command())
+                .args_override_self(true)
+                .get_matches_from(raw_config_args);
 
             // If the user did not provide any arguments just read from config.
             if !user_args.args_present() {
@@ -177,12 +809,20 @@ impl Context {
 
             let mut ids = user_args.ids().map(Id::as_str).collect::<Vec<&str>>();
 
+            #[allow(clippy::needless_collect)]
             ids.extend(config_args.ids().map(Id::as_str).collect::<Vec<&str>>());
 
             ids = crate::utils::uniq(ids);
 
+            let cmd = Self::command();
+
             for id in ids {
-                if id == "Context" {
+                if id == "Context" || negation::is_negation(id, &cmd) {
+                    continue;
+                }
+
+                // A flag negated on the command line is dropped regardless of the config.
+                if negation::is_negated(id, user_args) {
                     continue;
                 }
                 if id == "dir" {
@@ -197,7 +837,7 @@ impl Context {
                 if let Some(user_arg) = user_args.value_source(id) {
                     match user_arg {
                         // prioritize the user arg if user provided a command line argument
-                        ValueSource::CommandLine => Self::pick_args_from(id, &user_args, &mut args),
+                        ValueSource::CommandLine => Self::pick_args_from(id, user_args, &mut args),
 
                         // otherwise prioritize argument from the config
                         _ => Self::pick_args_from(id, &config_args, &mut args),
@@ -211,7 +851,210 @@ impl Context {
             return Self::from_arg_matches(&clargs).map_err(Error::Config);
         }
 
-        Self::from_arg_matches(&user_args).map_err(Error::ArgParse)
+        Self::from_arg_matches(user_args).map_err(Error::ArgParse)
+    }
+
+    /// Checks that the arguments of `config`, as returned by [`config::read_config_to_string`],
+    /// are understood when read with `profile`, without exiting on failure as reading it does.
+    pub fn check_config(config: &str, profile: Option<&str>) -> Result<(), Error> {
+        let raw_config_args = config::parse(config, profile)
+            .ok_or_else(|| Error::MissingProfile(profile.unwrap_or_default().to_owned()))?;
+
+        let config_args = negation::augment(Self::command())
+            .args_override_self(true)
+            .try_get_matches_from(raw_config_args)
+            .map_err(Error::Config)?;
+
+        Self::from_arg_matches(&config_args)
+            .map(|_| ())
+            .map_err(Error::Config)
+    }
+
+    /// Returns a copy of [Context] that will traverse `dir` instead.
+    pub fn with_dir(&self, dir: PathBuf) -> Self {
+        Self {
+            dir: Some(dir),
+            ..self.clone()
+        }
+    }
+
+    /// What to display in place of the name of the root directory, if anything, as determined by
+    /// `--root-label` and `--relative`.
+    pub fn root_label(&self) -> Option<Cow<'_, str>> {
+        if let Some(ref label) = self.root_label {
+            return Some(Cow::from(label.as_str()));
+        }
+
+        self.relative.then(|| {
+            let dir = self.display_path(self.dir());
+            Cow::from(dir.to_string_lossy().into_owned())
+        })
+    }
+
+    /// Path of the root directory as it appears in the paths of traversed entries; this is the
+    /// canonical path unless `--no-canonicalize` is provided.
+    pub fn base_path(&self) -> PathBuf {
+        let dir = self.dir();
+
+        if self.no_canonicalize {
+            return dir.to_path_buf();
+        }
+
+        dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf())
+    }
+
+    /// Path as it should appear in the output which is without the verbatim prefix on Windows if
+    /// `--strip-verbatim` was provided.
+    pub fn display_path<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+        if self.strip_verbatim {
+            crate::fs::path::strip_verbatim(path)
+        } else {
+            Cow::Borrowed(path)
+        }
+    }
+
+    /// Whether physical disk usage is among the kinds provided to `--disk-usage`.
+    pub fn measures_physical(&self) -> bool {
+        self.disk_usage
+            .iter()
+            .any(|kind| matches!(kind, DiskUsage::Physical))
+    }
+
+    /// Whether files are inspected to tell how they're stored on disk, such as whether they're
+    /// sparse or compressed, which takes opening them. That's only done when asked about physical
+    /// disk usage or sparse files.
+    pub fn inspects_allocation(&self) -> bool {
+        self.sparse_only || self.measures_physical()
+    }
+
+    /// The kind of disk usage that sizes are computed, sorted, and reported in. Any others
+    /// requested with `--disk-usage` are only printed alongside it.
+    pub fn disk_usage(&self) -> DiskUsage {
+        self.disk_usage.first().copied().unwrap_or_default()
+    }
+
+    /// Number of columns that human-readable sizes are padded to when aligned: 3 places before the
+    /// decimal, 1 for the decimal, and `--scale` after it unless all sizes are in a single
+    /// `--unit`.
+    pub const fn size_width(&self) -> usize {
+        match self.unit_size_width {
+            Some(width) => width,
+            None => 3 + 1 + self.scale,
+        }
+    }
+
+    /// Pads sizes in `--unit` to `width` columns, that of the largest of them.
+    pub fn align_unit_sizes(&mut self, width: usize) {
+        self.unit_size_width = Some(width);
+    }
+
+    /// Number of columns that exact byte counts printed with `--show-bytes` are padded to when
+    /// aligned.
+    pub const fn bytes_width(&self) -> usize {
+        self.bytes_width
+    }
+
+    /// Pads exact byte counts to `width` columns, that of the largest of them.
+    pub fn align_exact_sizes(&mut self, width: usize) {
+        self.bytes_width = width;
+    }
+
+    /// Formats the integer `n` with its digits grouped as per `--numeric-locale`, if provided.
+    pub fn group_digits(&self, n: impl Display) -> String {
+        let Some(locale) = self.numeric_locale else {
+            return n.to_string();
+        };
+
+        locale.group(n)
+    }
+
+    /// File to persist the progress of the traversal to with `--checkpoint`, or the one it's
+    /// resumed from with `--resume` if not provided.
+    pub fn checkpoint_path(&self) -> Option<&Path> {
+        self.checkpoint.as_deref().or(self.resume.as_deref())
+    }
+
+    /// Settings that decide which entries are traversed and how large they're considered to be,
+    /// which a traversal resumed with `--resume` has to share with the one it was checkpointed by
+    /// lest the sizes of directories be computed under different rules.
+    pub fn traversal_settings(&self) -> String {
+        let selection = (
+            self.user_globs(),
+            &self.filter,
+            self.invert_match,
+            self.hidden,
+            self.ignore_git,
+            self.ignore_git_ignore,
+            self.no_vcs_dirs,
+            self.skip_build_artifacts,
+            self.artifacts_only,
+            &self.level,
+        );
+
+        let files = (
+            self.dirs_only,
+            self.sparse_only,
+            self.text_only,
+            self.binary_only,
+            self.materialized_only,
+            self.taken_after,
+            self.taken_before,
+        );
+
+        let sizes = (
+            self.approx,
+            self.follow_links,
+            self.max_link_depth,
+            self.link_sizes,
+            self.include_dir_entry_size,
+            self.reflinks,
+            self.shallow_sizes,
+        );
+
+        format!("{selection:?}{files:?}{sizes:?}")
+    }
+
+    /// Whether files are filtered by when they were taken with `--taken-after` or `--taken-before`.
+    pub const fn filters_taken(&self) -> bool {
+        self.taken_after.is_some() || self.taken_before.is_some()
+    }
+
+    /// Whether a file taken at `taken` falls within `--taken-after` and `--taken-before`.
+    pub fn taken_within(&self, taken: SystemTime) -> bool {
+        self.taken_after.map_or(true, |after| taken >= after)
+            && self.taken_before.map_or(true, |before| taken < before)
+    }
+
+    /// Whether lines of code are to be counted and printed in place of disk usage.
+    pub fn code_report(&self) -> bool {
+        self.report == Some(ReportKind::Code)
+    }
+
+    /// Whether the tree itself is printed, with every entry on a line of its own, as opposed to a
+    /// report, summary, or another format that lists entries.
+    pub fn prints_tree(&self) -> bool {
+        let tree_report = self.code_report() || self.permissions_report();
+
+        !self.no_render
+            && !(self.report.is_some() && (self.json || !tree_report))
+            && !self.du_compat
+            && !self.porcelain
+            && matches!(self.output, Output::Tree | Output::TreeSvg | Output::Html)
+            && !self.total_only
+            && !self.grid
+    }
+
+    /// Whether only files with risky permissions are to be printed.
+    pub fn permissions_report(&self) -> bool {
+        self.report == Some(ReportKind::Permissions)
+    }
+
+    /// Whether files are to be classified as text or binary, which requires reading them. Besides
+    /// filtering by it, that's only the case for the JSON report of disk usage which includes it.
+    pub const fn classify_content(&self) -> bool {
+        let json_du = self.json && matches!(self.report, Some(ReportKind::Du) | None);
+
+        self.text_only || self.binary_only || json_du
     }
 
     /// Returns reference to the path of the root directory to be traversed.
@@ -221,9 +1064,103 @@ impl Context {
             .map_or_else(|| Path::new("."), |pb| pb.as_path())
     }
 
-    /// The sort-order used for printing.
-    pub const fn sort(&self) -> SortType {
-        self.sort
+    /// Whether the root is itself a hidden directory such as `~/.dotfiles` holding nothing but
+    /// hidden entries and `--hidden` wasn't provided, in which case the hidden entries directly
+    /// beneath the root are shown anyway rather than printing an empty tree. Those further down
+    /// as well as the metadata directories of version control systems are still left out.
+    pub fn hidden_root(&self) -> bool {
+        if self.hidden {
+            return false;
+        }
+
+        let dir = fs::canonicalize(self.dir()).unwrap_or_else(|_| self.dir().to_path_buf());
+        let is_hidden = |name: &OsStr| name.to_string_lossy().starts_with('.');
+
+        dir.file_name().map_or(false, is_hidden)
+            && fs::read_dir(&dir).map_or(false, |entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .all(|entry| is_hidden(&entry.file_name()))
+            })
+    }
+
+    /// Number of threads to traverse the filesystem with, as resolved by [`Context::init`] when not
+    /// provided explicitly.
+    pub fn threads(&self) -> usize {
+        self.threads.unwrap_or(DEFAULT_THREADS)
+    }
+
+    /// The URL of the bucket provided as the root, if any.
+    pub fn object_store(&self) -> Option<&str> {
+        self.dir
+            .as_deref()
+            .and_then(Path::to_str)
+            .filter(|dir| dir.starts_with(s3::SCHEME))
+    }
+
+    /// Traverses `root`, the local mirror of the `--remote` directory, bucket, or `--docker` image,
+    /// in its stead, labelling it with `label` unless another label was provided.
+    pub fn mirror(&mut self, root: &Path, label: &str) {
+        if self.root_label.is_none() {
+            self.root_label = Some(label.to_owned());
+        }
+
+        self.dir = Some(root.to_owned());
+        self.mirrored = true;
+    }
+
+    /// Whether the root is a local mirror of a remote directory, bucket, or image, whose files are all
+    /// sparse regardless of how they're stored remotely.
+    pub const fn is_mirrored(&self) -> bool {
+        self.mirrored
+    }
+
+    /// `--invert-match` inverts the selection of the glob patterns and `--filter` expression, so
+    /// without any of them it would leave out every file.
+    fn check_invert_match(&self) -> Result<(), Error> {
+        if !self.glob.is_empty() || !self.iglob.is_empty() || self.filter.is_some() {
+            return Ok(());
+        }
+
+        let error = Self::command().error(
+            ClapErrorKind::MissingRequiredArgument,
+            "the argument '--invert-match' requires glob patterns or a --filter expression",
+        );
+
+        Err(Error::ArgParse(error))
+    }
+
+    /// Buckets only have logical sizes and no symlinks, so the arguments that `--remote` conflicts
+    /// with are rejected for them as well.
+    fn check_object_store(&self) -> Result<(), Error> {
+        let conflicting = [
+            (self.physical, "--physical"),
+            (
+                self.disk_usage
+                    .iter()
+                    .any(|kind| !matches!(kind, DiskUsage::Logical)),
+                "--disk-usage",
+            ),
+            (self.follow_links, "--follow-links"),
+            (self.link_sizes != LinkSize::default(), "--link-sizes"),
+            (self.include_dir_entry_size, "--include-dir-entry-size"),
+        ];
+
+        let Some((_, arg)) = conflicting.into_iter().find(|(conflicts, _)| *conflicts) else {
+            return Ok(());
+        };
+
+        let error = Self::command().error(
+            ClapErrorKind::ArgumentConflict,
+            format!("the argument '{arg}' cannot be used with object storage"),
+        );
+
+        Err(Error::ArgParse(error))
+    }
+
+    /// The keys to sort by when printing, each breaking ties left by the ones before it.
+    pub fn sort(&self) -> &[SortType] {
+        &self.sort
     }
 
     /// Getter for `dirs_first` field.
@@ -231,52 +1168,250 @@ impl Context {
         self.dirs_first
     }
 
-    /// The max depth to print. Note that all directories are fully traversed to compute file
-    /// sizes; this just determines how much to print.
-    pub const fn level(&self) -> Option<usize> {
+    /// The max depth to print anywhere in the tree, accounting for the subtrees that `--level`
+    /// lets go deeper. Note that all directories are fully traversed to compute file sizes unless
+    /// `--shallow-sizes` is provided; this just determines how much to print.
+    pub fn level(&self) -> Option<usize> {
+        let global = self.global_level()?;
+
+        let deepest = self
+            .subtree_levels()
+            .map(Level::max_depth)
+            .fold(global, usize::max);
+
+        Some(deepest)
+    }
+
+    /// The max depth to print outside of the subtrees given their own `--level`, the last one
+    /// provided taking precedence. `None` if there's no limit.
+    fn global_level(&self) -> Option<usize> {
         self.level
+            .iter()
+            .rev()
+            .find(|level| level.subtree.is_none())
+            .map(|level| level.depth)
     }
 
-    /// Ignore file overrides.
+    /// Levels provided for subtrees of the root rather than the whole tree.
+    fn subtree_levels(&self) -> impl Iterator<Item = &Level> {
+        self.level.iter().filter(|level| level.subtree.is_some())
+    }
+
+    /// The max depth to print the entry at `path`, relative to the root, as determined by the
+    /// `--level` of the innermost subtree containing it, if any. `None` if there's no limit, as is
+    /// the case for the directories leading to a subtree.
+    pub fn level_of(&self, path: &Path) -> Option<usize> {
+        if self.subtree_levels().any(|level| level.leads_through(path)) {
+            return None;
+        }
+
+        self.subtree_levels()
+            .filter(|level| level.applies_to(path))
+            .max_by_key(|level| {
+                level
+                    .subtree
+                    .as_deref()
+                    .map(|subtree| subtree.iter().count())
+            })
+            .map_or_else(|| self.global_level(), |level| Some(level.max_depth()))
+    }
+
+    /// Whether the directory at `path`, relative to the root, is to be printed without its contents
+    /// as requested by `--collapse`. Names without a separator match at any depth.
+    pub fn is_collapsed(&self, path: &Path) -> bool {
+        self.collapse.iter().any(|collapsed| {
+            let mut components = collapsed.iter();
+
+            match (components.next(), components.next()) {
+                (Some(name), None) => path.file_name() == Some(name),
+                _ => path == collapsed,
+            }
+        })
+    }
+
+    /// Whether any directories are to be printed without their contents with `--collapse`.
+    pub fn has_collapsed(&self) -> bool {
+        !self.collapse.is_empty()
+    }
+
+    /// Whether any `--level` applies to a subtree of the root rather than the whole tree.
+    pub fn has_subtree_levels(&self) -> bool {
+        self.subtree_levels().next().is_some()
+    }
+
+    /// Ignore file overrides. Globs are matched case insensitively if `--glob-case-insensitive`
+    /// is provided. When a file matches multiple globs the last one wins, so build artifacts as
+    /// well as exclusions of hidden files and `.git` are added last; otherwise a glob such as
+    /// `*.txt` would pull in hidden files despite `--hidden` not being provided.
     pub fn overrides(&self) -> Result<Override, ignore::Error> {
-        let mut builder = OverrideBuilder::new(self.dir());
+        Self::build_overrides(self.dir(), &self.override_globs())
+    }
 
-        if self.ignore_git {
-            builder.add("!.git")?;
+    /// The globs that make up [Context::overrides] from lowest to highest precedence, each paired
+    /// with whether it's matched case insensitively.
+    pub fn override_globs(&self) -> Vec<(String, bool)> {
+        // Inverted globs are matched against each entry after the traversal instead.
+        let globbed = (!self.glob.is_empty() || !self.iglob.is_empty()) && !self.invert_match;
+
+        let mut globs = if globbed { self.user_globs() } else { vec![] };
+
+        for artifact in BUILD_ARTIFACTS {
+            if self.skip_build_artifacts {
+                globs.push((format!("!{artifact}/"), false));
+            } else if self.artifacts_only {
+                globs.push((format!("**/{artifact}/**"), false));
+            }
         }
 
-        if self.glob.is_empty() && self.iglob.is_empty() {
-            return builder.build();
+        if (globbed || self.artifacts_only) && !self.hidden {
+            globs.push(("!.*".to_owned(), false));
         }
 
-        if self.glob_case_insensitive {
-            builder.case_insensitive(true).unwrap();
+        // Hidden build artifacts such as `.venv` are what was asked for, though what's hidden
+        // within them is still left out.
+        if self.artifacts_only && !self.hidden {
+            for artifact in BUILD_ARTIFACTS.iter().filter(|name| name.starts_with('.')) {
+                globs.push((format!("{artifact}/"), false));
+            }
         }
 
-        for glob in &self.glob {
-            builder.add(glob)?;
+        if self.ignore_git {
+            globs.push(("!.git".to_owned(), false));
+        }
+
+        globs
+    }
+
+    /// Matcher for the glob patterns whose matches are to be left out as requested by
+    /// `--invert-match`. Returns `None` if not inverting or if there are no glob patterns.
+    pub fn inverted_globs(&self) -> Result<Option<Override>, ignore::Error> {
+        if !self.invert_match || (self.glob.is_empty() && self.iglob.is_empty()) {
+            return Ok(None);
         }
 
-        // all subsequent patterns are case insensitive
-        builder.case_insensitive(true).unwrap();
-        for glob in &self.iglob {
+        Self::build_overrides(self.dir(), &self.user_globs()).map(Some)
+    }
+
+    /// `--glob` and `--iglob` patterns, each paired with whether it's matched case insensitively
+    /// which `--iglob` patterns always are and `--glob` patterns are if `--glob-case-insensitive`
+    /// is provided.
+    pub fn user_globs(&self) -> Vec<(String, bool)> {
+        let globs = self
+            .glob
+            .iter()
+            .map(|glob| (glob.clone(), self.glob_case_insensitive));
+
+        let iglobs = self.iglob.iter().map(|glob| (glob.clone(), true));
+
+        globs.chain(iglobs).collect()
+    }
+
+    /// Builds an [Override] rooted at `dir` from `globs` as returned by [Context::override_globs].
+    fn build_overrides(dir: &Path, globs: &[(String, bool)]) -> Result<Override, ignore::Error> {
+        let mut builder = OverrideBuilder::new(dir);
+
+        for (glob, case_insensitive) in globs {
+            builder.case_insensitive(*case_insensitive)?;
             builder.add(glob)?;
         }
 
         builder.build()
     }
 
+    /// Matcher for the entries directly beneath the root directory if the root argument was a
+    /// glob pattern.
+    pub fn root_glob(&self) -> Result<Option<Override>, ignore::Error> {
+        let Some(ref pattern) = self.root_glob else {
+            return Ok(None);
+        };
+
+        let mut builder = OverrideBuilder::new(self.dir());
+        builder.case_insensitive(self.glob_case_insensitive)?;
+        builder.add(pattern)?;
+        builder.build().map(Some)
+    }
+
+    /// Treats a root argument that doesn't exist but whose final component is a glob pattern as
+    /// its parent directory along with the pattern to match its entries against.
+    fn split_root_glob(&mut self) {
+        let Some(dir) = self.dir.as_deref() else {
+            return;
+        };
+
+        if dir.symlink_metadata().is_ok() {
+            return;
+        }
+
+        let Some(pattern) = dir
+            .file_name()
+            .and_then(OsStr::to_str)
+            .filter(|name| name.contains(['*', '?', '[', '{']))
+        else {
+            return;
+        };
+
+        let parent = dir
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+
+        self.root_glob = Some(pattern.to_owned());
+        self.dir = Some(parent);
+    }
+
+    /// Glob patterns in the file at `path`, one per line. Lines that are blank are skipped whereas
+    /// comments starting with `#` are left to the glob matcher which ignores them, as it does in
+    /// ignore files.
+    fn read_patterns(path: &Path) -> Result<Vec<String>, Error> {
+        let patterns = fs::read_to_string(path)
+            .map_err(|e| Error::Patterns(path.display().to_string(), e))?
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(str::to_owned)
+            .collect();
+
+        Ok(patterns)
+    }
+
     /// Used to pick either from config or user args when constructing [Context].
     fn pick_args_from(id: &str, matches: &ArgMatches, args: &mut Vec<OsString>) {
         if let Ok(Some(raw)) = matches.try_get_raw(id) {
             let kebap = id.replace('_', "-");
 
+            // Lists of values such as `--sort size,name` are passed back as they were provided.
+            let delimiter = Self::command()
+                .get_arguments()
+                .find(|arg| arg.get_id() == id)
+                .and_then(clap::Arg::get_value_delimiter);
+
+            if let Some(delimiter) = delimiter {
+                let mut arg = OsString::from(format!("--{kebap}="));
+
+                for (i, value) in raw.enumerate() {
+                    if i > 0 {
+                        arg.push(delimiter.to_string());
+                    }
+                    arg.push(value);
+                }
+
+                args.push(arg);
+                return;
+            }
+
+            // Values are attached with `=` as some arguments such as `--report` require it.
             let raw_args = raw
-                .map(OsStr::to_owned)
-                .map(|s| vec![OsString::from(format!("--{kebap}")), s])
-                .filter(|pair| pair[1] != "false")
-                .flatten()
-                .filter(|s| s != "true")
+                .filter(|s| *s != "false")
+                .map(|s| {
+                    let mut arg = OsString::from(format!("--{kebap}"));
+
+                    if s != "true" {
+                        arg.push("=");
+                        arg.push(s);
+                    }
+
+                    arg
+                })
                 .collect::<Vec<OsString>>();
 
             args.extend(raw_args);
@@ -288,6 +1423,10 @@ impl Context {
 pub enum Error {
     #[error("{0}")]
     ArgParse(#[source] ClapError),
-    #[error("A configuration file was found but failed to parse: {0}")]
+    #[error("{}", tr_args("error-config-parse", [("error", .0.to_string())]))]
     Config(#[source] ClapError),
+    #[error("{}", tr_args("error-missing-profile", [("profile", .0.as_str())]))]
+    MissingProfile(String),
+    #[error("{}", tr_args("error-read-patterns", [("path", .0.clone()), ("error", .1.to_string())]))]
+    Patterns(String, #[source] io::Error),
 }
@@ -0,0 +1,54 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+/// Prefix of the long flag as well as the id of every generated negation.
+const PREFIX: &str = "no";
+
+/// Adds a hidden `--no-<flag>` counterpart to every boolean flag of `cmd` so that a flag enabled
+/// by the config file can be disabled for a single invocation. Whichever of the two is provided
+/// last wins.
+pub fn augment(cmd: Command) -> Command {
+    let negations = cmd
+        .get_arguments()
+        .filter(|arg| matches!(arg.get_action(), ArgAction::SetTrue))
+        .filter_map(|arg| {
+            let long = arg.get_long()?;
+            let id = arg.get_id().as_str();
+
+            // Flags such as `--no-config` are already negations.
+            if id.starts_with(&format!("{PREFIX}_")) {
+                return None;
+            }
+
+            let negation = Arg::new(negation_id(id))
+                .long(format!("{PREFIX}-{long}"))
+                .action(ArgAction::SetTrue)
+                .overrides_with(id.to_owned())
+                .hide(true);
+
+            Some(negation)
+        })
+        .collect::<Vec<_>>();
+
+    cmd.args(negations)
+}
+
+/// Whether the boolean flag with `id` was negated in `matches`.
+pub fn is_negated(id: &str, matches: &ArgMatches) -> bool {
+    matches
+        .try_get_one::<bool>(&negation_id(id))
+        .ok()
+        .flatten()
+        .map_or(false, bool::clone)
+}
+
+/// Whether `id` belongs to a generated negation.
+pub fn is_negation(id: &str, cmd: &Command) -> bool {
+    id.strip_prefix(&format!("{PREFIX}_"))
+        .map_or(false, |negated| {
+            cmd.get_arguments().any(|arg| arg.get_id() == negated)
+        })
+}
+
+fn negation_id(id: &str) -> String {
+    format!("{PREFIX}_{id}")
+}
@@ -0,0 +1,91 @@
+use std::{env, fmt::Display};
+
+/// How the digits of large numbers are grouped in thousands, e.g. `1,234,567` or `1 234 567`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumericLocale {
+    /// Character placed between groups of three digits, if digits are grouped at all.
+    separator: Option<char>,
+}
+
+/// Languages whose digit groups are separated by the same character, by ISO 639-1 code.
+const SEPARATORS: &[(char, &[&str])] = &[
+    (',', &["en", "ja", "ko", "zh", "he", "th", "hi", "ga", "mt"]),
+    (
+        '.',
+        &[
+            "de", "es", "it", "nl", "pt", "da", "id", "tr", "el", "ro", "sl", "hr", "sr", "vi",
+        ],
+    ),
+    (
+        ' ',
+        &[
+            "fr", "ru", "pl", "cs", "sk", "sv", "fi", "nb", "nn", "no", "uk", "hu", "bg", "et",
+            "lv", "lt",
+        ],
+    ),
+];
+
+/// Parses a locale such as `en_US`, `de_DE.UTF-8`, or `fr`. `auto` uses the locale of the
+/// environment as determined by `LC_ALL`, `LC_NUMERIC`, and `LANG` in that order, whereas `C` and
+/// `POSIX` leave digits ungrouped. Swiss locales use an apostrophe.
+pub fn parse(input: &str) -> Result<NumericLocale, String> {
+    if input == "auto" {
+        let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .into_iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty());
+
+        // Locales of the environment that aren't recognized simply leave digits ungrouped.
+        return Ok(locale
+            .and_then(|locale| parse(&locale).ok())
+            .unwrap_or(NumericLocale { separator: None }));
+    }
+
+    // Strip the encoding and modifier as in `de_DE.UTF-8@euro`.
+    let name = input.split(['.', '@']).next().unwrap_or_default();
+
+    if matches!(name, "C" | "POSIX") {
+        return Ok(NumericLocale { separator: None });
+    }
+
+    let mut parts = name.split(['_', '-']);
+    let language = parts.next().unwrap_or_default().to_ascii_lowercase();
+    let territory = parts.next().unwrap_or_default().to_ascii_uppercase();
+
+    if territory == "CH" && matches!(language.as_str(), "de" | "it" | "fr" | "rm") {
+        return Ok(NumericLocale {
+            separator: Some('\''),
+        });
+    }
+
+    SEPARATORS
+        .iter()
+        .find(|(_, languages)| languages.contains(&language.as_str()))
+        .map(|(separator, _)| NumericLocale {
+            separator: Some(*separator),
+        })
+        .ok_or_else(|| format!("unrecognized locale '{input}'"))
+}
+
+impl NumericLocale {
+    /// Formats the integer `n` with its digits grouped in thousands.
+    pub fn group(self, n: impl Display) -> String {
+        let digits = n.to_string();
+
+        let Some(separator) = self.separator else {
+            return digits;
+        };
+
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push(separator);
+            }
+
+            grouped.push(digit);
+        }
+
+        grouped
+    }
+}
@@ -0,0 +1,25 @@
+use clap::ValueEnum;
+
+/// Format in which to print the tree.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum Output {
+    /// Print an ASCII tree
+    #[default]
+    Tree,
+
+    /// Print the disk usage of the largest directories as Prometheus/OpenMetrics gauges
+    Prometheus,
+
+    /// Print nested JSON with the name, size, and children of each entry for d3 or Plotly treemaps
+    Treemap,
+
+    /// Print an SVG drawing of a treemap
+    Svg,
+
+    /// Print an SVG image of the tree as it would appear in the terminal
+    TreeSvg,
+
+    /// Print an HTML document with the tree, the largest files, disk usage by extension, and a
+    /// summary that can be printed or saved as PDF
+    Html,
+}
@@ -0,0 +1,34 @@
+use clap::ValueEnum;
+
+/// Kinds of reports that can be printed in place of the tree.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum ReportKind {
+    /// Print the disk usage of every entry along with its file-type akin to `du`
+    #[default]
+    Du,
+
+    /// Print the directories containing the most files, counting those of subdirectories
+    Files,
+
+    /// Print the tree with the lines of code of source files in place of disk usage
+    Code,
+
+    /// Print the number of text files by encoding and line endings in every directory
+    Encodings,
+
+    /// Print the tree of only world-writable, setuid, setgid, and misplaced root-owned files
+    Permissions,
+
+    /// Print every zero-byte file and empty directory
+    Empty,
+
+    /// Print the `--top` least and most recently modified files
+    Ages,
+
+    /// Print the disk usage of files by the author who last committed to them in git
+    Authors,
+
+    /// Print how large every directory is estimated to be were its files compressed, as sampled
+    /// with zstd
+    Compressibility,
+}
@@ -0,0 +1,48 @@
+const KIB: f64 = 1024.0;
+const KB: f64 = 1000.0;
+
+/// Parses a human-friendly size such as `512`, `10K`, `1.5GiB`, or `200MB` into bytes. Units
+/// without a trailing `B` as well as those with an `i` are binary whereas the likes of `KB` are
+/// SI. A bare number is interpreted as bytes.
+pub fn parse(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+
+    if input.is_empty() {
+        return Err(String::from("size cannot be empty"));
+    }
+
+    let split = input
+        .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+        .unwrap_or(input.len());
+
+    let (amount, unit) = input.split_at(split);
+
+    let amount = amount
+        .parse::<f64>()
+        .map_err(|_| format!("expected a number in '{input}'"))?;
+
+    let unit = unit.trim();
+    let normalized = unit.to_ascii_uppercase();
+
+    let (prefix, base) = match normalized.as_str() {
+        "" | "B" => return Ok(to_bytes(amount, 1.0)),
+        u if u.ends_with("IB") => (&u[..u.len() - 2], KIB),
+        u if u.len() > 1 && u.ends_with('B') => (&u[..u.len() - 1], KB),
+        u => (u, KIB),
+    };
+
+    let exponent = match prefix {
+        "K" => 1,
+        "M" => 2,
+        "G" => 3,
+        "T" => 4,
+        _ => return Err(format!("invalid size unit '{unit}' in '{input}'")),
+    };
+
+    Ok(to_bytes(amount, base.powi(exponent)))
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_bytes(amount: f64, multiplier: f64) -> u64 {
+    (amount * multiplier).round() as u64
+}
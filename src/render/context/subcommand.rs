@@ -0,0 +1,75 @@
+use super::duration;
+use clap::{Args, Subcommand as ClapSubcommand};
+use std::{path::PathBuf, time::Duration};
+
+/// Operations other than printing a single tree.
+#[derive(ClapSubcommand, Clone, Debug)]
+pub enum Subcommand {
+    /// Periodically scan directories and record their disk usage history
+    Daemon(DaemonArgs),
+
+    /// Plot the recorded disk usage of a directory over time
+    History(HistoryArgs),
+
+    /// Time repeated traversals of a directory
+    Bench(BenchArgs),
+
+    /// Check the terminal, configuration file, and cache directories for problems
+    Doctor,
+
+    /// Explain whether paths would be walked and which ignore rule decided it
+    IgnoreCheck(IgnoreCheckArgs),
+}
+
+/// Arguments for [`Subcommand::Daemon`].
+#[derive(Args, Clone, Debug)]
+pub struct DaemonArgs {
+    /// Directories to scan; defaults to current working directory
+    pub roots: Vec<PathBuf>,
+
+    /// Time to wait between scans e.g. 30m, 1h, 1d
+    #[arg(long, default_value = "1h", value_parser = duration::parse)]
+    pub interval: Duration,
+
+    /// Directory to store history in; defaults to $XDG_DATA_HOME/erdtree
+    #[arg(long, value_name = "DIR")]
+    pub history_dir: Option<PathBuf>,
+
+    /// Number of scans to perform before exiting; runs indefinitely if omitted
+    #[arg(long, value_name = "NUM")]
+    pub runs: Option<usize>,
+}
+
+/// Arguments for [`Subcommand::History`].
+#[derive(Args, Clone, Debug)]
+pub struct HistoryArgs {
+    /// Directory whose history to plot; defaults to current working directory
+    pub path: Option<PathBuf>,
+
+    /// Directory history is stored in; defaults to $XDG_DATA_HOME/erdtree
+    #[arg(long, value_name = "DIR")]
+    pub history_dir: Option<PathBuf>,
+}
+
+/// Arguments for [`Subcommand::Bench`].
+#[derive(Args, Clone, Debug)]
+pub struct BenchArgs {
+    /// Directory to traverse; defaults to current working directory
+    pub path: Option<PathBuf>,
+
+    /// Number of traversals to time
+    #[arg(long, default_value_t = 5, value_name = "NUM")]
+    pub runs: usize,
+
+    /// Drop the page, dentry, and inode caches before every traversal; requires root on Linux
+    #[arg(long)]
+    pub drop_caches: bool,
+}
+
+/// Arguments for [`Subcommand::IgnoreCheck`].
+#[derive(Args, Clone, Debug)]
+pub struct IgnoreCheckArgs {
+    /// Paths to check against the ignore rules of the root directory
+    #[arg(required = true)]
+    pub paths: Vec<PathBuf>,
+}
@@ -17,7 +17,7 @@ fn config() {
 
     assert_eq!(
         sort,
-        SortType::Size,
+        [SortType::Size],
         "Failed to properly read 'sort' from config"
     );
 
@@ -30,7 +30,7 @@ fn context_from_config() -> Option<Context> {
     config::read_config_to_string(Some(TEST_CONFIG))
         .as_ref()
         .and_then(|config| {
-            let raw_config_args = config::parse(config);
+            let raw_config_args = config::parse(config, None)?;
             let config_args = Context::command().get_matches_from(raw_config_args);
             Context::from_arg_matches(&config_args).ok()
         })
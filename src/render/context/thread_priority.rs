@@ -0,0 +1,12 @@
+use clap::ValueEnum;
+
+/// How the threads that traverse the filesystem are scheduled relative to other work.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum ThreadPriority {
+    /// Only use CPU and disk time that nothing else wants
+    Low,
+
+    /// Compete for CPU and disk time like any other program
+    #[default]
+    Normal,
+}
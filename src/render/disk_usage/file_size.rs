@@ -1,5 +1,9 @@
-use super::units::{BinPrefix, PrefixKind, SiPrefix, UnitPrefix};
-use crate::{render::styles::get_du_theme, Context};
+use super::units::{BinPrefix, PrefixKind, SiPrefix, Unit, UnitPrefix};
+use crate::{
+    i18n::tr,
+    render::styles::{get_du_theme, get_muted_style},
+    Context,
+};
 use clap::ValueEnum;
 use filesize::PathExt;
 use std::{fs::Metadata, ops::AddAssign, path::Path};
@@ -8,10 +12,11 @@ use std::{fs::Metadata, ops::AddAssign, path::Path};
 #[derive(Clone, Debug)]
 pub struct FileSize {
     pub bytes: u64,
-    #[allow(dead_code)]
     disk_usage: DiskUsage,
     prefix_kind: PrefixKind,
     scale: usize,
+    rounding: Rounding,
+    unit: Option<Unit>,
 }
 
 /// Disk usage information in human readable format
@@ -20,6 +25,20 @@ pub struct HumanReadableComponents {
     pub unit: String,
 }
 
+/// How human-readable sizes are rounded to the number of digits after the decimal
+#[derive(Copy, Clone, Debug, ValueEnum, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round to the nearest value
+    #[default]
+    Nearest,
+
+    /// Round towards the larger value so that sizes are never understated
+    Up,
+
+    /// Round towards the smaller value so that sizes are never overstated
+    Down,
+}
+
 /// Determines between logical or physical size for display
 #[derive(Copy, Clone, Debug, ValueEnum, Default)]
 pub enum DiskUsage {
@@ -28,6 +47,7 @@ pub enum DiskUsage {
     Logical,
 
     /// How much actual space on disk, taking into account sparse files and compression.
+    #[value(alias = "block")]
     Physical,
 }
 
@@ -38,19 +58,41 @@ impl FileSize {
         disk_usage: DiskUsage,
         prefix_kind: PrefixKind,
         scale: usize,
+        rounding: Rounding,
+        unit: Option<Unit>,
     ) -> Self {
         Self {
             bytes,
             disk_usage,
             prefix_kind,
             scale,
+            rounding,
+            unit,
         }
     }
 
+    /// The kind of disk usage the size is in.
+    pub const fn disk_usage(&self) -> DiskUsage {
+        self.disk_usage
+    }
+
     /// Computes the logical size of a file given its [Metadata].
-    pub fn logical(md: &Metadata, prefix_kind: PrefixKind, scale: usize) -> Self {
+    pub fn logical(
+        md: &Metadata,
+        prefix_kind: PrefixKind,
+        scale: usize,
+        rounding: Rounding,
+        unit: Option<Unit>,
+    ) -> Self {
         let bytes = md.len();
-        Self::new(bytes, DiskUsage::Logical, prefix_kind, scale)
+        Self::new(
+            bytes,
+            DiskUsage::Logical,
+            prefix_kind,
+            scale,
+            rounding,
+            unit,
+        )
     }
 
     /// Computes the physical size of a file given its [Path] and [Metadata].
@@ -59,42 +101,48 @@ impl FileSize {
         md: &Metadata,
         prefix_kind: PrefixKind,
         scale: usize,
+        rounding: Rounding,
+        unit: Option<Unit>,
     ) -> Option<Self> {
-        path.size_on_disk_fast(md)
-            .ok()
-            .map(|bytes| Self::new(bytes, DiskUsage::Physical, prefix_kind, scale))
+        path.size_on_disk_fast(md).ok().map(|bytes| {
+            Self::new(
+                bytes,
+                DiskUsage::Physical,
+                prefix_kind,
+                scale,
+                rounding,
+                unit,
+            )
+        })
     }
 
     /// Transforms the `FileSize` into a string.
     /// `Display` / `ToString` traits not used in order to have control over alignment.
     ///
-    /// `align` false makes strings such as
+    /// `align` of `None` makes strings such as
     /// `123.45 KiB`
     /// `1.23 MiB`
     /// `12 B`
     ///
-    /// `align` true makes strings such as
+    /// `align` of `Some(6)`, the width given by [Context::size_width], makes strings such as
     /// `123.45 KiB`
     /// `  1.23 MiB`
     /// `    12   B`
-    pub fn format(&self, align: bool) -> String {
+    pub fn format(&self, align: Option<usize>) -> String {
         let du_themes = get_du_theme();
 
         let HumanReadableComponents { size, unit } = Self::human_readable_components(self);
         let color = du_themes.get(unit.as_str()).unwrap();
 
-        if align {
-            match self.prefix_kind {
-                PrefixKind::Bin => color
-                    .paint(format!("{size:>len$} {unit:>3}", len = self.scale + 4))
-                    .to_string(),
-                PrefixKind::Si => color
-                    .paint(format!("{size:>len$} {unit:>2}", len = self.scale + 4))
-                    .to_string(),
-            }
-        } else {
-            color.paint(format!("{size} {unit}")).to_string()
-        }
+        let formatted = align.map_or_else(
+            || format!("{size} {unit}"),
+            |len| {
+                let unit_len = Self::unit_len(self.prefix_kind, self.unit);
+                format!("{size:>len$} {unit:>unit_len$}")
+            },
+        );
+
+        color.paint(formatted).to_string()
     }
 
     /// Returns spaces times the length of a file size, formatted with the given options
@@ -103,23 +151,64 @@ impl FileSize {
         format!("{:len$}", "", len = Self::empty_string_len(ctx))
     }
 
-    const fn empty_string_len(ctx: &Context) -> usize {
-        // 3 places before the decimal
-        // 1 for the decimal
-        // ctx.scale after the decimal
+    /// Length of the string returned by [`Self::empty_string`].
+    pub const fn empty_string_len(ctx: &Context) -> usize {
+        // ctx.size_width() for the number
         // 1 space before unit
         // 2/3 spaces per unit, depending
-        3 + 1
-            + ctx.scale
-            + 1
-            + match ctx.prefix {
-                PrefixKind::Bin => 3,
-                PrefixKind::Si => 2,
-            }
+        // the exact byte count of --show-bytes, if any
+        ctx.size_width() + 1 + Self::unit_len(ctx.prefix, ctx.unit) + Self::exact_len(ctx)
+    }
+
+    /// Length of the exact byte count appended to aligned sizes with `--show-bytes`, such as
+    /// ` (1241 B)`, including the space that precedes it.
+    pub const fn exact_len(ctx: &Context) -> usize {
+        if ctx.show_bytes {
+            ctx.bytes_width() + 5
+        } else {
+            0
+        }
+    }
+
+    /// The exact byte count appended to the human-readable size with `--show-bytes`, such as
+    /// `(1241 B)`, padded to [Context::bytes_width] if `align`.
+    pub fn exact(&self, ctx: &Context, align: bool) -> String {
+        let bytes = ctx.group_digits(self.bytes);
+        let width = if align { ctx.bytes_width() } else { 0 };
+
+        get_muted_style()
+            .paint(format!("({bytes:>width$} {})", tr("unit-b")))
+            .to_string()
+    }
+
+    /// Number of columns that units are padded to: 3 for binary prefixes, which `--unit` always
+    /// is, and 2 for SI prefixes.
+    const fn unit_len(prefix_kind: PrefixKind, unit: Option<Unit>) -> usize {
+        match (prefix_kind, unit) {
+            (PrefixKind::Bin, _) | (_, Some(_)) => 3,
+            (PrefixKind::Si, None) => 2,
+        }
     }
 
     /// Returns a tuple of the human readable size and prefix.
     pub fn human_readable_components(&self) -> HumanReadableComponents {
+        // Sizes in a single unit may have fractional bytes as they're never printed in bytes
+        // instead.
+        if let Some(unit) = self.unit {
+            let unit = BinPrefix::from(unit);
+
+            let size = if matches!(unit, BinPrefix::Base) {
+                format!("{}", self.bytes)
+            } else {
+                self.round(unit.base_value())
+            };
+
+            return HumanReadableComponents {
+                size,
+                unit: format!("{unit}"),
+            };
+        }
+
         let fbytes = self.bytes as f64;
         let scale = self.scale;
         let power = u32::try_from(scale).unwrap();
@@ -136,10 +225,7 @@ impl FileSize {
                     if self.bytes <= 10_u64.pow(power) {
                         (format!("{}", self.bytes), format!("{}", BinPrefix::Base))
                     } else {
-                        (
-                            format!("{:.scale$}", fbytes / (base_value as f64)),
-                            format!("{unit}"),
-                        )
+                        (self.round(base_value), format!("{unit}"))
                     }
                 }
             }
@@ -155,10 +241,7 @@ impl FileSize {
                     if 10_u64.pow(power) >= base_value {
                         (format!("{}", self.bytes), format!("{}", SiPrefix::Base))
                     } else {
-                        (
-                            format!("{:.scale$}", fbytes / (base_value as f64)),
-                            format!("{unit}"),
-                        )
+                        (self.round(base_value), format!("{unit}"))
                     }
                 }
             }
@@ -166,6 +249,40 @@ impl FileSize {
 
         HumanReadableComponents { size, unit }
     }
+
+    /// Formats the number of `base_value`s in `bytes` with `scale` digits after the decimal,
+    /// rounded as per [Rounding]. Rounding up or down is done in integer arithmetic so that
+    /// values that are exact at the given scale aren't nudged by floating point error.
+    fn round(&self, base_value: u64) -> String {
+        let scale = self.scale;
+        let nearest = || format!("{:.scale$}", self.bytes as f64 / base_value as f64);
+
+        let divisor = u128::from(base_value);
+
+        let Some((scaled, factor)) = u32::try_from(scale)
+            .ok()
+            .and_then(|power| 10_u128.checked_pow(power))
+            .and_then(|factor| {
+                u128::from(self.bytes)
+                    .checked_mul(factor)
+                    .map(|scaled| (scaled, factor))
+            })
+        else {
+            return nearest();
+        };
+
+        let quotient = match self.rounding {
+            Rounding::Nearest => return nearest(),
+            Rounding::Up => (scaled + divisor - 1) / divisor,
+            Rounding::Down => scaled / divisor,
+        };
+
+        if scale == 0 {
+            return format!("{quotient}");
+        }
+
+        format!("{}.{:0scale$}", quotient / factor, quotient % factor)
+    }
 }
 
 impl AddAssign<u64> for FileSize {
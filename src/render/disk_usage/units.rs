@@ -1,3 +1,4 @@
+use crate::i18n::tr;
 use clap::ValueEnum;
 use std::{
     convert::From,
@@ -15,6 +16,26 @@ pub enum PrefixKind {
     Si,
 }
 
+/// A single unit that disk usage is printed in regardless of its magnitude.
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum Unit {
+    /// Bytes.
+    #[value(name = "B")]
+    B,
+
+    /// Kibibytes.
+    #[value(name = "KiB")]
+    KiB,
+
+    /// Mebibytes.
+    #[value(name = "MiB")]
+    MiB,
+
+    /// Gibibytes.
+    #[value(name = "GiB")]
+    GiB,
+}
+
 /// Binary prefixes.
 #[derive(Debug)]
 pub enum BinPrefix {
@@ -63,6 +84,17 @@ impl UnitPrefix for BinPrefix {
     }
 }
 
+impl From<Unit> for BinPrefix {
+    fn from(unit: Unit) -> Self {
+        match unit {
+            Unit::B => Self::Base,
+            Unit::KiB => Self::Kibi,
+            Unit::MiB => Self::Mebi,
+            Unit::GiB => Self::Gibi,
+        }
+    }
+}
+
 /// Get the closest human-readable unit prefix for value.
 impl From<f64> for BinPrefix {
     fn from(value: f64) -> Self {
@@ -104,11 +136,11 @@ impl From<f64> for SiPrefix {
 impl Display for BinPrefix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Base => f.pad("B"),
-            Self::Kibi => f.pad("KiB"),
-            Self::Mebi => f.pad("MiB"),
-            Self::Gibi => f.pad("GiB"),
-            Self::Tebi => f.pad("TiB"),
+            Self::Base => f.pad(&tr("unit-b")),
+            Self::Kibi => f.pad(&tr("unit-kib")),
+            Self::Mebi => f.pad(&tr("unit-mib")),
+            Self::Gibi => f.pad(&tr("unit-gib")),
+            Self::Tebi => f.pad(&tr("unit-tib")),
         }
     }
 }
@@ -116,11 +148,11 @@ impl Display for BinPrefix {
 impl Display for SiPrefix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Base => f.pad("B"),
-            Self::Kilo => f.pad("KB"),
-            Self::Mega => f.pad("MB"),
-            Self::Giga => f.pad("GB"),
-            Self::Tera => f.pad("TB"),
+            Self::Base => f.pad(&tr("unit-b")),
+            Self::Kilo => f.pad(&tr("unit-kb")),
+            Self::Mega => f.pad(&tr("unit-mb")),
+            Self::Giga => f.pad(&tr("unit-gb")),
+            Self::Tera => f.pad(&tr("unit-tb")),
         }
     }
 }
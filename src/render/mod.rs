@@ -1,3 +1,6 @@
+/// Drawing of output onto surfaces other than the terminal.
+pub mod canvas;
+
 /// CLI rules and definitions and context wherein [`Tree`] will operate.
 ///
 /// [`Tree`]: tree::Tree
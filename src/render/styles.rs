@@ -1,8 +1,9 @@
-use crate::hash;
-use ansi_term::Color;
+use super::canvas;
+use crate::{hash, i18n::tr};
+use ansi_term::{Color, Style};
 use lscolors::LsColors;
 use once_cell::sync::OnceCell;
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap, time::Duration};
 
 /// Used for padding between tree branches.
 pub const SEP: &str = "   ";
@@ -16,6 +17,40 @@ pub const UPRT: &str = "\u{2514}\u{2500} ";
 /// The `├─` box drawing characters.
 pub const VTRT: &str = "\u{251C}\u{2500} ";
 
+/// Depth at which [fade] starts dimming entries.
+pub const FADE_START_DEPTH: usize = 2;
+
+/// Brightest and darkest shades of the 256-color grayscale ramp used by [fade] for entries without
+/// a color of their own.
+const FADE_SHADES: (u8, u8) = (250, 238);
+
+/// Amount by which each level beyond [FADE_START_DEPTH] darkens uncolored entries.
+const FADE_STEP: u8 = 4;
+
+/// Most and least of their brightness, in percent, that [fade] leaves colored entries.
+const FADE_PERCENTS: (u8, u8) = (80, 50);
+
+/// Percentage of their brightness by which each level beyond [FADE_START_DEPTH] darkens colored
+/// entries.
+const FADE_PERCENT_STEP: u8 = 10;
+
+/// Shades of the 256-color grayscale ramp used by [age_shade] from the most to the least recently
+/// modified, each paired with the greatest age in seconds it applies to.
+const AGE_SHADES: [(u64, u8); 5] = [
+    (60 * 60, 255),
+    (60 * 60 * 24, 252),
+    (60 * 60 * 24 * 7, 249),
+    (60 * 60 * 24 * 30, 246),
+    (60 * 60 * 24 * 365, 243),
+];
+
+/// Shade of files modified longer ago than the last of [AGE_SHADES].
+const OLDEST_SHADE: u8 = 240;
+
+/// Colors of the 256-color palette that [owner_color] picks from, chosen to be told apart from
+/// one another on both dark and light backgrounds.
+const OWNER_COLORS: [u8; 12] = [33, 166, 35, 163, 178, 39, 203, 71, 135, 208, 44, 169];
+
 /// A runtime evaluated static. [LS_COLORS] the `LS_COLORS` environment variable to determine what
 /// ANSI colors to use when printing the names of files. If `LS_COLORS` is not set it will fallback
 /// to a default defined in the `lscolors` crate.
@@ -34,18 +69,103 @@ pub static TREE_THEME: OnceCell<ThemesMap> = OnceCell::new();
 pub static LINK_THEME: OnceCell<ThemesMap> = OnceCell::new();
 
 /// Runtime evaluated static that contains styles for disk usage output.
-pub static DU_THEME: OnceCell<HashMap<&'static str, Color>> = OnceCell::new();
+pub static DU_THEME: OnceCell<HashMap<Cow<'static, str>, Color>> = OnceCell::new();
+
+/// Runtime evaluated static that contains the style of secondary information such as notes.
+pub static MUTED_STYLE: OnceCell<Style> = OnceCell::new();
+
+/// Runtime evaluated static that contains the style of the targets of symlinks.
+pub static LINK_TARGET_STYLE: OnceCell<Style> = OnceCell::new();
 
 /// Map of the names box-drawing elements to their styled strings.
 pub type ThemesMap = HashMap<&'static str, String>;
 
-/// Initializes both [LS_COLORS] and all themes.
-pub fn init() {
+/// Removes ANSI escape sequences such as colors from `text`.
+pub fn strip_ansi(text: &str) -> String {
+    visible_chars(text).collect()
+}
+
+/// Number of columns `text` takes up in the terminal, ANSI escape sequences aside.
+pub fn visible_width(text: &str) -> usize {
+    visible_chars(text).count()
+}
+
+/// The characters of `text` that are printed, which excludes those of ANSI escape sequences.
+fn visible_chars(text: &str) -> impl Iterator<Item = char> + '_ {
+    segments(text)
+        .filter_map(|segment| match segment {
+            Segment::Text(text) => Some(text),
+            Segment::Sgr(_) | Segment::Escape => None,
+        })
+        .flat_map(str::chars)
+}
+
+/// A piece of text as printed to the terminal.
+pub enum Segment<'a> {
+    /// Text that's displayed as is.
+    Text(&'a str),
+
+    /// Parameters of a Select Graphic Rendition sequence e.g. `1;38;5;208`, which sets the colors
+    /// and other attributes of the text that follows.
+    Sgr(&'a str),
+
+    /// Any other escape sequence, such as those of hyperlinks.
+    Escape,
+}
+
+/// Splits `text` into the text that's displayed and the ANSI escape sequences in between.
+pub fn segments(text: &str) -> impl Iterator<Item = Segment<'_>> {
+    let mut rest = text;
+
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        let Some(sequence) = rest.strip_prefix('\u{1b}') else {
+            let (text, tail) = rest.split_at(rest.find('\u{1b}').unwrap_or(rest.len()));
+            rest = tail;
+            return Some(Segment::Text(text));
+        };
+
+        let (segment, len) = match sequence.chars().next() {
+            // Control sequences end with a byte in the range of `@` to `~`.
+            Some('[') => match sequence[1..]
+                .find(|ch| ('@'..='~').contains(&ch))
+                .map(|end| end + 1)
+            {
+                Some(end) if sequence[end..].starts_with('m') => {
+                    (Segment::Sgr(&sequence[1..end]), end + 1)
+                }
+                Some(end) => (Segment::Escape, end + 1),
+                None => (Segment::Escape, sequence.len()),
+            },
+
+            // Operating system commands end with a bell or a string terminator.
+            Some(']') => {
+                let bell = sequence.find('\u{7}').map(|end| end + 1);
+                let terminator = sequence.find("\u{1b}\\").map(|end| end + 2);
+                let end = bell.into_iter().chain(terminator).min();
+
+                (Segment::Escape, end.unwrap_or(sequence.len()))
+            }
+
+            ch => (Segment::Escape, ch.map_or(0, char::len_utf8)),
+        };
+
+        rest = &sequence[len..];
+
+        Some(segment)
+    })
+}
+
+/// Initializes both [LS_COLORS] and all themes. Themes are `accessible` with `--accessible`.
+pub fn init(accessible: bool) {
     #[cfg(windows)]
     let _ = ansi_term::enable_ansi_support();
 
     init_ls_colors();
-    init_themes();
+    init_themes(accessible);
 }
 
 /// Getter for [LS_COLORS]. Panics if not initialized.
@@ -54,10 +174,33 @@ pub fn get_ls_colors() -> &'static LsColors {
 }
 
 /// Getter for [DU_THEME]. Panics if not initialized.
-pub fn get_du_theme() -> &'static HashMap<&'static str, Color> {
+pub fn get_du_theme() -> &'static HashMap<Cow<'static, str>, Color> {
     DU_THEME.get().expect("DU_THEME not initialized")
 }
 
+/// Getter for [MUTED_STYLE]. Panics if not initialized.
+pub fn get_muted_style() -> Style {
+    *MUTED_STYLE.get().expect("MUTED_STYLE not initialized")
+}
+
+/// Getter for [LINK_TARGET_STYLE]. Panics if not initialized.
+pub fn get_link_target_style() -> Style {
+    *LINK_TARGET_STYLE
+        .get()
+        .expect("LINK_TARGET_STYLE not initialized")
+}
+
+/// Style of the names of entries with `--accessible`, which leaves them in the foreground color
+/// of the terminal as colors such as the bold blue of directories may be hard to make out against
+/// its background. Directories are told apart in bold as well as by their type marker.
+pub fn high_contrast(is_dir: bool) -> Style {
+    if is_dir {
+        Style::new().bold()
+    } else {
+        Style::new()
+    }
+}
+
 /// Getter for [TREE_THEME]. Panics if not initialized.
 pub fn get_tree_theme() -> &'static ThemesMap {
     TREE_THEME.get().expect("TREE_THEME not initialized")
@@ -68,6 +211,77 @@ pub fn get_link_theme() -> &'static ThemesMap {
     LINK_THEME.get().expect("LINK_THEME not initialized")
 }
 
+/// Progressively dims `style` by `depth` so that deeper entries recede. The foreground color,
+/// such as that of `LS_COLORS`, is darkened so that entries can still be told apart by it whereas
+/// entries without one are grayed out. Depths shallower than [FADE_START_DEPTH] keep their style.
+pub fn fade(style: Style, depth: usize) -> Style {
+    let Some(levels) = depth.checked_sub(FADE_START_DEPTH) else {
+        return style;
+    };
+
+    let levels = u8::try_from(levels).unwrap_or(u8::MAX);
+
+    let foreground = style.foreground.map_or_else(
+        || fade_uncolored(levels),
+        |color| fade_colored(color, levels),
+    );
+
+    Style {
+        foreground: Some(foreground),
+        ..style
+    }
+}
+
+/// Shade of gray of entries without a color of their own `levels` beyond [FADE_START_DEPTH].
+fn fade_uncolored(levels: u8) -> Color {
+    let (brightest, darkest) = FADE_SHADES;
+
+    let shade = levels.checked_mul(FADE_STEP).map_or(darkest, |dimming| {
+        brightest.saturating_sub(dimming).max(darkest)
+    });
+
+    Color::Fixed(shade)
+}
+
+/// `color` darkened for entries `levels` beyond [FADE_START_DEPTH].
+fn fade_colored(color: Color, levels: u8) -> Color {
+    let (brightest, darkest) = FADE_PERCENTS;
+
+    let percent = levels
+        .checked_mul(FADE_PERCENT_STEP)
+        .map_or(darkest, |dimming| {
+            brightest.saturating_sub(dimming).max(darkest)
+        });
+
+    let dim =
+        |level: u8| u8::try_from(u16::from(level) * u16::from(percent) / 100).unwrap_or(level);
+
+    let (r, g, b) = canvas::rgb(color);
+
+    Color::RGB(dim(r), dim(g), dim(b))
+}
+
+/// Maps how long ago a file was modified to a grayscale [Style] that is brightest within the hour
+/// and dims by the day, week, month, and year as with `--color-by age`.
+pub fn age_shade(age: Duration) -> Style {
+    let secs = age.as_secs();
+
+    let shade = AGE_SHADES
+        .iter()
+        .find(|(max_age, _)| secs <= *max_age)
+        .map_or(OLDEST_SHADE, |(_, shade)| *shade);
+
+    Color::Fixed(shade).normal()
+}
+
+/// Maps a user ID to a [Style] of its own as with `--color-by owner`. The same user always gets
+/// the same color, though users may share one if there are more of them than colors.
+pub fn owner_color(uid: u32) -> Style {
+    let index = usize::try_from(uid).unwrap_or_default() % OWNER_COLORS.len();
+
+    Color::Fixed(OWNER_COLORS[index]).normal()
+}
+
 /// Initializes [LS_COLORS] by reading in the `LS_COLORS` environment variable. If it isn't set, a
 /// default determined by `lscolors` crate will be used.
 fn init_ls_colors() {
@@ -76,8 +290,44 @@ fn init_ls_colors() {
         .unwrap();
 }
 
-/// Initializes all themes.
-fn init_themes() {
+/// Initializes all themes. `accessible` themes indent with blanks rather than box drawing
+/// characters, which screen readers read aloud, and don't dim secondary information or color the
+/// targets of symlinks.
+fn init_themes(accessible: bool) {
+    if accessible {
+        let blank = hash! {
+            "vt" => SEP.to_owned(),
+            "uprt" => SEP.to_owned(),
+            "vtrt" => SEP.to_owned()
+        };
+
+        TREE_THEME.set(blank.clone()).unwrap();
+        LINK_THEME.set(blank).unwrap();
+        MUTED_STYLE.set(Style::new()).unwrap();
+        LINK_TARGET_STYLE.set(Style::new().underline()).unwrap();
+    } else {
+        init_branch_themes();
+        MUTED_STYLE.set(Style::new().dimmed()).unwrap();
+        LINK_TARGET_STYLE.set(Color::Red.normal()).unwrap();
+    }
+
+    let du_theme = hash! {
+        tr("unit-b") => Color::Cyan,
+        tr("unit-kb") => Color::Yellow,
+        tr("unit-kib") => Color::Yellow,
+        tr("unit-mb") => Color::Green,
+        tr("unit-mib") => Color::Green,
+        tr("unit-gb") => Color::Red,
+        tr("unit-gib") => Color::Red,
+        tr("unit-tb") => Color::Blue,
+        tr("unit-tib") => Color::Blue
+    };
+
+    DU_THEME.set(du_theme).unwrap();
+}
+
+/// Initializes [TREE_THEME] and [LINK_THEME] with box drawing characters.
+fn init_branch_themes() {
     let theme = hash! {
         "vt" => format!("{}", Color::Purple.paint(VT)),
         "uprt" => format!("{}", Color::Purple.paint(UPRT)),
@@ -93,18 +343,4 @@ fn init_themes() {
     };
 
     LINK_THEME.set(link_theme).unwrap();
-
-    let du_theme = hash! {
-        "B" => Color::Cyan,
-        "KB" => Color::Yellow,
-        "KiB" => Color::Yellow,
-        "MB" => Color::Green,
-        "MiB" => Color::Green,
-        "GB" => Color::Red,
-        "GiB" => Color::Red,
-        "TB" => Color::Blue,
-        "TiB" => Color::Blue
-    };
-
-    DU_THEME.set(du_theme).unwrap();
 }
@@ -0,0 +1,58 @@
+use crate::i18n::tr_args;
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+/// Notes provided via `--annotate` keyed by path relative to the root directory.
+pub struct Annotations {
+    notes: HashMap<PathBuf, String>,
+}
+
+/// Errors that may occur while reading the file provided via `--annotate`.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{}", tr_args("error-annotations-read", [("error", .0.to_string())]))]
+    Io(#[from] io::Error),
+
+    #[error("{}", tr_args("error-annotations-parse", [("error", .0.to_string())]))]
+    Toml(#[from] toml::de::Error),
+
+    #[error("{}", tr_args("error-annotations-parse", [("error", .0.to_string())]))]
+    Json(#[from] serde_json::Error),
+}
+
+impl Annotations {
+    /// Reads the map of paths to notes at `path` which is parsed as JSON if it has a `.json`
+    /// extension and as TOML otherwise.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path)?;
+
+        let raw = if path.extension().map_or(false, |ext| ext == "json") {
+            serde_json::from_str::<HashMap<String, String>>(&contents)?
+        } else {
+            toml::from_str::<HashMap<String, String>>(&contents)?
+        };
+
+        let notes = raw
+            .into_iter()
+            .map(|(path, note)| (normalize(Path::new(&path)), note))
+            .collect();
+
+        Ok(Self { notes })
+    }
+
+    /// Note for the entry at `rel_path` relative to the root directory, if any.
+    pub fn get(&self, rel_path: &Path) -> Option<&str> {
+        self.notes.get(rel_path).map(String::as_str)
+    }
+}
+
+/// Drops `.` components so that `./src/`, `src/` and `src` all refer to the same entry and `.`
+/// refers to the root directory.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
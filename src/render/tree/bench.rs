@@ -0,0 +1,176 @@
+use super::{Result, Tree};
+use crate::render::context::{subcommand::BenchArgs, Context};
+use std::{
+    fmt::{self, Display},
+    path::PathBuf,
+    time::Duration,
+};
+
+/// Timings of repeated traversals of a single directory.
+pub struct Bench {
+    path: PathBuf,
+    runs: Vec<Run>,
+    threads: usize,
+    caches_dropped: bool,
+}
+
+/// Measurements of a single traversal.
+struct Run {
+    elapsed: Duration,
+    entries: usize,
+    usage: Usage,
+}
+
+/// Resource usage of the process attributed to a single traversal as reported by `getrusage`,
+/// which doesn't count system calls themselves. Both are zero where it isn't available.
+#[derive(Clone, Copy, Default)]
+struct Usage {
+    /// Number of block input operations i.e. reads the filesystem had to make from disk rather
+    /// than from the page cache.
+    block_inputs: u64,
+
+    /// Number of voluntary context switches i.e. times a thread gave up the CPU, usually because
+    /// it blocked on I/O or a lock.
+    voluntary_switches: u64,
+}
+
+/// Traverses the directory in `args` the requested number of times, timing each traversal.
+pub fn run(ctx: &Context, args: &BenchArgs) -> Result<Bench> {
+    let path = args.path.clone().unwrap_or_else(|| ctx.dir().to_path_buf());
+
+    let ctx = ctx.with_dir(path.clone());
+
+    let mut runs = Vec::with_capacity(args.runs);
+    let mut caches_dropped = args.drop_caches;
+
+    for _ in 0..args.runs {
+        if caches_dropped {
+            // Dropping caches is best-effort as it requires elevated privileges.
+            caches_dropped = drop_caches();
+        }
+
+        let before = Usage::now();
+        let tree = Tree::init(ctx.clone())?;
+        let after = Usage::now();
+
+        runs.push(Run {
+            elapsed: tree.traversal_time(),
+            entries: tree.inner().count(),
+            usage: after.since(before),
+        });
+    }
+
+    Ok(Bench {
+        path,
+        runs,
+        threads: ctx.threads(),
+        caches_dropped,
+    })
+}
+
+impl Run {
+    fn entries_per_sec(&self) -> f64 {
+        self.entries as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+impl Usage {
+    #[cfg(unix)]
+    fn now() -> Self {
+        // SAFETY: `rusage` is plain old data for which all zeroes is a valid value.
+        let mut usage = unsafe { std::mem::zeroed::<libc::rusage>() };
+
+        // SAFETY: `usage` is a valid pointer for the duration of the call.
+        if unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) } != 0 {
+            return Self::default();
+        }
+
+        Self {
+            block_inputs: u64::try_from(usage.ru_inblock).unwrap_or(0),
+            voluntary_switches: u64::try_from(usage.ru_nvcsw).unwrap_or(0),
+        }
+    }
+
+    /// Resource usage isn't available on this platform.
+    #[cfg(not(unix))]
+    fn now() -> Self {
+        Self::default()
+    }
+
+    const fn since(self, earlier: Self) -> Self {
+        Self {
+            block_inputs: self.block_inputs.saturating_sub(earlier.block_inputs),
+            voluntary_switches: self
+                .voluntary_switches
+                .saturating_sub(earlier.voluntary_switches),
+        }
+    }
+}
+
+/// Flushes dirty pages and drops the page, dentry, and inode caches so that the next traversal
+/// reads from disk. Returns whether the caches could be dropped.
+#[cfg(target_os = "linux")]
+fn drop_caches() -> bool {
+    // SAFETY: `sync` has no preconditions.
+    unsafe { libc::sync() };
+
+    std::fs::write("/proc/sys/vm/drop_caches", "3").is_ok()
+}
+
+/// Caches can't be dropped on this platform.
+#[cfg(not(target_os = "linux"))]
+const fn drop_caches() -> bool {
+    false
+}
+
+impl Display for Bench {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} (threads: {}, caches: {})",
+            self.path.display(),
+            self.threads,
+            if self.caches_dropped { "cold" } else { "warm" }
+        )?;
+
+        for (i, run) in self.runs.iter().enumerate() {
+            writeln!(
+                f,
+                "run {}: {:.2?}, {} entries, {:.0} entries/s, {} block inputs, {} voluntary context switches",
+                i + 1,
+                run.elapsed,
+                run.entries,
+                run.entries_per_sec(),
+                run.usage.block_inputs,
+                run.usage.voluntary_switches
+            )?;
+        }
+
+        let Some(fastest) = self.runs.iter().map(|run| run.elapsed).min() else {
+            return Ok(());
+        };
+
+        let slowest = self
+            .runs
+            .iter()
+            .map(|run| run.elapsed)
+            .max()
+            .unwrap_or(fastest);
+
+        let total = self.runs.iter().map(|run| run.elapsed).sum::<Duration>();
+        let mean = total / u32::try_from(self.runs.len()).unwrap_or(u32::MAX);
+
+        let entries_per_sec =
+            self.runs.iter().map(Run::entries_per_sec).sum::<f64>() / self.runs.len() as f64;
+
+        let runs = match self.runs.len() {
+            1 => String::from("1 run"),
+            n => format!("{n} runs"),
+        };
+
+        write!(
+            f,
+            "mean: {mean:.2?} (min {fastest:.2?}, max {slowest:.2?}), {entries_per_sec:.0} entries/s across {runs}"
+        )
+    }
+}
@@ -0,0 +1,323 @@
+use crate::render::disk_usage::file_size::{DiskUsage, FileSize};
+use clap::ValueEnum;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// Key of the line of a [Checkpoint] holding the root it was taken of.
+const ROOT_KEY: &str = "root";
+
+/// Key of the line of a [Checkpoint] holding the kinds of disk usage its sizes are in.
+const DISK_USAGE_KEY: &str = "disk-usage";
+
+/// Key of the line of a [Checkpoint] holding the fingerprint of the settings it was taken with.
+const SETTINGS_KEY: &str = "settings";
+
+/// How often the checkpoint is persisted at most while directories keep completing.
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Directories whose traversal completed, along with their disk usage, which is everything needed
+/// to resume an interrupted traversal with `--resume` without walking them again. Directories
+/// within others that completed are left out as they're covered by those. Checkpoints are
+/// persisted as plain-text: a `root<TAB>path`, a `disk-usage<TAB>kinds`, and a
+/// `settings<TAB>fingerprint` line followed by one `bytes[,bytes...]<TAB>path` line per
+/// directory, its path being relative to the root with components separated by `/`.
+#[derive(Clone, Debug)]
+pub struct Checkpoint {
+    root: PathBuf,
+    disk_usage: Vec<DiskUsage>,
+    settings: String,
+    completed: BTreeMap<String, Vec<u64>>,
+}
+
+/// A traversal in progress that periodically persists a [Checkpoint] to `path`. Entries are
+/// expected to be recorded in depth-first order so that a directory is known to be complete as
+/// soon as an entry outside of it is recorded.
+#[derive(Debug)]
+pub struct Progress {
+    path: PathBuf,
+    checkpoint: Checkpoint,
+
+    /// Directories being traversed from the root down, each along with its disk usage so far.
+    open: Vec<(PathBuf, Vec<u64>)>,
+
+    last_saved: Instant,
+}
+
+impl Checkpoint {
+    /// An empty checkpoint of the traversal of `root` measuring each kind of `disk_usage` with
+    /// the `settings` of [`Context::traversal_settings`].
+    ///
+    /// [`Context::traversal_settings`]: crate::render::context::Context::traversal_settings
+    pub fn new(root: &Path, disk_usage: &[DiskUsage], settings: &str) -> Self {
+        Self {
+            root: root.to_owned(),
+            disk_usage: disk_usage.to_vec(),
+            settings: fingerprint(settings),
+            completed: BTreeMap::new(),
+        }
+    }
+
+    /// Reads the checkpoint at `path`, which is expected to have been taken of `root` measuring
+    /// each kind of `disk_usage` with the same `settings`.
+    pub fn load(
+        path: &Path,
+        root: &Path,
+        disk_usage: &[DiskUsage],
+        settings: &str,
+    ) -> io::Result<Self> {
+        let mut checkpoint = Self::new(root, disk_usage, settings);
+        let mut lines = BufReader::new(fs::File::open(path)?).lines();
+
+        let mut header = |key| {
+            let line = lines.next().transpose()?.unwrap_or_default();
+
+            line.strip_prefix(key)
+                .and_then(|rest| rest.strip_prefix('\t'))
+                .map(str::to_owned)
+                .ok_or_else(|| invalid_data(format!("expected a line starting with '{key}'")))
+        };
+
+        let taken_of = header(ROOT_KEY)?;
+        let taken_in = header(DISK_USAGE_KEY)?;
+        let taken_with = header(SETTINGS_KEY)?;
+
+        if Path::new(&taken_of) != root {
+            return Err(invalid_data(format!("it was taken of {taken_of}")));
+        }
+
+        if taken_in != Self::disk_usage_names(disk_usage) {
+            return Err(invalid_data(format!(
+                "it was taken with --disk-usage {taken_in}"
+            )));
+        }
+
+        if taken_with != checkpoint.settings {
+            return Err(invalid_data(
+                "it was taken with different flags to select or measure entries".to_owned(),
+            ));
+        }
+
+        for line in lines {
+            let line = line?;
+
+            let Some((bytes, rel_path)) = line.split_once('\t') else {
+                continue;
+            };
+
+            let sizes = bytes
+                .split(',')
+                .map(|bytes| bytes.parse::<u64>().ok())
+                .collect::<Option<Vec<_>>>();
+
+            if let Some(sizes) = sizes.filter(|sizes| sizes.len() == disk_usage.len()) {
+                checkpoint.completed.insert(rel_path.to_owned(), sizes);
+            }
+        }
+
+        Ok(checkpoint)
+    }
+
+    /// Writes the checkpoint to `path` by way of a temporary file so that an interruption never
+    /// leaves a partially written checkpoint behind.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        let mut writer = BufWriter::new(fs::File::create(&tmp)?);
+
+        writeln!(writer, "{ROOT_KEY}\t{}", self.root.display())?;
+        writeln!(
+            writer,
+            "{DISK_USAGE_KEY}\t{}",
+            Self::disk_usage_names(&self.disk_usage)
+        )?;
+        writeln!(writer, "{SETTINGS_KEY}\t{}", self.settings)?;
+
+        for (rel_path, sizes) in &self.completed {
+            let sizes = sizes
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+
+            writeln!(writer, "{sizes}\t{rel_path}")?;
+        }
+
+        writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)?;
+
+        fs::rename(tmp, path)
+    }
+
+    /// Disk usage of the directory at `rel_path` relative to the root if its traversal completed.
+    /// Paths that aren't valid UTF-8 are never recorded and so are always traversed again.
+    pub fn completed(&self, rel_path: &Path) -> Option<&[u64]> {
+        key(rel_path)
+            .and_then(|key| self.completed.get(&key))
+            .map(Vec::as_slice)
+    }
+
+    /// Records the directory at `rel_path` as completed in place of the directories within it.
+    fn complete(&mut self, rel_path: &Path, sizes: Vec<u64>) {
+        let Some(key) = key(rel_path) else {
+            return;
+        };
+
+        let prefix = format!("{key}/");
+
+        let nested = self
+            .completed
+            .range(prefix.clone()..)
+            .map(|(nested, _)| nested)
+            .take_while(|nested| nested.starts_with(&prefix))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for nested in nested {
+            self.completed.remove(&nested);
+        }
+
+        self.completed.insert(key, sizes);
+    }
+
+    /// Names of `disk_usage` as they're passed to `--disk-usage`.
+    fn disk_usage_names(disk_usage: &[DiskUsage]) -> String {
+        disk_usage
+            .iter()
+            .filter_map(DiskUsage::to_possible_value)
+            .map(|value| value.get_name().to_owned())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+impl Progress {
+    /// Continues `checkpoint`, persisting it to `path`.
+    pub fn new(path: PathBuf, checkpoint: Checkpoint) -> Self {
+        Self {
+            path,
+            checkpoint,
+            open: vec![],
+            last_saved: Instant::now(),
+        }
+    }
+
+    /// The checkpoint as it was when the traversal started.
+    pub const fn checkpoint(&self) -> &Checkpoint {
+        &self.checkpoint
+    }
+
+    /// Disk usage of the directory at `rel_path` relative to the root if it was traversed in full
+    /// before.
+    pub fn completed(&self, rel_path: &Path) -> Option<&[u64]> {
+        self.checkpoint.completed(rel_path)
+    }
+
+    /// Adds the disk usage of the entry at `rel_path` relative to the root to that of the
+    /// directory it lies in, completing the directories it lies outside of. The contents of
+    /// directories that are to be traversed are expected to be recorded next, whereas those of
+    /// directories that aren't, such as those completed before, never are.
+    pub fn record(&mut self, rel_path: &Path, sizes: &[FileSize], traversed: bool) {
+        while self
+            .open
+            .last()
+            .map_or(false, |(dir, _)| !rel_path.starts_with(dir))
+        {
+            self.close();
+        }
+
+        let sizes = sizes.iter().map(|size| size.bytes).collect::<Vec<_>>();
+
+        if traversed {
+            self.open.push((rel_path.to_owned(), sizes));
+        } else if let Some((_, totals)) = self.open.last_mut() {
+            add(totals, &sizes);
+        }
+    }
+
+    /// Completes every directory still being traversed and persists the checkpoint.
+    pub fn finish(&mut self) {
+        while !self.open.is_empty() {
+            self.close();
+        }
+
+        self.save();
+    }
+
+    /// Completes the innermost directory being traversed, persisting the checkpoint if it hasn't
+    /// been for a while. Failing to persist it only costs traversing directories again if resumed.
+    fn close(&mut self) {
+        let Some((rel_path, sizes)) = self.open.pop() else {
+            return;
+        };
+
+        if let Some((_, totals)) = self.open.last_mut() {
+            add(totals, &sizes);
+        }
+
+        // The traversal is complete once the root is and then there's nothing left to resume.
+        if rel_path.as_os_str().is_empty() {
+            return;
+        }
+
+        let mut sizes = sizes;
+        sizes.resize(self.checkpoint.disk_usage.len(), 0);
+        self.checkpoint.complete(&rel_path, sizes);
+
+        if self.last_saved.elapsed() >= SAVE_INTERVAL {
+            self.save();
+        }
+    }
+
+    /// Persists the checkpoint to its file, warning if it can't be.
+    fn save(&mut self) {
+        self.last_saved = Instant::now();
+
+        if let Err(e) = self.checkpoint.save(&self.path) {
+            tracing::warn!(path = %self.path.display(), error = %e, "failed to save checkpoint");
+        }
+    }
+}
+
+/// Adds each of `sizes` to the respective one of `totals`.
+fn add(totals: &mut Vec<u64>, sizes: &[u64]) {
+    totals.resize(sizes.len().max(totals.len()), 0);
+
+    for (total, size) in totals.iter_mut().zip(sizes) {
+        *total += size;
+    }
+}
+
+/// Key of the directory at `rel_path` relative to the root, which is its components separated by
+/// `/`. Returns `None` if it isn't valid UTF-8.
+fn key(rel_path: &Path) -> Option<String> {
+    rel_path
+        .iter()
+        .map(|component| component.to_str())
+        .collect::<Option<Vec<_>>>()
+        .map(|components| components.join("/"))
+}
+
+/// Stable digest of `settings` in hexadecimal as per 64-bit FNV-1a, which unlike the hashers of
+/// the standard library is guaranteed not to change between releases.
+fn fingerprint(settings: &str) -> String {
+    let hash = settings
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+
+    format!("{hash:016x}")
+}
+
+/// Error for checkpoints that can't be resumed because of `reason`.
+fn invalid_data(reason: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
@@ -0,0 +1,151 @@
+use std::{
+    ffi::OsString,
+    io::Read,
+    path::Path,
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Placeholder in the command of `--column-cmd` that is replaced with the path of each entry.
+const PLACEHOLDER: &str = "{}";
+
+/// How long a run of the command of `--column-cmd` may take unless `--column-cmd-timeout` says
+/// otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often a run is checked on while waiting for it to exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Runs `cmd` for each of `paths`, at most `jobs` at a time, returning the first line of what each
+/// run printed to stdout in the same order. Runs that fail, print nothing, or don't exit within
+/// `timeout` have no output.
+///
+/// The command is split into words as by a POSIX shell, quotes and escapes included, but isn't run
+/// by one, with each `{}` replaced by the path; if there is none the path is passed as the last
+/// argument.
+pub fn run(cmd: &str, paths: &[&Path], jobs: usize, timeout: Duration) -> Vec<Option<String>> {
+    // Commands are checked to split when parsed.
+    let words = shell_words::split(cmd).unwrap_or_default();
+    let next = AtomicUsize::new(0);
+
+    let mut outputs = thread::scope(|s| {
+        let workers = (0..jobs.clamp(1, paths.len().max(1)))
+            .map(|_| {
+                s.spawn(|| {
+                    let mut outputs = vec![];
+
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+
+                        let Some(path) = paths.get(i) else {
+                            break;
+                        };
+
+                        outputs.push((i, run_one(&words, path, timeout)));
+                    }
+
+                    outputs
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    outputs.sort_unstable_by_key(|(i, _)| *i);
+    outputs.into_iter().map(|(_, output)| output).collect()
+}
+
+/// Runs the command made up of `words` for the entry at `path` as described by [run].
+fn run_one(words: &[String], path: &Path, timeout: Duration) -> Option<String> {
+    let mut args = args(words, path).into_iter();
+    let program = args.next()?;
+
+    let result = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+
+    let mut child = match result {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "failed to run --column-cmd");
+            return None;
+        }
+    };
+
+    // Read on a thread of its own so that runs that print more than fits in the pipe don't block.
+    let mut stdout = child.stdout.take()?;
+    let reader = thread::spawn(move || {
+        let mut output = vec![];
+        stdout.read_to_end(&mut output).map(|_| output)
+    });
+
+    let Some(status) = wait(&mut child, timeout) else {
+        tracing::warn!(path = %path.display(), ?timeout, "--column-cmd timed out");
+        return None;
+    };
+
+    if !status.success() {
+        tracing::debug!(path = %path.display(), %status, "--column-cmd failed");
+        return None;
+    }
+
+    let output = reader.join().ok()?.ok()?;
+
+    String::from_utf8_lossy(&output)
+        .lines()
+        .next()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_owned)
+}
+
+/// Waits for `child` to exit for at most `timeout`, killing it if it doesn't.
+fn wait(child: &mut Child, timeout: Duration) -> Option<ExitStatus> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Some(status),
+            Ok(None) if Instant::now() < deadline => thread::sleep(POLL_INTERVAL),
+            _ => break,
+        }
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+
+    None
+}
+
+/// The program and arguments of the command made up of `words` to run for the entry at `path`.
+fn args(words: &[String], path: &Path) -> Vec<OsString> {
+    let mut args = words.iter().map(OsString::from).collect::<Vec<_>>();
+
+    if !words.iter().any(|word| word.contains(PLACEHOLDER)) {
+        args.push(path.as_os_str().to_owned());
+        return args;
+    }
+    for arg in &mut args {
+        let Some(raw) = arg.to_str().filter(|raw| raw.contains(PLACEHOLDER)) else {
+            continue;
+        };
+
+        // Paths that aren't valid UTF-8 can only stand in for the placeholder on its own.
+        *arg = if raw == PLACEHOLDER {
+            path.as_os_str().to_owned()
+        } else {
+            OsString::from(raw.replace(PLACEHOLDER, &path.to_string_lossy()))
+        };
+    }
+
+    args
+}
@@ -1,18 +1,77 @@
 use super::Node;
-use std::{
-    convert::From,
-    fmt::{self, Display},
-};
+use crate::fs::content::{Encoding, LineEnding, TextProfile};
+use crate::{i18n::tr_args, render::context::Context};
+use fluent_bundle::FluentValue;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::convert::From;
 
 /// For keeping track of the number of various file-types of [Node]'s chlidren.
-#[allow(clippy::module_name_repetitions)]
-#[derive(Default)]
+#[allow(clippy::module_name_repetitions, clippy::struct_field_names)]
+#[derive(Clone, Copy, Default, Serialize, JsonSchema)]
 pub struct FileCount {
+    #[serde(rename = "directories")]
     pub num_dirs: usize,
+    #[serde(rename = "files")]
     pub num_files: usize,
+    #[serde(rename = "links")]
     pub num_links: usize,
 }
 
+/// For keeping track of the number of text files by encoding and line endings.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Default, Serialize, JsonSchema)]
+pub struct EncodingCount {
+    #[serde(rename = "utf-8")]
+    pub utf8: usize,
+    #[serde(rename = "utf-16")]
+    pub utf16: usize,
+    #[serde(rename = "latin-1")]
+    pub latin1: usize,
+    pub lf: usize,
+    pub crlf: usize,
+    pub mixed: usize,
+}
+
+impl EncodingCount {
+    /// Update [Self] with the profile of a single text file.
+    pub fn update(
+        &mut self,
+        TextProfile {
+            encoding,
+            line_ending,
+        }: TextProfile,
+    ) {
+        match encoding {
+            Encoding::Utf8 => self.utf8 += 1,
+            Encoding::Utf16 => self.utf16 += 1,
+            Encoding::Latin1 => self.latin1 += 1,
+        }
+
+        match line_ending {
+            LineEnding::Lf => self.lf += 1,
+            LineEnding::Crlf => self.crlf += 1,
+            LineEnding::Mixed => self.mixed += 1,
+            LineEnding::None => (),
+        }
+    }
+
+    /// Update [Self] with information from [Self].
+    pub fn update_from_count(&mut self, other: Self) {
+        self.utf8 += other.utf8;
+        self.utf16 += other.utf16;
+        self.latin1 += other.latin1;
+        self.lf += other.lf;
+        self.crlf += other.crlf;
+        self.mixed += other.mixed;
+    }
+
+    /// Number of text files counted.
+    pub const fn total(&self) -> usize {
+        self.utf8 + self.utf16 + self.latin1
+    }
+}
+
 impl FileCount {
     /// Update [Self] with information from [Node].
     pub fn update(&mut self, node: &Node) {
@@ -52,40 +111,29 @@ impl From<Vec<Self>> for FileCount {
     }
 }
 
-impl Display for FileCount {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl FileCount {
+    /// Formats [Self] e.g. `3 directories, 1 file` with digits grouped as per `--numeric-locale`.
+    pub fn format(&self, ctx: &Context) -> String {
         let mut components = vec![];
 
-        if self.num_dirs > 0 {
-            let output = if self.num_dirs > 1 {
-                format!("{} {}", self.num_dirs, "directories")
-            } else {
-                format!("{} {}", self.num_dirs, "directory")
-            };
-
-            components.push(output);
-        }
-
-        if self.num_files > 0 {
-            let output = if self.num_files > 1 {
-                format!("{} {}", self.num_files, "files")
-            } else {
-                format!("{} {}", self.num_files, "file")
-            };
-
-            components.push(output);
-        }
-
-        if self.num_links > 0 {
-            let output = if self.num_links > 1 {
-                format!("{} {}", self.num_links, "links")
-            } else {
-                format!("{} {}", self.num_links, "link")
-            };
+        for (count, id) in [
+            (self.num_dirs, "count-directories"),
+            (self.num_files, "count-files"),
+            (self.num_links, "count-links"),
+        ] {
+            if count == 0 {
+                continue;
+            }
 
-            components.push(output);
+            components.push(tr_args(
+                id,
+                [
+                    ("count", FluentValue::from(count)),
+                    ("digits", FluentValue::from(ctx.group_digits(count))),
+                ],
+            ));
         }
 
-        write!(f, "{}", components.join(", "))
+        components.join(", ")
     }
 }
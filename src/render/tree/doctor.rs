@@ -0,0 +1,343 @@
+use super::snapshot;
+use crate::{
+    icons,
+    render::context::{config, Context},
+};
+use ansi_term::Color;
+use is_terminal::IsTerminal;
+use std::{
+    env,
+    error::Error as _,
+    ffi::OsStr,
+    fmt::{self, Display},
+    fs,
+    io::stdout,
+    path::Path,
+};
+
+/// Terminals known to render OSC 8 hyperlinks as identified by `$TERM_PROGRAM`.
+const HYPERLINK_TERM_PROGRAMS: [&str; 4] = ["iTerm.app", "WezTerm", "vscode", "ghostty"];
+
+/// Terminals known to render OSC 8 hyperlinks as identified by `$TERM`.
+const HYPERLINK_TERMS: [&str; 3] = ["xterm-kitty", "foot", "alacritty"];
+
+/// Earliest version of VTE, the library behind GNOME Terminal among others, to render hyperlinks.
+const HYPERLINK_VTE_VERSION: u32 = 5000;
+
+/// Findings of every check performed by `et doctor`.
+pub struct Doctor {
+    findings: Vec<Finding>,
+}
+
+/// Outcome of a single check.
+struct Finding {
+    status: Status,
+    check: &'static str,
+    summary: String,
+    hint: Option<String>,
+}
+
+/// How much of a problem a [Finding] is.
+#[derive(Clone, Copy)]
+enum Status {
+    Ok,
+    Info,
+    Warn,
+    Error,
+}
+
+/// Checks the environment erdtree runs in for anything that might make its output look wrong.
+pub fn run() -> Doctor {
+    let findings = vec![
+        icon_glyphs(),
+        color_depth(),
+        hyperlinks(),
+        config_file(),
+        directory(
+            "Cache directory",
+            snapshot::cache_dir().as_deref(),
+            "set $ERDTREE_CACHE_DIR, $XDG_CACHE_HOME, or $HOME for --delta to work",
+        ),
+        directory(
+            "Data directory",
+            snapshot::data_dir().as_deref(),
+            "set $XDG_DATA_HOME or $HOME, or provide --history-dir, for `et daemon` to work",
+        ),
+    ];
+
+    Doctor { findings }
+}
+
+impl Finding {
+    fn new(status: Status, check: &'static str, summary: impl Into<String>) -> Self {
+        Self {
+            status,
+            check,
+            summary: summary.into(),
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+/// Whether the font of the terminal has Nerd Font glyphs can't be queried so the glyphs used by
+/// `--icons` are printed for the user to look at instead.
+fn icon_glyphs() -> Finding {
+    let glyphs = [
+        icons::icon_from_dir_name(OsStr::new("node_modules")),
+        icons::icon_from_ext(OsStr::new("rs")),
+        icons::icon_from_ext(OsStr::new("md")),
+        icons::icon_from_file_name(OsStr::new(".gitignore")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    Finding::new(
+        Status::Info,
+        "Icons",
+        format!("these should look like icons rather than boxes or question marks: {glyphs}"),
+    )
+    .hint("otherwise install a Nerd Font from https://www.nerdfonts.com and have the terminal use it for --icons")
+}
+
+/// Estimates how many colors the terminal supports from the environment.
+fn color_depth() -> Finding {
+    const CHECK: &str = "Colors";
+
+    let term = env::var("TERM").unwrap_or_default();
+    let colorterm = env::var("COLORTERM").unwrap_or_default();
+
+    if !stdout().is_terminal() {
+        return Finding::new(Status::Info, CHECK, "stdout isn't a terminal")
+            .hint("colors are still printed; use --deterministic to leave them out");
+    }
+
+    if term == "dumb" {
+        return Finding::new(Status::Error, CHECK, "TERM=dumb doesn't support colors")
+            .hint("use --deterministic to leave colors out");
+    }
+
+    if matches!(colorterm.as_str(), "truecolor" | "24bit") || env::var_os("WT_SESSION").is_some() {
+        return Finding::new(Status::Ok, CHECK, "24-bit");
+    }
+
+    if term.contains("256color") {
+        return Finding::new(Status::Ok, CHECK, "256 colors").hint(
+            "set COLORTERM=truecolor if the terminal supports 24-bit colors used by LS_COLORS",
+        );
+    }
+
+    Finding::new(
+        Status::Warn,
+        CHECK,
+        format!("only 16 colors assumed for TERM={term}"),
+    )
+    .hint("set TERM to a 256color variant or COLORTERM=truecolor if the terminal supports more")
+}
+
+/// Guesses whether the terminal renders OSC 8 hyperlinks from the environment.
+fn hyperlinks() -> Finding {
+    const CHECK: &str = "Hyperlinks";
+
+    let term = env::var("TERM").unwrap_or_default();
+    let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
+
+    let vte = env::var("VTE_VERSION")
+        .ok()
+        .and_then(|version| version.parse::<u32>().ok())
+        .filter(|version| *version >= HYPERLINK_VTE_VERSION)
+        .map(|_| "VTE");
+
+    let terminal = HYPERLINK_TERM_PROGRAMS
+        .into_iter()
+        .find(|program| *program == term_program)
+        .or_else(|| HYPERLINK_TERMS.into_iter().find(|known| *known == term))
+        .or_else(|| env::var_os("KITTY_WINDOW_ID").map(|_| "kitty"))
+        .or_else(|| env::var_os("WT_SESSION").map(|_| "Windows Terminal"))
+        .or(vte);
+
+    let Some(terminal) = terminal else {
+        return Finding::new(
+            Status::Warn,
+            CHECK,
+            "couldn't tell whether this terminal supports them",
+        )
+        .hint("terminals without support print links as plain text");
+    };
+
+    Finding::new(Status::Ok, CHECK, format!("supported by {terminal}"))
+}
+
+/// Checks that the config file, if any, and each of its profiles parse.
+fn config_file() -> Finding {
+    const CHECK: &str = "Config file";
+
+    let Some(path) = config::config_path() else {
+        return Finding::new(Status::Info, CHECK, "none found; defaults apply");
+    };
+
+    let Some(config) = config::read_config_to_string(Some(&path)) else {
+        return Finding::new(
+            Status::Error,
+            CHECK,
+            format!("{} is unreadable", path.display()),
+        );
+    };
+
+    let profiles = config::profiles(&config);
+
+    // Profiles are only checked once the lines that always apply are known to be fine.
+    let errors = config_error(&config, None).map_or_else(
+        || {
+            profiles
+                .iter()
+                .filter_map(|name| {
+                    config_error(&config, Some(name)).map(|error| format!("[{name}] {error}"))
+                })
+                .collect()
+        },
+        |error| vec![error],
+    );
+
+    if errors.is_empty() {
+        let summary = match profiles.len() {
+            0 => format!("{} is valid", path.display()),
+            n => format!("{} is valid along with {n} profile(s)", path.display()),
+        };
+
+        return Finding::new(Status::Ok, CHECK, summary);
+    }
+
+    Finding::new(
+        Status::Error,
+        CHECK,
+        format!("{} is invalid", path.display()),
+    )
+    .hint(errors.join("\n"))
+}
+
+/// Describes what's wrong with `config` when read with `profile`, if anything.
+fn config_error(config: &str, profile: Option<&str>) -> Option<String> {
+    let error = Context::check_config(config, profile).err()?;
+
+    // The error of the argument parser itself is the informative part.
+    let description = error
+        .source()
+        .map_or_else(|| error.to_string(), ToString::to_string);
+
+    // Only the first line is of interest; what follows is usage.
+    let description = description.lines().next().unwrap_or_default();
+
+    Some(description.trim_start_matches("error: ").to_owned())
+}
+
+/// Checks that `dir`, if it can be located at all, can be written to or created.
+fn directory(check: &'static str, dir: Option<&Path>, missing_hint: &str) -> Finding {
+    let Some(dir) = dir else {
+        return Finding::new(Status::Error, check, "couldn't be located").hint(missing_hint);
+    };
+
+    if !dir.exists() {
+        let creatable = dir
+            .ancestors()
+            .find(|ancestor| ancestor.exists())
+            .map_or(false, is_writable);
+
+        return if creatable {
+            Finding::new(
+                Status::Ok,
+                check,
+                format!("{} will be created when needed", dir.display()),
+            )
+        } else {
+            Finding::new(
+                Status::Error,
+                check,
+                format!("{} can't be created", dir.display()),
+            )
+            .hint("check the permissions of its closest existing parent")
+        };
+    }
+
+    if !dir.is_dir() {
+        return Finding::new(
+            Status::Error,
+            check,
+            format!("{} isn't a directory", dir.display()),
+        )
+        .hint("remove or rename it");
+    }
+
+    if !is_writable(dir) {
+        return Finding::new(
+            Status::Error,
+            check,
+            format!("{} isn't writable", dir.display()),
+        )
+        .hint("check its permissions");
+    }
+
+    Finding::new(Status::Ok, check, format!("{} is writable", dir.display()))
+}
+
+/// Whether a file can be created in `dir`, checked by doing so.
+fn is_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".erdtree-doctor-{}", std::process::id()));
+
+    let writable = fs::write(&probe, b"").is_ok();
+    let _ = fs::remove_file(&probe);
+
+    writable
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (label, color) = match self {
+            Self::Ok => ("ok", Color::Green),
+            Self::Info => ("info", Color::Blue),
+            Self::Warn => ("warn", Color::Yellow),
+            Self::Error => ("error", Color::Red),
+        };
+
+        // Padded to the longest label before painting so that columns line up.
+        write!(f, "{}", color.paint(format!("{label:<5}")))
+    }
+}
+
+impl Display for Doctor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .findings
+            .iter()
+            .map(|finding| finding.check.len())
+            .max()
+            .unwrap_or(0);
+
+        for (i, finding) in self.findings.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            let Finding {
+                status,
+                check,
+                summary,
+                hint,
+            } = finding;
+
+            write!(f, "{status}  {check:<width$}  {summary}")?;
+
+            for line in hint.iter().flat_map(|hint| hint.lines()) {
+                write!(f, "\n{:indent$}{line}", "", indent = width + 9)?;
+            }
+        }
+
+        Ok(())
+    }
+}
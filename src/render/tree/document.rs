@@ -0,0 +1,250 @@
+use super::{stats::Stats, Tree};
+use crate::{
+    i18n::{self, tr, tr_args},
+    render::{
+        canvas::{self, escape, html::Html, BACKGROUND, FOREGROUND},
+        context::duration,
+        disk_usage::file_size::{FileSize, HumanReadableComponents},
+    },
+};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fmt::{self, Display},
+    time::SystemTime,
+};
+
+/// Label of files without an extension in the breakdown by extension.
+const NO_EXTENSION: &str = "(none)";
+
+/// For output as a standalone HTML document, meant to be printed or saved as PDF from a browser,
+/// that gathers the tree, the largest files, disk usage by extension, and a summary.
+pub struct Document<'a> {
+    tree: &'a Tree,
+    rendered: &'a str,
+}
+
+impl<'a> Document<'a> {
+    /// Initializes a [Self] with a reference to [Tree] and its output as printed to the terminal,
+    /// which is embedded as is.
+    pub const fn new(tree: &'a Tree, rendered: &'a str) -> Self {
+        Self { tree, rendered }
+    }
+
+    /// `bytes` as a human readable size in the units of the disk usage being reported.
+    fn size(&self, bytes: u64) -> String {
+        let ctx = self.tree.context();
+
+        let HumanReadableComponents { size, unit } = FileSize::new(
+            bytes,
+            ctx.disk_usage(),
+            ctx.prefix,
+            ctx.scale,
+            ctx.rounding,
+            ctx.unit,
+        )
+        .human_readable_components();
+
+        format!("{size} {unit}")
+    }
+
+    /// Writes the total disk usage, the number of files and directories, and how long the
+    /// traversal took.
+    fn fmt_summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.tree.context();
+        let inner = self.tree.inner();
+        let root = inner[self.tree.root()].get();
+
+        let (dirs, files) =
+            self.tree
+                .root()
+                .descendants(inner)
+                .skip(1)
+                .fold((0, 0), |(dirs, files), node_id| {
+                    if inner[node_id].get().is_dir() {
+                        (dirs + 1, files)
+                    } else {
+                        (dirs, files + 1)
+                    }
+                });
+
+        let rows = [
+            (
+                tr("html-disk-usage"),
+                self.size(root.file_size().map_or(0, |fs| fs.bytes)),
+            ),
+            (tr("html-files"), ctx.group_digits(files)),
+            (tr("html-directories"), ctx.group_digits(dirs)),
+            (tr("html-traversal"), Stats::new(self.tree).to_string()),
+        ];
+
+        writeln!(f, "<h2>{}</h2>", tr("html-summary"))?;
+        writeln!(f, "<table>")?;
+
+        for (label, value) in rows {
+            writeln!(f, "<tr><th>{label}</th><td>{}</td></tr>", escape(&value))?;
+        }
+
+        writeln!(f, "</table>")
+    }
+
+    /// Writes the `--top` largest files in descending order of disk usage.
+    fn fmt_largest(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.tree.context();
+        let inner = self.tree.inner();
+        let root = inner[self.tree.root()].get();
+
+        let mut files = self
+            .tree
+            .root()
+            .descendants(inner)
+            .map(|node_id| inner[node_id].get())
+            .filter(|node| !node.is_dir())
+            .map(|node| (node.file_size().map_or(0, |fs| fs.bytes), node.path()))
+            .collect::<Vec<_>>();
+
+        files.sort_by(|(a_bytes, a_path), (b_bytes, b_path)| {
+            b_bytes.cmp(a_bytes).then_with(|| a_path.cmp(b_path))
+        });
+
+        writeln!(f, "<h2>{}</h2>", tr("html-largest-files"))?;
+        writeln!(f, "<table>")?;
+        writeln!(
+            f,
+            r#"<tr><th class="num">#</th><th class="num">{}</th><th>{}</th></tr>"#,
+            tr("html-size"),
+            tr("html-path")
+        )?;
+
+        for (rank, (bytes, path)) in files.into_iter().take(ctx.top).enumerate() {
+            let path = path.strip_prefix(root.path()).unwrap_or(path);
+
+            writeln!(
+                f,
+                r#"<tr><td class="num">{}</td><td class="num">{}</td><td>{}</td></tr>"#,
+                rank + 1,
+                self.size(bytes),
+                escape(&ctx.display_path(path).to_string_lossy())
+            )?;
+        }
+
+        writeln!(f, "</table>")
+    }
+
+    /// Writes the disk usage and number of files by extension, in descending order of disk usage,
+    /// along with their share of the total.
+    fn fmt_extensions(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.tree.context();
+        let inner = self.tree.inner();
+
+        let mut totals = HashMap::<String, (u64, u64)>::new();
+
+        for node in self
+            .tree
+            .root()
+            .descendants(inner)
+            .map(|node_id| inner[node_id].get())
+            .filter(|node| !node.is_dir())
+        {
+            let extension = node.path().extension().map_or_else(
+                || NO_EXTENSION.to_owned(),
+                |ext| ext.to_string_lossy().to_lowercase(),
+            );
+
+            let (bytes, files) = totals.entry(extension).or_default();
+            *bytes += node.file_size().map_or(0, |fs| fs.bytes);
+            *files += 1;
+        }
+
+        let total = totals.values().map(|(bytes, _)| bytes).sum::<u64>();
+
+        let mut extensions = totals.into_iter().collect::<Vec<_>>();
+        extensions.sort_by_key(|(extension, (bytes, _))| (Reverse(*bytes), extension.clone()));
+
+        writeln!(f, "<h2>{}</h2>", tr("html-by-extension"))?;
+        writeln!(f, "<table>")?;
+        writeln!(
+            f,
+            r#"<tr><th>{}</th><th class="num">{}</th><th class="num">{}</th><th class="num">{}</th></tr>"#,
+            tr("html-extension"),
+            tr("html-files"),
+            tr("html-size"),
+            tr("html-share")
+        )?;
+
+        for (extension, (bytes, files)) in extensions {
+            let share = if total == 0 {
+                0.0
+            } else {
+                bytes as f64 * 100.0 / total as f64
+            };
+
+            writeln!(
+                f,
+                r#"<tr><td>{}</td><td class="num">{}</td><td class="num">{}</td><td class="num">{share:.1}%</td></tr>"#,
+                escape(&extension),
+                ctx.group_digits(files),
+                self.size(bytes),
+            )?;
+        }
+
+        writeln!(f, "</table>")
+    }
+}
+
+impl Display for Document<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.tree.context();
+        let root = self.tree.inner()[self.tree.root()].get();
+        let title = tr_args("html-title", [("root", root.display_name(ctx))]);
+
+        let mut html = Html::default();
+        canvas::paint(self.rendered, &mut html);
+
+        writeln!(f, "<!DOCTYPE html>")?;
+        writeln!(f, r#"<html lang="{}">"#, i18n::lang().code())?;
+        writeln!(f, "<head>")?;
+        writeln!(f, r#"<meta charset="utf-8">"#)?;
+        writeln!(f, "<title>{}</title>", escape(&title))?;
+        writeln!(f, "<style>")?;
+        writeln!(f, "body {{ font-family: sans-serif; margin: 2em; }}")?;
+        writeln!(
+            f,
+            "pre {{ background: {BACKGROUND}; color: {FOREGROUND}; padding: 1em; border-radius: 6px; white-space: pre-wrap; print-color-adjust: exact; -webkit-print-color-adjust: exact; }}"
+        )?;
+        writeln!(f, "table {{ border-collapse: collapse; }}")?;
+        writeln!(
+            f,
+            "th, td {{ text-align: left; padding: 0.2em 1em 0.2em 0; border-bottom: 1px solid #ddd; }}"
+        )?;
+        writeln!(f, ".num {{ text-align: right; }}")?;
+        writeln!(f, "h2 {{ break-after: avoid; }}")?;
+        writeln!(f, "</style>")?;
+        writeln!(f, "</head>")?;
+        writeln!(f, "<body>")?;
+        writeln!(f, "<h1>{}</h1>", escape(&title))?;
+
+        // Left out of deterministic output so that documents can be compared.
+        if !ctx.deterministic {
+            writeln!(
+                f,
+                "<p>{}</p>",
+                tr_args(
+                    "html-generated",
+                    [("timestamp", duration::timestamp(SystemTime::now()))]
+                )
+            )?;
+        }
+
+        self.fmt_summary(f)?;
+
+        writeln!(f, "<h2>{}</h2>", tr("html-tree"))?;
+        writeln!(f, "<pre>{html}</pre>")?;
+
+        self.fmt_largest(f)?;
+        self.fmt_extensions(f)?;
+
+        writeln!(f, "</body>")?;
+        write!(f, "</html>")
+    }
+}
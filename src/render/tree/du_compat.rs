@@ -0,0 +1,55 @@
+use super::Tree;
+use indextree::NodeEdge;
+use std::fmt::{self, Display};
+
+/// For output that mirrors `du -a` i.e. `size<TAB>path` lines where every entry is printed after
+/// its descendants.
+pub struct DuCompat<'a> {
+    tree: &'a Tree,
+}
+
+impl<'a> DuCompat<'a> {
+    /// Initializes a [Self] with a reference to [Tree].
+    pub const fn new(tree: &'a Tree) -> Self {
+        Self { tree }
+    }
+}
+
+impl Display for DuCompat<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+        let max_depth = ctx.level().unwrap_or(usize::MAX);
+        let dir = ctx.dir();
+
+        let base_path = ctx.base_path();
+
+        for edge in root.traverse(tree) {
+            let NodeEdge::End(node_id) = edge else {
+                continue;
+            };
+
+            let node = tree[node_id].get();
+
+            if node.depth() > max_depth {
+                continue;
+            }
+
+            let bytes = node.file_size().map_or(0, |fs| fs.bytes);
+
+            let full_path = node.path();
+
+            let path = full_path
+                .strip_prefix(&base_path)
+                .map_or_else(|_| full_path.to_path_buf(), |rel| dir.join(rel));
+
+            // Joining an empty relative path would otherwise leave a trailing separator on root.
+            let path = if node_id == root { dir } else { &path };
+
+            writeln!(f, "{bytes}\t{}", ctx.display_path(path).display())?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use super::annotation;
+use crate::i18n::{tr, tr_args};
 use ignore::Error as IgnoreError;
 use std::io::Error as IoError;
 
@@ -6,18 +8,39 @@ use std::io::Error as IoError;
 /// [`Tree`]: super::Tree
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[error("{0}")]
+    Annotation(#[from] annotation::Error),
+
     #[error("{0}")]
     DirNotFound(String),
 
-    #[error("File expected to have parent")]
+    #[error("{}", tr("error-missing-parent"))]
     ExpectedParent,
 
-    #[error("Invalid glob patterns: {0}")]
+    #[error("{}", tr_args("error-invalid-glob", [("error", .0.to_string())]))]
     InvalidGlobPatterns(#[from] IgnoreError),
 
-    #[error("Failed to compute root node")]
+    #[error("{}", tr("error-missing-root"))]
     MissingRoot,
 
+    #[error("{}", tr("error-missing-data-dir"))]
+    MissingDataDir,
+
+    #[error("{}", tr_args("error-missing-history", [("path", .0.as_str())]))]
+    MissingHistory(String),
+
+    #[error("{}", tr("error-missing-cache-dir"))]
+    MissingCacheDir,
+
     #[error("{0}")]
     PathCanonicalization(#[from] IoError),
+
+    #[error("{}", tr_args("error-snapshot", [("error", .0.to_string())]))]
+    Snapshot(#[source] IoError),
+
+    #[error("{}", tr_args("error-checkpoint", [("error", .0.to_string())]))]
+    Checkpoint(#[source] IoError),
+
+    #[error("{}", tr_args("error-git-history", [("error", .0.to_string())]))]
+    Git(#[source] IoError),
 }
@@ -0,0 +1,195 @@
+use super::{node::Node, Tree};
+use crate::render::{context::Context, styles};
+use indextree::NodeId;
+use std::fmt::{self, Display};
+
+/// Number of columns assumed when laying out a grid if the width of the output is unknown.
+const DEFAULT_WIDTH: usize = 80;
+
+/// Columns of a grid are separated by this many spaces.
+const GUTTER: usize = 2;
+
+/// For output in which consecutive siblings without children of their own are laid out in
+/// multiple columns across the width of the terminal akin to `ls`, whereas directories with
+/// children are printed as they are in the regular tree.
+pub struct Grid<'a> {
+    tree: &'a Tree,
+}
+
+/// A [Node] printed in a cell of a grid.
+struct Cell<'a> {
+    node: &'a Node,
+    ctx: &'a Context,
+}
+
+impl<'a> Grid<'a> {
+    /// Initializes a [Self] with a reference to [Tree].
+    pub const fn new(tree: &'a Tree) -> Self {
+        Self { tree }
+    }
+
+    /// Writes the children of `node_id` whose branches are preceded by `prefix`, grouping
+    /// consecutive children without children of their own into grids.
+    fn fmt_children(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        node_id: NodeId,
+        prefix: &str,
+    ) -> fmt::Result {
+        let inner = self.tree.inner();
+        let level = self.tree.level();
+
+        let children = node_id
+            .children(inner)
+            .filter(|child_id| inner[*child_id].get().depth() <= level)
+            .collect::<Vec<_>>();
+
+        let is_leaf = |child_id: &NodeId| {
+            inner[*child_id].get().depth() == level || child_id.children(inner).next().is_none()
+        };
+
+        let mut groups: Vec<Vec<NodeId>> = vec![];
+
+        for child_id in children {
+            match groups.last_mut() {
+                Some(group) if is_leaf(&child_id) && group.iter().all(is_leaf) => {
+                    group.push(child_id);
+                }
+                _ => groups.push(vec![child_id]),
+            }
+        }
+
+        let last_group = groups.len().saturating_sub(1);
+
+        for (i, group) in groups.into_iter().enumerate() {
+            let first = inner[group[0]].get();
+
+            let theme = if first.is_symlink() {
+                styles::get_link_theme()
+            } else {
+                styles::get_tree_theme()
+            };
+
+            let (branch, continuation) = if i == last_group {
+                (theme.get("uprt").unwrap().as_str(), styles::SEP)
+            } else {
+                (
+                    theme.get("vtrt").unwrap().as_str(),
+                    theme.get("vt").unwrap().as_str(),
+                )
+            };
+
+            if group.len() == 1 && !is_leaf(&group[0]) {
+                first.display(f, &format!("{prefix}{branch}"), self.tree.context())?;
+                writeln!(f)?;
+                self.fmt_children(f, group[0], &format!("{prefix}{continuation}"))?;
+                continue;
+            }
+
+            self.fmt_grid(f, &group, prefix, branch, continuation)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `group` as a grid filled top to bottom then left to right, using as many columns
+    /// as fit in the width of the output.
+    fn fmt_grid(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        group: &[NodeId],
+        prefix: &str,
+        branch: &str,
+        continuation: &str,
+    ) -> fmt::Result {
+        let inner = self.tree.inner();
+        let ctx = self.tree.context();
+
+        let cells = group
+            .iter()
+            .map(|node_id| {
+                Cell {
+                    node: inner[*node_id].get(),
+                    ctx,
+                }
+                .to_string()
+            })
+            .collect::<Vec<_>>();
+
+        let cell_widths = cells
+            .iter()
+            .map(|cell| styles::visible_width(cell))
+            .collect::<Vec<_>>();
+
+        let available = ctx
+            .width
+            .unwrap_or(DEFAULT_WIDTH)
+            .saturating_sub(styles::visible_width(prefix) + styles::visible_width(branch));
+
+        let (rows, column_widths) = layout(&cell_widths, available);
+
+        for row in 0..rows {
+            let lead = if row == 0 { branch } else { continuation };
+            write!(f, "{prefix}{lead}")?;
+
+            let mut row_cells = (0..column_widths.len())
+                .filter_map(|column| {
+                    let index = column * rows + row;
+                    (index < cells.len()).then_some(index)
+                })
+                .peekable();
+
+            while let Some(index) = row_cells.next() {
+                write!(f, "{}", cells[index])?;
+
+                if row_cells.peek().is_some() {
+                    let padding = column_widths[index / rows] - cell_widths[index] + GUTTER;
+                    write!(f, "{:padding$}", "")?;
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Determines the fewest rows that cells of the given widths can be laid out in without exceeding
+/// `available` columns, along with the widths of the resulting columns.
+fn layout(cell_widths: &[usize], available: usize) -> (usize, Vec<usize>) {
+    for rows in 1..cell_widths.len() {
+        let column_widths = cell_widths
+            .chunks(rows)
+            .map(|column| column.iter().copied().max().unwrap_or(0))
+            .collect::<Vec<_>>();
+
+        let total = column_widths.iter().sum::<usize>() + GUTTER * (column_widths.len() - 1);
+
+        if total <= available {
+            return (rows, column_widths);
+        }
+    }
+
+    let widest = cell_widths.iter().copied().max().unwrap_or(0);
+
+    (cell_widths.len(), vec![widest])
+}
+
+impl Display for Cell<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.node.display(f, "", self.ctx)
+    }
+}
+
+impl Display for Grid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+
+        self.tree.inner()[root].get().display(f, "", ctx)?;
+        writeln!(f)?;
+
+        self.fmt_children(f, root, "")
+    }
+}
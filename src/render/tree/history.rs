@@ -0,0 +1,261 @@
+use super::{
+    error::Error,
+    snapshot::{self, Snapshot},
+    Result, Tree,
+};
+use crate::render::{
+    context::{
+        duration,
+        subcommand::{DaemonArgs, HistoryArgs},
+        Context,
+    },
+    disk_usage::file_size::FileSize,
+};
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Name of the file within a root's history directory that records the path of the root.
+const ROOT_FILE: &str = "root";
+
+/// Extension of snapshot files within a root's history directory.
+const SNAPSHOT_EXT: &str = "snapshot";
+
+/// Characters used to plot a [Plot], from lowest to highest.
+const SPARKS: [char; 8] = [
+    '\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}',
+];
+
+/// On-disk record of directory-only [Snapshot]s taken over time. Every scanned root gets its own
+/// directory containing a file with the path of the root as well as one snapshot per scan named
+/// after the number of milliseconds since the epoch at which the scan completed.
+pub struct History {
+    dir: PathBuf,
+}
+
+/// Disk usage of a single directory over time.
+pub struct Plot {
+    path: PathBuf,
+    series: Vec<(SystemTime, u64)>,
+    ctx: Context,
+}
+
+impl History {
+    /// Initializes [History] at `dir` or at the default data directory if `None`.
+    pub fn new(dir: Option<PathBuf>) -> Result<Self> {
+        dir.or_else(snapshot::data_dir)
+            .map(|dir| Self { dir })
+            .ok_or(Error::MissingDataDir)
+    }
+
+    /// Appends a snapshot of `tree` to the history of its root.
+    pub fn record(&self, tree: &Tree) -> Result<()> {
+        let root_path = tree.inner()[tree.root()].get().path();
+
+        // Roots are recorded by canonical path regardless of `--no-canonicalize`.
+        let root_path = &fs::canonicalize(root_path).unwrap_or_else(|_| root_path.to_owned());
+        let root_dir = self.root_dir(root_path);
+
+        fs::create_dir_all(&root_dir).map_err(Error::Snapshot)?;
+        fs::write(
+            root_dir.join(ROOT_FILE),
+            root_path.to_string_lossy().as_bytes(),
+        )
+        .map_err(Error::Snapshot)?;
+
+        let mut millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        let mut snapshot_path = root_dir.join(format!("{millis}.{SNAPSHOT_EXT}"));
+
+        // Scans of small directories may complete within the same millisecond.
+        while snapshot_path.exists() {
+            millis += 1;
+            snapshot_path = root_dir.join(format!("{millis}.{SNAPSHOT_EXT}"));
+        }
+
+        Snapshot::directories_from_tree(tree.inner(), tree.root())
+            .save(&snapshot_path)
+            .map_err(Error::Snapshot)
+    }
+
+    /// Collects the recorded disk usage of the directory at `path`, which is expected to be
+    /// canonicalized, oldest first. The directory may be any descendant of a recorded root.
+    pub fn series(&self, path: &Path) -> Result<Vec<(SystemTime, u64)>> {
+        let Some((root_dir, rel_path)) = self.find_root(path)? else {
+            return Ok(vec![]);
+        };
+
+        let mut snapshots = fs::read_dir(root_dir)
+            .map_err(Error::Snapshot)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter_map(|path| {
+                let millis = path
+                    .extension()
+                    .filter(|ext| *ext == SNAPSHOT_EXT)
+                    .and_then(|_| path.file_stem())
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.parse::<u64>().ok())?;
+
+                Some((UNIX_EPOCH + Duration::from_millis(millis), path))
+            })
+            .collect::<Vec<_>>();
+
+        snapshots.sort_unstable();
+
+        let mut series = vec![];
+
+        for (time, snapshot_path) in snapshots {
+            let snapshot = Snapshot::load(&snapshot_path).map_err(Error::Snapshot)?;
+
+            if let Some(bytes) = snapshot.and_then(|s| s.get(&rel_path)) {
+                series.push((time, bytes));
+            }
+        }
+
+        Ok(series)
+    }
+
+    /// Finds the history directory of the recorded root closest to `path` along with the path
+    /// relative to that root.
+    fn find_root(&self, path: &Path) -> Result<Option<(PathBuf, PathBuf)>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(Error::Snapshot(e)),
+        };
+
+        let closest = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter_map(|root_dir| {
+                let root = fs::read_to_string(root_dir.join(ROOT_FILE)).ok()?;
+                let rel_path = path.strip_prefix(&root).ok()?.to_path_buf();
+                Some((root_dir, rel_path))
+            })
+            .min_by_key(|(_, rel_path)| rel_path.components().count());
+
+        Ok(closest)
+    }
+
+    /// Directory containing the history of `root`.
+    fn root_dir(&self, root: &Path) -> PathBuf {
+        let name = format!(
+            "{:016x}",
+            snapshot::fnv1a(root.to_string_lossy().as_bytes())
+        );
+        self.dir.join(name)
+    }
+}
+
+/// Scans each of the roots in `args` every `interval` and records them in [History].
+pub fn run_daemon(ctx: &Context, args: &DaemonArgs) -> Result<()> {
+    let history = History::new(args.history_dir.clone())?;
+
+    let roots = if args.roots.is_empty() {
+        vec![ctx.dir().to_path_buf()]
+    } else {
+        args.roots.clone()
+    };
+
+    let mut runs = 0;
+
+    loop {
+        for root in &roots {
+            // A root that is temporarily unavailable shouldn't bring down the daemon.
+            match Tree::init(ctx.with_dir(root.clone())) {
+                Ok(tree) => history.record(&tree)?,
+                Err(e) => eprintln!("{}: {e}", root.display()),
+            }
+        }
+
+        runs += 1;
+
+        if args.runs.map_or(false, |max| runs >= max) {
+            return Ok(());
+        }
+
+        thread::sleep(args.interval);
+    }
+}
+
+/// Gathers the recorded history of the directory in `args` into a [Plot].
+pub fn plot(ctx: &Context, args: &HistoryArgs) -> Result<Plot> {
+    let history = History::new(args.history_dir.clone())?;
+
+    let path = args.path.as_deref().unwrap_or_else(|| ctx.dir());
+    let path = fs::canonicalize(path)?;
+
+    let series = history.series(&path)?;
+
+    if series.is_empty() {
+        let path = ctx.display_path(&path);
+        return Err(Error::MissingHistory(path.display().to_string()));
+    }
+
+    Ok(Plot {
+        path,
+        series,
+        ctx: ctx.clone(),
+    })
+}
+
+impl Plot {
+    /// Renders the series as a sparkline scaled between its minimum and maximum.
+    fn sparkline(&self) -> String {
+        let min = self.series.iter().map(|(_, b)| *b).min().unwrap_or(0);
+        let max = self.series.iter().map(|(_, b)| *b).max().unwrap_or(0);
+        let range = max - min;
+
+        self.series
+            .iter()
+            .map(|(_, bytes)| {
+                let index = ((bytes - min) * (SPARKS.len() as u64 - 1))
+                    .checked_div(range)
+                    .and_then(|i| usize::try_from(i).ok())
+                    .unwrap_or(0);
+
+                SPARKS[index]
+            })
+            .collect()
+    }
+
+    fn human_readable(&self, bytes: u64) -> String {
+        let ctx = &self.ctx;
+        FileSize::new(
+            bytes,
+            ctx.disk_usage(),
+            ctx.prefix,
+            ctx.scale,
+            ctx.rounding,
+            ctx.unit,
+        )
+        .format(None)
+    }
+}
+
+impl Display for Plot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (first_time, first_bytes) = self.series[0];
+        let (last_time, last_bytes) = self.series[self.series.len() - 1];
+
+        let span = last_time.duration_since(first_time).unwrap_or_default();
+
+        writeln!(f, "{}", self.ctx.display_path(&self.path).display())?;
+
+        write!(
+            f,
+            "{} {} \u{2192} {} across {} scans spanning {}",
+            self.sparkline(),
+            self.human_readable(first_bytes),
+            self.human_readable(last_bytes),
+            self.series.len(),
+            duration::format(span)
+        )
+    }
+}
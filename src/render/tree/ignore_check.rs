@@ -0,0 +1,408 @@
+use super::{Node, Result, Tree, VCS_DIRS};
+use crate::render::context::{subcommand::IgnoreCheckArgs, Context};
+use ansi_term::Color;
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder, Glob},
+    overrides::Override,
+    Match, WalkBuilder,
+};
+use std::{
+    env,
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    result::Result as StdResult,
+};
+
+/// Verdicts of `et ignore-check` on each of the paths it was given.
+pub struct IgnoreCheck {
+    root: PathBuf,
+    verdicts: Vec<(PathBuf, Verdict)>,
+}
+
+/// Whether a path would be walked and what decided it.
+enum Verdict {
+    /// The path isn't beneath the root directory.
+    Outside,
+
+    /// The path would be walked, explicitly so if a rule whitelisted it.
+    Walked(Option<Rule>),
+
+    /// The path would be skipped along with everything beneath it because either it or
+    /// `ancestor` was excluded by `rule`.
+    Skipped {
+        rule: Rule,
+        ancestor: Option<PathBuf>,
+    },
+}
+
+/// What decided whether a path would be walked.
+enum Rule {
+    /// A pattern read from `file` or provided on the command line if there's no file.
+    Pattern {
+        pattern: String,
+        file: Option<PathBuf>,
+    },
+
+    /// Files that don't match any `--glob` pattern are skipped.
+    Unmatched,
+
+    /// Hidden files are skipped unless `--hidden` is provided.
+    Hidden,
+
+    /// `.git`, `.hg`, and `.svn` directories are skipped by `--no-vcs-dirs`.
+    VcsDir,
+
+    /// Entries directly beneath the root are skipped unless they match its glob pattern.
+    RootGlob,
+
+    /// Files are left out of the tree by `--dirs-only` once walked.
+    DirsOnly,
+
+    /// Files of the given kind are left out of the tree once walked by flags such as `--filter`,
+    /// `--invert-match`, `--text-only`, or `--taken-after`.
+    Excluded(&'static str),
+}
+
+/// The ignore rules the traversal is subject to, mirroring how the walker is set up from
+/// [Context].
+struct Rules {
+    /// The globs of [Context::overrides] as gitignore patterns, i.e. with their meaning inverted,
+    /// as the [Override] they're normally matched with doesn't expose them.
+    overrides: Gitignore,
+    root_glob: Option<Override>,
+    global: Gitignore,
+    git_ignore: bool,
+    hidden: bool,
+    hidden_root: bool,
+    no_vcs_dirs: bool,
+    follow_links: bool,
+
+    /// Patterns of `--glob` and `--iglob` when inverted by `--invert-match`, which only apply
+    /// once walked.
+    inverted_globs: Option<Override>,
+}
+
+/// The ignore files that apply to entries beneath a single directory.
+struct Level {
+    ignore: Gitignore,
+    git_ignore: Gitignore,
+    git_exclude: Gitignore,
+    has_git: bool,
+}
+
+/// Determines for each path in `args` whether it would be walked given the root and ignore
+/// settings of `ctx`, and which rule decided it.
+pub fn run(ctx: &Context, args: &IgnoreCheckArgs) -> Result<IgnoreCheck> {
+    let root = fs::canonicalize(ctx.dir())?;
+    let rules = Rules::of(ctx, &root)?;
+
+    let verdicts = args
+        .paths
+        .iter()
+        .map(|path| {
+            let path_abs = absolute(path);
+
+            let verdict = match rules.check(&root, &path_abs) {
+                Verdict::Walked(rule) if path_abs != root => rules
+                    .exclusion(ctx, &path_abs)
+                    .map_or(Verdict::Walked(rule), |rule| Verdict::Skipped {
+                        rule,
+                        ancestor: None,
+                    }),
+                verdict => verdict,
+            };
+
+            (path.clone(), verdict)
+        })
+        .collect();
+
+    Ok(IgnoreCheck { root, verdicts })
+}
+
+/// `path` made absolute without resolving symlinks in its final component, which would otherwise
+/// be checked in place of the link itself.
+fn absolute(path: &Path) -> PathBuf {
+    let path = env::current_dir().map_or_else(|_| path.to_path_buf(), |cwd| cwd.join(path));
+
+    path.parent()
+        .and_then(|parent| fs::canonicalize(parent).ok())
+        .zip(path.file_name())
+        .map_or_else(|| path.clone(), |(parent, name)| parent.join(name))
+}
+
+impl Rules {
+    fn of(ctx: &Context, root: &Path) -> Result<Self> {
+        let mut builder = GitignoreBuilder::new(root);
+
+        for (glob, case_insensitive) in ctx.override_globs() {
+            builder.case_insensitive(case_insensitive)?;
+            builder.add_line(None, &glob)?;
+        }
+
+        Ok(Self {
+            overrides: builder.build()?,
+            root_glob: ctx.root_glob()?,
+            global: Gitignore::global().0,
+            git_ignore: !ctx.ignore_git_ignore,
+            hidden: !ctx.hidden,
+            hidden_root: ctx.hidden_root(),
+            no_vcs_dirs: ctx.no_vcs_dirs,
+            follow_links: ctx.follow_links,
+            inverted_globs: ctx.inverted_globs()?,
+        })
+    }
+
+    /// The rule that leaves `path` out of the tree despite it being walked, as decided by the same
+    /// checks that are applied to every entry received during the traversal.
+    fn exclusion(&self, ctx: &Context, path: &Path) -> Option<Rule> {
+        let parent = path.parent()?;
+        let name = path.file_name()?.to_owned();
+
+        // The entry is walked to from its parent so that it's a node like any other beneath it.
+        let dir_entry = WalkBuilder::new(parent)
+            .standard_filters(false)
+            .follow_links(self.follow_links)
+            .max_depth(Some(1))
+            .filter_entry(move |entry| entry.depth() == 0 || entry.file_name() == name)
+            .build()
+            .filter_map(StdResult::ok)
+            .find(|entry| entry.depth() == 1)?;
+
+        let node = Node::try_from((dir_entry, ctx)).ok()?;
+
+        if ctx.dirs_only && !node.is_dir() {
+            return Some(Rule::DirsOnly);
+        }
+
+        let selected = |node: &Node| Tree::selected(node, ctx, self.inverted_globs.as_ref());
+
+        Tree::exclusion(&node, ctx, selected).map(Rule::Excluded)
+    }
+
+    /// Checks `path` and each of its ancestors beneath `root` in turn as the walker would only
+    /// get to `path` if none of them are skipped.
+    fn check(&self, root: &Path, path: &Path) -> Verdict {
+        let Ok(rel_path) = path.strip_prefix(root) else {
+            return Verdict::Outside;
+        };
+
+        let mut entry = root.to_path_buf();
+        let mut verdict = Verdict::Walked(None);
+
+        for (depth, component) in rel_path.components().enumerate() {
+            entry.push(component);
+
+            let is_dir = if self.follow_links {
+                fs::metadata(&entry)
+            } else {
+                fs::symlink_metadata(&entry)
+            }
+            .map_or(false, |md| md.is_dir());
+
+            verdict = self.decide(&entry, is_dir, depth + 1);
+
+            if let Verdict::Skipped { rule, .. } = verdict {
+                let ancestor = Some(entry.strip_prefix(root).unwrap_or(&entry).to_path_buf())
+                    .filter(|ancestor| ancestor != rel_path);
+
+                return Verdict::Skipped { rule, ancestor };
+            }
+        }
+
+        verdict
+    }
+
+    /// Whether the entry at `path` would be walked, leaving aside its ancestors.
+    fn decide(&self, path: &Path, is_dir: bool, depth: usize) -> Verdict {
+        let skipped = |rule| Verdict::Skipped {
+            rule,
+            ancestor: None,
+        };
+
+        let name = path.file_name().unwrap_or_default();
+
+        // Overrides take precedence over ignore files, after which hidden files are considered.
+        let verdict = match self.overrides.matched(path, is_dir) {
+            Match::Ignore(glob) => Verdict::Walked(Some(Rule::of(glob))),
+            Match::Whitelist(glob) => skipped(Rule::of(glob)),
+            Match::None if !is_dir && self.overrides.num_ignores() > 0 => skipped(Rule::Unmatched),
+            Match::None => match self.ignore_files(path, is_dir) {
+                Match::Ignore(rule) => skipped(rule),
+                Match::Whitelist(rule) => Verdict::Walked(Some(rule)),
+                Match::None
+                    if self.hidden
+                        && !(self.hidden_root
+                            && depth == 1
+                            && !VCS_DIRS.iter().any(|vcs| name == *vcs))
+                        && name.to_string_lossy().starts_with('.') =>
+                {
+                    skipped(Rule::Hidden)
+                }
+                Match::None => Verdict::Walked(None),
+            },
+        };
+
+        if matches!(verdict, Verdict::Skipped { .. }) {
+            return verdict;
+        }
+
+        if self.no_vcs_dirs && is_dir && VCS_DIRS.iter().any(|vcs| name == *vcs) {
+            return skipped(Rule::VcsDir);
+        }
+
+        if let Some(ref root_glob) = self.root_glob {
+            if depth == 1 && !root_glob.matched(name, is_dir).is_whitelist() {
+                return skipped(Rule::RootGlob);
+            }
+        }
+
+        verdict
+    }
+
+    /// Matches `path` against the ignore files of the directories above it. The nearest match
+    /// wins, with `.ignore` files taking precedence over `.gitignore` files followed by the
+    /// excludes of the repository and the global gitignore. Git's own ignore files only apply
+    /// within a repository and no further up than its root.
+    fn ignore_files(&self, path: &Path, is_dir: bool) -> Match<Rule> {
+        let levels = path
+            .ancestors()
+            .skip(1)
+            .map(|dir| Level::load(dir, self.git_ignore))
+            .collect::<Vec<_>>();
+
+        let any_git = levels.iter().any(|level| level.has_git);
+        let mut saw_git = false;
+
+        let (mut ignore, mut git_ignore, mut git_exclude) = (Match::None, Match::None, Match::None);
+
+        for level in &levels {
+            if ignore.is_none() {
+                ignore = level.ignore.matched(path, is_dir).map(Rule::of);
+            }
+
+            if any_git && !saw_git && git_ignore.is_none() {
+                git_ignore = level.git_ignore.matched(path, is_dir).map(Rule::of);
+            }
+
+            if any_git && !saw_git && git_exclude.is_none() {
+                git_exclude = level.git_exclude.matched(path, is_dir).map(Rule::of);
+            }
+
+            saw_git = saw_git || level.has_git;
+        }
+
+        let global = if any_git {
+            self.global.matched(path, is_dir).map(Rule::of)
+        } else {
+            Match::None
+        };
+
+        ignore.or(git_ignore).or(git_exclude).or(global)
+    }
+}
+
+impl Level {
+    fn load(dir: &Path, git_ignore: bool) -> Self {
+        let git_dir = dir.join(".git");
+
+        Self {
+            ignore: gitignore(dir, &dir.join(".ignore")),
+            git_ignore: if git_ignore {
+                gitignore(dir, &dir.join(".gitignore"))
+            } else {
+                Gitignore::empty()
+            },
+            git_exclude: gitignore(dir, &git_dir.join("info").join("exclude")),
+            has_git: git_dir.exists(),
+        }
+    }
+}
+
+/// The rules in the ignore file at `path` which apply to entries beneath `dir`. Files that don't
+/// exist or can't be parsed hold no rules, just as for the walker.
+fn gitignore(dir: &Path, path: &Path) -> Gitignore {
+    if !path.is_file() {
+        return Gitignore::empty();
+    }
+
+    let mut builder = GitignoreBuilder::new(dir);
+    builder.add(path);
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+impl Rule {
+    fn of(glob: &Glob) -> Self {
+        Self::Pattern {
+            pattern: glob.original().to_owned(),
+            file: glob.from().map(Path::to_path_buf),
+        }
+    }
+
+    /// Why a path was walked or skipped as per this rule e.g. "by `target/` in .gitignore".
+    fn describe(&self, root: &Path) -> String {
+        match self {
+            Self::Pattern {
+                pattern,
+                file: Some(file),
+            } => {
+                let file = file.strip_prefix(root).unwrap_or(file);
+                format!("by `{pattern}` in {}", file.display())
+            }
+            Self::Pattern {
+                pattern,
+                file: None,
+            } => format!("by `{pattern}` from the command line"),
+            Self::Unmatched => "as it doesn't match any --glob pattern".to_owned(),
+            Self::Hidden => "as it's hidden; provide --hidden to include it".to_owned(),
+            Self::VcsDir => "by --no-vcs-dirs".to_owned(),
+            Self::RootGlob => "as it doesn't match the glob pattern of the root".to_owned(),
+            Self::DirsOnly => "by --dirs-only".to_owned(),
+            Self::Excluded(kind) => format!("as it's a {kind}"),
+        }
+    }
+}
+
+impl Display for IgnoreCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (path, verdict)) in self.verdicts.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{}: ", path.display())?;
+
+            match verdict {
+                Verdict::Outside => write!(f, "not beneath {}", self.root.display())?,
+                Verdict::Walked(None) => write!(f, "{}", Color::Green.paint("walked"))?,
+                Verdict::Walked(Some(rule)) => write!(
+                    f,
+                    "{} as whitelisted {}",
+                    Color::Green.paint("walked"),
+                    rule.describe(&self.root)
+                )?,
+                Verdict::Skipped {
+                    rule,
+                    ancestor: None,
+                } => write!(
+                    f,
+                    "{} {}",
+                    Color::Red.paint("skipped"),
+                    rule.describe(&self.root)
+                )?,
+                Verdict::Skipped {
+                    rule,
+                    ancestor: Some(ancestor),
+                } => write!(
+                    f,
+                    "{} beneath {} which is skipped {}",
+                    Color::Red.paint("skipped"),
+                    ancestor.display(),
+                    rule.describe(&self.root)
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,37 +1,107 @@
-use crate::render::{context::Context, disk_usage::file_size::FileSize, styles};
-use count::FileCount;
+use crate::fs::{
+    code::LineCount,
+    compression::Compressibility,
+    content::{self, Content},
+    git,
+};
+use crate::i18n::{tr, tr_args};
+use crate::render::context::output::Output;
+use crate::render::{
+    context::{bundles::Bundles, report::ReportKind, Context},
+    disk_usage::file_size::{FileSize, HumanReadableComponents},
+    styles,
+};
+use annotation::Annotations;
+use ansi_term::Color;
+use checkpoint::{Checkpoint, Progress};
+use count::{EncodingCount, FileCount};
+use du_compat::DuCompat;
 use error::Error;
-use ignore::{WalkBuilder, WalkParallel};
-use indextree::{Arena, NodeId};
-use node::Node;
+use fluent_bundle::FluentValue;
+use grid::Grid;
+use ignore::{overrides::Override, ParallelVisitorBuilder, WalkBuilder, WalkParallel, WalkState};
+use indextree::{Arena, NodeEdge, NodeId};
+use node::{column, Node};
+use porcelain::Porcelain;
+use prometheus::Prometheus;
 use report::Report;
+use snapshot::Snapshot;
+use stats::Stats;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
     convert::TryFrom,
     fmt::{self, Display, Formatter},
-    fs,
-    path::PathBuf,
+    fs::{self, FileType, Metadata},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
     result::Result as StdResult,
     sync::mpsc::{self, Sender},
     thread,
+    time::{Duration, Instant, SystemTime},
 };
+use treemap::{Svg, Treemap};
 use visitor::{BranchVisitorBuilder, TraversalState};
 
+/// Notes attached to entries via `--annotate`.
+pub mod annotation;
+
+/// Timing of repeated traversals.
+pub mod bench;
+
+/// Printable HTML documents for `--output html`.
+pub mod document;
+
+/// Diagnosis of the environment with `et doctor`.
+pub mod doctor;
+
+/// Explanation of which ignore rules apply to paths with `et ignore-check`.
+pub mod ignore_check;
+
+/// Treemaps of disk usage for `--output treemap` and `--output svg`.
+pub mod treemap;
+
+/// Persisting the progress of traversals with `--checkpoint` so they can be resumed.
+mod checkpoint;
+
+/// Running the command of `--column-cmd` for every entry.
+mod column_cmd;
+
 /// Operations to handle and display aggregate file counts based on their type.
 mod count;
 
+/// For generating `du -a` compatible output.
+pub mod du_compat;
+
+/// For laying out entries without children in multiple columns.
+pub mod grid;
+
 /// Errors related to traversal, [Tree] construction, and the like.
 pub mod error;
 
+/// Recording and plotting of disk usage over time.
+pub mod history;
+
 /// Contains components of the [`Tree`] data structure that derive from [`DirEntry`].
 ///
 /// [`Tree`]: Tree
 /// [`DirEntry`]: ignore::DirEntry
 pub mod node;
 
+/// For generating metrics in the Prometheus text exposition format.
+pub mod prometheus;
+
+/// For output in a stable, line-oriented format meant for scripts.
+pub mod porcelain;
+
 /// For generating plain-text report of disk usage without ASCII tree.
 pub mod report;
 
+/// Persisting scans between runs to compute changes in disk usage.
+pub mod snapshot;
+
+/// Timing and throughput of a traversal.
+pub mod stats;
+
 /// Custom visitor that operates on each thread during filesystem traversal.
 mod visitor;
 
@@ -41,26 +111,367 @@ pub struct Tree {
     inner: Arena<Node>,
     root: NodeId,
     ctx: Context,
+    removed: Vec<(PathBuf, u64)>,
+    traversal_time: Duration,
 }
 
 pub type Result<T> = StdResult<T, Error>;
 
+/// Names of the metadata directories of version control systems skipped with `--no-vcs-dirs`.
+const VCS_DIRS: [&str; 3] = [".git", ".hg", ".svn"];
+
+/// Padding between the branches of the tree and the lines of a file previewed with `--preview`.
+const PREVIEW_INDENT: &str = "  ";
+
 impl Tree {
     /// Constructor for [Tree].
     pub const fn new(inner: Arena<Node>, root: NodeId, ctx: Context) -> Self {
-        Self { inner, root, ctx }
+        Self {
+            inner,
+            root,
+            ctx,
+            removed: vec![],
+            traversal_time: Duration::ZERO,
+        }
     }
 
     /// Initiates file-system traversal and [Tree construction].
     pub fn init(ctx: Context) -> Result<Self> {
+        let start = Instant::now();
         let (inner, root) = Self::traverse(&ctx)?;
+        let traversal_time = start.elapsed();
+
+        let mut tree = Self::new(inner, root, ctx);
+        tree.traversal_time = traversal_time;
+
+        if tree.ctx.delta {
+            tree.compute_deltas()?;
+        }
+
+        if let Some(threshold) = tree.ctx.stale {
+            tree.mark_stale(threshold);
+        }
+
+        if tree.ctx.rank {
+            tree.rank_largest(tree.ctx.top);
+        }
+
+        if let Some(ref path) = tree.ctx.annotate {
+            let annotations = Annotations::load(path)?;
+            tree.apply_annotations(&annotations);
+        }
+
+        if tree.ctx.git_authors || tree.ctx.report == Some(ReportKind::Authors) {
+            tree.apply_git_authors()?;
+        }
+
+        if tree.ctx.sparse_checkout {
+            tree.mark_sparse_checkout()?;
+        }
+
+        if let Some(cmd) = tree.ctx.column_cmd.clone() {
+            tree.apply_column_cmd(&cmd);
+        }
+
+        if let Some(lines) = tree.ctx.preview.filter(|_| tree.ctx.prints_tree()) {
+            tree.apply_previews(lines);
+        }
+
+        if tree.ctx.unit.is_some() {
+            tree.align_unit_sizes();
+        }
+
+        if tree.ctx.show_bytes {
+            tree.align_exact_sizes();
+        }
+
+        Ok(tree)
+    }
+
+    /// Pads sizes in `--unit` to the width of the largest of them so that their decimals line up.
+    fn align_unit_sizes(&mut self) {
+        let width = self
+            .inner
+            .iter()
+            .flat_map(|node| node.get().file_sizes())
+            .map(|size| size.human_readable_components().size.len())
+            .max()
+            .unwrap_or(0);
+
+        self.ctx.align_unit_sizes(width);
+    }
+
+    /// Pads the exact byte counts of `--show-bytes` to the width of the largest of them.
+    fn align_exact_sizes(&mut self) {
+        let width = self
+            .inner
+            .iter()
+            .flat_map(|node| node.get().file_sizes())
+            .map(|size| self.ctx.group_digits(size.bytes).chars().count())
+            .max()
+            .unwrap_or(0);
+
+        self.ctx.align_exact_sizes(width);
+    }
+
+    /// Marks directories whose most recently modified descendant was modified longer than
+    /// `threshold` ago. Empty directories go by their own modification time.
+    fn mark_stale(&mut self, threshold: Duration) {
+        let Some(cutoff) = SystemTime::now().checked_sub(threshold) else {
+            return;
+        };
+
+        let mut newest: HashMap<NodeId, SystemTime> = HashMap::new();
+
+        for edge in self.root.traverse(&self.inner) {
+            let NodeEdge::End(node_id) = edge else {
+                continue;
+            };
+
+            let node = self.inner[node_id].get();
+
+            let modified = if node.is_dir() {
+                node_id
+                    .children(&self.inner)
+                    .filter_map(|child_id| newest.get(&child_id).copied())
+                    .max()
+                    .or_else(|| node.modified())
+            } else {
+                node.modified()
+            };
+
+            if let Some(modified) = modified {
+                newest.insert(node_id, modified);
+            }
+        }
+
+        for (node_id, modified) in newest {
+            let node = self.inner[node_id].get_mut();
+
+            if node.is_dir() && modified < cutoff {
+                node.set_stale();
+            }
+        }
+    }
+
+    /// Ranks the `count` largest files anywhere in the tree by disk usage, largest first.
+    fn rank_largest(&mut self, count: usize) {
+        let mut files = self
+            .root
+            .descendants(&self.inner)
+            .filter_map(|node_id| {
+                let node = self.inner[node_id].get();
+
+                if node.is_dir() {
+                    return None;
+                }
+
+                node.file_size().map(|fs| (node_id, fs.bytes))
+            })
+            .collect::<Vec<_>>();
+
+        files.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        for (rank, (node_id, _)) in files.into_iter().take(count).enumerate() {
+            self.inner[node_id].get_mut().set_rank(rank + 1);
+        }
+    }
+
+    /// Attaches the notes of `annotations` to the nodes whose paths relative to the root
+    /// directory they are keyed by.
+    fn apply_annotations(&mut self, annotations: &Annotations) {
+        let root_path = self.inner[self.root].get().path().to_owned();
+        let node_ids = self.root.descendants(&self.inner).collect::<Vec<_>>();
+
+        for node_id in node_ids {
+            let node = self.inner[node_id].get();
+            let rel_path = node
+                .path()
+                .strip_prefix(&root_path)
+                .unwrap_or_else(|_| Path::new(""));
+
+            if let Some(note) = annotations.get(rel_path) {
+                let note = note.to_owned();
+                self.inner[node_id].get_mut().set_note(note);
+            }
+        }
+    }
+
+    /// The directory to run git from, which is the root or the parent of a root file, as git can
+    /// only be run from within a directory.
+    fn git_root(&self) -> PathBuf {
+        let root_node = self.inner[self.root].get();
+
+        if root_node.is_dir() {
+            root_node.path()
+        } else {
+            root_node.parent_path().unwrap_or_else(|| Path::new("."))
+        }
+        .to_owned()
+    }
+
+    /// Attaches to every file the author who last committed to it in git.
+    fn apply_git_authors(&mut self) -> Result<()> {
+        let root_path = self.git_root();
+
+        let files = self
+            .root
+            .descendants(&self.inner)
+            .filter(|node_id| !self.inner[*node_id].get().is_dir())
+            .filter_map(|node_id| {
+                let path = self.inner[node_id].get().path();
+                let rel_path = path.strip_prefix(&root_path).ok()?.to_path_buf();
+                Some((node_id, rel_path))
+            })
+            .collect::<Vec<_>>();
+
+        let wanted = files.iter().map(|(_, rel_path)| rel_path.clone()).collect();
+
+        let mut authors = git::last_authors(&root_path, &wanted).map_err(Error::Git)?;
+
+        for (node_id, rel_path) in files {
+            if let Some(author) = authors.remove(&rel_path) {
+                self.inner[node_id].get_mut().set_author(author);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Attaches to every entry to be printed what `cmd` printed for it with `--column-cmd`.
+    fn apply_column_cmd(&mut self, cmd: &str) {
+        let level = self.level();
+
+        let node_ids = self
+            .root
+            .descendants(&self.inner)
+            .filter(|node_id| self.inner[*node_id].get().depth() <= level)
+            .collect::<Vec<_>>();
+
+        let paths = node_ids
+            .iter()
+            .map(|node_id| self.inner[*node_id].get().path())
+            .collect::<Vec<_>>();
+
+        let timeout = self
+            .ctx
+            .column_cmd_timeout
+            .unwrap_or(column_cmd::DEFAULT_TIMEOUT);
+
+        let outputs = column_cmd::run(cmd, &paths, self.ctx.threads(), timeout);
+
+        for (node_id, output) in node_ids.into_iter().zip(outputs) {
+            if let Some(output) = output {
+                self.inner[node_id].get_mut().set_command_output(output);
+            }
+        }
+    }
 
-        Ok(Self::new(inner, root, ctx))
+    /// Reads the first `lines` lines of every file to be printed for `--preview`, leaving out those
+    /// deeper than `--level` and those past `--max-lines`.
+    fn apply_previews(&mut self, lines: u64) {
+        let lines = usize::try_from(lines).unwrap_or(usize::MAX);
+        let level = self.level();
+
+        let node_ids = self
+            .root
+            .descendants(&self.inner)
+            .filter(|node_id| self.inner[*node_id].get().depth() <= level)
+            .take(self.ctx.max_lines.unwrap_or(usize::MAX))
+            .collect::<Vec<_>>();
+
+        for node_id in node_ids {
+            let node = self.inner[node_id].get_mut();
+
+            let Some(len) = node.metadata().filter(|md| md.is_file()).map(Metadata::len) else {
+                continue;
+            };
+
+            if let Ok(Some(preview)) = content::preview(node.path(), len, lines) {
+                node.set_preview(preview);
+            }
+        }
+    }
+
+    /// Counts the files that git's sparse-checkout kept out of the working tree against the
+    /// nearest of their ancestors that is in the tree, so that it's apparent where the tree
+    /// differs from the contents of the repository.
+    fn mark_sparse_checkout(&mut self) -> Result<()> {
+        let root_path = self.git_root();
+
+        let skipped = git::skip_worktree(&root_path).map_err(Error::Git)?;
+
+        let dirs = self
+            .root
+            .descendants(&self.inner)
+            .filter(|node_id| self.inner[*node_id].get().is_dir())
+            .filter_map(|node_id| {
+                let path = self.inner[node_id].get().path();
+                let rel_path = path.strip_prefix(&root_path).ok()?.to_path_buf();
+                Some((rel_path, node_id))
+            })
+            .collect::<HashMap<_, _>>();
+
+        for rel_path in skipped {
+            let nearest = rel_path
+                .ancestors()
+                .skip(1)
+                .find_map(|ancestor| dirs.get(ancestor));
+
+            if let Some(node_id) = nearest {
+                self.inner[*node_id].get_mut().add_not_checked_out();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Annotates every node with its change in disk usage since the previously saved
+    /// [Snapshot] of the root directory and saves the current state as the new snapshot.
+    fn compute_deltas(&mut self) -> Result<()> {
+        let root_path = self.inner[self.root].get().path();
+
+        // Snapshots are keyed by canonical path regardless of `--no-canonicalize`.
+        let root_path = fs::canonicalize(root_path).unwrap_or_else(|_| root_path.to_owned());
+
+        let snapshot_path = Snapshot::path_for(&root_path).ok_or(Error::MissingCacheDir)?;
+
+        let current = Snapshot::from_tree(&self.inner, self.root);
+
+        if let Some(previous) = Snapshot::load(&snapshot_path).map_err(Error::Snapshot)? {
+            let node_ids = self.root.descendants(&self.inner).collect::<Vec<_>>();
+
+            for node_id in node_ids {
+                let node = self.inner[node_id].get();
+                let rel_path = node
+                    .path()
+                    .strip_prefix(&root_path)
+                    .unwrap_or_else(|_| Path::new(""));
+                let bytes = node.file_size().map_or(0, |fs| fs.bytes);
+
+                if let Some(delta) = previous.delta(rel_path, bytes) {
+                    self.inner[node_id].get_mut().set_delta(delta);
+                }
+            }
+
+            self.removed = previous
+                .missing_from(&current)
+                .into_iter()
+                .map(|(path, bytes)| (path.to_owned(), bytes))
+                .collect();
+        }
+
+        current.save(&snapshot_path).map_err(Error::Snapshot)
     }
 
     /// Maximum depth to display.
     fn level(&self) -> usize {
-        self.ctx.level.unwrap_or(usize::MAX)
+        self.ctx.level().unwrap_or(usize::MAX)
+    }
+
+    /// Time it took to traverse the file-system and assemble [Tree].
+    pub const fn traversal_time(&self) -> Duration {
+        self.traversal_time
     }
 
     /// Grab a reference to [Context].
@@ -78,51 +489,124 @@ impl Tree {
         &self.inner
     }
 
-    pub const fn report(&self) -> Report {
+    pub const fn report(&self) -> Report<'_> {
         Report::new(self)
     }
 
+    pub const fn du_compat(&self) -> DuCompat<'_> {
+        DuCompat::new(self)
+    }
+
+    pub const fn porcelain(&self) -> Porcelain<'_> {
+        Porcelain::new(self)
+    }
+
+    pub const fn stats(&self) -> Stats<'_> {
+        Stats::new(self)
+    }
+
+    pub const fn prometheus(&self) -> Prometheus<'_> {
+        Prometheus::new(self)
+    }
+
+    pub const fn grid(&self) -> Grid<'_> {
+        Grid::new(self)
+    }
+
     /// Parallel traversal of the root directory and its contents. Parallel traversal relies on
     /// `WalkParallel`. Any filesystem I/O or related system calls are expected to occur during
     /// parallel traversal; post-processing post-processing of all directory entries should
     /// be completely CPU-bound.
     fn traverse(ctx: &Context) -> Result<(Arena<Node>, NodeId)> {
-        let walker = WalkParallel::try_from(ctx)?;
+        let mut progress = ctx
+            .checkpoint_path()
+            .map(|path| Self::progress(ctx, path))
+            .transpose()?;
+
+        // Resuming only needs to know which directories to skip from the walking thread.
+        let resumed = progress.as_ref().map(Progress::checkpoint).cloned();
+        let checkpointed = resumed.is_some();
+
+        // Configured before the receiving thread is spawned so that it never waits on a walk that
+        // failed to start.
+        let walk_builder = Self::walk_builder(ctx, resumed)?;
+        let inverted_globs = ctx.inverted_globs()?;
         let (tx, rx) = mpsc::channel();
 
+        let selected = |node: &Node| Self::selected(node, ctx, inverted_globs.as_ref());
+
         thread::scope(|s| {
             let res = s.spawn(move || {
                 let mut tree = Arena::new();
                 let mut branches: HashMap<PathBuf, Vec<NodeId>> = HashMap::new();
                 let mut inodes = HashSet::new();
+                let mut reflinked = vec![];
 
-                let mut root_id = None;
+                let mut unsampled: HashMap<PathBuf, u64> = HashMap::new();
+                let mut dirs_by_inode: HashMap<(u64, u64), Vec<NodeId>> = HashMap::new();
 
-                while let Ok(TraversalState::Ongoing(node)) = rx.recv() {
-                    if node.is_dir() {
-                        let node_path = node.path();
+                let mut root_id = None;
 
-                        if !branches.contains_key(node_path) {
-                            branches.insert(node_path.to_owned(), vec![]);
+                loop {
+                    let mut node = match rx.recv() {
+                        Ok(TraversalState::Ongoing(node)) => node,
+                        Ok(TraversalState::Unsampled(parent)) => {
+                            *unsampled.entry(parent).or_default() += 1;
+                            continue;
                         }
+                        _ => break,
+                    };
 
-                        if node.depth() == 0 {
-                            root_id = Some(tree.new_node(node));
-                            continue;
+                    if let Some(reason) = Self::exclusion(&node, ctx, selected) {
+                        tracing::trace!(path = %node.path().display(), "skipping {reason}");
+                        continue;
+                    }
+
+                    if node.is_dir() && !branches.contains_key(node.path()) {
+                        branches.insert(node.path().to_owned(), vec![]);
+                    }
+
+                    // The root may also be a regular file in which case it's the only entry.
+                    if node.depth() == 0 {
+                        if let Some(progress) = progress.as_mut() {
+                            progress.record(Path::new(""), node.file_sizes(), node.is_dir());
                         }
+
+                        root_id = Some(tree.new_node(node));
+                        continue;
                     }
 
-                    // If a hard-link is already accounted for, skip all subsequent ones.
-                    if let Some(inode) = node.inode() {
+                    // If a hard-link is already accounted for, skip all subsequent ones. Directories
+                    // can't be hard-linked so theirs are links from their entries and subdirectories.
+                    if let Some(inode) = node.inode().filter(|_| !node.is_dir()) {
                         if inode.nlink > 1 && !inodes.insert(inode) {
+                            tracing::debug!(path = %node.path().display(), "skipping hardlink");
                             continue;
                         }
                     }
 
+                    if let Some(progress) = progress.as_mut() {
+                        Self::record_progress(&mut node, &tree, root_id, progress, ctx);
+                    }
+
                     let parent = node.parent_path().ok_or(Error::ExpectedParent)?.to_owned();
 
+                    let dir_inode = node
+                        .inode()
+                        .filter(|_| node.is_dir())
+                        .map(|inode| (inode.dev, inode.ino));
+
+                    let has_shared_extents = node.has_shared_extents();
                     let node_id = tree.new_node(node);
 
+                    if has_shared_extents {
+                        reflinked.push(node_id);
+                    }
+
+                    if let Some(dir_inode) = dir_inode {
+                        dirs_by_inode.entry(dir_inode).or_default().push(node_id);
+                    }
+
                     if branches
                         .get_mut(&parent)
                         .map(|mut_ref| mut_ref.push(node_id))
@@ -132,29 +616,329 @@ impl Tree {
                     }
                 }
 
+                if let Some(progress) = progress.as_mut() {
+                    progress.finish();
+                }
+
                 let root = root_id.ok_or(Error::MissingRoot)?;
 
-                Self::assemble_tree(&mut tree, root, &mut branches, ctx);
+                // Likewise data shared through reflinks only counts toward the file whose path
+                // comes first, regardless of the order in which files were read.
+                reflinked.sort_by(|a, b| tree[*a].get().path().cmp(tree[*b].get().path()));
 
-                if ctx.prune {
-                    Self::prune_directories(root, &mut tree);
-                }
+                let mut extents = HashSet::new();
 
-                if ctx.dirs_only {
-                    Self::filter_directories(root, &mut tree);
+                for node_id in reflinked {
+                    tree[node_id].get_mut().deduct_shared_extents(&mut extents);
                 }
 
+                let assemble = tracing::info_span!("assemble", entries = tree.count()).entered();
+                Self::assemble(&mut tree, root, branches, dirs_by_inode, &unsampled, ctx);
+                assemble.exit();
+
                 Ok::<(Arena<Node>, NodeId), Error>((tree, root))
             });
 
-            let mut visitor_builder = BranchVisitorBuilder::new(ctx, Sender::clone(&tx));
+            tracing::info_span!("walk", root = %ctx.dir().display(), threads = ctx.threads())
+                .in_scope(|| Self::walk(ctx, &walk_builder, checkpointed, &tx));
+
+            res.join().unwrap()
+        })
+    }
+
+    /// Puts the entries received during the traversal in their place beneath `root`, then removes
+    /// or collapses them as requested.
+    fn assemble(
+        tree: &mut Arena<Node>,
+        root: NodeId,
+        mut branches: HashMap<PathBuf, Vec<NodeId>>,
+        dirs_by_inode: HashMap<(u64, u64), Vec<NodeId>>,
+        unsampled: &HashMap<PathBuf, u64>,
+        ctx: &Context,
+    ) {
+        Self::dedup_directories(tree, &mut branches, dirs_by_inode);
+
+        if tree[root].get().is_dir() {
+            Self::assemble_tree(tree, root, &mut branches, ctx);
+        }
+
+        if !unsampled.is_empty() {
+            Self::extrapolate_unsampled(root, tree, unsampled, ctx);
+        }
+
+        Self::reshape(root, tree, ctx);
+    }
+
+    /// Walks the file-system beneath the root as configured by `walk_builder`, sending every entry
+    /// visited over `tx`. Traversals that are `checkpointed` walk it sequentially instead so that
+    /// entries are sent in depth-first order, which tells when a directory is complete.
+    fn walk(
+        ctx: &Context,
+        walk_builder: &WalkBuilder,
+        checkpointed: bool,
+        tx: &Sender<TraversalState>,
+    ) {
+        let mut visitor_builder = BranchVisitorBuilder::new(ctx, Sender::clone(tx));
+
+        if !checkpointed {
+            walk_builder.build_parallel().visit(&mut visitor_builder);
+            tx.send(TraversalState::Done).unwrap();
+            return;
+        }
+
+        let mut visitor = visitor_builder.build();
+
+        // Contents of directories the visitor skips are still walked sequentially so they're
+        // dropped here instead.
+        let mut skipped: Option<PathBuf> = None;
+
+        for entry in walk_builder.build() {
+            let dir = match entry {
+                Ok(ref entry)
+                    if skipped
+                        .as_ref()
+                        .map_or(false, |dir| entry.path().starts_with(dir)) =>
+                {
+                    continue;
+                }
+                Ok(ref entry) => entry
+                    .file_type()
+                    .filter(FileType::is_dir)
+                    .map(|_| entry.path().to_owned()),
+                Err(_) => None,
+            };
+
+            match visitor.visit(entry) {
+                WalkState::Quit => break,
+                WalkState::Skip if dir.is_some() => skipped = dir,
+                WalkState::Skip | WalkState::Continue => (),
+            }
+        }
+
+        tx.send(TraversalState::Done).unwrap();
+    }
+
+    /// The [Progress] of a traversal with `--checkpoint` that's persisted to `path`, starting from
+    /// the checkpoint provided to `--resume` if any.
+    fn progress(ctx: &Context, path: &Path) -> Result<Progress> {
+        let root = Self::walk_root(ctx)?;
+        let settings = ctx.traversal_settings();
+
+        let checkpoint = match ctx.resume {
+            Some(ref resume) => Checkpoint::load(resume, &root, &ctx.disk_usage, &settings)
+                .map_err(Error::Checkpoint)?,
+            None => Checkpoint::new(&root, &ctx.disk_usage, &settings),
+        };
+
+        Ok(Progress::new(path.to_owned(), checkpoint))
+    }
+
+    /// Adds the size of `node` to the [Progress] of the directory it lies in. A directory that was
+    /// completed before instead takes on its size from then as its contents aren't walked again.
+    fn record_progress(
+        node: &mut Node,
+        tree: &Arena<Node>,
+        root_id: Option<NodeId>,
+        progress: &mut Progress,
+        ctx: &Context,
+    ) {
+        let Some(root) = root_id.map(|root_id| tree[root_id].get().path()) else {
+            return;
+        };
 
-            walker.visit(&mut visitor_builder);
+        let Ok(rel_path) = node.path().strip_prefix(root).map(Path::to_path_buf) else {
+            return;
+        };
+
+        let completed = progress.completed(&rel_path).filter(|_| node.is_dir());
+
+        if let Some(sizes) = completed {
+            let sizes = ctx
+                .disk_usage
+                .iter()
+                .zip(sizes)
+                .map(|(disk_usage, bytes)| {
+                    FileSize::new(
+                        *bytes,
+                        *disk_usage,
+                        ctx.prefix,
+                        ctx.scale,
+                        ctx.rounding,
+                        ctx.unit,
+                    )
+                })
+                .collect();
+
+            node.set_file_sizes(sizes);
+            node.set_resumed();
+        }
+
+        let traversed = node.is_dir() && completed.is_none();
+        progress.record(&rel_path, node.file_sizes(), traversed);
+    }
+
+    /// Whether `node` is selected by the `inverted_globs` of [`Context::inverted_globs`] and the
+    /// filter expression, neither of which are applied during the traversal itself.
+    fn selected(node: &Node, ctx: &Context, inverted_globs: Option<&Override>) -> bool {
+        let globbed =
+            inverted_globs.map_or(true, |globs| !globs.matched(node.path(), false).is_ignore());
+
+        globbed
+            && ctx
+                .filter
+                .as_ref()
+                .map_or(true, |filter| filter.matches(node))
+    }
+
+    /// Determines whether `node` is to be left out of the tree due to flags that select which
+    /// files to print, returning what kind of file it is if so. `selected` tells whether it
+    /// matches the glob patterns and filter expression that weren't applied during traversal.
+    fn exclusion(
+        node: &Node,
+        ctx: &Context,
+        selected: impl Fn(&Node) -> bool,
+    ) -> Option<&'static str> {
+        // The root is never excluded, nor are directories which are pruned later if empty.
+        if node.is_dir() || node.depth() == 0 {
+            return None;
+        }
+
+        if ctx.sparse_only && !node.is_sparse() {
+            return Some("non-sparse file");
+        }
+
+        if (ctx.filter.is_some() || ctx.invert_match) && selected(node) == ctx.invert_match {
+            return Some("file not selected by --filter or --invert-match");
+        }
+
+        if ctx.code_report() && node.lines().is_none() {
+            return Some("non-source file");
+        }
+
+        if ctx.permissions_report() && node.permission_issues().is_empty() {
+            return Some("file without risky permissions");
+        }
+
+        if ctx.text_only && node.content() != Some(Content::Text) {
+            return Some("non-text file");
+        }
+
+        if ctx.binary_only && node.content() != Some(Content::Binary) {
+            return Some("non-binary file");
+        }
+
+        if ctx.materialized_only && node.is_placeholder() {
+            return Some("cloud placeholder");
+        }
+
+        if ctx.filters_taken() && !node.taken().map_or(false, |taken| ctx.taken_within(taken)) {
+            return Some("file taken outside of range");
+        }
+
+        None
+    }
+
+    /// Removes or collapses entries of the assembled tree as requested by flags such as
+    /// `--prune`, `--dirs-only`, and `--collapse-identical`.
+    fn reshape(root: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        Self::mark_submodules(root, tree, ctx.no_submodules);
+
+        if ctx.prune
+            || ctx.sparse_only
+            || ctx.code_report()
+            || ctx.permissions_report()
+            || ctx.text_only
+            || ctx.binary_only
+            || ctx.artifacts_only
+            || ctx.filter.is_some()
+            || ctx.invert_match
+            || ctx.filters_taken()
+        {
+            Self::prune_directories(root, tree);
+        }
+
+        if ctx.bundles == Bundles::Collapse {
+            Self::collapse_bundles(root, tree);
+        }
+
+        if ctx.has_collapsed() {
+            Self::collapse_subtrees(root, tree, ctx);
+        }
+
+        if ctx.dirs_only {
+            Self::filter_directories(root, tree);
+        }
+
+        if ctx.collapse_identical {
+            Self::collapse_identical(root, tree, ctx);
+        }
+
+        if ctx.collapse_single_child {
+            Self::collapse_single_children(root, tree, ctx);
+        }
+
+        if ctx.has_subtree_levels() {
+            Self::limit_subtree_levels(root, tree, ctx);
+        }
+    }
+
+    /// Directories that appear more than once, through bind mounts or symlinks followed with
+    /// `--follow-links`, only have their contents accounted for at their shallowest appearance,
+    /// ties broken by path. The others are marked as seen above and left empty.
+    fn dedup_directories(
+        tree: &mut Arena<Node>,
+        branches: &mut HashMap<PathBuf, Vec<NodeId>>,
+        dirs_by_inode: HashMap<(u64, u64), Vec<NodeId>>,
+    ) {
+        for mut appearances in dirs_by_inode.into_values() {
+            if appearances.len() < 2 {
+                continue;
+            }
+
+            appearances.sort_by(|id_a, id_b| {
+                let node_a = tree[*id_a].get();
+                let node_b = tree[*id_b].get();
+
+                node_a
+                    .depth()
+                    .cmp(&node_b.depth())
+                    .then_with(|| node_a.path().cmp(node_b.path()))
+            });
+
+            for dup_id in appearances.into_iter().skip(1) {
+                // Appearances within others that were emptied are gone along with them.
+                if tree[dup_id].is_removed() {
+                    continue;
+                }
+
+                tracing::debug!(path = %tree[dup_id].get().path().display(), "directory seen above");
+
+                tree[dup_id].get_mut().set_seen_above();
+
+                let path = tree[dup_id].get().path().to_owned();
+                Self::discard_contents(&path, tree, branches);
+            }
+        }
+    }
+
+    /// Removes the entries beneath the directory at `path` that are yet to be assembled, leaving
+    /// the directory empty.
+    fn discard_contents(
+        path: &Path,
+        tree: &mut Arena<Node>,
+        branches: &mut HashMap<PathBuf, Vec<NodeId>>,
+    ) {
+        let children = branches.insert(path.to_owned(), vec![]).unwrap_or_default();
 
-            tx.send(TraversalState::Done).unwrap();
+        for child_id in children {
+            if tree[child_id].get().is_dir() {
+                let child_path = tree[child_id].get().path().to_owned();
+                Self::discard_contents(&child_path, tree, branches);
+                branches.remove(&child_path);
+            }
 
-            res.join().unwrap()
-        })
+            child_id.remove(tree);
+        }
     }
 
     /// Takes the results of the parallel traversal and uses it to construct the [Tree] data
@@ -169,7 +953,25 @@ impl Tree {
 
         let mut children = branches.remove(current_node.path()).unwrap();
 
-        let mut dir_size = FileSize::new(0, ctx.disk_usage, ctx.prefix, ctx.scale);
+        // Non-zero only if the size of the directory entry itself is to be included.
+        let mut dir_sizes = ctx
+            .disk_usage
+            .iter()
+            .enumerate()
+            .map(|(i, disk_usage)| {
+                let own_bytes = current_node.file_sizes().get(i).map_or(0, |fs| fs.bytes);
+                FileSize::new(
+                    own_bytes,
+                    *disk_usage,
+                    ctx.prefix,
+                    ctx.scale,
+                    ctx.rounding,
+                    ctx.unit,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut dir_lines: Option<LineCount> = None;
 
         for child_id in &children {
             let index = *child_id;
@@ -183,17 +985,27 @@ impl Tree {
                 Self::assemble_tree(tree, index, branches, ctx);
             }
 
-            if let Some(file_size) = tree[index].get().file_size() {
-                dir_size += file_size.bytes;
+            for (dir_size, file_size) in dir_sizes.iter_mut().zip(tree[index].get().file_sizes()) {
+                *dir_size += file_size.bytes;
+            }
+
+            if let Some(lines) = tree[index].get().lines() {
+                *dir_lines.get_or_insert_with(LineCount::default) += lines;
             }
         }
 
-        if dir_size.bytes > 0 {
-            tree[current_node_id].get_mut().set_file_size(dir_size);
+        if dir_sizes.iter().any(|dir_size| dir_size.bytes > 0) {
+            tree[current_node_id].get_mut().set_file_sizes(dir_sizes);
+        }
+
+        if let Some(lines) = dir_lines {
+            tree[current_node_id].get_mut().set_lines(lines);
         }
 
         // Sort if sorting specified
         if let Some(func) = node::cmp::comparator(ctx) {
+            let _sort = tracing::trace_span!("sort", children = children.len()).entered();
+
             children.sort_by(|id_a, id_b| {
                 let node_a = tree[*id_a].get();
                 let node_b = tree[*id_b].get();
@@ -207,6 +1019,123 @@ impl Tree {
         }
     }
 
+    /// Adds the estimated size of the files skipped by `--approx` to their directories and all of
+    /// their ancestors. Each skipped file is assumed to be as large as the average of the files
+    /// read in its directory, or of those read anywhere if none were read in its directory, and
+    /// every affected directory is marked with the margin of error of its estimate at a 95%
+    /// confidence level.
+    fn extrapolate_unsampled(
+        root: NodeId,
+        tree: &mut Arena<Node>,
+        unsampled: &HashMap<PathBuf, u64>,
+        ctx: &Context,
+    ) {
+        let kinds = ctx.disk_usage.len();
+
+        // Estimated bytes in each kind of disk usage and variance of the estimate in the first,
+        // which sizes are sorted and reported in, by directory, including descendants.
+        let mut estimates: HashMap<NodeId, (Vec<u64>, f64)> = HashMap::new();
+
+        let everywhere = Self::samples(
+            root.descendants(tree).map(|node_id| tree[node_id].get()),
+            kinds,
+        );
+
+        let dirs = root
+            .traverse(tree)
+            .filter_map(|edge| match edge {
+                NodeEdge::End(node_id) if tree[node_id].get().is_dir() => Some(node_id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        for dir_id in dirs {
+            let mut bytes = vec![0; kinds];
+            let mut variance = 0.0;
+
+            for (child_bytes, child_variance) in dir_id
+                .children(tree)
+                .filter_map(|child_id| estimates.get(&child_id))
+            {
+                for (total, child_bytes) in bytes.iter_mut().zip(child_bytes) {
+                    *total += child_bytes;
+                }
+                variance += child_variance;
+            }
+
+            let skipped = unsampled
+                .get(tree[dir_id].get().path())
+                .copied()
+                .unwrap_or(0);
+
+            let local = Self::samples(
+                dir_id.children(tree).map(|child_id| tree[child_id].get()),
+                kinds,
+            );
+
+            let samples = if local.first().map_or(true, Vec::is_empty) {
+                &everywhere
+            } else {
+                &local
+            };
+
+            let count = samples.first().map_or(0, Vec::len);
+
+            if skipped > 0 && count > 0 {
+                let count = count as f64;
+                let skipped = skipped as f64;
+
+                for (kind, (total, samples)) in bytes.iter_mut().zip(samples).enumerate() {
+                    let mean = samples.iter().sum::<f64>() / count;
+
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    {
+                        *total += (mean * skipped).round() as u64;
+                    }
+
+                    if kind == 0 {
+                        let sample_variance =
+                            samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>()
+                                / (count - 1.0).max(1.0);
+
+                        variance += skipped * skipped * sample_variance / count;
+                    }
+                }
+            }
+
+            if bytes.iter().all(|bytes| *bytes == 0) && variance == 0.0 && skipped == 0 {
+                continue;
+            }
+
+            let node = tree[dir_id].get_mut();
+            node.add_file_sizes(&bytes, ctx);
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            node.set_estimate_margin((1.96 * variance.sqrt()).round() as u64);
+
+            estimates.insert(dir_id, (bytes, variance));
+        }
+    }
+
+    /// Sizes in each of `kinds` kinds of disk usage of the files among `nodes`, which are the
+    /// samples that `--approx` extrapolates from.
+    fn samples<'a>(nodes: impl Iterator<Item = &'a Node>, kinds: usize) -> Vec<Vec<f64>> {
+        let files = nodes.filter(|node| !node.is_dir()).collect::<Vec<_>>();
+
+        (0..kinds)
+            .map(|kind| {
+                files
+                    .iter()
+                    .map(|node| {
+                        node.file_sizes()
+                            .get(kind)
+                            .map_or(0.0, |fs| fs.bytes as f64)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
     /// Function to remove empty directories.
     fn prune_directories(root_id: NodeId, tree: &mut Arena<Node>) {
         let mut to_prune = vec![];
@@ -234,6 +1163,164 @@ impl Tree {
         Self::prune_directories(root_id, tree);
     }
 
+    /// Removes the contents of bundles so that they appear as single entries.
+    fn collapse_bundles(root: NodeId, tree: &mut Arena<Node>) {
+        Self::collapse_where(root, tree, |node| {
+            node.is_dir() && crate::fs::bundle::is_bundle(node.path())
+        });
+    }
+
+    /// Removes the contents of the directories requested with `--collapse` so that they appear as
+    /// single entries.
+    fn collapse_subtrees(root: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        let root_path = tree[root].get().path().to_owned();
+
+        Self::collapse_where(root, tree, |node| {
+            let path = node
+                .path()
+                .strip_prefix(&root_path)
+                .unwrap_or_else(|_| node.path());
+
+            node.is_dir() && ctx.is_collapsed(path)
+        });
+    }
+
+    /// Marks the working trees of git submodules and, if `collapse` is set, removes their contents
+    /// so that they appear as single entries.
+    fn mark_submodules(root: NodeId, tree: &mut Arena<Node>, collapse: bool) {
+        let is_submodule = |node: &Node| node.is_dir() && git::is_submodule(node.path());
+
+        let submodules = if collapse {
+            Self::collapse_where(root, tree, is_submodule)
+        } else {
+            root.descendants(tree)
+                .skip(1)
+                .filter(|node_id| is_submodule(tree[*node_id].get()))
+                .collect()
+        };
+
+        for submodule_id in submodules {
+            tree[submodule_id].get_mut().set_submodule();
+        }
+    }
+
+    /// Removes the contents of the entries beneath `root` that satisfy `pred` so that they appear
+    /// as single entries, returning those that are left in the tree. Entries nested within others
+    /// that satisfy it are removed along with the outermost one. Their sizes already account for
+    /// their contents.
+    fn collapse_where(
+        root: NodeId,
+        tree: &mut Arena<Node>,
+        pred: impl Fn(&Node) -> bool,
+    ) -> Vec<NodeId> {
+        let matches = root
+            .descendants(tree)
+            .skip(1)
+            .filter(|node_id| pred(tree[*node_id].get()))
+            .collect::<Vec<_>>();
+
+        let mut collapsed = vec![];
+
+        for node_id in matches {
+            if tree[node_id].is_removed() {
+                continue;
+            }
+
+            let children = node_id.children(tree).collect::<Vec<_>>();
+
+            for child_id in children {
+                child_id.remove_subtree(tree);
+            }
+
+            collapsed.push(node_id);
+        }
+
+        collapsed
+    }
+
+    /// Merges every directory whose only entry is another directory with that directory so that
+    /// chains of them are printed on a single line e.g. `com/example/app`. The root is left as is.
+    fn collapse_single_children(root: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        let dirs = root
+            .descendants(tree)
+            .skip(1)
+            .filter(|node_id| tree[*node_id].get().is_dir())
+            .collect::<Vec<_>>();
+
+        for dir_id in dirs {
+            // Directories that were merged into their parents are gone.
+            if tree[dir_id].is_removed() {
+                continue;
+            }
+
+            let mut merged = 0;
+
+            loop {
+                let mut children = dir_id.children(tree);
+
+                let (Some(child_id), None) = (children.next(), children.next()) else {
+                    break;
+                };
+
+                if !tree[child_id].get().is_dir() {
+                    break;
+                }
+
+                let child_name = tree[child_id].get().file_name_lossy().into_owned();
+                tree[dir_id].get_mut().merge_single_child(&child_name, ctx);
+
+                let grandchildren = child_id.children(tree).collect::<Vec<_>>();
+
+                for grandchild_id in grandchildren {
+                    grandchild_id.detach(tree);
+                    dir_id.append(grandchild_id, tree);
+                }
+
+                child_id.remove(tree);
+                merged += 1;
+            }
+
+            if merged == 0 {
+                continue;
+            }
+
+            let descendants = dir_id.descendants(tree).skip(1).collect::<Vec<_>>();
+
+            for descendant_id in descendants {
+                tree[descendant_id].get_mut().decrement_depth(merged);
+            }
+        }
+    }
+
+    /// Removes entries deeper than the `--level` of the subtree they're in, which may be
+    /// shallower than the deepest one that the tree is otherwise printed to. Sizes already
+    /// account for the removed entries.
+    fn limit_subtree_levels(root: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        let root_path = tree[root].get().path().to_owned();
+
+        let to_remove = root
+            .descendants(tree)
+            .skip(1)
+            .filter(|node_id| {
+                let node = tree[*node_id].get();
+                let path = node
+                    .path()
+                    .strip_prefix(&root_path)
+                    .unwrap_or_else(|_| node.path());
+
+                ctx.level_of(path)
+                    .map_or(false, |level| node.depth() > level)
+            })
+            .collect::<Vec<_>>();
+
+        for node_id in to_remove {
+            // Entries beneath others that were removed are gone along with them.
+            if !tree[node_id].is_removed() {
+                node_id.remove_subtree(tree);
+            }
+        }
+    }
+
     /// Filter for only directories.
     fn filter_directories(root: NodeId, tree: &mut Arena<Node>) {
         let mut to_detach = vec![];
@@ -249,6 +1336,115 @@ impl Tree {
         }
     }
 
+    /// Collapses sibling directories whose contents are structurally identical, ignoring their
+    /// own names, into the first of them which is then annotated with the number and combined
+    /// size of the others.
+    fn collapse_identical(root: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        let mut signatures = HashMap::new();
+        Self::compute_signature(root, tree, &mut signatures);
+
+        let dirs = root
+            .descendants(tree)
+            .filter(|node_id| tree[*node_id].get().is_dir())
+            .collect::<Vec<_>>();
+
+        for dir in dirs {
+            let mut representatives: HashMap<u64, NodeId> = HashMap::new();
+            let mut similar: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+
+            for child_id in dir.children(tree) {
+                if !tree[child_id].get().is_dir() {
+                    continue;
+                }
+
+                let representative = *representatives
+                    .entry(signatures[&child_id])
+                    .or_insert(child_id);
+
+                if representative != child_id {
+                    similar.entry(representative).or_default().push(child_id);
+                }
+            }
+
+            for (representative, others) in similar {
+                let mut sizes = ctx
+                    .disk_usage
+                    .iter()
+                    .map(|disk_usage| {
+                        FileSize::new(
+                            0,
+                            *disk_usage,
+                            ctx.prefix,
+                            ctx.scale,
+                            ctx.rounding,
+                            ctx.unit,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                for other in &others {
+                    for (size, file_size) in sizes.iter_mut().zip(tree[*other].get().file_sizes()) {
+                        *size += file_size.bytes;
+                    }
+                    other.detach(tree);
+                }
+
+                tree[representative]
+                    .get_mut()
+                    .set_similar(others.len(), sizes);
+            }
+        }
+    }
+
+    /// Computes a hash of the names, types, and sizes of everything beneath `node_id`, recursively,
+    /// and records it for every directory encountered. Files are hashed with the order of magnitude
+    /// of their size in bytes so that e.g. logs of a similar size are still deemed identical, and
+    /// directories without their size as it's the sum of that of their contents.
+    fn compute_signature(
+        node_id: NodeId,
+        tree: &Arena<Node>,
+        signatures: &mut HashMap<NodeId, u64>,
+    ) -> u64 {
+        let mut children = node_id
+            .children(tree)
+            .map(|child_id| {
+                let child = tree[child_id].get();
+
+                let (signature, bytes) = if child.is_dir() {
+                    (Self::compute_signature(child_id, tree, signatures), vec![])
+                } else {
+                    let bytes = child
+                        .file_sizes()
+                        .iter()
+                        .map(|fs| u64::BITS - fs.bytes.leading_zeros())
+                        .collect();
+                    (0, bytes)
+                };
+
+                (
+                    child.file_name(),
+                    child.file_type_identifier(),
+                    signature,
+                    bytes,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        children.sort_unstable();
+
+        let mut hasher = DefaultHasher::new();
+
+        for child in &children {
+            child.hash(&mut hasher);
+        }
+
+        let signature = hasher.finish();
+
+        signatures.insert(node_id, signature);
+
+        signature
+    }
+
     fn compute_file_count(node_id: NodeId, tree: &Arena<Node>) -> FileCount {
         let mut count = FileCount::default();
 
@@ -258,23 +1454,227 @@ impl Tree {
 
         count
     }
+
+    /// Gathers the cells of the columns selected with `--columns` for every [Node] to be printed
+    /// along with how wide each column has to be. Both are empty without `--columns`.
+    fn column_rows(&self) -> (HashMap<NodeId, Vec<String>>, Vec<usize>) {
+        let ctx = self.context();
+        let inner = self.inner();
+
+        if ctx.columns.is_empty() {
+            return (HashMap::new(), vec![]);
+        }
+
+        let rows = self
+            .root
+            .descendants(inner)
+            .filter(|node_id| inner[*node_id].get().depth() <= self.level())
+            .map(|node_id| (node_id, column::cells(inner[node_id].get(), ctx)))
+            .collect::<HashMap<_, _>>();
+
+        let widths = column::widths(
+            rows.iter()
+                .map(|(node_id, cells)| (inner[*node_id].get(), cells.as_slice())),
+            ctx,
+        );
+
+        (rows, widths)
+    }
+
+    /// Counts the text files beneath each directory by encoding and line endings in a single
+    /// post-order pass.
+    fn compute_encoding_counts(root: NodeId, tree: &Arena<Node>) -> HashMap<NodeId, EncodingCount> {
+        let mut counts = HashMap::new();
+
+        for edge in root.traverse(tree) {
+            let NodeEdge::End(node_id) = edge else {
+                continue;
+            };
+
+            if !tree[node_id].get().is_dir() {
+                continue;
+            }
+
+            let mut count = EncodingCount::default();
+
+            for child_id in node_id.children(tree) {
+                if let Some(profile) = tree[child_id].get().text_profile() {
+                    count.update(profile);
+                } else if let Some(child_count) = counts.get(&child_id) {
+                    count.update_from_count(*child_count);
+                }
+            }
+
+            counts.insert(node_id, count);
+        }
+
+        counts
+    }
+
+    /// Sums up how large the files beneath each directory are estimated to be once compressed in
+    /// a single post-order pass.
+    fn compute_compressibility(
+        root: NodeId,
+        tree: &Arena<Node>,
+    ) -> HashMap<NodeId, Compressibility> {
+        let mut estimates = HashMap::new();
+
+        for edge in root.traverse(tree) {
+            let NodeEdge::End(node_id) = edge else {
+                continue;
+            };
+
+            if !tree[node_id].get().is_dir() {
+                continue;
+            }
+
+            let mut estimate = Compressibility::default();
+
+            for child_id in node_id.children(tree) {
+                if let Some(child_estimate) = tree[child_id]
+                    .get()
+                    .compressibility()
+                    .or_else(|| estimates.get(&child_id).copied())
+                {
+                    estimate += child_estimate;
+                }
+            }
+
+            estimates.insert(node_id, estimate);
+        }
+
+        estimates
+    }
+
+    /// Like [`Tree::compute_file_count`] but counts every descendant of each directory rather
+    /// than just its children, computed for all directories in a single post-order pass.
+    fn compute_cumulative_file_counts(
+        root: NodeId,
+        tree: &Arena<Node>,
+    ) -> HashMap<NodeId, FileCount> {
+        let mut counts = HashMap::new();
+
+        for edge in root.traverse(tree) {
+            let NodeEdge::End(node_id) = edge else {
+                continue;
+            };
+
+            if !tree[node_id].get().is_dir() {
+                continue;
+            }
+
+            let mut count = Self::compute_file_count(node_id, tree);
+
+            for child_id in node_id.children(tree) {
+                if let Some(child_count) = counts.get(&child_id) {
+                    count.update_from_count(*child_count);
+                }
+            }
+
+            counts.insert(node_id, count);
+        }
+
+        counts
+    }
 }
 
 impl TryFrom<&Context> for WalkParallel {
     type Error = Error;
 
     fn try_from(clargs: &Context) -> StdResult<Self, Self::Error> {
-        let root = fs::canonicalize(clargs.dir())?;
+        Tree::walker(clargs)
+    }
+}
+
+impl Tree {
+    /// Path the traversal starts from, which is canonicalized unless `--no-canonicalize`.
+    fn walk_root(clargs: &Context) -> Result<PathBuf> {
+        let root = if clargs.no_canonicalize {
+            clargs.dir().to_path_buf()
+        } else {
+            fs::canonicalize(clargs.dir())?
+        };
+
+        fs::metadata(&root).map_err(|e| {
+            let root = clargs.display_path(&root);
+            Error::DirNotFound(format!("{}: {e}", root.display()))
+        })?;
+
+        Ok(root)
+    }
+
+    /// Parallel walk of the file-system beneath the root.
+    fn walker(clargs: &Context) -> Result<WalkParallel> {
+        Ok(Self::walk_builder(clargs, None)?.build_parallel())
+    }
+
+    /// Configures a walk of the file-system beneath the root as requested, which is either run in
+    /// parallel or, with `--checkpoint`, sequentially. The contents of the directories that were
+    /// completed before as per `resumed` are left out.
+    fn walk_builder(clargs: &Context, resumed: Option<Checkpoint>) -> Result<WalkBuilder> {
+        let root = Self::walk_root(clargs)?;
+
+        let hidden_root = clargs.hidden_root();
 
-        fs::metadata(&root).map_err(|e| Error::DirNotFound(format!("{}: {e}", root.display())))?;
+        let mut builder = WalkBuilder::new(&root);
 
-        Ok(WalkBuilder::new(root)
+        builder
             .follow_links(clargs.follow_links)
             .git_ignore(!clargs.ignore_git_ignore)
-            .hidden(!clargs.hidden)
-            .threads(clargs.threads)
-            .overrides(clargs.overrides()?)
-            .build_parallel())
+            .hidden(!clargs.hidden && !hidden_root)
+            .threads(clargs.threads())
+            .overrides(clargs.overrides()?);
+
+        if clargs.shallow_sizes {
+            builder.max_depth(clargs.level());
+        }
+
+        let root_glob = clargs.root_glob()?;
+        let no_vcs_dirs = clargs.no_vcs_dirs;
+
+        if root_glob.is_some() || no_vcs_dirs || hidden_root || resumed.is_some() {
+            builder.filter_entry(move |entry| {
+                let is_dir = entry.file_type().map_or(false, |ft| ft.is_dir());
+
+                let in_completed = entry.depth() > 1
+                    && resumed.as_ref().map_or(false, |resumed| {
+                        entry
+                            .path()
+                            .parent()
+                            .and_then(|parent| parent.strip_prefix(&root).ok())
+                            .map_or(false, |rel_path| resumed.completed(rel_path).is_some())
+                    });
+
+                if in_completed {
+                    return false;
+                }
+
+                // Only the hidden entries directly beneath a hidden root are walked, and not the
+                // metadata of version control systems among them.
+                if hidden_root
+                    && entry.file_name().to_string_lossy().starts_with('.')
+                    && (entry.depth() > 1 || VCS_DIRS.iter().any(|vcs| entry.file_name() == *vcs))
+                {
+                    return false;
+                }
+
+                if no_vcs_dirs
+                    && is_dir
+                    && entry.depth() > 0
+                    && VCS_DIRS.iter().any(|vcs| entry.file_name() == *vcs)
+                {
+                    tracing::debug!(path = %entry.path().display(), "skipping VCS directory");
+                    return false;
+                }
+
+                root_glob.as_ref().map_or(true, |root_glob| {
+                    entry.depth() != 1
+                        || root_glob.matched(entry.file_name(), is_dir).is_whitelist()
+                })
+            });
+        }
+
+        Ok(builder)
     }
 }
 
@@ -282,33 +1682,75 @@ impl Display for Tree {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let ctx = self.context();
 
-        if ctx.report {
+        if ctx.no_render {
+            return write!(f, "{}", self.stats());
+        }
+
+        // Lines of code and risky permissions are printed in the tree itself unless JSON is
+        // requested.
+        if ctx.report.is_some() && (ctx.json || !(ctx.code_report() || ctx.permissions_report())) {
             let report = self.report();
             return write!(f, "{report}");
         }
 
-        let root = self.root;
+        if ctx.du_compat {
+            let du_compat = self.du_compat();
+            return write!(f, "{du_compat}");
+        }
+
+        if ctx.porcelain {
+            return write!(f, "{}", self.porcelain());
+        }
+
+        match ctx.output {
+            Output::Prometheus => return write!(f, "{}", self.prometheus()),
+            Output::Treemap => return write!(f, "{}", Treemap::new(self)),
+            Output::Svg => return write!(f, "{}", Svg::new(self)),
+            // SVG images of the tree are drawn from its terminal output.
+            Output::Tree | Output::TreeSvg | Output::Html => (),
+        }
+
+        if ctx.total_only {
+            return self.fmt_total(f);
+        }
+
         let inner = self.inner();
         let level = self.level();
-        let show_count = ctx.count;
         let mut file_count_data = vec![];
 
-        let mut descendants = root.descendants(inner).skip(1).peekable();
+        if ctx.grid {
+            return self.fmt_grid(f);
+        }
+
+        let mut descendants = self.root.descendants(inner).skip(1).peekable();
 
-        let mut display_node = |node_id: NodeId, prefix: &str| -> fmt::Result {
+        let (rows, widths) = self.column_rows();
+
+        if ctx.header {
+            column::display_header(f, ctx, &widths)?;
+        }
+
+        let mut display_node = |node_id: NodeId, prefix: &str, preview: &str| -> fmt::Result {
             let node = inner[node_id].get();
 
-            node.display(f, prefix, ctx)?;
+            match rows.get(&node_id) {
+                Some(cells) => node.display_columns(f, prefix, ctx, cells, &widths)?,
+                None => node.display(f, prefix, ctx)?,
+            }
 
-            if show_count {
+            if ctx.count {
                 let count = Self::compute_file_count(node_id, inner);
                 file_count_data.push(count);
             }
 
-            writeln!(f)
+            writeln!(f)?;
+            node.display_preview(f, preview, ctx)
         };
 
-        display_node(root, "")?;
+        display_node(self.root, "", "")?;
+
+        let mut lines = 1;
+        let mut truncated = None;
 
         let mut prefix_components = vec![""];
 
@@ -338,7 +1780,18 @@ impl Display for Tree {
             let prefix = current_prefix_components.join("");
 
             if current_node_depth <= level {
-                display_node(current_node_id, &prefix)?;
+                if ctx.max_lines.map_or(false, |max_lines| lines >= max_lines) {
+                    let remaining = descendants
+                        .filter(|node_id| inner[*node_id].get().depth() <= level)
+                        .count();
+
+                    truncated = Some(remaining + 1);
+                    break;
+                }
+
+                let preview = self.preview_prefix(&widths, &prefix_components, last_sibling);
+                display_node(current_node_id, &prefix, &preview)?;
+                lines += 1;
             }
 
             if let Some(next_id) = descendants.peek() {
@@ -360,8 +1813,143 @@ impl Display for Tree {
             }
         }
 
+        if let Some(remaining) = truncated {
+            self.fmt_truncated(f, remaining)?;
+        }
+
+        self.fmt_summary(f, file_count_data)
+    }
+}
+
+impl Tree {
+    /// Prefix of the lines of a file previewed with `--preview`, which hang off the branch that
+    /// children of the file would, given the `prefix_components` of its parent.
+    fn preview_prefix(&self, widths: &[usize], prefix_components: &[&str], last: bool) -> String {
+        let ctx = self.context();
+
+        if ctx.preview.is_none() {
+            return String::new();
+        }
+
+        let offset = " ".repeat(Node::branch_offset(ctx, widths));
+        let branch = if last {
+            styles::SEP
+        } else {
+            styles::get_tree_theme().get("vt").unwrap()
+        };
+
+        format!(
+            "{offset}{}{branch}{PREVIEW_INDENT}",
+            prefix_components.concat()
+        )
+    }
+
+    /// Writes the tree with `--grid` followed by the usual summary.
+    fn fmt_grid(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let inner = self.inner();
+        let level = self.level();
+
+        write!(f, "{}", self.grid())?;
+
+        let file_count_data = if self.context().count {
+            self.root
+                .descendants(inner)
+                .filter(|node_id| inner[*node_id].get().depth() <= level)
+                .map(|node_id| Self::compute_file_count(node_id, inner))
+                .collect()
+        } else {
+            vec![]
+        };
+
+        self.fmt_summary(f, file_count_data)
+    }
+
+    /// Writes the disk usage of the root directory followed by a tab and its path in place of the
+    /// tree with `--total-only`, followed by the usual summary. Like `--du-compat` the size is
+    /// left uncolored for the sake of scripts.
+    fn fmt_total(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let ctx = self.context();
+        let inner = self.inner();
+        let root = self.root;
+
+        let size = inner[root].get().file_size().cloned().unwrap_or_else(|| {
+            FileSize::new(
+                0,
+                ctx.disk_usage(),
+                ctx.prefix,
+                ctx.scale,
+                ctx.rounding,
+                ctx.unit,
+            )
+        });
+
+        let path = ctx.display_path(ctx.dir());
+
+        let HumanReadableComponents { size, unit } = size.human_readable_components();
+
+        writeln!(f, "{size} {unit}\t{}", path.display())?;
+
+        let file_count_data = if ctx.count {
+            root.descendants(inner)
+                .map(|node_id| Self::compute_file_count(node_id, inner))
+                .collect()
+        } else {
+            vec![]
+        };
+
+        self.fmt_summary(f, file_count_data)
+    }
+
+    /// Writes the line noting how many entries were left out because of `--max-lines`. It leads
+    /// the summary so, like the lines of the summary, it's left for the next to break from.
+    fn fmt_truncated(&self, f: &mut Formatter<'_>, remaining: usize) -> fmt::Result {
+        let ctx = self.context();
+        let ellipsis = if ctx.accessible { "..." } else { "\u{2026}" };
+        let marker = tr_args(
+            "output-truncated",
+            [
+                ("count", FluentValue::from(remaining)),
+                ("digits", FluentValue::from(ctx.group_digits(remaining))),
+                ("ellipsis", FluentValue::from(ellipsis)),
+            ],
+        );
+
+        write!(f, "{}", styles::get_muted_style().paint(marker))
+    }
+
+    /// Writes what follows the tree itself: the number of files, the totals of `--report=code`,
+    /// `--stats`, and the entries that are gone since the previous `--delta`.
+    fn fmt_summary(&self, f: &mut Formatter<'_>, file_count_data: Vec<FileCount>) -> fmt::Result {
+        let ctx = self.context();
+        let inner = self.inner();
+        let root = self.root;
+
         if !file_count_data.is_empty() {
-            write!(f, "\n{}", FileCount::from(file_count_data))?;
+            write!(f, "\n{}", FileCount::from(file_count_data).format(ctx))?;
+        }
+
+        if ctx.code_report() {
+            let lines = inner[root].get().lines().unwrap_or_default();
+            write!(f, "\n{lines}")?;
+        }
+
+        if ctx.stats {
+            write!(f, "\n{}", self.stats())?;
+        }
+
+        if !self.removed.is_empty() {
+            for (path, bytes) in &self.removed {
+                let size = FileSize::new(
+                    *bytes,
+                    ctx.disk_usage(),
+                    ctx.prefix,
+                    ctx.scale,
+                    ctx.rounding,
+                    ctx.unit,
+                );
+                let gone = Color::Red.paint(tr("delta-gone"));
+                write!(f, "\n{} ({}) {gone}", path.display(), size.format(None))?;
+            }
         }
 
         Ok(())
@@ -7,15 +7,48 @@ pub type NodeComparator = dyn Fn(&Node, &Node) -> Ordering;
 
 /// Yields function pointer to the appropriate `Node` comparator.
 pub fn comparator(ctx: &Context) -> Option<Box<NodeComparator>> {
-    let sort_type = ctx.sort;
+    let base = chained_comparator(ctx.sort());
+
+    // The order in which entries are read varies from one traversal to the next.
+    let base = if ctx.deterministic {
+        Some(path_tiebreaker(base))
+    } else {
+        base
+    };
 
     if ctx.dirs_first {
-        return Some(Box::new(move |a, b| {
-            dir_comparator(a, b, base_comparator(sort_type))
-        }));
+        return Some(Box::new(move |a, b| dir_comparator(a, b, base.as_deref())));
+    }
+
+    base
+}
+
+/// Chains the comparators of each of `sort_types` such that each one only decides the order of
+/// [Node]s that all previous ones consider equal.
+fn chained_comparator(sort_types: &[SortType]) -> Option<Box<NodeComparator>> {
+    let comparators = sort_types
+        .iter()
+        .filter_map(|sort_type| base_comparator(*sort_type))
+        .collect::<Vec<_>>();
+
+    if comparators.len() <= 1 {
+        return comparators.into_iter().next();
     }
 
-    base_comparator(sort_type)
+    Some(Box::new(move |a, b| {
+        comparators.iter().fold(Ordering::Equal, |ordering, cmp| {
+            ordering.then_with(|| cmp(a, b))
+        })
+    }))
+}
+
+/// Orders [Node]s that `base` considers equal, or all of them if there's no `base`, by path.
+fn path_tiebreaker(base: Option<Box<NodeComparator>>) -> Box<NodeComparator> {
+    Box::new(move |a, b| {
+        base.as_ref()
+            .map_or(Ordering::Equal, |cmp| cmp(a, b))
+            .then_with(|| a.path().cmp(b.path()))
+    })
 }
 
 /// Grabs the comparator for two non-dir type [Node]s.
@@ -0,0 +1,193 @@
+use super::Node;
+use crate::{
+    i18n::tr,
+    render::{
+        context::{column::Column, duration, Context},
+        disk_usage::file_size::HumanReadableComponents,
+        styles,
+    },
+};
+use ansi_term::Style;
+use std::fmt::{self, Formatter};
+
+impl Column {
+    /// Name of the column printed in the header row with `--header`.
+    const fn header(self) -> &'static str {
+        match self {
+            Self::Perms => "Permissions",
+            Self::Owner => "Owner",
+            Self::Size => "Size",
+            Self::Mtime => "Modified",
+            Self::Cmd => "Output",
+            Self::Name => "Name",
+        }
+    }
+
+    /// Plain text of the cell of `node` in this column. Names are written along with the branches
+    /// of the tree by [`Node::display_columns`] instead.
+    fn cell(self, node: &Node, ctx: &Context) -> String {
+        match self {
+            Self::Perms => format!(
+                "{}{}",
+                node.file_type_identifier().unwrap_or("-"),
+                // Unknown permissions are marked as `ls -l` does.
+                node.metadata().map_or_else(
+                    || String::from("?????????"),
+                    crate::fs::permissions::symbolic
+                )
+            ),
+            Self::Owner => node
+                .metadata()
+                .and_then(crate::fs::owner::owner)
+                .unwrap_or_else(|| String::from("-")),
+            Self::Size => node.file_size().map_or_else(String::new, |fs| {
+                let HumanReadableComponents { size, unit } = fs.human_readable_components();
+
+                if ctx.show_bytes {
+                    format!(
+                        "{size} {unit} ({} {})",
+                        ctx.group_digits(fs.bytes),
+                        tr("unit-b")
+                    )
+                } else {
+                    format!("{size} {unit}")
+                }
+            }),
+            Self::Mtime => node
+                .modified()
+                .map_or_else(|| String::from("-"), duration::timestamp),
+            Self::Cmd => node
+                .command_output()
+                .map_or_else(|| String::from("-"), str::to_owned),
+            Self::Name => String::new(),
+        }
+    }
+}
+
+/// Computes the cells of every column selected with `--columns` for `node`.
+pub fn cells(node: &Node, ctx: &Context) -> Vec<String> {
+    ctx.columns
+        .iter()
+        .map(|column| column.cell(node, ctx))
+        .collect()
+}
+
+/// Computes how wide each column selected with `--columns` has to be to fit the cells of every
+/// row, as well as the header if requested. The name column also spans the branches of the tree.
+pub fn widths<'a>(
+    rows: impl Iterator<Item = (&'a Node, &'a [String])>,
+    ctx: &Context,
+) -> Vec<usize> {
+    let mut widths = ctx
+        .columns
+        .iter()
+        .map(|column| if ctx.header { column.header().len() } else { 0 })
+        .collect::<Vec<_>>();
+
+    for (node, cells) in rows {
+        for ((column, cell), width) in ctx.columns.iter().zip(cells).zip(&mut widths) {
+            let cell_width = if *column == Column::Name {
+                node.name_width(ctx)
+            } else {
+                cell.chars().count()
+            };
+
+            *width = (*width).max(cell_width);
+        }
+    }
+
+    widths
+}
+
+/// Writes the header row naming each of the columns selected with `--columns`, aligned with the
+/// cells beneath.
+pub fn display_header(f: &mut Formatter, ctx: &Context, widths: &[usize]) -> fmt::Result {
+    let last = ctx.columns.len().saturating_sub(1);
+
+    for (i, (column, width)) in ctx.columns.iter().zip(widths).enumerate() {
+        if i > 0 {
+            write!(f, "  ")?;
+        }
+
+        let header = column.header();
+
+        // Padding is left out of the underline.
+        let (left, right) = match column {
+            Column::Size => (width.saturating_sub(header.len()), 0),
+            _ if i == last => (0, 0),
+            _ => (0, width.saturating_sub(header.len())),
+        };
+
+        let header = Style::new().underline().paint(header);
+
+        write!(f, "{:left$}{header}{:right$}", "", "")?;
+    }
+
+    writeln!(f)
+}
+
+impl Node {
+    /// Number of columns taken up by the branches of the tree leading up to the [Node], its icon,
+    /// and its name.
+    fn name_width(&self, ctx: &Context) -> usize {
+        let prefix_width = self.depth() * styles::SEP.len();
+        let icon_width = if self.icon().is_empty() { 0 } else { 2 };
+        let link_width = self.symlink_target_file_name().map_or(0, |name| {
+            name.to_string_lossy().chars().count() + super::link_arrow(ctx).chars().count() + 2
+        });
+
+        let rank_width = self.rank_prefix().map_or(0, |rank| rank.len());
+
+        prefix_width + icon_width + rank_width + self.display_name(ctx).chars().count() + link_width
+    }
+
+    /// Alternative to [`Node::display`] that prints the [Node] as a row made up of the columns
+    /// selected with `--columns`, each padded to the corresponding entry of `widths`. Annotations
+    /// are printed at the end of the row.
+    pub fn display_columns(
+        &self,
+        f: &mut Formatter,
+        prefix: &str,
+        ctx: &Context,
+        cells: &[String],
+        widths: &[usize],
+    ) -> fmt::Result {
+        let last = ctx.columns.len().saturating_sub(1);
+
+        for (i, ((column, cell), width)) in ctx.columns.iter().zip(cells).zip(widths).enumerate() {
+            if i > 0 {
+                write!(f, "  ")?;
+            }
+
+            match column {
+                Column::Name => {
+                    let icon = self.icon();
+                    let icon_padding = if icon.is_empty() {
+                        0
+                    } else {
+                        icon.chars().count() + 1
+                    };
+
+                    let file_name = self.display_name(ctx);
+                    let styled_name = self
+                        .stylize_link_name(&file_name, ctx)
+                        .unwrap_or_else(|| self.stylize(&file_name));
+
+                    let rank = self.styled_rank();
+
+                    write!(f, "{prefix}{icon:<icon_padding$}{rank}{styled_name}")?;
+
+                    if i < last {
+                        let padding = width.saturating_sub(self.name_width(ctx));
+                        write!(f, "{:padding$}", "")?;
+                    }
+                }
+                Column::Size => write!(f, "{cell:>width$}")?,
+                _ if i == last => write!(f, "{cell}")?,
+                _ => write!(f, "{cell:<width$}")?,
+            }
+        }
+
+        self.display_annotations(f, ctx)
+    }
+}
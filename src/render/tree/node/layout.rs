@@ -1,4 +1,7 @@
-use crate::render::{context::Context, disk_usage::file_size::FileSize};
+use crate::render::{
+    context::Context,
+    disk_usage::file_size::{FileSize, HumanReadableComponents},
+};
 
 /// Simple struct to define location to put the `FileSize` while printing a `Node`
 #[derive(Copy, Clone, Default)]
@@ -13,22 +16,75 @@ impl SizeLocation {
     pub fn default_string(self, ctx: &Context) -> String {
         match self {
             Self::Right => String::new(),
-            Self::Left => FileSize::empty_string(ctx),
+            Self::Left => vec![FileSize::empty_string(ctx); ctx.disk_usage.len()].join(" "),
         }
     }
 
-    /// Given a [`FileSize`], style it in the expected way for its printing location
-    pub fn format(self, size: &FileSize) -> String {
+    /// Given the [`FileSize`] in each kind of disk usage, style them in the expected way for
+    /// their printing location
+    pub fn format(self, sizes: &[FileSize], ctx: &Context) -> String {
         match self {
-            Self::Right => format!("({})", size.format(false)),
-            Self::Left => size.format(true),
+            Self::Right => {
+                let sizes = sizes
+                    .iter()
+                    .map(|size| {
+                        if ctx.show_bytes {
+                            format!("{} {}", size.format(None), size.exact(ctx, false))
+                        } else {
+                            size.format(None)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                format!("({})", sizes.join(", "))
+            }
+            Self::Left => sizes
+                .iter()
+                .map(|size| {
+                    let formatted = size.format(Some(ctx.size_width()));
+
+                    if ctx.show_bytes {
+                        format!("{formatted} {}", size.exact(ctx, true))
+                    } else {
+                        formatted
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+
+    /// Number of columns occupied by the sizes, including the space separating them from the
+    /// rest of the line, sans ANSI escape sequences.
+    pub fn width(self, sizes: &[FileSize], ctx: &Context) -> usize {
+        match self {
+            Self::Right if sizes.is_empty() => 0,
+            Self::Right => {
+                let widths = sizes
+                    .iter()
+                    .map(|size| {
+                        let exact = if ctx.show_bytes {
+                            ctx.group_digits(size.bytes).chars().count() + 5
+                        } else {
+                            0
+                        };
+
+                        let HumanReadableComponents { size, unit } =
+                            size.human_readable_components();
+                        size.len() + unit.len() + 1 + exact
+                    })
+                    .sum::<usize>();
+
+                widths + 2 * (sizes.len() - 1) + 3
+            }
+            Self::Left => (FileSize::empty_string_len(ctx) + 1) * ctx.disk_usage.len(),
         }
     }
 }
 
 impl From<&Context> for SizeLocation {
     fn from(ctx: &Context) -> Self {
-        if ctx.size_left && !ctx.suppress_size {
+        if ctx.size_left && !ctx.suppress_size && !ctx.code_report() {
             Self::Left
         } else {
             Self::Right
@@ -1,33 +1,67 @@
 use crate::{
-    fs::inode::Inode,
-    icons::{self, icon_from_ext, icon_from_file_name, icon_from_file_type},
+    fs::{
+        allocation::Allocation,
+        code::LineCount,
+        compression::Compressibility,
+        content::{Content, TextProfile},
+        extent::{SharedExtent, Storage},
+        inode::Inode,
+        permissions::Issue,
+        reparse::ReparsePoint,
+    },
+    icons::{self, icon_from_dir_name, icon_from_ext, icon_from_file_name, icon_from_file_type},
     render::{
-        context::Context,
-        disk_usage::file_size::{DiskUsage, FileSize},
-        styles::get_ls_colors,
-        tree::error::Error,
+        context::{
+            color_by::ColorBy, column::Column, link_size::LinkSize, report::ReportKind, Context,
+        },
+        disk_usage::file_size::{DiskUsage, FileSize, HumanReadableComponents},
+        styles::{self, get_ls_colors},
+        tree::{error::Error, snapshot::Delta},
     },
 };
 use ansi_term::Color;
 use ansi_term::Style;
-use ignore::DirEntry;
+use ignore::{DirEntry, Error as IgnoreError};
 use layout::SizeLocation;
 use lscolors::Style as LS_Style;
 use std::{
     borrow::Cow,
+    collections::HashSet,
     convert::TryFrom,
     ffi::OsStr,
     fmt::{self, Formatter},
-    fs::{FileType, Metadata},
+    fs::{self, FileType, Metadata},
+    io,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 /// Ordering and sorting rules for [Node].
 pub mod cmp;
 
+/// Metadata printed in columns of their own with `--columns`.
+pub mod column;
+
 /// For determining orientation of disk usage information for [Node].
 mod layout;
 
+/// Names are never truncated to fewer columns than this, ellipsis included, unless there is
+/// simply no room.
+const MIN_NAME_WIDTH: usize = 8;
+
+/// Badge of directories marked by `--stale` unless another is provided via `--stale-badge`.
+const STALE_BADGE: &str = "[stale]";
+
+/// Usage of a top-level directory at or beyond this percentage of `--quota` is shown in yellow.
+const QUOTA_WARNING_PERCENT: u64 = 75;
+
+/// Usage of a top-level directory at or beyond this percentage of `--quota` is shown in red.
+const QUOTA_CRITICAL_PERCENT: u64 = 90;
+
+/// Compressed files are only annotated if they occupy at most this percentage of their apparent
+/// size on disk.
+const COMPRESSED_ANNOTATION_PERCENT: u64 = 80;
+
 /// A node of [`Tree`] that can be created from a [DirEntry]. Any filesystem I/O and
 /// relevant system calls are expected to complete after initialization. A `Node` when `Display`ed
 /// uses ANSI colors determined by the file-type and [`LS_COLORS`].
@@ -37,30 +71,87 @@ mod layout;
 #[derive(Debug)]
 pub struct Node {
     dir_entry: DirEntry,
-    metadata: Metadata,
-    file_size: Option<FileSize>,
+    metadata: Result<Metadata, io::ErrorKind>,
+    file_sizes: Vec<FileSize>,
     style: Style,
     icon: String,
     symlink_target: Option<PathBuf>,
+    allocation: Option<Allocation>,
+    shared_extents: Vec<SharedExtent>,
+    storage: Option<Storage>,
+    reparse_point: Option<ReparsePoint>,
+    reflinked: Option<u64>,
+    delta: Option<Delta>,
+    similar: Option<(usize, Vec<FileSize>)>,
+    estimate_margin: Option<u64>,
+    stale: bool,
+    submodule: bool,
+    seen_above: bool,
+    resumed: bool,
+    link_depth_exceeded: bool,
+    note: Option<String>,
+    author: Option<String>,
+    command_output: Option<String>,
+    not_checked_out: usize,
+    lines: Option<LineCount>,
+    compressibility: Option<Compressibility>,
+    taken: Option<SystemTime>,
+    content: Option<Content>,
+    preview: Option<Vec<String>>,
+    text_profile: Option<TextProfile>,
+    permission_issues: Vec<Issue>,
+    collapsed_name: Option<String>,
+    depth_offset: usize,
+    rank: Option<usize>,
 }
 
 impl Node {
     /// Initializes a new [Node].
     pub const fn new(
         dir_entry: DirEntry,
-        metadata: Metadata,
-        file_size: Option<FileSize>,
+        metadata: Result<Metadata, io::ErrorKind>,
+        file_sizes: Vec<FileSize>,
         style: Style,
         icon: String,
         symlink_target: Option<PathBuf>,
+        allocation: Option<Allocation>,
+        shared_extents: Vec<SharedExtent>,
+        reparse_point: Option<ReparsePoint>,
     ) -> Self {
         Self {
             dir_entry,
             metadata,
-            file_size,
+            file_sizes,
             style,
             icon,
             symlink_target,
+            allocation,
+            shared_extents,
+            storage: None,
+            reparse_point,
+            reflinked: None,
+            delta: None,
+            similar: None,
+            estimate_margin: None,
+            stale: false,
+            submodule: false,
+            seen_above: false,
+            resumed: false,
+            link_depth_exceeded: false,
+            note: None,
+            author: None,
+            command_output: None,
+            not_checked_out: 0,
+            lines: None,
+            compressibility: None,
+            taken: None,
+            content: None,
+            preview: None,
+            text_profile: None,
+            permission_issues: vec![],
+            collapsed_name: None,
+            depth_offset: 0,
+            rank: None,
         }
     }
 
@@ -70,14 +161,64 @@ impl Node {
         self.dir_entry.file_name()
     }
 
-    /// Get depth level of [Node].
+    /// Gets the [Metadata] of the [Node] if it could be read.
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref().ok()
+    }
+
+    /// Gets the kind of error the [Metadata] of the [Node] couldn't be read because of, if any.
+    pub fn metadata_error(&self) -> Option<io::ErrorKind> {
+        self.metadata.as_ref().err().copied()
+    }
+
+    /// Time of last modification, if available on this platform.
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.metadata()?.modified().ok()
+    }
+
+    /// When the file was taken as per its EXIF data if it's an image that has any, or else when it
+    /// was last modified. Only read when filtering with `--taken-after` or `--taken-before`.
+    pub fn taken(&self) -> Option<SystemTime> {
+        self.taken.or_else(|| self.modified())
+    }
+
+    /// Get depth level of [Node], not counting ancestors merged into others with
+    /// `--collapse-single-child`.
     pub fn depth(&self) -> usize {
-        self.dir_entry.depth()
+        self.dir_entry.depth() - self.depth_offset
     }
 
-    /// Gets the underlying [Inode] of the entry.
+    /// Merges the only entry of this directory, named `child_name`, into it so that both are
+    /// printed on one line. The descendants of the child are expected to be moved to this [Node]
+    /// and to have their depth decremented.
+    pub fn merge_single_child(&mut self, child_name: &str, ctx: &Context) {
+        let name = format!("{}/{child_name}", self.display_name(ctx));
+
+        self.collapsed_name = Some(name);
+    }
+
+    /// Decrements the depth of [Node] by `levels` as that many of its ancestors were merged into
+    /// others.
+    pub fn decrement_depth(&mut self, levels: usize) {
+        self.depth_offset += levels;
+    }
+
+    /// Gets the underlying [Inode] of the entry. On Windows the metadata gathered while reading a
+    /// directory lacks the file index so the entry has to be opened to determine it.
     pub fn inode(&self) -> Option<Inode> {
-        Inode::try_from(&self.metadata).ok()
+        let inode = self.metadata().and_then(|md| Inode::try_from(md).ok());
+
+        inode.or_else(|| {
+            cfg!(windows)
+                .then(|| fs::symlink_metadata(self.path()).ok())
+                .flatten()
+                .and_then(|md| Inode::try_from(&md).ok())
+        })
+    }
+
+    /// Whether the entry is an NTFS directory junction.
+    pub fn is_junction(&self) -> bool {
+        self.reparse_point == Some(ReparsePoint::Junction)
     }
 
     /// Converts `OsStr` to `String`; if fails does a lossy conversion replacing non-Unicode
@@ -88,6 +229,44 @@ impl Node {
             .map_or_else(|| self.file_name().to_string_lossy(), Cow::from)
     }
 
+    /// Name to display for the [Node] which is its [`Node::file_name_lossy`] unless it is the root
+    /// and a label was provided for it. The name of a root without one, such as a drive or network
+    /// share, is its path.
+    pub fn display_name<'a>(&'a self, ctx: &'a Context) -> Cow<'a, str> {
+        if let Some(ref name) = self.collapsed_name {
+            return Cow::from(name.as_str());
+        }
+
+        if self.depth() == 0 {
+            if let Some(label) = ctx.root_label() {
+                return label;
+            }
+
+            if ctx.strip_verbatim {
+                let path = ctx.display_path(Path::new(self.file_name()));
+                return Cow::from(path.to_string_lossy().into_owned());
+            }
+        }
+
+        self.file_name_lossy()
+    }
+
+    /// Returns `true` if node is a sparse file.
+    pub fn is_sparse(&self) -> bool {
+        self.allocation == Some(Allocation::Sparse)
+    }
+
+    /// Returns `true` if node is a cloud placeholder whose contents aren't stored locally.
+    pub fn is_placeholder(&self) -> bool {
+        self.allocation == Some(Allocation::Placeholder)
+    }
+
+    /// Returns `true` if node is a regular file without any contents.
+    pub fn is_empty_file(&self) -> bool {
+        self.metadata()
+            .map_or(false, |md| md.is_file() && md.len() == 0)
+    }
+
     /// Returns `true` if node is a directory.
     pub fn is_dir(&self) -> bool {
         self.file_type().map_or(false, |ft| ft.is_dir())
@@ -123,14 +302,215 @@ impl Node {
         self.dir_entry.path()
     }
 
-    /// Gets 'file_size'.
-    pub const fn file_size(&self) -> Option<&FileSize> {
-        self.file_size.as_ref()
+    /// Gets the size of the [Node] in the kind of disk usage that takes precedence, being the
+    /// first provided to `--disk-usage`.
+    pub fn file_size(&self) -> Option<&FileSize> {
+        self.file_sizes.first()
+    }
+
+    /// Gets the sizes of the [Node] in each kind of disk usage provided to `--disk-usage`, in
+    /// order. Empty if the [Node] has no size.
+    pub fn file_sizes(&self) -> &[FileSize] {
+        &self.file_sizes
     }
 
-    /// Sets `file_size`.
-    pub fn set_file_size(&mut self, size: FileSize) {
-        self.file_size = Some(size);
+    /// Adds each of `bytes` to the size in the respective kind of disk usage provided to
+    /// `--disk-usage`, starting from nothing in kinds the node has no size in yet.
+    pub fn add_file_sizes(&mut self, bytes: &[u64], ctx: &Context) {
+        for (kind, (bytes, disk_usage)) in bytes.iter().zip(&ctx.disk_usage).enumerate() {
+            match self.file_sizes.get_mut(kind) {
+                Some(file_size) => file_size.bytes += bytes,
+                None => self.file_sizes.push(FileSize::new(
+                    *bytes,
+                    *disk_usage,
+                    ctx.prefix,
+                    ctx.scale,
+                    ctx.rounding,
+                    ctx.unit,
+                )),
+            }
+        }
+    }
+
+    /// Sets the sizes in each kind of disk usage provided to `--disk-usage`.
+    pub fn set_file_sizes(&mut self, sizes: Vec<FileSize>) {
+        self.file_sizes = sizes;
+    }
+
+    /// Whether this [Node] has extents that may be shared with other files through reflinks.
+    pub fn has_shared_extents(&self) -> bool {
+        !self.shared_extents.is_empty()
+    }
+
+    /// Deducts the extents this [Node] shares with files already in `seen` from its size so that
+    /// reflinked data is only counted once, then adds the remainder of its shared extents to
+    /// `seen`.
+    pub fn deduct_shared_extents(&mut self, seen: &mut HashSet<SharedExtent>) {
+        let mut deducted = 0;
+
+        for extent in &self.shared_extents {
+            if !seen.insert(*extent) {
+                deducted += extent.length;
+            }
+        }
+
+        if deducted == 0 {
+            return;
+        }
+
+        let logical = self.metadata().map_or(0, Metadata::len);
+
+        for file_size in &mut self.file_sizes {
+            // Extents are mapped in logical bytes which take up proportionally less on disk if
+            // compressed, or more if they partially fill blocks.
+            let delta = match file_size.disk_usage() {
+                DiskUsage::Logical => deducted,
+                DiskUsage::Physical => {
+                    let scaled = u128::from(deducted) * u128::from(file_size.bytes)
+                        / u128::from(logical.max(1));
+
+                    u64::try_from(scaled).unwrap_or(u64::MAX)
+                }
+            };
+
+            file_size.bytes = file_size.bytes.saturating_sub(delta);
+        }
+
+        self.reflinked = Some(deducted);
+    }
+
+    /// Whether the node is a text or binary file, if it was classified.
+    pub const fn content(&self) -> Option<Content> {
+        self.content
+    }
+
+    /// Encoding and line endings of a text file detected with `--report=encodings`, if any.
+    pub const fn text_profile(&self) -> Option<TextProfile> {
+        self.text_profile
+    }
+
+    /// Risky permissions of the file detected with `--report=permissions`.
+    pub fn permission_issues(&self) -> &[Issue] {
+        &self.permission_issues
+    }
+
+    /// Gets the lines of code, comments, and blanks counted with `--report=code`, if any.
+    pub const fn lines(&self) -> Option<LineCount> {
+        self.lines
+    }
+
+    /// Gets how large the file is estimated to be once compressed with `--report=compressibility`,
+    /// if it's a regular file that could be read.
+    pub const fn compressibility(&self) -> Option<Compressibility> {
+        self.compressibility
+    }
+
+    /// Sets the lines of code, comments, and blanks of a directory.
+    pub fn set_lines(&mut self, lines: LineCount) {
+        self.lines = Some(lines);
+    }
+
+    /// Gets the change in disk usage since the previous snapshot, if any.
+    pub const fn delta(&self) -> Option<Delta> {
+        self.delta
+    }
+
+    /// Sets `delta`.
+    pub fn set_delta(&mut self, delta: Delta) {
+        self.delta = Some(delta);
+    }
+
+    /// Records that `count` structurally identical siblings of combined `sizes`, in each kind of
+    /// disk usage, were collapsed into this [Node].
+    pub fn set_similar(&mut self, count: usize, sizes: Vec<FileSize>) {
+        self.similar = Some((count, sizes));
+    }
+
+    /// Marks the size of this [Node] as estimated by `--approx` to within `margin` bytes.
+    pub fn set_estimate_margin(&mut self, margin: u64) {
+        self.estimate_margin = Some(margin);
+    }
+
+    /// Marks this [Node] as a directory with nothing modified within `--stale`.
+    pub fn set_stale(&mut self) {
+        self.stale = true;
+    }
+
+    /// Marks this [Node] as the working tree of a git submodule.
+    pub fn set_submodule(&mut self) {
+        self.submodule = true;
+    }
+
+    /// Marks this [Node] as a directory that appears elsewhere in the tree, through a bind mount or
+    /// a followed symlink, whose contents are only accounted for there.
+    pub fn set_seen_above(&mut self) {
+        self.seen_above = true;
+    }
+
+    /// Marks this [Node] as a directory whose size was carried over from the checkpoint the
+    /// traversal was resumed from with `--resume` rather than traversed again.
+    pub fn set_resumed(&mut self) {
+        self.resumed = true;
+    }
+
+    /// Marks this [Node] as a symlink to a directory that wasn't descended into as it's nested
+    /// within more followed symlinks than `--max-link-depth` allows.
+    pub fn set_link_depth_exceeded(&mut self) {
+        self.link_depth_exceeded = true;
+    }
+
+    /// Sets the position of this [Node] among the largest files of the tree with `--rank`.
+    pub fn set_rank(&mut self, rank: usize) {
+        self.rank = Some(rank);
+    }
+
+    /// The rank of this [Node] as printed in front of its name e.g. `#1 `.
+    fn rank_prefix(&self) -> Option<String> {
+        self.rank.map(|rank| format!("#{rank} "))
+    }
+
+    /// [`Node::rank_prefix`] highlighted for printing, or nothing if the [Node] isn't ranked.
+    fn styled_rank(&self) -> String {
+        self.rank_prefix().map_or_else(String::new, |rank| {
+            Color::Red.bold().paint(rank).to_string()
+        })
+    }
+
+    /// Sets the note provided for this [Node] via `--annotate`.
+    pub fn set_note(&mut self, note: String) {
+        self.note = Some(note);
+    }
+
+    /// The author who last committed to the file in git, if it's tracked and authors were looked
+    /// up.
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// Sets the author who last committed to the file in git.
+    pub fn set_author(&mut self, author: String) {
+        self.author = Some(author);
+    }
+
+    /// What `--column-cmd` printed for the entry, if it was run and printed anything.
+    pub fn command_output(&self) -> Option<&str> {
+        self.command_output.as_deref()
+    }
+
+    /// Sets what `--column-cmd` printed for the entry.
+    pub fn set_command_output(&mut self, output: String) {
+        self.command_output = Some(output);
+    }
+
+    /// Sets the first lines of the file read with `--preview`.
+    pub fn set_preview(&mut self, preview: Vec<String>) {
+        self.preview = Some(preview);
+    }
+
+    /// Records a file beneath this directory that git's sparse-checkout left out of the working
+    /// tree, and which has no nearer ancestor on disk.
+    pub fn add_not_checked_out(&mut self) {
+        self.not_checked_out += 1;
     }
 
     /// Sets 'style'.
@@ -148,19 +528,24 @@ impl Node {
     /// [`LS_COLORS`]: crate::render::styles::LS_COLORS
     fn stylize(&self, entity: &str) -> String {
         self.style().foreground.map_or_else(
-            || entity.to_string(),
+            || self.style().paint(entity).to_string(),
             |fg| fg.bold().paint(entity).to_string(),
         )
     }
 
     /// Stylizes symlink name for display.
-    fn stylize_link_name(&self) -> Option<String> {
-        self.symlink_target_file_name().map(|name| {
-            let file_name = self.file_name_lossy();
-            let styled_name = self.stylize(&file_name);
-            let target_name = Color::Red.paint(format!("\u{2192} {}", name.to_string_lossy()));
-            format!("{styled_name} {target_name}")
-        })
+    fn stylize_link_name(&self, file_name: &str, ctx: &Context) -> Option<String> {
+        self.symlink_target_file_name()
+            .map(|name| self.stylize_link(file_name, &name.to_string_lossy(), ctx))
+    }
+
+    /// Stylizes `file_name` followed by an arrow pointing to the name of the `target` of the
+    /// symlink.
+    fn stylize_link(&self, file_name: &str, target: &str, ctx: &Context) -> String {
+        let styled_name = self.stylize(file_name);
+        let target_name =
+            styles::get_link_target_style().paint(format!("{} {target}", link_arrow(ctx)));
+        format!("{styled_name} {target_name}")
     }
 
     /// General method for printing a `Node`. The `Display` (and `ToString`) traits are not used,
@@ -182,37 +567,388 @@ impl Node {
     pub fn display(&self, f: &mut Formatter, prefix: &str, ctx: &Context) -> fmt::Result {
         let size_loc = SizeLocation::from(ctx);
 
-        let size = self.file_size().map_or_else(
-            || size_loc.default_string(ctx),
-            |size| size_loc.format(size),
+        // Lines of code take the place of disk usage with `--report=code`.
+        let (mut size, size_width) = self.lines.map_or_else(
+            || {
+                let sizes = self.file_sizes();
+
+                let size = if sizes.is_empty() {
+                    size_loc.default_string(ctx)
+                } else {
+                    size_loc.format(sizes, ctx)
+                };
+
+                (size, size_loc.width(sizes, ctx))
+            },
+            |lines| {
+                let code = format!("{} loc", lines.code);
+                let width = code.len() + 3;
+                (format!("({})", Color::Yellow.paint(code)), width)
+            },
         );
 
+        let icon = self.icon();
+
+        // One column of padding regardless of how many bytes the icon is made up of.
+        let icon_padding = if icon.is_empty() {
+            0
+        } else {
+            icon.chars().count() + 1
+        };
+
+        let mut file_name = self.display_name(ctx);
+        let mut link_target = self.symlink_target_file_name().map(OsStr::to_string_lossy);
+
+        let mut annotations = String::new();
+        self.display_annotations(&mut annotations, ctx)?;
+
+        if let Some(width) = ctx.width {
+            let prefix_width = self.depth() * styles::SEP.len();
+            let icon_width = if icon.is_empty() { 0 } else { 2 };
+            let link_width = link_target.as_ref().map_or(0, |target| {
+                target.chars().count() + link_arrow(ctx).chars().count() + 2
+            });
+
+            let rank_width = self.rank_prefix().map_or(0, |rank| rank.len());
+
+            let reserved =
+                prefix_width + icon_width + rank_width + styles::visible_width(&annotations);
+            let mut available = width.saturating_sub(reserved + size_width);
+
+            // Disk usage on the right is the first thing to go if the name would otherwise be
+            // illegible; on the left it's kept to preserve alignment.
+            if available.saturating_sub(link_width) < MIN_NAME_WIDTH
+                && matches!(size_loc, SizeLocation::Right)
+            {
+                size.clear();
+                available = width.saturating_sub(reserved);
+            }
+
+            (file_name, link_target) = fit(file_name, link_target, available, ctx);
+        }
+
         let size_padding = if size.is_empty() {
             String::new()
         } else {
             String::from(" ")
         };
 
-        let icon = self.icon();
-
-        let icon_padding = if icon.len() > 1 { icon.len() - 1 } else { 0 };
+        let styled_name = link_target.map_or_else(
+            || self.stylize(&file_name),
+            |target| self.stylize_link(&file_name, &target, ctx),
+        );
 
-        let styled_name = self.stylize_link_name().unwrap_or_else(|| {
-            let file_name = self.file_name_lossy();
-            self.stylize(&file_name)
-        });
+        let rank = self.styled_rank();
 
         match size_loc {
-            SizeLocation::Right => {
-                write!(
-                    f,
-                    "{prefix}{icon:<icon_padding$}{styled_name}{size_padding}{size}"
-                )
+            SizeLocation::Right => write!(
+                f,
+                "{prefix}{icon:<icon_padding$}{rank}{styled_name}{size_padding}{size}{annotations}"
+            ),
+            SizeLocation::Left => write!(
+                f,
+                "{size} {prefix}{icon:<icon_padding$}{rank}{styled_name}{annotations}"
+            ),
+        }
+    }
+
+    /// Number of columns that precede the branches of the tree on every line, taken up by disk
+    /// usage on the left or by the columns before the name given the `widths` of `--columns`.
+    pub fn branch_offset(ctx: &Context, widths: &[usize]) -> usize {
+        if !widths.is_empty() {
+            return ctx
+                .columns
+                .iter()
+                .zip(widths)
+                .take_while(|(column, _)| **column != Column::Name)
+                .map(|(_, width)| width + 2)
+                .sum();
+        }
+
+        match SizeLocation::from(ctx) {
+            SizeLocation::Left => SizeLocation::Left.width(&[], ctx),
+            SizeLocation::Right => 0,
+        }
+    }
+
+    /// Writes the first lines of the file read with `--preview` dimmed, each on a line of its own
+    /// beneath the [Node] following `prefix`.
+    pub fn display_preview(&self, f: &mut Formatter, prefix: &str, ctx: &Context) -> fmt::Result {
+        let Some(ref preview) = self.preview else {
+            return Ok(());
+        };
+
+        let available = ctx
+            .width
+            .map(|width| width.saturating_sub(styles::visible_width(prefix)));
+
+        for line in preview {
+            let line = available.map_or_else(
+                || Cow::from(line.as_str()),
+                |width| truncate(Cow::from(line.as_str()), width),
+            );
+
+            writeln!(f, "{prefix}{}", styles::get_muted_style().paint(line))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the bracketed annotations that follow the name of the [Node] such as whether it's
+    /// sparse or stale, each preceded by a space.
+    fn display_annotations(&self, f: &mut impl fmt::Write, ctx: &Context) -> fmt::Result {
+        if let Some(marker) = self.type_marker().filter(|_| ctx.accessible) {
+            write!(f, " {marker}")?;
+        }
+
+        if let Some(kind) = self.metadata_error() {
+            write!(
+                f,
+                " {}",
+                Color::Yellow.paint(format!("[{} {kind}]", warning_sign(ctx)))
+            )?;
+        }
+
+        if self.is_junction() {
+            write!(f, " {}", styles::get_muted_style().paint("[junction]"))?;
+        }
+
+        if let Some(annotation) = self.allocation_annotation(ctx) {
+            write!(f, " {annotation}")?;
+        }
+
+        if !self.permission_issues.is_empty() {
+            let issues = self
+                .permission_issues
+                .iter()
+                .map(Issue::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            write!(f, " {}", Color::Red.paint(format!("[{issues}]")))?;
+        }
+
+        if let Some(annotation) = self.quota_annotation(ctx) {
+            write!(f, " {annotation}")?;
+        }
+
+        // Shared data is already accounted for with --reflinks.
+        if let Some(shared) = self
+            .storage
+            .map(|storage| storage.shared)
+            .filter(|_| !ctx.reflinks)
+        {
+            if shared > 0 {
+                let shared = Self::human_readable(shared, ctx);
+                let cloned = format!("[cloned: {shared} shared with other files]");
+                write!(f, " {}", Color::Blue.paint(cloned))?;
             }
-            SizeLocation::Left => {
-                write!(f, "{size} {prefix}{icon:<icon_padding$}{styled_name}")
+        }
+
+        if let Some(bytes) = self.reflinked {
+            let reflinked = format!("[reflink: {} shared]", Self::human_readable(bytes, ctx));
+            write!(f, " {}", Color::Blue.paint(reflinked))?;
+        }
+
+        if let Some((count, ref sizes)) = self.similar {
+            let sizes = sizes
+                .iter()
+                .map(|size| {
+                    let HumanReadableComponents { size, unit } = size.human_readable_components();
+                    format!("{size} {unit}")
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let similar = format!("\u{d7} {count} similar ({sizes} total)");
+            write!(f, " {}", styles::get_muted_style().paint(similar))?;
+        }
+
+        if let Some(delta) = self.delta() {
+            write!(f, " {}", delta.styled(ctx))?;
+        }
+
+        if let Some(annotation) = self.estimate_annotation() {
+            write!(f, " {annotation}")?;
+        }
+
+        if self.stale {
+            let badge = ctx.stale_badge.as_deref().unwrap_or(STALE_BADGE);
+            write!(f, " {}", Color::Yellow.paint(badge))?;
+        }
+
+        if self.submodule {
+            write!(f, " {}", Color::Blue.paint("[submodule]"))?;
+        }
+
+        if self.link_depth_exceeded {
+            write!(f, " {}", Color::Yellow.paint("[link depth exceeded]"))?;
+        }
+
+        if self.seen_above {
+            write!(f, " {}", styles::get_muted_style().paint("[seen above]"))?;
+        }
+
+        if self.resumed {
+            write!(f, " {}", styles::get_muted_style().paint("[resumed]"))?;
+        }
+
+        if let Some(author) = self.author().filter(|_| ctx.git_authors) {
+            write!(f, " {}", Color::Cyan.paint(format!("[{author}]")))?;
+        }
+
+        if self.not_checked_out > 0 {
+            let count = self.not_checked_out;
+            let files = if count == 1 { "file" } else { "files" };
+            let badge = format!("[sparse: {count} {files} not checked out]");
+            write!(f, " {}", Color::Purple.paint(badge))?;
+        }
+
+        if let Some(ref note) = self.note {
+            write!(
+                f,
+                " {}",
+                styles::get_muted_style().paint(format!("# {note}"))
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Type of the [Node] spelled out for `--accessible` so that it isn't conveyed by color alone.
+    /// Regular files that aren't executable have none.
+    fn type_marker(&self) -> Option<&'static str> {
+        let marker = match self.file_type_identifier()? {
+            "d" => "[dir]",
+            "l" => "[link]",
+            "p" => "[fifo]",
+            "s" => "[socket]",
+            "c" => "[char device]",
+            "b" => "[block device]",
+            _ if self.is_executable() => "[exec]",
+            _ => return None,
+        };
+
+        Some(marker)
+    }
+
+    /// Whether the [Node] is a file that anyone may execute.
+    #[cfg(unix)]
+    fn is_executable(&self) -> bool {
+        use std::os::unix::fs::PermissionsExt;
+
+        self.metadata().map_or(false, |md| {
+            md.is_file() && md.permissions().mode() & 0o111 != 0
+        })
+    }
+
+    /// Whether the [Node] is a file that anyone may execute, which isn't told by its permissions
+    /// outside of unix.
+    #[cfg(not(unix))]
+    const fn is_executable(&self) -> bool {
+        false
+    }
+
+    /// Annotation for directories whose size was estimated by `--approx` showing the margin of
+    /// error as a percentage of the estimate.
+    fn estimate_annotation(&self) -> Option<String> {
+        let margin = self.estimate_margin?;
+        let bytes = self.file_size().map_or(0, |fs| fs.bytes);
+
+        let percent = if bytes == 0 {
+            0.0
+        } else {
+            margin as f64 * 100.0 / bytes as f64
+        };
+
+        let annotation = format!("[estimate \u{b1}{percent:.1}%]");
+
+        Some(styles::get_muted_style().paint(annotation).to_string())
+    }
+
+    /// `bytes` in the human-readable units of the first kind of disk usage being reported.
+    fn human_readable(bytes: u64, ctx: &Context) -> String {
+        let HumanReadableComponents { size, unit } = FileSize::new(
+            bytes,
+            ctx.disk_usage(),
+            ctx.prefix,
+            ctx.scale,
+            ctx.rounding,
+            ctx.unit,
+        )
+        .human_readable_components();
+
+        format!("{size} {unit}")
+    }
+
+    /// Annotation for sparse files, and for compressed files that take up significantly less
+    /// space on disk than their apparent size, showing both their allocated and apparent size.
+    /// Cloud placeholders show the size of the contents that have yet to be downloaded.
+    fn allocation_annotation(&self, ctx: &Context) -> Option<String> {
+        let human_readable = |bytes| Self::human_readable(bytes, ctx);
+
+        let metadata = self.metadata()?;
+        let allocated = crate::fs::allocation::allocated_bytes(metadata).unwrap_or(0);
+        let apparent = metadata.len();
+
+        let annotation = match self.allocation? {
+            Allocation::Sparse => Color::Yellow.paint(format!(
+                "[sparse: {} allocated of {}]",
+                human_readable(allocated),
+                human_readable(apparent)
+            )),
+            Allocation::Compressed
+                if allocated * 100 <= apparent * COMPRESSED_ANNOTATION_PERCENT =>
+            {
+                Color::Cyan.paint(format!(
+                    "[compressed: {} on disk of {}]",
+                    human_readable(allocated),
+                    human_readable(apparent)
+                ))
+            }
+            // Extents compressed by btrfs count in full toward the size on disk that's reported.
+            Allocation::Compressed => {
+                let encoded = self.storage.map_or(0, |storage| storage.encoded);
+
+                if encoded == 0 {
+                    return None;
+                }
+
+                Color::Cyan.paint(format!(
+                    "[compressed: {} of {} stored compressed]",
+                    human_readable(encoded),
+                    human_readable(apparent)
+                ))
             }
+            Allocation::Placeholder => Color::Purple.paint(format!(
+                "[cloud: {} not stored locally]",
+                human_readable(apparent)
+            )),
+        };
+
+        Some(annotation.to_string())
+    }
+
+    /// Annotation for top-level directories showing their usage as a percentage of `--quota`,
+    /// colored by how close they are to exceeding it.
+    fn quota_annotation(&self, ctx: &Context) -> Option<String> {
+        let quota = ctx.quota?;
+
+        if self.depth() != 1 || !self.is_dir() {
+            return None;
         }
+
+        let bytes = self.file_size().map_or(0, |fs| fs.bytes);
+        let percent = (bytes * 100).checked_div(quota).unwrap_or(100);
+
+        let color = if percent >= QUOTA_CRITICAL_PERCENT {
+            Color::Red
+        } else if percent >= QUOTA_WARNING_PERCENT {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+
+        Some(color.paint(format!("[{percent}% of quota]")).to_string())
     }
 
     /// Unix file identifiers that you'd find in the `ls -l` command.
@@ -261,6 +997,183 @@ impl Node {
         Some(iden)
     }
 
+    /// Whether an entry of [FileType] has a size of its own that counts toward disk usage. Files
+    /// always do whereas directories only do so if explicitly requested, as is the case with
+    /// `du`.
+    fn counts_toward_size(file_type: FileType, ctx: &Context) -> bool {
+        !ctx.suppress_size
+            && (file_type.is_file() || (file_type.is_dir() && ctx.include_dir_entry_size))
+    }
+
+    /// Computes the size of the entry at `path` in each kind of disk usage provided to
+    /// `--disk-usage` using `size_of`. Empty if any of them can't be computed.
+    fn sizes<F>(ctx: &Context, size_of: F) -> Vec<FileSize>
+    where
+        F: Fn(DiskUsage) -> Option<FileSize>,
+    {
+        ctx.disk_usage
+            .iter()
+            .map(|disk_usage| size_of(*disk_usage))
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default()
+    }
+
+    /// Computes the size of the entry at `path` as either its logical or physical size.
+    fn size(
+        path: &Path,
+        metadata: &Metadata,
+        disk_usage: DiskUsage,
+        ctx: &Context,
+    ) -> Option<FileSize> {
+        match disk_usage {
+            DiskUsage::Logical => Some(FileSize::logical(
+                metadata,
+                ctx.prefix,
+                ctx.scale,
+                ctx.rounding,
+                ctx.unit,
+            )),
+            DiskUsage::Physical => FileSize::physical(
+                path,
+                metadata,
+                ctx.prefix,
+                ctx.scale,
+                ctx.rounding,
+                ctx.unit,
+            ),
+        }
+    }
+
+    /// Computes the size of the symlink at `path` whose own metadata is `metadata` as determined
+    /// by `--link-sizes`. Links to directories and broken links never have a target size.
+    fn link_size(
+        path: &Path,
+        metadata: &Metadata,
+        disk_usage: DiskUsage,
+        ctx: &Context,
+    ) -> Option<FileSize> {
+        if ctx.suppress_size {
+            return None;
+        }
+
+        match ctx.link_sizes {
+            LinkSize::Own => Self::size(path, metadata, disk_usage, ctx),
+            LinkSize::Target => {
+                let target_metadata = fs::metadata(path).ok().filter(Metadata::is_file)?;
+                Self::size(path, &target_metadata, disk_usage, ctx)
+            }
+            LinkSize::None => None,
+        }
+    }
+
+    /// Style of the name of the entry at `path` at `depth`: high-contrast with `--accessible`, or
+    /// else as per `--color-by` if not by type and as per `LS_COLORS` otherwise, dimmed by depth
+    /// with `--fade`.
+    fn compute_style(path: &Path, metadata: &Metadata, depth: usize, ctx: &Context) -> Style {
+        if ctx.accessible {
+            return styles::high_contrast(metadata.is_dir());
+        }
+
+        let colored = match ctx.color_by {
+            ColorBy::Type => None,
+            // Files modified in the future as per their clocks count as recent.
+            ColorBy::Age => metadata
+                .modified()
+                .ok()
+                .map(|modified| {
+                    SystemTime::now()
+                        .duration_since(modified)
+                        .unwrap_or_default()
+                })
+                .map(styles::age_shade),
+            ColorBy::Owner => crate::fs::owner::uid(metadata).map(styles::owner_color),
+        };
+
+        let style = colored.unwrap_or_else(|| {
+            get_ls_colors()
+                .style_for_path_with_metadata(path, Some(metadata))
+                .map(LS_Style::to_ansi_term_style)
+                .unwrap_or_default()
+        });
+
+        if ctx.fade {
+            styles::fade(style, depth)
+        } else {
+            style
+        }
+    }
+
+    /// A [Node] for `dir_entry` whose metadata couldn't be read, which is printed in place along
+    /// with the kind of `error` rather than left out so that the tree doesn't misrepresent what's
+    /// there. Nothing else is known about it.
+    fn unreadable(dir_entry: DirEntry, error: &IgnoreError, ctx: &Context) -> Self {
+        let kind = error
+            .io_error()
+            .map_or(io::ErrorKind::Other, io::Error::kind);
+
+        tracing::warn!(path = %dir_entry.path().display(), error = %error, "failed to read metadata");
+
+        let style = Style::default();
+
+        let icon = if ctx.icons {
+            let symlink_target = crate::fs::symlink_target(&dir_entry);
+            Self::compute_icon(&dir_entry, symlink_target.as_deref(), &style)
+        } else {
+            String::new()
+        };
+
+        Self::new(
+            dir_entry,
+            Err(kind),
+            vec![],
+            style,
+            icon,
+            None,
+            None,
+            vec![],
+            None,
+        )
+    }
+
+    /// Icon of `dir_entry` colored as per `style`, falling back to the default icon if none of the
+    /// more specific ones apply.
+    fn compute_icon(dir_entry: &DirEntry, symlink_target: Option<&Path>, style: &Style) -> String {
+        let path = dir_entry.path();
+        let file_type = dir_entry.file_type();
+
+        // Links are told apart by what they point to, following them if need be.
+        let link_icon = symlink_target.map(|_| {
+            let target_is_dir = fs::metadata(path).ok().map(|md| md.is_dir());
+            icons::icon_from_symlink(target_is_dir)
+        });
+
+        let plain_icon = link_icon
+            .or_else(|| {
+                file_type.and_then(|ft| Self::icon_from_dir_name(ft, dir_entry.file_name()))
+            })
+            .or_else(|| file_type.as_ref().and_then(Self::icon_from_file_type))
+            .or_else(|| {
+                symlink_target.map_or_else(|| Self::icon_from_path(path), Self::icon_from_path)
+            })
+            .or_else(|| Self::icon_from_file_name(dir_entry.file_name()))
+            .unwrap_or_else(icons::get_default_icon);
+
+        style.foreground.map_or_else(
+            || String::from(plain_icon),
+            |fg| fg.bold().paint(plain_icon).to_string(),
+        )
+    }
+
+    /// Tries to compute which icon to use for a directory from its name. Well-known directories
+    /// such as `.git`, `node_modules`, or `Downloads` get their own icons.
+    fn icon_from_dir_name(file_type: FileType, file_name: &OsStr) -> Option<&str> {
+        if file_type.is_dir() {
+            icon_from_dir_name(file_name)
+        } else {
+            None
+        }
+    }
+
     /// Tries to compute which icon to use from [FileType]. Directories and links for example have
     /// special icons based on file-type as opposed to extension.
     fn icon_from_file_type(file_type: &FileType) -> Option<&str> {
@@ -279,6 +1192,33 @@ impl Node {
     }
 }
 
+impl Node {
+    /// How the file at `path` is stored on disk, which is only inspected in full when asked about
+    /// physical disk usage or sparse files as it takes opening the file; otherwise only cloud
+    /// placeholders are detected.
+    fn allocation(
+        path: &Path,
+        md: &Metadata,
+        storage: Option<Storage>,
+        ctx: &Context,
+    ) -> Option<Allocation> {
+        if ctx.is_mirrored() {
+            return None;
+        }
+
+        if !ctx.inspects_allocation() {
+            return Allocation::placeholder(md);
+        }
+
+        // Compression on btrfs can only be told from the extents of files.
+        Allocation::of(path, md).or_else(|| {
+            storage
+                .filter(|storage| storage.encoded > 0)
+                .map(|_| Allocation::Compressed)
+        })
+    }
+}
+
 impl TryFrom<(DirEntry, &Context)> for Node {
     type Error = Error;
 
@@ -289,51 +1229,180 @@ impl TryFrom<(DirEntry, &Context)> for Node {
 
         let symlink_target = crate::fs::symlink_target(&dir_entry);
 
-        let metadata = dir_entry.metadata()?;
+        let metadata = match dir_entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => return Ok(Self::unreadable(dir_entry, &e, ctx)),
+        };
 
-        let style = get_ls_colors()
-            .style_for_path_with_metadata(path, Some(&metadata))
-            .map(LS_Style::to_ansi_term_style)
-            .unwrap_or_default();
+        let style = Self::compute_style(path, &metadata, dir_entry.depth(), ctx);
 
         let file_type = dir_entry.file_type();
 
-        let file_size = match file_type {
-            Some(ref ft) if ft.is_file() && !ctx.suppress_size => match ctx.disk_usage {
-                DiskUsage::Logical => Some(FileSize::logical(&metadata, ctx.prefix, ctx.scale)),
-                DiskUsage::Physical => FileSize::physical(path, &metadata, ctx.prefix, ctx.scale),
-            },
-            _ => None,
+        // Mirrored files are all sparse locally, which says nothing about them.
+        let storage = (ctx.measures_physical() && !ctx.is_mirrored())
+            .then(|| crate::fs::extent::storage(path, &metadata).ok())
+            .flatten();
+
+        let allocation = Self::allocation(path, &metadata, storage, ctx);
+
+        let reparse_point = crate::fs::reparse::reparse_point(path, &metadata);
+
+        // Files whose extents can't be mapped are simply counted in full.
+        let shared_extents = if ctx.reflinks {
+            crate::fs::extent::shared_extents(path, &metadata).unwrap_or_default()
+        } else {
+            vec![]
         };
 
-        let icon = if ctx.icons {
-            let plain_icon = file_type
-                .as_ref()
-                .and_then(Self::icon_from_file_type)
-                .or_else(|| {
-                    symlink_target.as_ref().map_or_else(
-                        || Self::icon_from_path(path),
-                        |target| Self::icon_from_path(target),
-                    )
-                })
-                .or_else(|| Self::icon_from_file_name(dir_entry.file_name()))
-                .unwrap_or_else(icons::get_default_icon);
+        let file_sizes = match file_type {
+            Some(ft) if ft.is_symlink() => Self::sizes(ctx, |disk_usage| {
+                Self::link_size(path, &metadata, disk_usage, ctx)
+            }),
 
-            style.foreground.map_or_else(
-                || String::from(plain_icon),
-                |fg| fg.bold().paint(plain_icon).to_string(),
-            )
+            // Placeholders take up no space locally until their contents are downloaded.
+            Some(ft) if allocation == Some(Allocation::Placeholder) => {
+                if Self::counts_toward_size(ft, ctx) {
+                    Self::sizes(ctx, |disk_usage| {
+                        Some(FileSize::new(
+                            0,
+                            disk_usage,
+                            ctx.prefix,
+                            ctx.scale,
+                            ctx.rounding,
+                            ctx.unit,
+                        ))
+                    })
+                } else {
+                    vec![]
+                }
+            }
+
+            Some(ft) if Self::counts_toward_size(ft, ctx) => Self::sizes(ctx, |disk_usage| {
+                Self::size(path, &metadata, disk_usage, ctx)
+            }),
+            _ => vec![],
+        };
+
+        let icon = if ctx.icons {
+            Self::compute_icon(&dir_entry, symlink_target.as_deref(), &style)
         } else {
             String::new()
         };
 
-        Ok(Self::new(
+        let lines = (ctx.code_report() && metadata.is_file())
+            .then(|| crate::fs::code::count(path))
+            .flatten();
+
+        // Unreadable files are left unclassified.
+        let content = (ctx.classify_content() && metadata.is_file())
+            .then(|| Content::of(path).ok())
+            .flatten();
+
+        let text_profile = (ctx.report == Some(ReportKind::Encodings) && metadata.is_file())
+            .then(|| TextProfile::of(path).ok().flatten())
+            .flatten();
+
+        // Unreadable files are left out of the estimate.
+        let compressibility = (ctx.report == Some(ReportKind::Compressibility)
+            && metadata.is_file())
+        .then(|| Compressibility::of(path, metadata.len()).ok())
+        .flatten();
+
+        let taken = (ctx.filters_taken() && metadata.is_file())
+            .then(|| crate::fs::exif::taken(path))
+            .flatten();
+
+        let permission_issues = if ctx.permissions_report() {
+            crate::fs::permissions::issues(path, &metadata)
+        } else {
+            vec![]
+        };
+
+        let mut node = Self::new(
             dir_entry,
-            metadata,
-            file_size,
+            Ok(metadata),
+            file_sizes,
             style,
             icon,
             symlink_target,
-        ))
+            allocation,
+            shared_extents,
+            reparse_point,
+        );
+
+        node.lines = lines;
+        node.compressibility = compressibility;
+        node.taken = taken;
+        node.content = content;
+        node.text_profile = text_profile;
+        node.permission_issues = permission_issues;
+        node.storage = storage;
+
+        Ok(node)
+    }
+}
+
+/// Shortens `name` to at most `width` columns, marking the truncation with an ellipsis. The
+/// ellipsis is kept even if there's no room at all so that it's never left out entirely.
+fn truncate(name: Cow<'_, str>, width: usize) -> Cow<'_, str> {
+    if name.chars().count() <= width {
+        return name;
+    }
+
+    let mut truncated = name
+        .chars()
+        .take(width.saturating_sub(1))
+        .collect::<String>();
+    truncated.push('\u{2026}');
+
+    Cow::from(truncated)
+}
+
+/// Truncates `name` and the name of the symlink `target`, if any, to fit within `width` columns.
+/// The name takes precedence and the target is dropped along with its arrow if there's no room
+/// left for it.
+fn fit<'a>(
+    name: Cow<'a, str>,
+    target: Option<Cow<'a, str>>,
+    width: usize,
+    ctx: &Context,
+) -> (Cow<'a, str>, Option<Cow<'a, str>>) {
+    let Some(target) = target else {
+        return (truncate(name, width), None);
+    };
+
+    let arrow_width = link_arrow(ctx).chars().count() + 2;
+    let link_width = target.chars().count() + arrow_width;
+
+    let name = truncate(
+        name,
+        width
+            .saturating_sub(link_width)
+            .max(width.min(MIN_NAME_WIDTH)),
+    );
+
+    let left = width.saturating_sub(name.chars().count() + arrow_width);
+    let target = (left > 0).then(|| truncate(target, left));
+
+    (name, target)
+}
+
+/// Sign in front of the kind of error of entries whose metadata couldn't be read, spelled out with
+/// `--accessible` as screen readers may skip it otherwise.
+const fn warning_sign(ctx: &Context) -> &'static str {
+    if ctx.accessible {
+        "warning:"
+    } else {
+        "\u{26a0}"
+    }
+}
+
+/// Arrow between the name of a symlink and that of its target, spelled out in ASCII with
+/// `--accessible` as screen readers may skip it otherwise.
+const fn link_arrow(ctx: &Context) -> &'static str {
+    if ctx.accessible {
+        "->"
+    } else {
+        "\u{2192}"
     }
 }
@@ -0,0 +1,155 @@
+use super::Tree;
+use std::{
+    fmt::{self, Display, Write},
+    path::{Component, Path},
+};
+
+/// Unit tests for the escaping of paths.
+#[cfg(test)]
+mod test;
+
+/// First line of the output identifying the version of the schema. Records of a given version
+/// never change; anything that would is released as a new version instead.
+pub const HEADER: &str = "# erdtree porcelain v1";
+
+/// Output meant for scripts with `--porcelain` that, unlike the tree, stays the same between
+/// releases. After [HEADER] every entry is printed in the order of the tree as one record of
+/// tab-separated fields:
+///
+/// ```text
+/// type<TAB>depth<TAB>bytes<TAB>path
+/// ```
+///
+/// - `type` is one of `d`, `-`, `l`, `p`, `s`, `c`, or `b` as in `ls -l`, or `?` if unknown.
+/// - `depth` is how deep the entry is beneath the root, which is at `0`.
+/// - `bytes` is the disk usage in bytes, or `-` for entries without one.
+/// - `path` is relative to the root, which is `.`, with components separated by `/` and escaped
+///   as per [escape].
+pub struct Porcelain<'a> {
+    tree: &'a Tree,
+}
+
+impl<'a> Porcelain<'a> {
+    /// Initializes a [Self] with a reference to [Tree].
+    pub const fn new(tree: &'a Tree) -> Self {
+        Self { tree }
+    }
+}
+
+impl Display for Porcelain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+        let max_depth = ctx.level().unwrap_or(usize::MAX);
+
+        let root_path = tree[root].get().path();
+
+        writeln!(f, "{HEADER}")?;
+
+        for node_id in root.descendants(tree) {
+            let node = tree[node_id].get();
+
+            if node.depth() > max_depth {
+                continue;
+            }
+
+            let file_type = node.file_type_identifier().unwrap_or("?");
+
+            let bytes = node
+                .file_size()
+                .map_or_else(|| String::from("-"), |fs| fs.bytes.to_string());
+
+            let path = node
+                .path()
+                .strip_prefix(root_path)
+                .map_or_else(|_| escape(node.path()), escape);
+
+            writeln!(f, "{file_type}\t{}\t{bytes}\t{path}", node.depth())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Joins the components of `path` with `/` and escapes them so that the result fits on a single
+/// line without tabs: a backslash is written `\\`, a tab `\t`, a newline `\n`, a carriage return
+/// `\r`, any other ASCII control character as well as any byte that isn't valid UTF-8 `\xHH` in
+/// lowercase hex, and everything else as is. An empty path is written `.`.
+pub fn escape(path: &Path) -> String {
+    let mut escaped = String::new();
+
+    for component in path.components() {
+        if !escaped.is_empty() && !escaped.ends_with('/') {
+            escaped.push('/');
+        }
+
+        match component {
+            Component::RootDir => escaped.push('/'),
+            Component::CurDir => escaped.push('.'),
+            Component::ParentDir => escaped.push_str(".."),
+            Component::Prefix(prefix) => escape_bytes(&bytes(prefix.as_os_str()), &mut escaped),
+            Component::Normal(name) => escape_bytes(&bytes(name), &mut escaped),
+        }
+    }
+
+    if escaped.is_empty() {
+        escaped.push('.');
+    }
+
+    escaped
+}
+
+/// Appends `raw` to `escaped` as described by [escape].
+fn escape_bytes(raw: &[u8], escaped: &mut String) {
+    let mut rest = raw;
+
+    while !rest.is_empty() {
+        let (valid, invalid) = match std::str::from_utf8(rest) {
+            Ok(valid) => (valid, 0),
+            Err(e) => {
+                let valid = std::str::from_utf8(&rest[..e.valid_up_to()]).unwrap_or_default();
+                let invalid = e
+                    .error_len()
+                    .unwrap_or_else(|| rest.len() - e.valid_up_to());
+                (valid, invalid)
+            }
+        };
+
+        for ch in valid.chars() {
+            match ch {
+                '\\' => escaped.push_str(r"\\"),
+                '\t' => escaped.push_str(r"\t"),
+                '\n' => escaped.push_str(r"\n"),
+                '\r' => escaped.push_str(r"\r"),
+                ch if ch.is_ascii_control() => {
+                    let _ = write!(escaped, r"\x{:02x}", u32::from(ch));
+                }
+                ch => escaped.push(ch),
+            }
+        }
+
+        for byte in &rest[valid.len()..valid.len() + invalid] {
+            let _ = write!(escaped, r"\x{byte:02x}");
+        }
+
+        rest = &rest[valid.len() + invalid..];
+    }
+}
+
+/// Raw bytes of `name`, which are only guaranteed to be preserved on unix.
+#[cfg(unix)]
+fn bytes(name: &std::ffi::OsStr) -> std::borrow::Cow<'_, [u8]> {
+    use std::os::unix::ffi::OsStrExt;
+
+    std::borrow::Cow::Borrowed(name.as_bytes())
+}
+
+/// Raw bytes of `name`, which are only guaranteed to be preserved on unix.
+#[cfg(not(unix))]
+fn bytes(name: &std::ffi::OsStr) -> std::borrow::Cow<'_, [u8]> {
+    match name.to_string_lossy() {
+        std::borrow::Cow::Borrowed(name) => std::borrow::Cow::Borrowed(name.as_bytes()),
+        std::borrow::Cow::Owned(name) => std::borrow::Cow::Owned(name.into_bytes()),
+    }
+}
@@ -0,0 +1,52 @@
+use super::escape;
+use std::path::Path;
+
+#[test]
+fn escape_plain() {
+    assert_eq!(escape(Path::new("src/main.rs")), "src/main.rs");
+}
+
+#[test]
+fn escape_root() {
+    assert_eq!(escape(Path::new("")), ".");
+}
+
+#[test]
+fn escape_unicode() {
+    assert_eq!(
+        escape(Path::new("données/über café.txt")),
+        "données/über café.txt"
+    );
+}
+
+#[test]
+fn escape_separators() {
+    assert_eq!(escape(Path::new("a\tb/c\nd\re")), r"a\tb/c\nd\re");
+}
+
+#[cfg(unix)]
+#[test]
+fn escape_backslash() {
+    assert_eq!(escape(Path::new(r"a\tb")), r"a\\tb");
+}
+
+#[test]
+fn escape_control() {
+    assert_eq!(escape(Path::new("bell\x07del\x7f")), r"bell\x07del\x7f");
+}
+
+#[cfg(unix)]
+#[test]
+fn escape_invalid_utf8() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let name = OsStr::from_bytes(b"latin\xe9-\xff\xfe.txt");
+
+    assert_eq!(escape(Path::new(name)), r"latin\xe9-\xff\xfe.txt");
+}
+
+#[cfg(unix)]
+#[test]
+fn escape_absolute() {
+    assert_eq!(escape(Path::new("/srv/data")), "/srv/data");
+}
@@ -0,0 +1,60 @@
+use super::Tree;
+use std::fmt::{self, Display};
+
+/// Name of the gauge that reports the disk usage of a directory.
+const DIRECTORY_BYTES: &str = "erdtree_directory_bytes";
+
+/// For output in the Prometheus text exposition format, which OpenMetrics is a superset of,
+/// containing the disk usage of the largest directories so that it can be scraped by monitoring.
+pub struct Prometheus<'a> {
+    tree: &'a Tree,
+}
+
+impl<'a> Prometheus<'a> {
+    /// Initializes a [Self] with a reference to [Tree].
+    pub const fn new(tree: &'a Tree) -> Self {
+        Self { tree }
+    }
+}
+
+impl Display for Prometheus<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+        let max_depth = ctx.level().unwrap_or(usize::MAX);
+
+        let mut dirs = root
+            .descendants(tree)
+            .map(|node_id| tree[node_id].get())
+            .filter(|node| node.is_dir() && node.depth() <= max_depth)
+            .map(|node| (node.file_size().map_or(0, |fs| fs.bytes), node.path()))
+            .collect::<Vec<_>>();
+
+        // Largest first with ties broken by path so output is stable between scrapes.
+        dirs.sort_unstable_by(|(a_bytes, a_path), (b_bytes, b_path)| {
+            b_bytes.cmp(a_bytes).then_with(|| a_path.cmp(b_path))
+        });
+
+        writeln!(
+            f,
+            "# HELP {DIRECTORY_BYTES} Disk usage of a directory in bytes."
+        )?;
+        writeln!(f, "# TYPE {DIRECTORY_BYTES} gauge")?;
+
+        for (bytes, path) in dirs.into_iter().take(ctx.top) {
+            let path = escape_label_value(&ctx.display_path(path).to_string_lossy());
+            writeln!(f, "{DIRECTORY_BYTES}{{path=\"{path}\"}} {bytes}")?;
+        }
+
+        write!(f, "# EOF")
+    }
+}
+
+/// Escapes backslashes, double-quotes, and line feeds as required of label values.
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
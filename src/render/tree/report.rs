@@ -1,13 +1,24 @@
-use super::{node::Node, FileCount, Tree};
-use crate::render::disk_usage::{
-    file_size::{FileSize, HumanReadableComponents},
-    units::PrefixKind,
+use super::{count::EncodingCount, node::Node, FileCount, Tree};
+use crate::fs::{
+    code::LineCount, compression::Compressibility, content::Content, permissions::Issue, stat::Stat,
 };
+use crate::render::{
+    context::{duration, report::ReportKind},
+    disk_usage::{
+        file_size::{FileSize, HumanReadableComponents},
+        units::PrefixKind,
+    },
+};
+use clap::ValueEnum;
+use schemars::{schema_for, JsonSchema};
+use serde::Serialize;
 use std::{
+    collections::HashMap,
     convert::AsRef,
     ffi::OsStr,
     fmt::{self, Display},
     path::Path,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 /// For a plain text output of disk usage information akin to `du`.
@@ -15,11 +26,759 @@ pub struct Report<'a> {
     tree: &'a Tree,
 }
 
+/// Version of the schema of the JSON reports, which is bumped whenever a field is removed or
+/// changes in meaning but not when one is added.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Any of the JSON reports along with the version of the schema it conforms to.
+#[derive(Serialize, JsonSchema)]
+struct Versioned<T> {
+    /// Bumped whenever a field is removed or changes in meaning.
+    schema_version: u32,
+    #[serde(flatten)]
+    report: T,
+}
+
+/// Machine-readable counterpart of [Report] printed with `--json`.
+#[derive(Serialize, JsonSchema)]
+struct JsonReport<'a> {
+    root: String,
+    total_bytes: u64,
+    counts: FileCount,
+    entries: Vec<JsonEntry<'a>>,
+}
+
+/// Machine-readable counterpart of the [`ReportKind::Files`] report.
+#[derive(Serialize, JsonSchema)]
+struct JsonFilesReport {
+    directories: Vec<JsonDirectoryCount>,
+}
+
+/// Machine-readable counterpart of the [`ReportKind::Encodings`] report.
+#[derive(Serialize, JsonSchema)]
+struct JsonEncodingsReport {
+    directories: Vec<JsonEncodingCount>,
+}
+
+/// A single directory of [JsonEncodingsReport] along with the counts of its text files.
+#[derive(Serialize, JsonSchema)]
+struct JsonEncodingCount {
+    path: String,
+    #[serde(flatten)]
+    counts: EncodingCount,
+}
+
+/// A single directory of [JsonFilesReport] along with the number of its descendants.
+#[derive(Serialize, JsonSchema)]
+struct JsonDirectoryCount {
+    path: String,
+    #[serde(flatten)]
+    counts: FileCount,
+}
+
+/// Machine-readable counterpart of the [`ReportKind::Code`] report.
+#[derive(Serialize, JsonSchema)]
+struct JsonCodeReport {
+    root: String,
+    #[serde(flatten)]
+    lines: LineCount,
+    entries: Vec<JsonCodeEntry>,
+}
+
+/// A single source file or directory of [JsonCodeReport] along with its lines.
+#[derive(Serialize, JsonSchema)]
+struct JsonCodeEntry {
+    path: String,
+    #[serde(flatten)]
+    lines: LineCount,
+}
+
+/// Machine-readable counterpart of the [`ReportKind::Permissions`] report.
+#[derive(Serialize, JsonSchema)]
+struct JsonPermissionsReport<'a> {
+    root: String,
+    entries: Vec<JsonPermissionsEntry<'a>>,
+}
+
+/// A single file of [JsonPermissionsReport] along with its risky permissions.
+#[derive(Serialize, JsonSchema)]
+struct JsonPermissionsEntry<'a> {
+    path: String,
+    issues: &'a [Issue],
+}
+
+/// Machine-readable counterpart of the [`ReportKind::Empty`] report.
+#[derive(Serialize, JsonSchema)]
+struct JsonEmptyReport<'a> {
+    counts: FileCount,
+    entries: Vec<JsonEmptyEntry<'a>>,
+}
+
+/// A single empty file or directory of [JsonEmptyReport].
+#[derive(Serialize, JsonSchema)]
+struct JsonEmptyEntry<'a> {
+    #[serde(rename = "type")]
+    file_type: &'a str,
+    path: String,
+}
+
+/// Machine-readable counterpart of the [`ReportKind::Ages`] report.
+#[derive(Serialize, JsonSchema)]
+struct JsonAgesReport {
+    oldest: Vec<JsonAgeEntry>,
+    newest: Vec<JsonAgeEntry>,
+}
+
+/// A single file of [JsonAgesReport] along with when it was last modified in seconds since the
+/// epoch.
+#[derive(Serialize, JsonSchema)]
+struct JsonAgeEntry {
+    path: String,
+    modified: u64,
+}
+
+/// Machine-readable counterpart of the [`ReportKind::Authors`] report.
+#[derive(Serialize, JsonSchema)]
+struct JsonAuthorsReport {
+    authors: Vec<JsonAuthorEntry>,
+}
+
+/// A single author of [JsonAuthorsReport] along with the files they last committed to. Untracked
+/// files are attributed to no author.
+#[derive(Serialize, JsonSchema)]
+struct JsonAuthorEntry {
+    author: Option<String>,
+    bytes: u64,
+    files: u64,
+}
+
+/// Machine-readable counterpart of the [`ReportKind::Compressibility`] report.
+#[derive(Serialize, JsonSchema)]
+struct JsonCompressibilityReport {
+    directories: Vec<JsonCompressibilityEntry>,
+}
+
+/// A single directory of [JsonCompressibilityReport] along with the size of its files and how
+/// large they're estimated to be once compressed.
+#[derive(Serialize, JsonSchema)]
+struct JsonCompressibilityEntry {
+    path: String,
+    #[serde(flatten)]
+    estimate: Compressibility,
+}
+
+/// A single entry of [JsonReport].
+#[derive(Serialize, JsonSchema)]
+struct JsonEntry<'a> {
+    #[serde(rename = "type")]
+    file_type: &'a str,
+    path: String,
+    bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<Content>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    stat: Option<Stat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
 impl<'a> Report<'a> {
     /// Initializes a [Self] with a reference to [Tree].
     pub const fn new(tree: &'a Tree) -> Self {
         Self { tree }
     }
+
+    /// Path of `node` relative to the root, or the name of the root if `node` is the root.
+    fn relative_path(&self, node: &Node, base_path: &Path) -> String {
+        let ctx = self.tree.context();
+        let full_path = node.path();
+
+        match full_path.strip_prefix(base_path) {
+            Ok(rel) if rel.as_os_str().is_empty() => node.display_name(ctx).into_owned(),
+            Ok(rel) => rel.to_string_lossy().into_owned(),
+            Err(_) => ctx.display_path(full_path).to_string_lossy().into_owned(),
+        }
+    }
+
+    /// Writes the number of text files beneath each directory by encoding and line endings,
+    /// leaving out directories without any.
+    fn fmt_encodings(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const HEADERS: [&str; 6] = ["utf-8", "utf-16", "latin-1", "lf", "crlf", "mixed"];
+
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+        let max_depth = ctx.level().unwrap_or(usize::MAX);
+
+        let base_path = ctx.base_path();
+
+        let counts = Tree::compute_encoding_counts(root, tree);
+
+        let directories = root
+            .descendants(tree)
+            .filter(|node_id| tree[*node_id].get().depth() <= max_depth)
+            .filter_map(|node_id| counts.get(&node_id).map(|count| (node_id, *count)))
+            .filter(|(_, count)| count.total() > 0)
+            .map(|(node_id, count)| (self.relative_path(tree[node_id].get(), &base_path), count))
+            .collect::<Vec<_>>();
+
+        if ctx.json {
+            let report = JsonEncodingsReport {
+                directories: directories
+                    .into_iter()
+                    .map(|(path, counts)| JsonEncodingCount { path, counts })
+                    .collect(),
+            };
+
+            return write_json(f, report);
+        }
+
+        let columns = |count: &EncodingCount| {
+            [
+                count.utf8,
+                count.utf16,
+                count.latin1,
+                count.lf,
+                count.crlf,
+                count.mixed,
+            ]
+        };
+
+        // The root has the most files of any directory so its counts are the widest.
+        let root_columns = counts.get(&root).map(columns).unwrap_or_default();
+
+        let widths = HEADERS
+            .iter()
+            .zip(root_columns)
+            .map(|(header, count)| header.len().max(ctx.group_digits(count).len()))
+            .collect::<Vec<_>>();
+
+        for (header, width) in HEADERS.iter().zip(&widths) {
+            write!(f, "{header:>width$}  ")?;
+        }
+
+        writeln!(f, " path")?;
+
+        for (path, count) in directories {
+            for (count, width) in columns(&count).iter().zip(&widths) {
+                let count = ctx.group_digits(count);
+                write!(f, "{count:>width$}  ")?;
+            }
+
+            writeln!(f, " {path}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes every zero-byte file and every directory without any entries left after filtering,
+    /// followed by how many of each there are.
+    fn fmt_empty(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+        let max_depth = ctx.level().unwrap_or(usize::MAX);
+
+        let base_path = ctx.base_path();
+
+        let mut counts = FileCount::default();
+
+        let empty = root
+            .descendants(tree)
+            .skip(1)
+            .filter(|node_id| {
+                let node = tree[*node_id].get();
+
+                node.depth() <= max_depth
+                    && (node.is_empty_file()
+                        || (node.is_dir() && node_id.children(tree).next().is_none()))
+            })
+            .map(|node_id| tree[node_id].get())
+            .inspect(|node| counts.update(node))
+            .map(|node| {
+                (
+                    node.file_type_identifier().unwrap_or("-"),
+                    self.relative_path(node, &base_path),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if ctx.json {
+            let report = JsonEmptyReport {
+                counts,
+                entries: empty
+                    .into_iter()
+                    .map(|(file_type, path)| JsonEmptyEntry { file_type, path })
+                    .collect(),
+            };
+
+            return write_json(f, report);
+        }
+
+        for (file_type, path) in empty {
+            writeln!(f, "{file_type}   {path}")?;
+        }
+
+        if counts.num_dirs + counts.num_files == 0 {
+            return write!(f, "no empty files or directories");
+        }
+
+        write!(f, "\n{}", counts.format(ctx))
+    }
+
+    /// Writes the `--top` least recently modified files followed by the `--top` most recently
+    /// modified ones, each from oldest to newest.
+    fn fmt_ages(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+        let max_depth = ctx.level().unwrap_or(usize::MAX);
+
+        let base_path = ctx.base_path();
+
+        let mut files = root
+            .descendants(tree)
+            .skip(1)
+            .map(|node_id| tree[node_id].get())
+            .filter(|node| node.depth() <= max_depth && !node.is_dir() && !node.is_symlink())
+            .filter_map(|node| {
+                node.modified()
+                    .map(|modified| (modified, node.path(), node))
+            })
+            .collect::<Vec<_>>();
+
+        files.sort_unstable_by_key(|(modified, path, _)| (*modified, *path));
+
+        let oldest = &files[..ctx.top.min(files.len())];
+        let newest = &files[files.len().saturating_sub(ctx.top)..];
+
+        if ctx.json {
+            let entries = |files: &[(SystemTime, &Path, &Node)]| {
+                files
+                    .iter()
+                    .map(|(modified, _, node)| JsonAgeEntry {
+                        path: self.relative_path(node, &base_path),
+                        modified: modified
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs(),
+                    })
+                    .collect()
+            };
+
+            let report = JsonAgesReport {
+                oldest: entries(oldest),
+                newest: entries(newest),
+            };
+
+            return write_json(f, report);
+        }
+
+        for (heading, files) in [("oldest", oldest), ("newest", newest)] {
+            if heading == "newest" {
+                writeln!(f)?;
+            }
+
+            writeln!(f, "{heading}")?;
+
+            for (modified, _, node) in files {
+                let timestamp = duration::timestamp(*modified);
+                writeln!(f, "{timestamp}   {}", self.relative_path(node, &base_path))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the disk usage and number of files by the author who last committed to them, in
+    /// descending order of disk usage. Untracked files are listed last.
+    fn fmt_authors(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+        let max_depth = ctx.level().unwrap_or(usize::MAX);
+
+        let mut totals = HashMap::<Option<&str>, (u64, u64)>::new();
+
+        for node in root.descendants(tree).map(|node_id| tree[node_id].get()) {
+            if node.depth() > max_depth || node.is_dir() {
+                continue;
+            }
+
+            let (bytes, files) = totals.entry(node.author()).or_default();
+            *bytes += node.file_size().map_or(0, |fs| fs.bytes);
+            *files += 1;
+        }
+
+        let mut authors = totals.into_iter().collect::<Vec<_>>();
+
+        authors.sort_by(|(author_a, (bytes_a, _)), (author_b, (bytes_b, _))| {
+            author_a
+                .is_none()
+                .cmp(&author_b.is_none())
+                .then_with(|| bytes_b.cmp(bytes_a))
+                .then_with(|| author_a.cmp(author_b))
+        });
+
+        if ctx.json {
+            let report = JsonAuthorsReport {
+                authors: authors
+                    .into_iter()
+                    .map(|(author, (bytes, files))| JsonAuthorEntry {
+                        author: author.map(ToOwned::to_owned),
+                        bytes,
+                        files,
+                    })
+                    .collect(),
+            };
+
+            return write_json(f, report);
+        }
+
+        let size_of = |bytes| {
+            if ctx.human {
+                let HumanReadableComponents { size, unit } = FileSize::new(
+                    bytes,
+                    ctx.disk_usage(),
+                    ctx.prefix,
+                    ctx.scale,
+                    ctx.rounding,
+                    ctx.unit,
+                )
+                .human_readable_components();
+
+                format!("{size} {unit}")
+            } else {
+                format!("{} B", ctx.group_digits(bytes))
+            }
+        };
+
+        let rows = authors
+            .into_iter()
+            .map(|(author, (bytes, files))| {
+                let files = ctx.group_digits(files);
+                (size_of(bytes), files, author.unwrap_or("(untracked)"))
+            })
+            .collect::<Vec<_>>();
+
+        let size_width = rows.iter().map(|(size, ..)| size.len()).max().unwrap_or(0);
+        let files_width = rows
+            .iter()
+            .map(|(_, files, _)| files.len())
+            .max()
+            .unwrap_or(0);
+
+        for (size, files, author) in rows {
+            writeln!(f, "{size:>size_width$}   {files:>files_width$}   {author}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the size of the files beneath each directory along with how large they're estimated
+    /// to be once compressed and by what ratio, leaving out directories without any files.
+    fn fmt_compressibility(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+        let max_depth = ctx.level().unwrap_or(usize::MAX);
+
+        let base_path = ctx.base_path();
+
+        let estimates = Tree::compute_compressibility(root, tree);
+
+        let directories = root
+            .descendants(tree)
+            .filter(|node_id| tree[*node_id].get().depth() <= max_depth)
+            .filter_map(|node_id| estimates.get(&node_id).map(|estimate| (node_id, *estimate)))
+            .filter(|(_, estimate)| estimate.bytes > 0)
+            .map(|(node_id, estimate)| {
+                (
+                    self.relative_path(tree[node_id].get(), &base_path),
+                    estimate,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if ctx.json {
+            let report = JsonCompressibilityReport {
+                directories: directories
+                    .into_iter()
+                    .map(|(path, estimate)| JsonCompressibilityEntry { path, estimate })
+                    .collect(),
+            };
+
+            return write_json(f, report);
+        }
+
+        let size_of = |bytes| {
+            if ctx.human {
+                let HumanReadableComponents { size, unit } = FileSize::new(
+                    bytes,
+                    ctx.disk_usage(),
+                    ctx.prefix,
+                    ctx.scale,
+                    ctx.rounding,
+                    ctx.unit,
+                )
+                .human_readable_components();
+
+                format!("{size} {unit}")
+            } else {
+                format!("{} B", ctx.group_digits(bytes))
+            }
+        };
+
+        let rows = directories
+            .into_iter()
+            .map(|(path, estimate)| {
+                let ratio = format!("{:.2}x", estimate.ratio());
+                (
+                    size_of(estimate.bytes),
+                    size_of(estimate.compressed_bytes),
+                    ratio,
+                    path,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let size_width = rows.iter().map(|(size, ..)| size.len()).max().unwrap_or(0);
+        let compressed_width = rows
+            .iter()
+            .map(|(_, compressed, ..)| compressed.len())
+            .max()
+            .unwrap_or(0);
+        let ratio_width = rows
+            .iter()
+            .map(|(_, _, ratio, _)| ratio.len())
+            .max()
+            .unwrap_or(0);
+
+        for (size, compressed, ratio, path) in rows {
+            writeln!(
+                f,
+                "{size:>size_width$}   {compressed:>compressed_width$}   {ratio:>ratio_width$}   {path}"
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the `--top` directories with the most files in them, including those of all
+    /// subdirectories, in descending order.
+    fn fmt_files(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+
+        let base_path = ctx.base_path();
+
+        let mut directories = Tree::compute_cumulative_file_counts(root, tree)
+            .into_iter()
+            .map(|(node_id, count)| (tree[node_id].get(), count))
+            .collect::<Vec<_>>();
+
+        directories.sort_by(|(node_a, count_a), (node_b, count_b)| {
+            count_b
+                .num_files
+                .cmp(&count_a.num_files)
+                .then_with(|| node_a.path().cmp(node_b.path()))
+        });
+
+        directories.truncate(ctx.top);
+
+        let path_of = |node: &Node| self.relative_path(node, &base_path);
+
+        if ctx.json {
+            let report = JsonFilesReport {
+                directories: directories
+                    .into_iter()
+                    .map(|(node, counts)| JsonDirectoryCount {
+                        path: path_of(node),
+                        counts,
+                    })
+                    .collect(),
+            };
+
+            return write_json(f, report);
+        }
+
+        let width = directories
+            .first()
+            .map_or(1, |(_, count)| ctx.group_digits(count.num_files).len());
+
+        for (node, count) in directories {
+            let num_files = ctx.group_digits(count.num_files);
+            writeln!(f, "{num_files:>width$}   {}", path_of(node))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the lines of code, comments, and blanks of every source file and directory as a
+    /// single JSON object.
+    fn fmt_code_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+        let max_depth = ctx.level().unwrap_or(usize::MAX);
+
+        let base_path = ctx.base_path();
+
+        let root_node = tree[root].get();
+
+        let entries = root
+            .descendants(tree)
+            .skip(1)
+            .map(|node_id| tree[node_id].get())
+            .filter(|node| node.depth() <= max_depth)
+            .map(|node| {
+                let full_path = node.path();
+
+                JsonCodeEntry {
+                    path: full_path
+                        .strip_prefix(&base_path)
+                        .unwrap_or(full_path)
+                        .to_string_lossy()
+                        .into_owned(),
+                    lines: node.lines().unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        let report = JsonCodeReport {
+            root: root_node.display_name(ctx).into_owned(),
+            lines: root_node.lines().unwrap_or_default(),
+            entries,
+        };
+
+        write_json(f, report)
+    }
+
+    /// Writes every file with risky permissions as a single JSON object.
+    fn fmt_permissions_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+        let max_depth = ctx.level().unwrap_or(usize::MAX);
+
+        let base_path = ctx.base_path();
+
+        let entries = root
+            .descendants(tree)
+            .skip(1)
+            .map(|node_id| tree[node_id].get())
+            .filter(|node| node.depth() <= max_depth && !node.permission_issues().is_empty())
+            .map(|node| JsonPermissionsEntry {
+                path: self.relative_path(node, &base_path),
+                issues: node.permission_issues(),
+            })
+            .collect();
+
+        let report = JsonPermissionsReport {
+            root: tree[root].get().display_name(ctx).into_owned(),
+            entries,
+        };
+
+        write_json(f, report)
+    }
+
+    /// Writes the report as a single JSON object; sizes are always in bytes.
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree.inner();
+        let root = self.tree.root();
+        let ctx = self.tree.context();
+        let max_depth = ctx.level().unwrap_or(usize::MAX);
+
+        let base_path = ctx.base_path();
+        let bytes_of = |node: &Node| node.file_size().map_or(0, |fs| fs.bytes);
+
+        let root_node = tree[root].get();
+
+        let counts = root
+            .descendants(tree)
+            .map(|node_id| Tree::compute_file_count(node_id, tree))
+            .collect::<Vec<_>>();
+
+        let entries = root
+            .descendants(tree)
+            .skip(1)
+            .map(|node_id| tree[node_id].get())
+            .filter(|node| node.depth() <= max_depth)
+            .map(|node| {
+                let path = if ctx.file_name {
+                    node.file_name_lossy().into_owned()
+                } else {
+                    let full_path = node.path();
+
+                    full_path
+                        .strip_prefix(&base_path)
+                        .unwrap_or(full_path)
+                        .to_string_lossy()
+                        .into_owned()
+                };
+
+                JsonEntry {
+                    file_type: node.file_type_identifier().unwrap_or("-"),
+                    path,
+                    bytes: bytes_of(node),
+                    content: node.content(),
+                    stat: ctx
+                        .extended_metadata
+                        .then(|| node.metadata().map(Stat::from))
+                        .flatten(),
+                    error: node.metadata_error().map(|kind| kind.to_string()),
+                }
+            })
+            .collect();
+
+        let report = JsonReport {
+            root: root_node.display_name(ctx).into_owned(),
+            total_bytes: bytes_of(root_node),
+            counts: FileCount::from(counts),
+            entries,
+        };
+
+        write_json(f, report)
+    }
+}
+
+/// Writes `report` as JSON tagged with the [SCHEMA_VERSION] it conforms to.
+fn write_json<T: Serialize>(f: &mut fmt::Formatter<'_>, report: T) -> fmt::Result {
+    let versioned = Versioned {
+        schema_version: SCHEMA_VERSION,
+        report,
+    };
+
+    let json = serde_json::to_string(&versioned).map_err(|_| fmt::Error)?;
+
+    write!(f, "{json}")
+}
+
+/// The JSON Schema of the JSON report of `kind` printed with `--schema`, generated from the same
+/// types the report is serialized from.
+pub fn schema(kind: Option<ReportKind>) -> serde_json::Result<String> {
+    let mut schema = match kind {
+        Some(ReportKind::Files) => schema_for!(Versioned<JsonFilesReport>),
+        Some(ReportKind::Code) => schema_for!(Versioned<JsonCodeReport>),
+        Some(ReportKind::Encodings) => schema_for!(Versioned<JsonEncodingsReport>),
+        Some(ReportKind::Permissions) => schema_for!(Versioned<JsonPermissionsReport>),
+        Some(ReportKind::Empty) => schema_for!(Versioned<JsonEmptyReport>),
+        Some(ReportKind::Ages) => schema_for!(Versioned<JsonAgesReport>),
+        Some(ReportKind::Authors) => schema_for!(Versioned<JsonAuthorsReport>),
+        Some(ReportKind::Compressibility) => schema_for!(Versioned<JsonCompressibilityReport>),
+        Some(ReportKind::Du) | None => schema_for!(Versioned<JsonReport>),
+    };
+
+    let name = kind
+        .unwrap_or(ReportKind::Du)
+        .to_possible_value()
+        .map(|value| value.get_name().to_owned())
+        .unwrap_or_default();
+
+    schema.schema.metadata().title = Some(format!("erdtree {name} report v{SCHEMA_VERSION}"));
+
+    serde_json::to_string_pretty(&schema)
 }
 
 impl Display for Report<'_> {
@@ -27,8 +786,24 @@ impl Display for Report<'_> {
         let tree = self.tree.inner();
         let root = self.tree.root();
         let ctx = self.tree.context();
+
+        match ctx.report {
+            Some(ReportKind::Files) => return self.fmt_files(f),
+            Some(ReportKind::Code) => return self.fmt_code_json(f),
+            Some(ReportKind::Encodings) => return self.fmt_encodings(f),
+            Some(ReportKind::Permissions) => return self.fmt_permissions_json(f),
+            Some(ReportKind::Empty) => return self.fmt_empty(f),
+            Some(ReportKind::Ages) => return self.fmt_ages(f),
+            Some(ReportKind::Authors) => return self.fmt_authors(f),
+            Some(ReportKind::Compressibility) => return self.fmt_compressibility(f),
+            Some(ReportKind::Du) | None => (),
+        }
+
+        if ctx.json {
+            return self.fmt_json(f);
+        }
+
         let max_depth = ctx.level().unwrap_or(usize::MAX);
-        let dir = ctx.dir();
         let prefix_kind = ctx.prefix;
         let show_count = ctx.count;
         let mut file_count_data = vec![];
@@ -44,7 +819,7 @@ impl Display for Report<'_> {
             } else {
                 let size = node
                     .file_size()
-                    .map_or_else(|| String::from("0"), |fs| format!("{}", fs.bytes));
+                    .map_or_else(|| String::from("0"), |fs| ctx.group_digits(fs.bytes));
 
                 let unit = String::from("B");
 
@@ -61,7 +836,7 @@ impl Display for Report<'_> {
 
         let total_du_width = root_node
             .file_size()
-            .map_or_else(|| String::from("0"), |fs| format!("{}", fs.bytes))
+            .map_or_else(|| String::from("0"), |fs| ctx.group_digits(fs.bytes))
             .len();
 
         let (total_du, root_unit) = du_info(root_node);
@@ -81,14 +856,14 @@ impl Display for Report<'_> {
         let root_du_info = format!("{total_du} {root_unit}");
         let root_iden = root_node.file_type_identifier().unwrap_or("-");
 
-        let root_name = <OsStr as AsRef<Path>>::as_ref(root_node.file_name()).display();
+        let root_name = root_node.display_name(ctx);
 
         writeln!(
             f,
             "{root_iden}   {root_du_info:>width_du_col$}   {root_name}"
         )?;
 
-        let base_path = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let base_path = ctx.base_path();
 
         for node_id in root.descendants(tree).skip(1) {
             let node = tree[node_id].get();
@@ -121,7 +896,11 @@ impl Display for Report<'_> {
         }
 
         if !file_count_data.is_empty() {
-            write!(f, "\n{}", FileCount::from(file_count_data))?;
+            write!(f, "\n{}", FileCount::from(file_count_data).format(ctx))?;
+        }
+
+        if ctx.stats {
+            write!(f, "\n{}", self.tree.stats())?;
         }
 
         Ok(())
@@ -0,0 +1,221 @@
+use super::node::Node;
+use crate::render::{context::Context, disk_usage::file_size::FileSize};
+use ansi_term::Color;
+use indextree::{Arena, NodeId};
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+const ERDTREE_CACHE_DIR: &str = "ERDTREE_CACHE_DIR";
+const ERDTREE_DIR: &str = "erdtree";
+const CACHE_DIR: &str = ".cache";
+const DATA_DIR: &str = ".local/share";
+const HOME: &str = "HOME";
+const XDG_CACHE_HOME: &str = "XDG_CACHE_HOME";
+const XDG_DATA_HOME: &str = "XDG_DATA_HOME";
+
+/// A compact record of the disk usage of every entry of a [`Tree`] keyed by path relative to the
+/// root. Snapshots are persisted as plain-text, one `bytes<TAB>path` line per entry.
+///
+/// [`Tree`]: super::Tree
+#[derive(Debug, Default)]
+pub struct Snapshot {
+    entries: HashMap<PathBuf, u64>,
+}
+
+/// Change in disk usage of a single entry relative to a previous [Snapshot].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delta {
+    /// Entry did not exist in the previous snapshot.
+    New,
+
+    /// Entry grew by the given amount of bytes.
+    Grew(u64),
+
+    /// Entry shrank by the given amount of bytes.
+    Shrank(u64),
+}
+
+impl Snapshot {
+    /// Records the disk usage of every node in `tree` relative to `root`.
+    pub fn from_tree(tree: &Arena<Node>, root: NodeId) -> Self {
+        Self::from_tree_filtered(tree, root, |_| true)
+    }
+
+    /// Records the disk usage of only the directories in `tree` relative to `root`.
+    pub fn directories_from_tree(tree: &Arena<Node>, root: NodeId) -> Self {
+        Self::from_tree_filtered(tree, root, Node::is_dir)
+    }
+
+    /// Records the disk usage of every node in `tree` that satisfies `predicate`.
+    fn from_tree_filtered(
+        tree: &Arena<Node>,
+        root: NodeId,
+        predicate: impl Fn(&Node) -> bool,
+    ) -> Self {
+        let root_path = tree[root].get().path();
+
+        let entries = root
+            .descendants(tree)
+            .filter(|node_id| predicate(tree[*node_id].get()))
+            .map(|node_id| {
+                let node = tree[node_id].get();
+                let rel_path = node
+                    .path()
+                    .strip_prefix(root_path)
+                    .unwrap_or_else(|_| node.path());
+                let bytes = node.file_size().map_or(0, |fs| fs.bytes);
+
+                (rel_path.to_path_buf(), bytes)
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Disk usage of the entry at `rel_path` at the time the snapshot was taken.
+    pub fn get(&self, rel_path: &Path) -> Option<u64> {
+        self.entries.get(rel_path).copied()
+    }
+
+    /// Computes the [Delta] of an entry currently `bytes` in size relative to this snapshot.
+    /// Returns `None` if the entry is unchanged.
+    pub fn delta(&self, rel_path: &Path, bytes: u64) -> Option<Delta> {
+        match self.get(rel_path) {
+            None => Some(Delta::New),
+            Some(prev) if bytes > prev => Some(Delta::Grew(bytes - prev)),
+            Some(prev) if bytes < prev => Some(Delta::Shrank(prev - bytes)),
+            Some(_) => None,
+        }
+    }
+
+    /// Entries present in this snapshot that are absent from `other`, sorted by path.
+    pub fn missing_from(&self, other: &Self) -> Vec<(&Path, u64)> {
+        let mut missing = self
+            .entries
+            .iter()
+            .filter(|(path, _)| !other.entries.contains_key(*path))
+            .map(|(path, bytes)| (path.as_path(), *bytes))
+            .collect::<Vec<_>>();
+
+        missing.sort_unstable();
+
+        missing
+    }
+
+    /// Reads a snapshot from `path`. Returns `Ok(None)` if there is no snapshot at `path`.
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        let file = match fs::File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let mut entries = HashMap::new();
+
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+
+            let Some((bytes, rel_path)) = line.split_once('\t') else {
+                continue;
+            };
+
+            if let Ok(bytes) = bytes.parse::<u64>() {
+                entries.insert(PathBuf::from(rel_path), bytes);
+            }
+        }
+
+        Ok(Some(Self { entries }))
+    }
+
+    /// Writes the snapshot to `path`, creating any missing parent directories.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_unstable();
+
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+
+        for (rel_path, bytes) in entries {
+            writeln!(writer, "{bytes}\t{}", rel_path.display())?;
+        }
+
+        writer.flush()
+    }
+
+    /// Location of the most recent snapshot for the directory at `root` which is expected to be
+    /// canonicalized. Snapshots are stored in the first available of the following:
+    ///
+    /// - `$ERDTREE_CACHE_DIR`
+    /// - `$XDG_CACHE_HOME/erdtree`
+    /// - `$HOME/.cache/erdtree`
+    pub fn path_for(root: &Path) -> Option<PathBuf> {
+        let file_name = format!("{:016x}.snapshot", fnv1a(root.to_string_lossy().as_bytes()));
+
+        cache_dir().map(|dir| dir.join(file_name))
+    }
+}
+
+impl Delta {
+    /// Human-readable and colorized representation of [Delta], green for growth and red for
+    /// shrinkage.
+    pub fn styled(self, ctx: &Context) -> String {
+        let human_readable = |bytes| {
+            let size = FileSize::new(
+                bytes,
+                ctx.disk_usage(),
+                ctx.prefix,
+                ctx.scale,
+                ctx.rounding,
+                ctx.unit,
+            );
+            let components = size.human_readable_components();
+            format!("{} {}", components.size, components.unit)
+        };
+
+        match self {
+            Self::New => Color::Green.paint("new").to_string(),
+            Self::Grew(bytes) => Color::Green
+                .paint(format!("+{}", human_readable(bytes)))
+                .to_string(),
+            Self::Shrank(bytes) => Color::Red
+                .paint(format!("-{}", human_readable(bytes)))
+                .to_string(),
+        }
+    }
+}
+
+/// Directory where erdtree persists data between runs.
+pub fn cache_dir() -> Option<PathBuf> {
+    env::var_os(ERDTREE_CACHE_DIR)
+        .map(PathBuf::from)
+        .or_else(|| env::var_os(XDG_CACHE_HOME).map(|dir| PathBuf::from(dir).join(ERDTREE_DIR)))
+        .or_else(|| {
+            env::var_os(HOME).map(|home| PathBuf::from(home).join(CACHE_DIR).join(ERDTREE_DIR))
+        })
+}
+
+/// Directory where erdtree persists long-lived data such as [`History`].
+///
+/// [`History`]: super::history::History
+pub fn data_dir() -> Option<PathBuf> {
+    env::var_os(XDG_DATA_HOME)
+        .map(|dir| PathBuf::from(dir).join(ERDTREE_DIR))
+        .or_else(|| {
+            env::var_os(HOME).map(|home| PathBuf::from(home).join(DATA_DIR).join(ERDTREE_DIR))
+        })
+}
+
+/// 64-bit FNV-1a; unlike the standard library's hasher its output is stable across releases which
+/// makes it suitable for naming files that must be found again by future invocations.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
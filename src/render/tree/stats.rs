@@ -0,0 +1,90 @@
+use super::Tree;
+use crate::{
+    i18n::tr_args,
+    render::disk_usage::file_size::{FileSize, HumanReadableComponents},
+};
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
+
+/// How long a traversal took and how quickly it went, printed in the summary with `--stats`.
+pub struct Stats<'a> {
+    tree: &'a Tree,
+}
+
+impl<'a> Stats<'a> {
+    /// Initializes a [Self] with a reference to [Tree].
+    pub const fn new(tree: &'a Tree) -> Self {
+        Self { tree }
+    }
+
+    /// Number of entries in the tree, not counting those that were read but then removed from the
+    /// arena which still holds on to them.
+    fn entries(&self) -> usize {
+        self.tree.root().descendants(self.tree.inner()).count()
+    }
+
+    /// Total disk usage of the root.
+    fn bytes(&self) -> u64 {
+        let root = self.tree.inner()[self.tree.root()].get();
+        root.file_size().map_or(0, |fs| fs.bytes)
+    }
+
+    const fn elapsed(&self) -> Duration {
+        self.tree.traversal_time()
+    }
+
+    fn per_sec(&self, amount: f64) -> f64 {
+        amount / self.elapsed().as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+impl Display for Stats<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.tree.context();
+
+        let entries = self.entries();
+
+        if ctx.deterministic {
+            return write!(
+                f,
+                "{}",
+                tr_args("stats-entries", [("entries", entries.to_string())])
+            );
+        }
+
+        let entries_per_sec = self.per_sec(entries as f64);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let bytes_per_sec = self.per_sec(self.bytes() as f64).round() as u64;
+
+        let HumanReadableComponents { size, unit } = FileSize::new(
+            bytes_per_sec,
+            ctx.disk_usage(),
+            ctx.prefix,
+            ctx.scale,
+            ctx.rounding,
+            ctx.unit,
+        )
+        .human_readable_components();
+
+        let elapsed = format!("{:.2?}", self.elapsed());
+        let entries_per_sec = format!("{entries_per_sec:.0}");
+        let bytes_per_sec = format!("{size} {unit}");
+
+        write!(
+            f,
+            "{}",
+            tr_args(
+                "stats-throughput",
+                [
+                    ("entries", entries.to_string()),
+                    ("elapsed", elapsed),
+                    ("entries-per-sec", entries_per_sec),
+                    ("bytes-per-sec", bytes_per_sec),
+                ]
+            )
+        )
+    }
+}
@@ -0,0 +1,333 @@
+use super::Tree;
+use indextree::NodeId;
+use serde::Serialize;
+use std::{
+    cmp::Reverse,
+    fmt::{self, Display, Write},
+};
+
+/// Dimensions of the SVG drawing in pixels.
+const WIDTH: f64 = 1280.0;
+const HEIGHT: f64 = 720.0;
+
+/// Space left around the contents of a directory and above them for its name.
+const PADDING: f64 = 2.0;
+const HEADER: f64 = 16.0;
+
+/// Approximate width of a character of the labels.
+const CHAR_WIDTH: f64 = 6.5;
+
+/// Colors of the branches beneath the root, which are cycled through, as in d3's `schemeTableau10`.
+const PALETTE: [&str; 10] = [
+    "#4e79a7", "#f28e2c", "#e15759", "#76b7b2", "#59a14f", "#edc949", "#af7aa1", "#ff9da7",
+    "#9c755f", "#bab0ab",
+];
+
+/// For output as nested JSON of the shape that d3's `hierarchy` and Plotly's treemaps consume.
+pub struct Treemap<'a> {
+    tree: &'a Tree,
+}
+
+/// For output as an SVG drawing of a squarified treemap.
+pub struct Svg<'a> {
+    tree: &'a Tree,
+}
+
+/// An entry of the treemap. Directories always have children, even if there are none, whereas
+/// files never do. Only entries without children have a `value` so that consumers, which add up
+/// the values of children, don't count the size of directories twice.
+#[derive(Serialize)]
+struct Cell {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    children: Option<Vec<Self>>,
+
+    /// Disk usage in bytes, including that of children.
+    #[serde(skip)]
+    bytes: u64,
+}
+
+/// Area of the drawing allotted to a [Cell].
+#[derive(Clone, Copy)]
+struct Rect {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+impl<'a> Treemap<'a> {
+    /// Initializes a [Self] with a reference to [Tree].
+    pub const fn new(tree: &'a Tree) -> Self {
+        Self { tree }
+    }
+}
+
+impl<'a> Svg<'a> {
+    /// Initializes a [Self] with a reference to [Tree].
+    pub const fn new(tree: &'a Tree) -> Self {
+        Self { tree }
+    }
+}
+
+impl Display for Treemap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let root = Cell::of(self.tree, self.tree.root());
+        let json = serde_json::to_string(&root).map_err(|_| fmt::Error)?;
+        write!(f, "{json}")
+    }
+}
+
+impl Display for Svg<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let root = Cell::of(self.tree, self.tree.root());
+
+        let mut svg = String::new();
+
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="sans-serif" font-size="11">"#
+        )?;
+
+        let canvas = Rect {
+            x: 0.0,
+            y: 0.0,
+            w: WIDTH,
+            h: HEIGHT,
+        };
+
+        root.draw(&mut svg, canvas, &root.name, "#ffffff", true)?;
+
+        write!(svg, "</svg>")?;
+
+        write!(f, "{svg}")
+    }
+}
+
+impl Cell {
+    /// Builds the [Cell] of `node_id` and, down to the depth requested by `--level`, those of its
+    /// descendants. Directories beyond it appear as files.
+    fn of(tree: &Tree, node_id: NodeId) -> Self {
+        let ctx = tree.context();
+        let inner = tree.inner();
+        let node = inner[node_id].get();
+
+        let children = (node.is_dir()).then(|| {
+            if ctx.level().map_or(true, |level| node.depth() < level) {
+                node_id
+                    .children(inner)
+                    .map(|child_id| Self::of(tree, child_id))
+                    .collect()
+            } else {
+                vec![]
+            }
+        });
+
+        let bytes = node.file_size().map_or(0, |fs| fs.bytes);
+
+        Self {
+            name: node.display_name(ctx).into_owned(),
+            value: children
+                .as_ref()
+                .map_or(true, Vec::is_empty)
+                .then_some(bytes),
+            children,
+            bytes,
+        }
+    }
+
+    /// Draws this [Cell] within `rect`, followed by its children within what's left after the
+    /// padding and its label. Branches beneath the root each get a color of their own.
+    fn draw(
+        &self,
+        svg: &mut String,
+        rect: Rect,
+        path: &str,
+        color: &str,
+        root: bool,
+    ) -> fmt::Result {
+        let opacity = if self.children.is_some() { 0.35 } else { 0.8 };
+
+        writeln!(
+            svg,
+            r##"<g><title>{} ({} B)</title><rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{color}" fill-opacity="{opacity}" stroke="#ffffff"/>"##,
+            escape(path),
+            self.bytes,
+            rect.x,
+            rect.y,
+            rect.w,
+            rect.h,
+        )?;
+
+        if let Some(label) =
+            truncate(&self.name, rect.w - PADDING - PADDING).filter(|_| rect.h >= HEADER)
+        {
+            writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}">{}</text>"#,
+                rect.x + PADDING + 2.0,
+                rect.y + HEADER - 4.0,
+                escape(&label),
+            )?;
+        }
+
+        writeln!(svg, "</g>")?;
+
+        let Some(ref children) = self.children else {
+            return Ok(());
+        };
+
+        let inner = Rect {
+            x: rect.x + PADDING,
+            y: rect.y + HEADER,
+            w: rect.w - PADDING - PADDING,
+            h: rect.h - HEADER - PADDING,
+        };
+
+        if inner.w <= 0.0 || inner.h <= 0.0 {
+            return Ok(());
+        }
+
+        let mut children = children.iter().filter(|c| c.bytes > 0).collect::<Vec<_>>();
+        children.sort_by_key(|c| Reverse(c.bytes));
+
+        let values = children.iter().map(|c| c.bytes as f64).collect::<Vec<_>>();
+
+        for (i, (child, child_rect)) in children.iter().zip(squarify(&values, inner)).enumerate() {
+            let color = if root {
+                PALETTE[i % PALETTE.len()]
+            } else {
+                color
+            };
+            let path = format!("{path}/{}", child.name);
+            child.draw(svg, child_rect, &path, color, false)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Lays out `values`, sorted from largest to smallest, within `rect` so that each gets an area
+/// proportional to it while keeping their aspect ratios close to 1, as per the squarified treemap
+/// algorithm of Bruls, Huizing, and van Wijk. Rows of values are laid along the shorter side of
+/// what's left of `rect` for as long as adding to them doesn't worsen the most elongated of them.
+fn squarify(values: &[f64], rect: Rect) -> Vec<Rect> {
+    let total = values.iter().sum::<f64>();
+
+    if total <= 0.0 {
+        return vec![];
+    }
+
+    let scale = rect.w * rect.h / total;
+    let areas = values.iter().map(|v| v * scale).collect::<Vec<_>>();
+
+    let mut rects = Vec::with_capacity(areas.len());
+    let mut free = rect;
+    let mut row: Vec<f64> = vec![];
+
+    for area in areas {
+        let side = free.w.min(free.h);
+
+        let mut extended = row.clone();
+        extended.push(area);
+
+        if row.is_empty() || worst(&extended, side) <= worst(&row, side) {
+            row = extended;
+        } else {
+            free = lay_row(&row, free, &mut rects);
+            row = vec![area];
+        }
+    }
+
+    lay_row(&row, free, &mut rects);
+
+    rects
+}
+
+/// The largest aspect ratio among the rectangles of `row` if laid along a side of length `side`.
+fn worst(row: &[f64], side: f64) -> f64 {
+    let sum = row.iter().sum::<f64>();
+    let max = row.iter().copied().fold(f64::MIN, f64::max);
+    let min = row.iter().copied().fold(f64::MAX, f64::min);
+
+    let side = side * side;
+    let sum = sum * sum;
+
+    (side * max / sum).max(sum / (side * min))
+}
+
+/// Lays out `row` along the shorter side of `free`, pushing its rectangles onto `rects`, and
+/// returns what's left of `free`.
+fn lay_row(row: &[f64], free: Rect, rects: &mut Vec<Rect>) -> Rect {
+    let sum = row.iter().sum::<f64>();
+
+    if free.w >= free.h {
+        // A column on the left.
+        let width = sum / free.h;
+        let mut y = free.y;
+
+        for area in row {
+            let height = area / width;
+            rects.push(Rect {
+                x: free.x,
+                y,
+                w: width,
+                h: height,
+            });
+            y += height;
+        }
+
+        Rect {
+            x: free.x + width,
+            w: free.w - width,
+            ..free
+        }
+    } else {
+        // A row along the top.
+        let height = sum / free.w;
+        let mut x = free.x;
+
+        for area in row {
+            let width = area / height;
+            rects.push(Rect {
+                x,
+                y: free.y,
+                w: width,
+                h: height,
+            });
+            x += width;
+        }
+
+        Rect {
+            y: free.y + height,
+            h: free.h - height,
+            ..free
+        }
+    }
+}
+
+/// `name` shortened with an ellipsis to fit within `width` pixels, if it can fit at all.
+fn truncate(name: &str, width: f64) -> Option<String> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let fits = (width / CHAR_WIDTH).max(0.0) as usize;
+
+    let len = name.chars().count();
+
+    if len <= fits {
+        Some(name.to_owned())
+    } else if fits >= 4 {
+        Some(name.chars().take(fits - 1).chain(['\u{2026}']).collect())
+    } else {
+        None
+    }
+}
+
+/// Escapes the characters that are special in the text and attributes of XML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,16 +1,35 @@
-use std::sync::mpsc::Sender;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::Sender,
+};
 
 use super::{Context, Node};
 use ignore::{DirEntry, Error as IgnoreError, ParallelVisitor, ParallelVisitorBuilder, WalkState};
 
+/// With `--approx` only files of a directory beyond this many are sampled.
+const APPROX_EXACT_ENTRIES: u64 = 1000;
+
+#[allow(clippy::large_enum_variant)]
 pub enum TraversalState {
     Ongoing(Node),
+    /// A file within the directory at the given path that was skipped by `--approx`.
+    Unsampled(PathBuf),
     Done,
 }
 
 pub struct Branch<'a> {
     ctx: &'a Context,
     tx: Sender<TraversalState>,
+
+    /// Number of files this visitor saw so far by directory. The entries of a single directory
+    /// may be visited by any thread, so with `--approx` each thread samples its share of them.
+    files_seen: HashMap<PathBuf, u64>,
+
+    /// Whether `--thread-priority` and `--cpu-affinity` were applied to the thread running this
+    /// visitor, which is only known once it starts visiting.
+    scheduled: bool,
 }
 
 pub struct BranchVisitorBuilder<'a> {
@@ -19,14 +38,75 @@ pub struct BranchVisitorBuilder<'a> {
 }
 
 impl<'a> BranchVisitorBuilder<'a> {
-    pub fn new(ctx: &'a Context, tx: Sender<TraversalState>) -> Self {
+    pub const fn new(ctx: &'a Context, tx: Sender<TraversalState>) -> Self {
         Self { ctx, tx }
     }
 }
 
 impl<'a> Branch<'a> {
     pub fn new(ctx: &'a Context, tx: Sender<TraversalState>) -> Self {
-        Self { ctx, tx }
+        Self {
+            ctx,
+            tx,
+            files_seen: HashMap::new(),
+            scheduled: false,
+        }
+    }
+
+    /// Whether `dir_entry` should be read in full when only every `nth` file of large directories
+    /// is sampled. Directories are always read. Files are counted by this visitor alone, so each
+    /// thread reads the first [APPROX_EXACT_ENTRIES] files it sees of a directory in full.
+    fn is_sampled(&mut self, dir_entry: &DirEntry, nth: u64) -> bool {
+        if dir_entry.file_type().map_or(true, |ft| ft.is_dir()) {
+            return true;
+        }
+
+        let Some(parent) = dir_entry.path().parent() else {
+            return true;
+        };
+
+        let seen = *self
+            .files_seen
+            .entry(parent.to_owned())
+            .and_modify(|seen| *seen += 1)
+            .or_insert(1);
+
+        seen <= APPROX_EXACT_ENTRIES || seen % nth == 0
+    }
+
+    /// The link and the ancestor it leads back to if `error` is due to following a link, which on
+    /// Windows includes directory junctions, that forms a cycle. Such links are detected by the
+    /// walker itself before they're visited.
+    fn cycle(error: &IgnoreError) -> Option<(&Path, &Path)> {
+        match error {
+            IgnoreError::Loop { ancestor, child } => Some((child, ancestor)),
+            IgnoreError::WithPath { err, .. }
+            | IgnoreError::WithDepth { err, .. }
+            | IgnoreError::WithLineNumber { err, .. } => Self::cycle(err),
+            _ => None,
+        }
+    }
+
+    /// Whether `dir_entry` is a symlink to a directory nested within more than `max` others,
+    /// counting itself, beneath the root.
+    fn exceeds_link_depth(dir_entry: &DirEntry, max: usize) -> bool {
+        let is_dir = dir_entry.file_type().map_or(false, |ft| ft.is_dir());
+
+        if !dir_entry.path_is_symlink() || !is_dir {
+            return false;
+        }
+
+        let linked_ancestors = dir_entry
+            .path()
+            .ancestors()
+            .skip(1)
+            .take(dir_entry.depth().saturating_sub(1))
+            .filter(|ancestor| {
+                fs::symlink_metadata(ancestor).map_or(false, |md| md.file_type().is_symlink())
+            })
+            .count();
+
+        linked_ancestors + 1 > max
     }
 }
 
@@ -38,16 +118,63 @@ impl From<Node> for TraversalState {
 
 impl ParallelVisitor for Branch<'_> {
     fn visit(&mut self, entry: Result<DirEntry, IgnoreError>) -> WalkState {
-        let Ok(dir_entry) = entry else {
-            return WalkState::Skip;
+        if !self.scheduled {
+            crate::sched::apply(self.ctx);
+            self.scheduled = true;
+        }
+
+        let dir_entry = match entry {
+            Ok(dir_entry) => dir_entry,
+            Err(e) => {
+                if let Some((link, ancestor)) = Self::cycle(&e) {
+                    tracing::warn!(
+                        path = %link.display(),
+                        ancestor = %ancestor.display(),
+                        "skipping link that leads back to an ancestor"
+                    );
+                } else {
+                    tracing::warn!(error = %e, "skipping entry");
+                }
+
+                return WalkState::Skip;
+            }
         };
 
+        if let Some(nth) = self.ctx.approx {
+            if dir_entry.depth() > 0 && !self.is_sampled(&dir_entry, nth) {
+                tracing::trace!(path = %dir_entry.path().display(), "skipping unsampled file");
+
+                if let Some(parent) = dir_entry.path().parent() {
+                    let unsampled = TraversalState::Unsampled(parent.to_owned());
+                    self.tx.send(unsampled).unwrap();
+                }
+
+                return WalkState::Continue;
+            }
+        }
+
+        let path = dir_entry.path().to_owned();
+
+        let link_depth_exceeded = self
+            .ctx
+            .max_link_depth
+            .map_or(false, |max| Self::exceeds_link_depth(&dir_entry, max));
+
         match Node::try_from((dir_entry, self.ctx)) {
+            Ok(mut node) if link_depth_exceeded => {
+                tracing::debug!(path = %path.display(), "skipping contents beyond link depth");
+                node.set_link_depth_exceeded();
+                self.tx.send(TraversalState::from(node)).unwrap();
+                WalkState::Skip
+            }
             Ok(node) => {
                 self.tx.send(TraversalState::from(node)).unwrap();
                 WalkState::Continue
             }
-            _ => WalkState::Skip,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "skipping entry");
+                WalkState::Skip
+            }
         }
     }
 }
@@ -0,0 +1,155 @@
+use crate::render::context::{thread_priority::ThreadPriority, Context};
+use std::io;
+
+/// Nice value of threads with a low priority.
+#[cfg(all(unix, not(target_os = "macos")))]
+const LOW_NICE: libc::c_int = 10;
+
+/// I/O scheduling class in which a thread only gets to use the disk when nothing else does.
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+
+/// Offset of the class within an I/O priority.
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+
+/// Selects a single thread or process as the target of `ioprio_set`.
+#[cfg(target_os = "linux")]
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+
+/// Applies `--thread-priority` and `--cpu-affinity` to the calling thread. Both are best-effort
+/// so failures are logged rather than cutting the traversal short.
+pub fn apply(ctx: &Context) {
+    if ctx.thread_priority == ThreadPriority::Low {
+        if let Err(e) = lower_priority() {
+            tracing::warn!(error = %e, "failed to lower thread priority");
+        }
+    }
+
+    if !ctx.cpu_affinity.is_empty() {
+        if let Err(e) = pin(&ctx.cpu_affinity) {
+            tracing::warn!(error = %e, "failed to pin thread to CPUs");
+        }
+    }
+}
+
+/// Lowers both the CPU and I/O priority of the calling thread.
+#[cfg(target_os = "linux")]
+fn lower_priority() -> io::Result<()> {
+    // SAFETY: `gettid` has no preconditions.
+    let tid = unsafe { libc::syscall(libc::SYS_gettid) };
+    let tid = libc::id_t::try_from(tid).map_err(|_| io::Error::from(io::ErrorKind::InvalidData))?;
+
+    // Unlike elsewhere the nice value is per thread on Linux.
+    // SAFETY: `setpriority` has no preconditions.
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, tid, LOW_NICE) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let ioprio = IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT;
+
+    // SAFETY: `ioprio_set` has no preconditions.
+    if unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, tid, ioprio) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Lowers both the CPU and I/O priority of the calling thread.
+#[cfg(target_os = "macos")]
+fn lower_priority() -> io::Result<()> {
+    // SAFETY: `pthread_set_qos_class_self_np` has no preconditions.
+    let rc =
+        unsafe { libc::pthread_set_qos_class_self_np(libc::qos_class_t::QOS_CLASS_BACKGROUND, 0) };
+
+    if rc != 0 {
+        return Err(io::Error::from_raw_os_error(rc));
+    }
+
+    Ok(())
+}
+
+/// Lowers the CPU priority of the process as priorities can't be set per thread here.
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "macos"))))]
+fn lower_priority() -> io::Result<()> {
+    // SAFETY: `setpriority` has no preconditions.
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, LOW_NICE) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Lowers both the CPU and I/O priority of the calling thread.
+#[cfg(windows)]
+fn lower_priority() -> io::Result<()> {
+    use windows_sys::Win32::System::Threading::{
+        GetCurrentThread, SetThreadPriority, THREAD_MODE_BACKGROUND_BEGIN,
+    };
+
+    // SAFETY: The pseudo handle of the current thread is always valid.
+    if unsafe { SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn lower_priority() -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+/// Restricts the calling thread to run on the given `cpus` only.
+#[cfg(target_os = "linux")]
+fn pin(cpus: &[usize]) -> io::Result<()> {
+    // SAFETY: `cpu_set_t` is plain old data for which all zeroes is the empty set.
+    let mut set = unsafe { std::mem::zeroed::<libc::cpu_set_t>() };
+
+    for cpu in cpus {
+        if *cpu >= libc::CPU_SETSIZE as usize {
+            return Err(io::Error::from(io::ErrorKind::InvalidInput));
+        }
+
+        // SAFETY: `cpu` was checked to be within the bounds of `set`.
+        unsafe { libc::CPU_SET(*cpu, &mut set) };
+    }
+
+    // SAFETY: `set` is valid for the duration of the call and 0 denotes the calling thread.
+    if unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Restricts the calling thread to run on the given `cpus` only.
+#[cfg(windows)]
+fn pin(cpus: &[usize]) -> io::Result<()> {
+    use windows_sys::Win32::System::Threading::{GetCurrentThread, SetThreadAffinityMask};
+
+    let mut mask = 0_usize;
+
+    for cpu in cpus {
+        let bit = u32::try_from(*cpu)
+            .ok()
+            .and_then(|cpu| 1_usize.checked_shl(cpu))
+            .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+        mask |= bit;
+    }
+
+    // SAFETY: The pseudo handle of the current thread is always valid.
+    if unsafe { SetThreadAffinityMask(GetCurrentThread(), mask) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Affinity can't be set for threads on this platform.
+#[cfg(not(any(target_os = "linux", windows)))]
+fn pin(_cpus: &[usize]) -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
@@ -0,0 +1,7 @@
+use terminal_size::{terminal_size, Width};
+
+/// Returns the number of columns of the terminal attached to stdout. Returns `None` if stdout
+/// is not a terminal e.g. when output is piped.
+pub fn columns() -> Option<usize> {
+    terminal_size().map(|(Width(cols), _)| usize::from(cols))
+}
@@ -1,4 +1,14 @@
-use std::{borrow::ToOwned, cmp::Eq, collections::HashSet, hash::Hash};
+use std::{
+    borrow::ToOwned,
+    cmp::Eq,
+    collections::HashSet,
+    hash::Hash,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Unit tests for date conversions.
+#[cfg(test)]
+mod test;
 
 #[macro_export]
 /// Ruby-like way to crate a hashmap.
@@ -25,3 +35,47 @@ where
         .filter(|item| set.insert(item.to_owned()))
         .collect::<Vec<T>>()
 }
+
+/// Converts a UTC date and time to a point in time, as per Howard Hinnant's `days_from_civil`.
+/// Returns `None` for dates that don't exist or precede the epoch.
+pub fn from_civil(
+    year: u64,
+    month: u64,
+    day: u64,
+    hour: u64,
+    minute: u64,
+    second: u64,
+) -> Option<SystemTime> {
+    let valid = (1970..=9999).contains(&year)
+        && (1..=12).contains(&month)
+        && (1..=days_in_month(year, month)).contains(&day)
+        && hour < 24
+        && minute < 60
+        && second < 60;
+
+    if !valid {
+        return None;
+    }
+
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = (era * 146_097 + doe).checked_sub(719_468)?;
+
+    let secs = days * 86_400 + hour * 3_600 + minute * 60 + second;
+
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Number of days in `month` of `year`, accounting for February of leap years.
+const fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
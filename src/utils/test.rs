@@ -0,0 +1,24 @@
+use super::from_civil;
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn from_civil_epoch() {
+    assert_eq!(from_civil(1970, 1, 1, 0, 0, 0), Some(UNIX_EPOCH));
+}
+
+#[test]
+fn from_civil_leap_day() {
+    assert_eq!(
+        from_civil(2024, 2, 29, 0, 0, 0),
+        Some(UNIX_EPOCH + Duration::from_secs(1_709_164_800))
+    );
+    assert!(from_civil(2000, 2, 29, 0, 0, 0).is_some());
+}
+
+#[test]
+fn from_civil_rejects_days_past_month_end() {
+    assert_eq!(from_civil(2023, 2, 29, 0, 0, 0), None);
+    assert_eq!(from_civil(2023, 2, 31, 0, 0, 0), None);
+    assert_eq!(from_civil(2023, 4, 31, 0, 0, 0), None);
+    assert_eq!(from_civil(2100, 2, 29, 0, 0, 0), None);
+}
@@ -0,0 +1,121 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn accessible() {
+    assert_eq!(
+        utils::run_cmd(&["--accessible", "--sort", "name", "tests/data"]),
+        indoc!(
+            "
+            data (1.21 KiB) [dir]
+               dream_cycle (308 B) [dir]
+                  polaris.txt (308 B)
+               lipsum (446 B) [dir]
+                  lipsum.txt (446 B)
+               necronomicon.txt (83 B)
+               nemesis.txt (161 B)
+               nylarlathotep.txt (100 B)
+               the_yellow_king (143 B) [dir]
+                  cassildas_song.md (143 B)"
+        )
+    );
+}
+
+#[test]
+fn accessible_no_dimming() {
+    let out = utils::run_cmd_raw(&["--accessible", "--max-lines", "2", "tests/data"]);
+
+    assert!(
+        out.ends_with("... output truncated (8 more entries)"),
+        "{out}"
+    );
+    assert!(!out.contains("\x1b[2m"), "Nothing should be dimmed");
+}
+
+#[test]
+fn accessible_conflicts_with_icons() {
+    let output = std::process::Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--no-config",
+            "--accessible",
+            "--icons",
+            "tests/data",
+        ])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+}
+
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::{
+        ffi::CString,
+        fs,
+        os::unix::{
+            ffi::OsStringExt,
+            fs::{symlink, PermissionsExt},
+            net::UnixListener,
+        },
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn accessible_type_markers() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("markers");
+
+        fs::create_dir(&root)?;
+        fs::write(root.join("notes.txt"), "notes")?;
+        fs::write(root.join("run.sh"), "#!/bin/sh\n")?;
+        fs::set_permissions(root.join("run.sh"), fs::Permissions::from_mode(0o755))?;
+        symlink("notes.txt", root.join("todo"))?;
+        UnixListener::bind(root.join("socket"))?;
+
+        let fifo = CString::new(root.join("pipe").into_os_string().into_vec())?;
+        assert_eq!(unsafe { libc::mkfifo(fifo.as_ptr(), 0o644) }, 0);
+
+        assert_eq!(
+            super::utils::run_cmd(&["--accessible", "--sort", "name", &root.to_string_lossy()]),
+            indoc!(
+                "
+                markers (15 B) [dir]
+                   notes.txt (5 B)
+                   pipe [fifo]
+                   run.sh (10 B) [exec]
+                   socket [socket]
+                   todo -> notes.txt [link]"
+            )
+        );
+
+        Ok(())
+    }
+    #[test]
+    fn accessible_high_contrast() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("contrast");
+
+        fs::create_dir_all(root.join("docs"))?;
+        fs::write(root.join("notes.txt"), "notes")?;
+        symlink("notes.txt", root.join("todo"))?;
+
+        let out = super::utils::run_cmd_raw(&["--accessible", &root.to_string_lossy()]);
+
+        assert!(
+            out.contains("\x1b[1mdocs\x1b[0m [dir]"),
+            "Directories should be bold in the color of the terminal: {out}"
+        );
+        assert!(
+            out.contains("\x1b[4m-> notes.txt\x1b[0m [link]"),
+            "Targets of symlinks should be underlined rather than red: {out}"
+        );
+        assert!(!out.contains("\x1b[1;34m"), "Nothing should be bold blue");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::{fs, path::Path, process::Command};
+    use tempfile::TempDir;
+
+    /// Sets the modification time of the file at `path` to `stamp` in UTC formatted as
+    /// `[[CC]YY]MMDDhhmm[.ss]`.
+    fn touch(path: &Path, stamp: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let status = Command::new("touch")
+            .env("TZ", "UTC")
+            .arg("-t")
+            .arg(stamp)
+            .arg(path)
+            .status()?;
+
+        assert!(status.success());
+
+        Ok(())
+    }
+
+    /// Creates an archive of files modified decades apart.
+    fn archive() -> Result<TempDir, Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("archive");
+
+        fs::create_dir_all(root.join("reports"))?;
+
+        for (path, stamp) in [
+            ("reports/1999.txt", "199912312359.59"),
+            ("reports/2008.txt", "200802290000.00"),
+            ("reports/2016.txt", "201607041200.30"),
+            ("budget.csv", "202301150830.00"),
+            ("plan.md", "202403010915.45"),
+        ] {
+            let path = root.join(path);
+            fs::write(&path, "")?;
+            touch(&path, stamp)?;
+        }
+
+        Ok(tmp)
+    }
+
+    #[test]
+    fn ages() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = archive()?;
+        let root = tmp.path().join("archive");
+
+        assert_eq!(
+            super::utils::run_cmd(&["--report=ages", "--top", "2", &root.to_string_lossy()]),
+            indoc!(
+                "
+                oldest
+                1999-12-31 23:59:59   reports/1999.txt
+                2008-02-29 00:00:00   reports/2008.txt
+
+                newest
+                2023-01-15 08:30:00   budget.csv
+                2024-03-01 09:15:45   plan.md"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn ages_json() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = archive()?;
+        let root = tmp.path().join("archive");
+
+        assert_eq!(
+            super::utils::run_cmd(&[
+                "--report=ages",
+                "--top",
+                "1",
+                "--json",
+                &root.to_string_lossy()
+            ]),
+            r#"{"schema_version":1,"oldest":[{"path":"reports/1999.txt","modified":946684799}],"newest":[{"path":"plan.md","modified":1709284545}]}"#
+        );
+
+        Ok(())
+    }
+}
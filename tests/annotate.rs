@@ -0,0 +1,52 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+fn annotate(file_name: &str, contents: &str) -> String {
+    let tmp = TempDir::new().unwrap();
+    let notes = tmp.path().join(file_name);
+
+    fs::write(&notes, contents).unwrap();
+
+    utils::run_cmd(&[
+        "--sort",
+        "name",
+        "--annotate",
+        &notes.to_string_lossy(),
+        "tests/data/the_yellow_king",
+    ])
+}
+
+#[test]
+fn annotate_toml() {
+    assert_eq!(
+        annotate(
+            "notes.toml",
+            indoc!(
+                r#"
+                "." = "The King in Yellow"
+                "./cassildas_song.md" = "Act 1, Scene 2"
+                "#
+            )
+        ),
+        indoc!(
+            "
+            the_yellow_king (143 B) # The King in Yellow
+            └─ cassildas_song.md (143 B) # Act 1, Scene 2"
+        )
+    );
+}
+
+#[test]
+fn annotate_json() {
+    assert_eq!(
+        annotate("notes.json", r#"{"cassildas_song.md": "Act 1, Scene 2"}"#),
+        indoc!(
+            "
+            the_yellow_king (143 B)
+            └─ cassildas_song.md (143 B) # Act 1, Scene 2"
+        )
+    );
+}
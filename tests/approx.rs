@@ -0,0 +1,40 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn approx() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("hoard");
+
+    fs::create_dir_all(root.join("scrolls"))?;
+
+    for i in 0..1100 {
+        fs::write(root.join("scrolls").join(format!("{i}.txt")), "0123456789")?;
+    }
+
+    assert_eq!(
+        utils::run_cmd(&["--dirs-only", "--approx", &root.to_string_lossy()]),
+        indoc!(
+            "
+            hoard (10.74 KiB) [estimate ±0.0%]
+            └─ scrolls (10.74 KiB) [estimate ±0.0%]"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn approx_small_directories() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--approx=2", "tests/data/the_yellow_king"]),
+        indoc!(
+            "
+            the_yellow_king (143 B)
+            └─ cassildas_song.md (143 B)"
+        )
+    );
+}
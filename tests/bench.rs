@@ -0,0 +1,43 @@
+mod utils;
+
+#[test]
+fn bench() {
+    let output = utils::run_cmd(&["bench", "--runs", "2", "tests/data"]);
+    let lines = output.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "tests/data (threads: 1, caches: warm)");
+    assert!(lines[1].starts_with("run 1: "));
+    assert!(lines[1].contains(", 10 entries, "));
+    assert!(lines[2].starts_with("run 2: "));
+    assert!(lines[3].starts_with("mean: "));
+    assert!(lines[3].ends_with(" across 2 runs"));
+}
+
+#[test]
+fn threads_default_to_storage() {
+    let output = std::process::Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--no-config",
+            "bench",
+            "--runs",
+            "1",
+            "tests/data",
+        ])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let threads = stdout
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("tests/data (threads: "))
+        .and_then(|rest| rest.split(',').next())
+        .and_then(|threads| threads.parse::<usize>().ok())
+        .unwrap();
+
+    assert!((2..=16).contains(&threads), "{threads}");
+}
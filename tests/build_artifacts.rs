@@ -0,0 +1,122 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+/// Creates a project with a Rust build directory and JavaScript dependencies among its sources.
+fn project() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("proj");
+
+    fs::create_dir_all(root.join("target").join("debug"))?;
+    fs::create_dir_all(root.join("web").join("node_modules").join("lodash"))?;
+    fs::create_dir_all(root.join("src"))?;
+
+    fs::write(
+        root.join("target").join("debug").join("et"),
+        "x".repeat(100),
+    )?;
+    fs::write(
+        root.join("web")
+            .join("node_modules")
+            .join("lodash")
+            .join("index.js"),
+        "x".repeat(50),
+    )?;
+    fs::write(root.join("web").join("app.js"), "x".repeat(20))?;
+    fs::write(root.join("src").join("main.rs"), "x".repeat(10))?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn skip_build_artifacts() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = project()?;
+    let root = tmp.path().join("proj");
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--skip-build-artifacts",
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            proj (30 B)
+            ├─ src (10 B)
+            │  └─ main.rs (10 B)
+            └─ web (20 B)
+               └─ app.js (20 B)"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn artifacts_only() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = project()?;
+    let root = tmp.path().join("proj");
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--artifacts-only",
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            proj (150 B)
+            ├─ target (100 B)
+            │  └─ debug (100 B)
+            │     └─ et (100 B)
+            └─ web (50 B)
+               └─ node_modules (50 B)
+                  └─ lodash (50 B)
+                     └─ index.js (50 B)"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn artifacts_only_hidden() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = project()?;
+    let root = tmp.path().join("proj");
+    let venv = root.join(".venv");
+
+    fs::create_dir_all(venv.join("lib"))?;
+    fs::write(venv.join("lib").join("site.py"), "x".repeat(25))?;
+    fs::write(venv.join(".lock"), "x".repeat(3))?;
+    fs::write(root.join(".env"), "x".repeat(5))?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--artifacts-only",
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            proj (175 B)
+            ├─ .venv (25 B)
+            │  └─ lib (25 B)
+            │     └─ site.py (25 B)
+            ├─ target (100 B)
+            │  └─ debug (100 B)
+            │     └─ et (100 B)
+            └─ web (50 B)
+               └─ node_modules (50 B)
+                  └─ lodash (50 B)
+                     └─ index.js (50 B)"
+        ),
+        "Hidden build artifacts should be printed without --hidden, unlike what's hidden within them"
+    );
+
+    Ok(())
+}
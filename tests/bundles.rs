@@ -0,0 +1,84 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+/// Creates an application bundle with a nested framework alongside a regular directory.
+fn applications() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("Applications");
+
+    let contents = root.join("Chess.app").join("Contents");
+    fs::create_dir_all(contents.join("MacOS"))?;
+    fs::create_dir_all(contents.join("Frameworks").join("Engine.framework"))?;
+    fs::create_dir_all(root.join("Utilities"))?;
+
+    fs::write(contents.join("MacOS").join("Chess"), "x".repeat(300))?;
+    fs::write(
+        contents
+            .join("Frameworks")
+            .join("Engine.framework")
+            .join("Engine"),
+        "x".repeat(200),
+    )?;
+    fs::write(root.join("Utilities").join("README"), "x".repeat(100))?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn collapse() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = applications()?;
+    let root = tmp.path().join("Applications");
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--bundles",
+            "collapse",
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            Applications (600 B)
+            ├─ Chess.app (500 B)
+            └─ Utilities (100 B)
+               └─ README (100 B)"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn expand() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = applications()?;
+    let root = tmp.path().join("Applications");
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--bundles",
+            "expand",
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            Applications (600 B)
+            ├─ Chess.app (500 B)
+            │  └─ Contents (500 B)
+            │     ├─ Frameworks (200 B)
+            │     │  └─ Engine.framework (200 B)
+            │     │     └─ Engine (200 B)
+            │     └─ MacOS (300 B)
+            │        └─ Chess (300 B)
+            └─ Utilities (100 B)
+               └─ README (100 B)"
+        )
+    );
+
+    Ok(())
+}
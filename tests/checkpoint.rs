@@ -0,0 +1,207 @@
+use indoc::indoc;
+use std::{
+    fs,
+    process::{Command, Stdio},
+};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn checkpoint() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("filer");
+    let checkpoint = tmp.path().join("filer.checkpoint");
+
+    fs::create_dir_all(root.join("alpha"))?;
+    fs::create_dir_all(root.join("beta").join("deep"))?;
+    fs::write(root.join("alpha").join("a"), "a".repeat(100))?;
+    fs::write(root.join("beta").join("deep").join("b"), "b".repeat(200))?;
+    fs::write(root.join("top"), "t".repeat(5))?;
+
+    let root = fs::canonicalize(root)?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--checkpoint",
+            &checkpoint.to_string_lossy(),
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            filer (305 B)
+            ├─ alpha (100 B)
+            │  └─ a (100 B)
+            ├─ beta (200 B)
+            │  └─ deep (200 B)
+            │     └─ b (200 B)
+            └─ top (5 B)"
+        ),
+        "Failed to traverse the directories beneath the root one at a time"
+    );
+
+    let saved = fs::read_to_string(&checkpoint)?;
+    let header = saved.lines().take(3).collect::<Vec<_>>().join("\n");
+
+    assert_eq!(
+        header,
+        format!(
+            "root\t{}\ndisk-usage\tlogical\nsettings\t{}",
+            root.display(),
+            header.rsplit('\t').next().unwrap_or_default()
+        ),
+        "Failed to write the header of the checkpoint"
+    );
+
+    assert_eq!(
+        saved,
+        format!("{header}\n100\talpha\n200\tbeta\n"),
+        "Failed to checkpoint the directories within the root in place of those within them"
+    );
+
+    // A checkpoint of a traversal that was interrupted after beta was done.
+    fs::write(&checkpoint, format!("{header}\n999\tbeta\n"))?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--resume",
+            &checkpoint.to_string_lossy(),
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            filer (1.08 KiB)
+            ├─ alpha (100 B)
+            │  └─ a (100 B)
+            ├─ beta (999 B) [resumed]
+            └─ top (5 B)"
+        ),
+        "Failed to skip the directory completed before the interruption"
+    );
+
+    assert_eq!(
+        fs::read_to_string(&checkpoint)?,
+        format!("{header}\n100\talpha\n999\tbeta\n"),
+        "Failed to keep checkpointing to the file resumed from"
+    );
+
+    // A checkpoint of a traversal that was interrupted within beta after deep was done.
+    fs::write(&checkpoint, format!("{header}\n999\tbeta/deep\n"))?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--resume",
+            &checkpoint.to_string_lossy(),
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            filer (1.08 KiB)
+            ├─ alpha (100 B)
+            │  └─ a (100 B)
+            ├─ beta (999 B)
+            │  └─ deep (999 B) [resumed]
+            └─ top (5 B)"
+        ),
+        "Failed to skip the nested directory completed before the interruption"
+    );
+
+    assert_eq!(
+        fs::read_to_string(&checkpoint)?,
+        format!("{header}\n100\talpha\n999\tbeta\n"),
+        "Failed to checkpoint the directory containing the one resumed"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn checkpoint_settings() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("filer");
+    let checkpoint = tmp.path().join("filer.checkpoint");
+
+    fs::create_dir_all(root.join("alpha"))?;
+    fs::write(root.join("alpha").join(".a"), "a".repeat(100))?;
+
+    utils::run_cmd(&[
+        "--checkpoint",
+        &checkpoint.to_string_lossy(),
+        &root.to_string_lossy(),
+    ]);
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "--no-config", "--hidden", "--resume"])
+        .arg(&checkpoint)
+        .arg(&root)
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .output()?;
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("different flags"),
+        "Failed to reject resuming a checkpoint taken with other flags"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn missing_root() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let checkpoint = tmp.path().join("missing.checkpoint");
+
+    for checkpointed in [false, true] {
+        let mut cmd = Command::new("cargo");
+        cmd.args(["run", "--", "--no-config"]);
+
+        if checkpointed {
+            cmd.arg("--checkpoint").arg(&checkpoint);
+        }
+
+        let output = cmd
+            .arg(tmp.path().join("missing"))
+            .stdin(Stdio::null())
+            .output()?;
+
+        assert!(!output.status.success());
+        assert!(
+            !output.stderr.is_empty(),
+            "Failed to report a root that doesn't exist"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn invalid_glob() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let checkpoint = tmp.path().join("data.checkpoint");
+
+    for checkpointed in [false, true] {
+        let mut cmd = Command::new("cargo");
+        cmd.args(["run", "--", "--no-config", "--glob", "{a"]);
+
+        if checkpointed {
+            cmd.arg("--checkpoint").arg(&checkpoint);
+        }
+
+        let output = cmd.arg("tests/data").stdin(Stdio::null()).output()?;
+
+        assert!(!output.status.success());
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("Invalid glob"),
+            "Failed to report an invalid glob"
+        );
+    }
+
+    Ok(())
+}
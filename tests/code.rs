@@ -0,0 +1,87 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+/// Creates a project with Rust and Python sources along with a file that isn't source code.
+fn project() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("proj");
+
+    fs::create_dir_all(root.join("src"))?;
+    fs::create_dir_all(root.join("scripts"))?;
+
+    fs::write(
+        root.join("src").join("main.rs"),
+        indoc!(
+            "
+            // Entry point.
+            fn main() {
+                /* Greets the
+                   world. */
+                println!(\"hello\"); // loudly
+
+            }
+            "
+        ),
+    )?;
+
+    fs::write(
+        root.join("scripts").join("build.py"),
+        "# Builds everything.\n\nprint('building')\n",
+    )?;
+
+    fs::write(root.join("README.md"), "# proj\n")?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn code() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = project()?;
+    let root = tmp.path().join("proj");
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--report=code", &root.to_string_lossy()]),
+        indoc!(
+            "
+            proj (4 loc)
+            ├─ scripts (1 loc)
+            │  └─ build.py (1 loc)
+            └─ src (3 loc)
+               └─ main.rs (3 loc)
+
+            4 lines of code, 4 comments, 2 blanks"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn code_json() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = project()?;
+    let root = tmp.path().join("proj");
+
+    let out = utils::run_cmd(&[
+        "--sort",
+        "name",
+        "--report=code",
+        "--json",
+        &root.to_string_lossy(),
+    ]);
+
+    assert_eq!(
+        out,
+        concat!(
+            r#"{"schema_version":1,"root":"proj","code":4,"comments":4,"blanks":2,"entries":["#,
+            r#"{"path":"scripts","code":1,"comments":1,"blanks":1},"#,
+            r#"{"path":"scripts/build.py","code":1,"comments":1,"blanks":1},"#,
+            r#"{"path":"src","code":3,"comments":3,"blanks":1},"#,
+            r#"{"path":"src/main.rs","code":3,"comments":3,"blanks":1}]}"#
+        )
+    );
+
+    Ok(())
+}
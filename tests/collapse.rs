@@ -0,0 +1,53 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn collapse() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--collapse",
+            "lipsum",
+            "--collapse",
+            "./the_yellow_king/",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1.21 KiB)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.txt (308 B)
+            ├─ lipsum (446 B)
+            ├─ necronomicon.txt (83 B)
+            ├─ nemesis.txt (161 B)
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king (143 B)"
+        ),
+        "Failed to print lipsum and the_yellow_king without their contents."
+    )
+}
+
+#[test]
+fn collapse_expanded() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--level",
+            "0",
+            "--expand",
+            "dream_cycle",
+            "--collapse",
+            "dream_cycle",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1.21 KiB)
+            └─ dream_cycle (308 B)"
+        ),
+        "Failed to collapse dream_cycle despite it being expanded."
+    )
+}
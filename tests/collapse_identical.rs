@@ -0,0 +1,45 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn collapse_identical() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let logs = tmp.path().join("logs");
+
+    for (date, len) in [
+        ("2023-01", 4),
+        ("2023-02", 5),
+        ("2023-03", 7),
+        ("2023-04", 64),
+    ] {
+        fs::create_dir_all(logs.join(date))?;
+        fs::write(logs.join(date).join("app.log"), "x".repeat(len))?;
+    }
+
+    fs::create_dir_all(logs.join("archive"))?;
+    fs::write(logs.join("archive").join("app.log.gz"), "dddd")?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--collapse-identical",
+            &logs.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            logs (84 B)
+            ├─ 2023-01 (4 B) × 2 similar (12 B total)
+            │  └─ app.log (4 B)
+            ├─ 2023-04 (64 B)
+            │  └─ app.log (64 B)
+            └─ archive (4 B)
+               └─ app.log.gz (4 B)"
+        )
+    );
+
+    Ok(())
+}
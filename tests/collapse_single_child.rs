@@ -0,0 +1,62 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn collapse_single_child() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let proj = tmp.path().join("proj");
+    let package = proj
+        .join("src")
+        .join("main")
+        .join("java")
+        .join("com")
+        .join("example");
+
+    fs::create_dir_all(package.join("app"))?;
+    fs::write(package.join("app").join("Main.java"), "abc")?;
+    fs::write(package.join("Util.java"), "de")?;
+
+    fs::create_dir_all(proj.join("docs"))?;
+    fs::write(proj.join("docs").join("a.md"), "x")?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--collapse-single-child",
+            &proj.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            proj (6 B)
+            ├─ docs (1 B)
+            │  └─ a.md (1 B)
+            └─ src/main/java/com/example (5 B)
+               ├─ Util.java (2 B)
+               └─ app (3 B)
+                  └─ Main.java (3 B)"
+        )
+    );
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--collapse-single-child",
+            "--level",
+            "1",
+            &proj.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            proj (6 B)
+            ├─ docs (1 B)
+            └─ src/main/java/com/example (5 B)"
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn color_by_age() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    fs::write(tmp.path().join("fresh.txt"), "")?;
+
+    let out = utils::run_cmd_raw(&["--color-by", "age", &tmp.path().to_string_lossy()]);
+
+    assert!(
+        out.contains("38;5;255mfresh.txt"),
+        "Files modified within the hour should be brightest"
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn color_by_owner() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    fs::write(tmp.path().join("a.txt"), "")?;
+    fs::write(tmp.path().join("b.txt"), "")?;
+
+    let out = utils::run_cmd_raw(&["--color-by", "owner", &tmp.path().to_string_lossy()]);
+
+    let color_of = |name: &str| {
+        let line = out.lines().find(|line| line.contains(name)).unwrap();
+        let start = line.find("38;5;").unwrap();
+        line[start..].split('m').next().unwrap().to_owned()
+    };
+
+    assert_eq!(
+        color_of("a.txt"),
+        color_of("b.txt"),
+        "Files of the same owner should share a color"
+    );
+
+    Ok(())
+}
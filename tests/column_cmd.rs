@@ -0,0 +1,106 @@
+use indoc::indoc;
+
+mod utils;
+
+#[cfg(unix)]
+#[test]
+fn column_cmd() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--column-cmd",
+            "basename {}",
+            "tests/data/the_yellow_king"
+        ]),
+        indoc!(
+            "
+            143 B  the_yellow_king    the_yellow_king
+            143 B  cassildas_song.md  └─ cassildas_song.md"
+        )
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn column_cmd_placed() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--columns",
+            "name,cmd",
+            "--column-cmd",
+            "false",
+            "tests/data/the_yellow_king"
+        ]),
+        indoc!(
+            "
+            the_yellow_king       -
+            └─ cassildas_song.md  -"
+        )
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn column_cmd_quoted() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--columns",
+            "name,cmd",
+            "--column-cmd",
+            r#"printf '%s\n' "in the yellow" {}"#,
+            "tests/data/the_yellow_king"
+        ]),
+        indoc!(
+            "
+            the_yellow_king       in the yellow
+            └─ cassildas_song.md  in the yellow"
+        )
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn column_cmd_timeout() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--columns",
+            "name,cmd",
+            "--column-cmd",
+            "sh -c 'exec sleep 30' sh {}",
+            "--column-cmd-timeout",
+            "1s",
+            "tests/data/the_yellow_king"
+        ]),
+        indoc!(
+            "
+            the_yellow_king       -
+            └─ cassildas_song.md  -"
+        )
+    );
+}
+
+#[test]
+fn column_cmd_unbalanced_quotes() {
+    let output = std::process::Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--no-config",
+            "--column-cmd",
+            "file -b '{}",
+            "tests/data",
+        ])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid command"));
+}
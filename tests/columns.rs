@@ -0,0 +1,120 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn columns() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--columns", "size,name", "tests/data"]),
+        indoc!(
+            "
+            1.21 KiB  data
+               308 B  ├─ dream_cycle
+               308 B  │  └─ polaris.txt
+               446 B  ├─ lipsum
+               446 B  │  └─ lipsum.txt
+                83 B  ├─ necronomicon.txt
+               161 B  ├─ nemesis.txt
+               100 B  ├─ nylarlathotep.txt
+               143 B  └─ the_yellow_king
+               143 B     └─ cassildas_song.md"
+        )
+    );
+}
+
+#[test]
+fn columns_name_first() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--columns",
+            "name,size",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data                  1.21 KiB
+            ├─ dream_cycle           308 B
+            ├─ lipsum                446 B
+            ├─ necronomicon.txt       83 B
+            ├─ nemesis.txt           161 B
+            ├─ nylarlathotep.txt     100 B
+            └─ the_yellow_king       143 B"
+        )
+    );
+}
+
+#[test]
+fn columns_header() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--columns",
+            "name,size",
+            "--header",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            Name                      Size
+            data                  1.21 KiB
+            ├─ dream_cycle           308 B
+            ├─ lipsum                446 B
+            ├─ necronomicon.txt       83 B
+            ├─ nemesis.txt           161 B
+            ├─ nylarlathotep.txt     100 B
+            └─ the_yellow_king       143 B"
+        )
+    );
+}
+
+#[cfg(unix)]
+mod unix {
+    use indoc::indoc;
+    use std::{
+        fs::{self, Permissions},
+        os::unix::fs::PermissionsExt,
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn columns_perms() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("bin");
+
+        fs::create_dir_all(&root)?;
+
+        for (name, mode) in [("mount", 0o4755), ("notes", 0o640), ("shared", 0o1777)] {
+            let path = root.join(name);
+            fs::write(&path, "x")?;
+            fs::set_permissions(&path, Permissions::from_mode(mode))?;
+        }
+
+        fs::set_permissions(&root, Permissions::from_mode(0o755))?;
+
+        assert_eq!(
+            super::utils::run_cmd(&[
+                "--sort",
+                "name",
+                "--columns",
+                "perms,name",
+                &root.to_string_lossy()
+            ]),
+            indoc!(
+                "
+                drwxr-xr-x  bin
+                -rwsr-xr-x  ├─ mount
+                -rw-r-----  ├─ notes
+                -rwxrwxrwt  └─ shared"
+            )
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+/// Creates a directory with a text file and a binary file.
+fn mixed() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("mix");
+
+    fs::create_dir_all(root.join("bin"))?;
+    fs::write(root.join("notes.txt"), "hello\n")?;
+    fs::write(root.join("bin").join("tool"), b"\x7fELF\0\x01\x02\x03")?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn text_only() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = mixed()?;
+    let root = tmp.path().join("mix");
+
+    assert_eq!(
+        utils::run_cmd(&["--text-only", &root.to_string_lossy()]),
+        indoc!(
+            "
+            mix (6 B)
+            └─ notes.txt (6 B)"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn binary_only() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = mixed()?;
+    let root = tmp.path().join("mix");
+
+    assert_eq!(
+        utils::run_cmd(&["--binary-only", &root.to_string_lossy()]),
+        indoc!(
+            "
+            mix (8 B)
+            └─ bin (8 B)
+               └─ tool (8 B)"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn content_in_json() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = mixed()?;
+    let root = tmp.path().join("mix");
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--report",
+            "--json",
+            &root.to_string_lossy()
+        ]),
+        concat!(
+            r#"{"schema_version":1,"root":"mix","total_bytes":14,"counts":{"directories":1,"files":2,"links":0},"#,
+            r#""entries":[{"type":"d","path":"bin","bytes":8},"#,
+            r#"{"type":"-","path":"bin/tool","bytes":8,"content":"binary"},"#,
+            r#"{"type":"-","path":"notes.txt","bytes":6,"content":"text"}]}"#
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use indoc::indoc;
+use std::{
+    fs,
+    path::Path,
+    process::{Command, Stdio},
+};
+use strip_ansi_escapes::strip as strip_ansi_escapes;
+use tempfile::TempDir;
+
+fn run_delta(cache: &Path, dir: &Path) -> String {
+    let output = Command::new("cargo")
+        .args(["run", "--", "--threads", "1", "--no-config", "--delta"])
+        .args(["--sort", "name"])
+        .arg(dir)
+        .env("ERDTREE_CACHE_DIR", cache)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    String::from_utf8(strip_ansi_escapes(output.stdout).unwrap())
+        .unwrap()
+        .trim()
+        .to_string()
+}
+
+#[test]
+fn delta() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = TempDir::new()?;
+    let data = TempDir::new()?;
+    let root = data.path().join("data");
+
+    fs::create_dir(&root)?;
+    fs::write(root.join("growing.txt"), "a".repeat(100))?;
+    fs::write(root.join("shrinking.txt"), "a".repeat(100))?;
+    fs::write(root.join("doomed.txt"), "a".repeat(10))?;
+
+    assert_eq!(
+        run_delta(cache.path(), &root),
+        indoc!(
+            "
+            data (210 B)
+            ├─ doomed.txt (10 B)
+            ├─ growing.txt (100 B)
+            └─ shrinking.txt (100 B)"
+        ),
+        "First run should not have anything to compare against"
+    );
+
+    fs::write(root.join("growing.txt"), "a".repeat(150))?;
+    fs::write(root.join("shrinking.txt"), "a".repeat(50))?;
+    fs::write(root.join("fresh.txt"), "a".repeat(5))?;
+    fs::remove_file(root.join("doomed.txt"))?;
+
+    assert_eq!(
+        run_delta(cache.path(), &root),
+        indoc!(
+            "
+            data (205 B) -5 B
+            ├─ fresh.txt (5 B) new
+            ├─ growing.txt (150 B) +50 B
+            └─ shrinking.txt (50 B) -50 B
+
+            doomed.txt (10 B) gone"
+        ),
+        "Second run should annotate changes since the first"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn deterministic() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let data = tmp.path().join("data");
+
+    fs::create_dir_all(data.join("b"))?;
+    fs::create_dir_all(data.join("a"))?;
+    fs::write(data.join("b").join("two"), "bb")?;
+    fs::write(data.join("b").join("one"), "bb")?;
+    fs::write(data.join("c"), "ccc")?;
+
+    // Neither colors nor the timing of --stats make it into the output and entries are ordered
+    // by path in the absence of --sort.
+    assert_eq!(
+        utils::run_cmd_raw(&[
+            "--deterministic",
+            "--stats",
+            "--threads",
+            "4",
+            &data.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            data (7 B)
+            ├─ a
+            ├─ b (4 B)
+            │  ├─ one (2 B)
+            │  └─ two (2 B)
+            └─ c (3 B)
+
+            6 entries"
+        )
+    );
+
+    // Ties are broken by path.
+    assert_eq!(
+        utils::run_cmd_raw(&[
+            "--deterministic",
+            "--sort",
+            "size-rev",
+            &data.join("b").to_string_lossy()
+        ]),
+        indoc!(
+            "
+            b (4 B)
+            ├─ one (2 B)
+            └─ two (2 B)"
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+#[cfg(target_os = "linux")]
+mod test {
+    use indoc::indoc;
+    use std::{
+        fs,
+        os::unix::fs::PermissionsExt,
+        path::Path,
+        process::{Command, Stdio},
+    };
+    use strip_ansi_escapes::strip as strip_ansi_escapes;
+    use tempfile::TempDir;
+
+    const MANIFEST: &str =
+        r#"[{"Config": "config.json", "Layers": ["base/layer.tar", "app/layer.tar"]}]"#;
+
+    const CONFIG: &str = r#"{"history": [
+        {"created_by": "/bin/sh -c #(nop) ADD file:4d35 in / "},
+        {"created_by": "/bin/sh -c #(nop)  CMD [\"sh\"]", "empty_layer": true},
+        {"created_by": "COPY app /app # buildkit"}
+    ]}"#;
+
+    fn tar(archive: &Path, dir: &Path) {
+        let status = Command::new("tar")
+            .arg("-cf")
+            .arg(archive)
+            .arg("-C")
+            .arg(dir)
+            .arg(".")
+            .status()
+            .unwrap();
+
+        assert!(status.success());
+    }
+
+    /// Lays out the export of an image with two layers, the second of which deletes a file and
+    /// replaces the contents of a directory of the first.
+    fn export(tmp: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let base = tmp.join("base");
+        fs::create_dir_all(base.join("bin"))?;
+        fs::create_dir_all(base.join("etc"))?;
+        fs::create_dir_all(base.join("tmp").join("cache"))?;
+        fs::write(base.join("bin").join("sh"), vec![0; 100])?;
+        fs::write(base.join("etc").join("hosts"), vec![0; 20])?;
+        fs::write(base.join("etc").join("passwd"), vec![0; 30])?;
+        fs::write(base.join("tmp").join("cache").join("big"), vec![0; 1000])?;
+
+        let app = tmp.join("app");
+        fs::create_dir_all(app.join("app"))?;
+        fs::create_dir_all(app.join("etc"))?;
+        fs::create_dir_all(app.join("tmp"))?;
+        fs::write(app.join("app").join("main"), vec![0; 5000])?;
+        fs::write(app.join("etc").join(".wh.passwd"), "")?;
+        fs::write(app.join("tmp").join(".wh..wh..opq"), "")?;
+        fs::write(app.join("tmp").join("new"), vec![0; 10])?;
+
+        let export = tmp.join("export");
+        fs::create_dir_all(export.join("base"))?;
+        fs::create_dir_all(export.join("app"))?;
+        tar(&export.join("base").join("layer.tar"), &base);
+        tar(&export.join("app").join("layer.tar"), &app);
+        fs::write(export.join("manifest.json"), MANIFEST)?;
+        fs::write(export.join("config.json"), CONFIG)?;
+
+        tar(&tmp.join("image.tar"), &export);
+
+        let docker = tmp.join("docker");
+        let script = format!("#!/bin/sh\ncp {} \"$3\"\n", tmp.join("image.tar").display());
+        fs::write(&docker, script)?;
+        fs::set_permissions(&docker, fs::Permissions::from_mode(0o755))?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn docker() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        export(tmp.path())?;
+
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--",
+                "--threads",
+                "1",
+                "--no-config",
+                "--sort",
+                "name",
+            ])
+            .args(["--docker", "app:latest"])
+            .env("ERDTREE_DOCKER", tmp.path().join("docker"))
+            .stdin(Stdio::null())
+            .output()?;
+
+        assert!(output.status.success());
+
+        assert_eq!(
+            String::from_utf8(strip_ansi_escapes(output.stdout)?)?.trim(),
+            indoc!(
+                "
+                app:latest (5.01 KiB)
+                ├─ app (4.88 KiB)
+                │  └─ main (4.88 KiB) # layer 2: COPY app /app
+                ├─ bin (100 B)
+                │  └─ sh (100 B) # layer 1: ADD file:4d35 in /
+                ├─ etc (20 B)
+                │  └─ hosts (20 B) # layer 1: ADD file:4d35 in /
+                └─ tmp (10 B)
+                   └─ new (10 B) # layer 2: COPY app /app"
+            )
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use indoc::indoc;
+
+mod utils;
+
+/// Lines of the output of `et doctor` for `check`, its hints included.
+fn finding<'a>(output: &'a str, check: &str) -> Vec<&'a str> {
+    let mut lines = output.lines().skip_while(|line| !line.contains(check));
+
+    // Hints are indented on the lines that follow.
+    let first = lines.next().unwrap_or_default();
+    let hints = lines.take_while(|line| line.starts_with(' '));
+
+    std::iter::once(first).chain(hints).map(str::trim).collect()
+}
+
+#[test]
+fn doctor_valid_config() {
+    let config = indoc!(
+        "
+        --sort name
+
+        [triage]
+        --level 1
+        "
+    );
+
+    let output = utils::run_cmd_with_config(config, &["doctor"]);
+    let lines = finding(&output, "Config file");
+
+    assert!(lines[0].starts_with("ok"));
+    assert!(lines[0].ends_with("is valid along with 1 profile(s)"));
+}
+
+#[test]
+fn doctor_invalid_config() {
+    let config = indoc!(
+        "
+        --sort name
+
+        [triage]
+        --sort nope
+        "
+    );
+
+    // A broken config doesn't stand in the way of diagnosing it.
+    let output = utils::run_cmd_with_config(config, &["doctor"]);
+    let lines = finding(&output, "Config file");
+
+    assert!(lines[0].starts_with("error"));
+    assert!(lines[0].ends_with("is invalid"));
+    assert_eq!(
+        lines[1],
+        "[triage] invalid value 'nope' for '--sort <SORT>'"
+    );
+}
@@ -0,0 +1,53 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn du_compat() {
+    assert_eq!(
+        utils::run_cmd_raw(&["--du-compat", "--sort", "name", "tests/data"]),
+        indoc!(
+            "
+            308\ttests/data/dream_cycle/polaris.txt
+            308\ttests/data/dream_cycle
+            446\ttests/data/lipsum/lipsum.txt
+            446\ttests/data/lipsum
+            83\ttests/data/necronomicon.txt
+            161\ttests/data/nemesis.txt
+            100\ttests/data/nylarlathotep.txt
+            143\ttests/data/the_yellow_king/cassildas_song.md
+            143\ttests/data/the_yellow_king
+            1241\ttests/data"
+        )
+    )
+}
+
+#[test]
+fn du_compat_with_level() {
+    assert_eq!(
+        utils::run_cmd_raw(&[
+            "--du-compat",
+            "--level",
+            "1",
+            "--sort",
+            "name",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            308\ttests/data/dream_cycle
+            446\ttests/data/lipsum
+            83\ttests/data/necronomicon.txt
+            161\ttests/data/nemesis.txt
+            100\ttests/data/nylarlathotep.txt
+            143\ttests/data/the_yellow_king
+            1241\ttests/data"
+        )
+    )
+}
+
+#[test]
+#[should_panic]
+fn du_compat_conflicts_with_report() {
+    utils::run_cmd(&["--du-compat", "--report", "tests/data"]);
+}
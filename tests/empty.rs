@@ -0,0 +1,88 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+/// Creates a directory with zero-byte files and empty directories, one of which only contains a
+/// hidden file.
+fn scratch() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("scratch");
+
+    fs::create_dir_all(root.join("cache").join("tmp"))?;
+    fs::create_dir_all(root.join("logs"))?;
+    fs::write(root.join("cache").join("index.db"), "")?;
+    fs::write(root.join("cache").join("lock"), "")?;
+    fs::write(root.join("cache").join("notes.txt"), "remember")?;
+    fs::write(root.join("logs").join(".keep"), "")?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn empty() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = scratch()?;
+    let root = tmp.path().join("scratch");
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--report=empty", &root.to_string_lossy()]),
+        indoc!(
+            "
+            -   cache/index.db
+            -   cache/lock
+            d   cache/tmp
+            d   logs
+
+            2 directories, 2 files"
+        )
+    );
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--report=empty",
+            "--hidden",
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            -   cache/index.db
+            -   cache/lock
+            d   cache/tmp
+            -   logs/.keep
+
+            1 directory, 3 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn empty_json() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = scratch()?;
+    let root = tmp.path().join("scratch");
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--report=empty",
+            "--json",
+            &root.to_string_lossy()
+        ]),
+        r#"{"schema_version":1,"counts":{"directories":2,"files":2,"links":0},"entries":[{"type":"-","path":"cache/index.db"},{"type":"-","path":"cache/lock"},{"type":"d","path":"cache/tmp"},{"type":"d","path":"logs"}]}"#
+    );
+
+    Ok(())
+}
+
+#[test]
+fn empty_none() {
+    assert_eq!(
+        utils::run_cmd(&["--report=empty", "tests/data"]),
+        "no empty files or directories"
+    );
+}
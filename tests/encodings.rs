@@ -0,0 +1,71 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+/// Creates a repository whose text files use a variety of encodings and line endings.
+fn repo() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("repo");
+
+    fs::create_dir_all(root.join("unix"))?;
+    fs::create_dir_all(root.join("win"))?;
+
+    fs::write(root.join("unix").join("build.sh"), "make\nmake install\n")?;
+    fs::write(root.join("unix").join("cafe.txt"), b"caf\xe9\n")?;
+    fs::write(root.join("unix").join("tool"), b"\x7fELF\0\x01")?;
+    fs::write(
+        root.join("win").join("build.bat"),
+        "nmake\r\nnmake install\r\n",
+    )?;
+    fs::write(root.join("win").join("notes.txt"), "one\ntwo\r\n")?;
+    fs::write(root.join("win").join("wide.txt"), b"\xff\xfea\0\r\0\n\0")?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn encodings() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = repo()?;
+    let root = tmp.path().join("repo");
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--report=encodings",
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            utf-8  utf-16  latin-1  lf  crlf  mixed   path
+                3       1        1   2     2      1   repo
+                1       0        1   2     0      0   unix
+                2       1        0   0     2      1   win"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn encodings_json() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = repo()?;
+    let root = tmp.path().join("repo");
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--report=encodings",
+            "--json",
+            "--level",
+            "0",
+            &root.to_string_lossy()
+        ]),
+        r#"{"schema_version":1,"directories":[{"path":"repo","utf-8":3,"utf-16":1,"latin-1":1,"lf":2,"crlf":2,"mixed":1}]}"#
+    );
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn expand() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--level",
+            "1",
+            "--expand",
+            "lipsum",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1.21 KiB)
+            ├─ dream_cycle (308 B)
+            ├─ lipsum (446 B)
+            │  └─ lipsum.txt (446 B)
+            ├─ necronomicon.txt (83 B)
+            ├─ nemesis.txt (161 B)
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king (143 B)"
+        ),
+        "Failed to print lipsum in full past the max level of 1."
+    )
+}
+
+#[test]
+fn expand_beneath_level_zero() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--level",
+            "0",
+            "--expand",
+            "the_yellow_king",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1.21 KiB)
+            └─ the_yellow_king (143 B)
+               └─ cassildas_song.md (143 B)"
+        ),
+        "Failed to print only the_yellow_king at a max level of 0."
+    )
+}
@@ -0,0 +1,48 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use serde_json::Value;
+    use std::{fs, os::unix::fs::MetadataExt};
+    use tempfile::TempDir;
+
+    #[test]
+    fn extended_metadata() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("data");
+
+        fs::create_dir_all(&root)?;
+        fs::write(root.join("notes.txt"), "notes")?;
+
+        let report: Value = serde_json::from_str(&super::utils::run_cmd(&[
+            "--report",
+            "--json",
+            "--extended-metadata",
+            &root.to_string_lossy(),
+        ]))?;
+
+        let md = fs::metadata(root.join("notes.txt"))?;
+        let entry = &report["entries"][0];
+
+        assert_eq!(entry["path"], "notes.txt");
+        assert_eq!(entry["dev"], md.dev());
+        assert_eq!(entry["ino"], md.ino());
+        assert_eq!(entry["nlink"], md.nlink());
+        assert_eq!(entry["uid"], md.uid());
+        assert_eq!(entry["gid"], md.gid());
+        assert_eq!(entry["mode"], md.mode());
+        assert_eq!(entry["mtime"], md.mtime());
+
+        // Reading the file to classify its contents may have updated its access time.
+        assert!(entry["atime"].is_i64());
+        assert!(entry["ctime"].is_i64());
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn extended_metadata_requires_json() {
+        super::utils::run_cmd(&["--report", "--extended-metadata"]);
+    }
+}
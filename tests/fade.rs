@@ -0,0 +1,33 @@
+mod utils;
+
+#[test]
+fn fade() {
+    let out = utils::run_cmd_raw(&["--sort", "name", "--fade", "tests/data"]);
+
+    let line_of = |name: &str| out.lines().find(|line| line.contains(name)).unwrap();
+
+    assert!(
+        !line_of("dream_cycle").contains("\x1b[1;38;5;"),
+        "Shallow entries should keep their usual style"
+    );
+
+    assert!(
+        line_of("polaris.txt").contains("\x1b[1;38;5;250mpolaris.txt"),
+        "Entries beyond the second level should be dimmed"
+    );
+}
+
+#[test]
+fn fade_keeps_colors() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::TempDir::new()?;
+    std::fs::create_dir_all(tmp.path().join("a").join("b"))?;
+
+    let out = utils::run_cmd_raw(&["--fade", &tmp.path().to_string_lossy()]);
+
+    assert!(
+        out.contains("\x1b[1;38;2;28;91;160mb"),
+        "Colored entries should be darkened rather than grayed out"
+    );
+
+    Ok(())
+}
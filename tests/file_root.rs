@@ -0,0 +1,46 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn file_root() {
+    assert_eq!(
+        utils::run_cmd(&["tests/data/nylarlathotep.txt"]),
+        "nylarlathotep.txt (100 B)"
+    );
+}
+
+#[test]
+fn file_root_report() {
+    assert_eq!(
+        utils::run_cmd(&["--report", "tests/data/nylarlathotep.txt"]),
+        "-   100 B   nylarlathotep.txt"
+    );
+}
+
+#[test]
+fn glob_root() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "tests/data/*.txt"]),
+        indoc!(
+            "
+            data (344 B)
+            ├─ necronomicon.txt (83 B)
+            ├─ nemesis.txt (161 B)
+            └─ nylarlathotep.txt (100 B)"
+        )
+    );
+}
+
+#[test]
+fn glob_root_matching_directory() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "tests/data/l*"]),
+        indoc!(
+            "
+            data (446 B)
+            └─ lipsum (446 B)
+               └─ lipsum.txt (446 B)"
+        )
+    );
+}
@@ -0,0 +1,55 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn filter() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--filter",
+            r#"size > 150 && ext == "txt""#,
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (915 B)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.txt (308 B)
+            ├─ lipsum (446 B)
+            │  └─ lipsum.txt (446 B)
+            └─ nemesis.txt (161 B)"
+        )
+    )
+}
+
+#[test]
+fn filter_precedence() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--filter",
+            "name == necronomicon.txt || depth == 2 && !(ext == md)",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (837 B)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.txt (308 B)
+            ├─ lipsum (446 B)
+            │  └─ lipsum.txt (446 B)
+            └─ necronomicon.txt (83 B)"
+        )
+    )
+}
+
+#[test]
+fn filter_nothing_matches() {
+    assert_eq!(
+        utils::run_cmd(&["--filter", "size >= 1G", "tests/data"]),
+        "data"
+    )
+}
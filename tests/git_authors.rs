@@ -0,0 +1,88 @@
+use indoc::indoc;
+use std::{fs, path::Path, process::Command};
+use tempfile::TempDir;
+
+mod utils;
+
+fn git(repo: &Path, author: &str, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["-c", &format!("user.name={author}")])
+        .args(["-c", "user.email=dev@example.com"])
+        .args(["-c", "commit.gpgsign=false"])
+        .args(args)
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+}
+
+/// A repository in which Carcosa wrote both files, then Hastur rewrote one of them, and which has
+/// a file that was never committed.
+fn repo() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let repo = tmp.path();
+
+    git(repo, "Carcosa", &["init", "--quiet"]);
+
+    fs::create_dir(repo.join("src"))?;
+    fs::write(repo.join("src").join("lib.rs"), "a".repeat(100))?;
+    fs::write(repo.join("README.md"), "a".repeat(40))?;
+    git(repo, "Carcosa", &["add", "."]);
+    git(
+        repo,
+        "Carcosa",
+        &["commit", "--quiet", "-m", "Initial commit"],
+    );
+
+    fs::write(repo.join("src").join("lib.rs"), "a".repeat(200))?;
+    git(repo, "Hastur", &["commit", "--quiet", "-am", "Rewrite"]);
+
+    fs::write(repo.join("notes.txt"), "a".repeat(10))?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn git_authors() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = repo()?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--git-authors",
+            "--sort",
+            "name",
+            "--root-label",
+            "repo",
+            &tmp.path().to_string_lossy()
+        ]),
+        indoc!(
+            "
+            repo (250 B)
+            ├─ README.md (40 B) [Carcosa]
+            ├─ notes.txt (10 B)
+            └─ src (200 B)
+               └─ lib.rs (200 B) [Hastur]"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn authors_report() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = repo()?;
+
+    assert_eq!(
+        utils::run_cmd(&["--report=authors", &tmp.path().to_string_lossy()]),
+        indoc!(
+            "
+            200 B   1   Hastur
+             40 B   1   Carcosa
+             10 B   1   (untracked)"
+        )
+    );
+
+    Ok(())
+}
@@ -81,6 +81,41 @@ fn iglob() {
     )
 }
 
+#[test]
+fn glob_skips_hidden() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--glob", "*a*", "tests/data"]),
+        indoc!(
+            "
+            data (551 B)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.txt (308 B)
+            ├─ lipsum
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king (143 B)
+               └─ cassildas_song.md (143 B)"
+        )
+    )
+}
+
+#[test]
+fn glob_hidden() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--glob", "*a*", "--hidden", "tests/data"]),
+        indoc!(
+            "
+            data (637 B)
+            ├─ .dagon (86 B)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.txt (308 B)
+            ├─ lipsum
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king (143 B)
+               └─ cassildas_song.md (143 B)"
+        )
+    )
+}
+
 #[test]
 fn glob_stdin() {
     use std::io::Write;
@@ -124,3 +159,67 @@ fn glob_stdin() {
     );
     assert!(output.status.success());
 }
+
+#[test]
+fn patterns_from() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::NamedTempFile::new()?;
+    std::fs::write(
+        tmp.path(),
+        "# Cleared for the audit\n*.txt\n\n!nemesis.txt\n",
+    )?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--patterns-from",
+            &tmp.path().to_string_lossy(),
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (937 B)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.txt (308 B)
+            ├─ lipsum (446 B)
+            │  └─ lipsum.txt (446 B)
+            ├─ necronomicon.txt (83 B)
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn patterns_from_precede_globs() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::NamedTempFile::new()?;
+    std::fs::write(tmp.path(), "*.txt\n!nemesis.txt\n")?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--patterns-from",
+            &tmp.path().to_string_lossy(),
+            "--glob",
+            "nemesis.txt",
+            "--glob",
+            "!lipsum",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (652 B)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.txt (308 B)
+            ├─ necronomicon.txt (83 B)
+            ├─ nemesis.txt (161 B)
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king"
+        )
+    );
+
+    Ok(())
+}
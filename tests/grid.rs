@@ -0,0 +1,62 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn grid() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("site");
+
+    fs::create_dir_all(root.join("assets"))?;
+    fs::create_dir_all(root.join("src"))?;
+    fs::write(root.join("LICENSE"), "MIT")?;
+    fs::write(root.join("src").join("main.rs"), "fn main() {}")?;
+
+    for i in 1..=7 {
+        fs::write(root.join("assets").join(format!("icon_{i}.svg")), "<svg/>")?;
+    }
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--grid",
+            "--width",
+            "60",
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            site (57 B)
+            ├─ LICENSE (3 B)
+            ├─ assets (42 B)
+            │  └─ icon_1.svg (6 B)  icon_4.svg (6 B)  icon_7.svg (6 B)
+            │     icon_2.svg (6 B)  icon_5.svg (6 B)
+            │     icon_3.svg (6 B)  icon_6.svg (6 B)
+            └─ src (12 B)
+               └─ main.rs (12 B)"
+        )
+    );
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--grid",
+            "--level",
+            "1",
+            "--width",
+            "50",
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            site (57 B)
+            └─ LICENSE (3 B)  assets (42 B)  src (12 B)"
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+/// A hidden directory such as `~/.dotfiles` with nothing but hidden entries directly beneath it,
+/// a repository among them, and more hidden entries further down.
+fn dotfiles() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join(".dotfiles");
+
+    fs::create_dir_all(root.join(".git"))?;
+    fs::create_dir_all(root.join(".vim"))?;
+    fs::write(root.join(".git").join("HEAD"), "a".repeat(5))?;
+    fs::write(root.join(".bashrc"), "a".repeat(10))?;
+    fs::write(root.join(".vim").join(".netrwhist"), "a".repeat(20))?;
+    fs::write(root.join(".vim").join("vimrc"), "a".repeat(30))?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn hidden_root() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = dotfiles()?;
+    let root = tmp.path().join(".dotfiles");
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", &root.to_string_lossy()]),
+        indoc!(
+            "
+            .dotfiles (40 B)
+            ├─ .bashrc (10 B)
+            └─ .vim (30 B)
+               └─ vimrc (30 B)"
+        ),
+        "Failed to print the hidden entries directly beneath a hidden root of only hidden entries"
+    );
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--hidden", &root.to_string_lossy()]),
+        indoc!(
+            "
+            .dotfiles (65 B)
+            ├─ .bashrc (10 B)
+            ├─ .git (5 B)
+            │  └─ HEAD (5 B)
+            └─ .vim (50 B)
+               ├─ .netrwhist (20 B)
+               └─ vimrc (30 B)"
+        ),
+        "Failed to print every hidden entry beneath a hidden root with --hidden"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn hidden_root_with_visible_entries() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = dotfiles()?;
+    let root = tmp.path().join(".dotfiles");
+
+    fs::write(root.join("README"), "a".repeat(15))?;
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", &root.to_string_lossy()]),
+        indoc!(
+            "
+            .dotfiles (15 B)
+            └─ README (15 B)"
+        ),
+        "Failed to leave out hidden entries beneath a hidden root with visible ones"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn hidden_root_ignore_check() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = dotfiles()?;
+    let root = tmp.path().join(".dotfiles");
+    let root = root.to_string_lossy();
+
+    assert_eq!(
+        utils::run_cmd(&[
+            &root,
+            "ignore-check",
+            &format!("{root}/.bashrc"),
+            &format!("{root}/.git/HEAD"),
+            &format!("{root}/.vim/.netrwhist"),
+        ])
+        .replace(&format!("{root}/"), ""),
+        [
+            ".bashrc: walked",
+            ".git/HEAD: skipped beneath .git which is skipped as it's hidden; provide --hidden to include it",
+            ".vim/.netrwhist: skipped as it's hidden; provide --hidden to include it",
+        ]
+        .join("\n")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use std::path::Path;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn history() -> Result<(), Box<dyn std::error::Error>> {
+    let history_dir = TempDir::new()?;
+    let history_dir = history_dir.path().to_string_lossy();
+
+    utils::run_cmd(&[
+        "daemon",
+        "--interval",
+        "0s",
+        "--runs",
+        "3",
+        "--history-dir",
+        &history_dir,
+        "tests/data",
+    ]);
+
+    let lipsum = Path::new("tests/data/lipsum").canonicalize()?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "history",
+            "--history-dir",
+            &history_dir,
+            "tests/data/lipsum"
+        ])
+        .lines()
+        .map(|line| line.split(" spanning ").next().unwrap())
+        .collect::<Vec<_>>(),
+        vec![
+            lipsum.to_string_lossy().as_ref(),
+            "\u{2581}\u{2581}\u{2581} 446 B \u{2192} 446 B across 3 scans"
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn history_requires_recorded_scans() {
+    let history_dir = TempDir::new().unwrap();
+
+    utils::run_cmd(&[
+        "history",
+        "--history-dir",
+        &history_dir.path().to_string_lossy(),
+        "tests/data",
+    ]);
+}
@@ -0,0 +1,63 @@
+mod utils;
+
+#[test]
+fn html() {
+    let html = utils::run_cmd_raw(&[
+        "--output",
+        "html",
+        "--deterministic",
+        "--sort",
+        "name",
+        "--top",
+        "2",
+        "tests/data",
+    ]);
+
+    let section = |heading: &str| {
+        let start = html.find(&format!("<h2>{heading}</h2>")).unwrap();
+        let end = html[start..].find("</table>").unwrap();
+        html[start..start + end]
+            .lines()
+            .skip(2)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h1>Disk usage of data</h1>"));
+    assert!(!html.contains("Generated on"));
+    assert!(html.ends_with("</html>"));
+
+    assert_eq!(
+        section("Summary"),
+        [
+            "<tr><th>Disk usage</th><td>1.21 KiB</td></tr>",
+            "<tr><th>Files</th><td>6</td></tr>",
+            "<tr><th>Directories</th><td>3</td></tr>",
+            "<tr><th>Traversal</th><td>10 entries</td></tr>",
+        ]
+        .join("\n")
+    );
+
+    assert_eq!(
+        section("Largest files"),
+        [
+            r#"<tr><th class="num">#</th><th class="num">Size</th><th>Path</th></tr>"#,
+            r#"<tr><td class="num">1</td><td class="num">446 B</td><td>lipsum/lipsum.txt</td></tr>"#,
+            r#"<tr><td class="num">2</td><td class="num">308 B</td><td>dream_cycle/polaris.txt</td></tr>"#,
+        ]
+        .join("\n")
+    );
+
+    assert_eq!(
+        section("By extension"),
+        [
+            r#"<tr><th>Extension</th><th class="num">Files</th><th class="num">Size</th><th class="num">Share</th></tr>"#,
+            r#"<tr><td>txt</td><td class="num">5</td><td class="num">1.07 KiB</td><td class="num">88.5%</td></tr>"#,
+            r#"<tr><td>md</td><td class="num">1</td><td class="num">143 B</td><td class="num">11.5%</td></tr>"#,
+        ]
+        .join("\n")
+    );
+
+    assert!(html.contains("\n   └─ cassildas_song.md (143 B)</pre>"));
+}
@@ -0,0 +1,45 @@
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn icon_map() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("schema");
+
+    fs::create_dir_all(&root)?;
+    fs::write(root.join("Justfile"), "")?;
+    fs::write(root.join("api.proto"), "")?;
+    fs::write(root.join("main.rs"), "")?;
+
+    let icon_map = tmp.path().join("icons.toml");
+
+    fs::write(
+        &icon_map,
+        r#"
+        [extensions]
+        proto = { glyph = "P", color = 33 }
+
+        [file_names]
+        Justfile = "J"
+        "#,
+    )?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--icons",
+            "--icon-map",
+            &icon_map.to_string_lossy(),
+            &root.to_string_lossy()
+        ]),
+        "\u{f413} schema\n\
+         ├─ J Justfile (0 B)\n\
+         ├─ P api.proto (0 B)\n\
+         └─ \u{e7a8} main.rs (0 B)"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn dir_name_icons() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("project");
+
+    for dir in ["node_modules", "other", "src", "tests"] {
+        fs::create_dir_all(root.join(dir))?;
+    }
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--icons", &root.to_string_lossy()]),
+        "\u{f413} project\n\
+         ├─ \u{e5fa} node_modules\n\
+         ├─ \u{f413} other\n\
+         ├─ \u{f121} src\n\
+         └─ \u{f0c3} tests"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use std::{fs, path::Path, process::Command};
+use tempfile::TempDir;
+
+mod utils;
+
+/// A repository that ignores `target/` and log files other than `keep.log`, and that has a
+/// hidden file of its own.
+fn repo() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let repo = tmp.path();
+
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["init", "--quiet"])
+        .status()?;
+
+    assert!(status.success());
+
+    fs::create_dir_all(repo.join("target").join("debug"))?;
+    fs::create_dir(repo.join("logs"))?;
+    fs::write(repo.join(".gitignore"), "target/\n*.log\n!keep.log\n")?;
+    fs::write(repo.join(".env"), "")?;
+    fs::write(repo.join("main.rs"), "")?;
+    fs::write(repo.join("target").join("debug").join("et"), "")?;
+    fs::write(repo.join("logs").join("a.log"), "")?;
+    fs::write(repo.join("logs").join("keep.log"), "")?;
+
+    Ok(tmp)
+}
+
+fn check(root: &Path, args: &[&str], paths: &[&str]) -> String {
+    let root = root.to_string_lossy();
+    let paths = paths
+        .iter()
+        .map(|path| root.to_string() + "/" + path)
+        .collect::<Vec<_>>();
+
+    let mut cmd_args = args.to_vec();
+    cmd_args.push(&root);
+    cmd_args.push("ignore-check");
+    cmd_args.extend(paths.iter().map(String::as_str));
+
+    utils::run_cmd(&cmd_args).replace(&format!("{root}/"), "")
+}
+
+#[test]
+fn ignore_check() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = repo()?;
+
+    assert_eq!(
+        check(
+            tmp.path(),
+            &[],
+            &[
+                "main.rs",
+                "target/debug/et",
+                "logs/a.log",
+                "logs/keep.log",
+                ".env"
+            ]
+        ),
+        [
+            "main.rs: walked",
+            "target/debug/et: skipped beneath target which is skipped by `target/` in .gitignore",
+            "logs/a.log: skipped by `*.log` in .gitignore",
+            "logs/keep.log: walked as whitelisted by `!keep.log` in .gitignore",
+            ".env: skipped as it's hidden; provide --hidden to include it",
+        ]
+        .join("\n")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ignore_check_globs() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = repo()?;
+
+    assert_eq!(
+        check(
+            tmp.path(),
+            &["--glob", "*.rs", "--glob", "!target"],
+            &["main.rs", "logs/keep.log", "target/debug/et"]
+        ),
+        [
+            "main.rs: walked as whitelisted by `*.rs` from the command line",
+            "logs/keep.log: skipped as it doesn't match any --glob pattern",
+            "target/debug/et: skipped beneath target which is skipped by `!target` from the command line",
+        ]
+        .join("\n")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ignore_check_exclusions() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = repo()?;
+
+    assert_eq!(
+        check(tmp.path(), &["--dirs-only"], &["main.rs", "logs"]),
+        ["main.rs: skipped by --dirs-only", "logs: walked"].join("\n")
+    );
+
+    assert_eq!(
+        check(
+            tmp.path(),
+            &["--invert-match", "--glob", "*.rs"],
+            &["main.rs", "logs/keep.log"]
+        ),
+        [
+            "main.rs: skipped as it's a file not selected by --filter or --invert-match",
+            "logs/keep.log: walked as whitelisted by `!keep.log` in .gitignore",
+        ]
+        .join("\n")
+    );
+
+    assert_eq!(
+        check(tmp.path(), &["--filter", "size > 0"], &["main.rs"]),
+        "main.rs: skipped as it's a file not selected by --filter or --invert-match"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ignore_check_outside_root() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = repo()?;
+    let root = tmp.path().join("logs");
+
+    assert_eq!(
+        utils::run_cmd(&[
+            &root.to_string_lossy(),
+            "ignore-check",
+            &tmp.path().join("main.rs").to_string_lossy()
+        ]),
+        format!(
+            "{}: not beneath {}",
+            tmp.path().join("main.rs").display(),
+            fs::canonicalize(&root)?.display()
+        )
+    );
+
+    Ok(())
+}
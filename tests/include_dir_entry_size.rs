@@ -0,0 +1,25 @@
+use std::{fs, path::Path};
+
+mod utils;
+
+fn root_bytes(args: &[&str]) -> u64 {
+    let out = utils::run_cmd_raw(&[&["--du-compat"], args, &["tests/data"]].concat());
+    let root = out.lines().last().unwrap();
+    root.split('\t').next().unwrap().parse().unwrap()
+}
+
+#[test]
+fn include_dir_entry_size() -> Result<(), Box<dyn std::error::Error>> {
+    let dir_entry_bytes = ["", "dream_cycle", "lipsum", "the_yellow_king"]
+        .into_iter()
+        .map(|dir| fs::metadata(Path::new("tests/data").join(dir)).map(|md| md.len()))
+        .sum::<Result<u64, _>>()?;
+
+    assert_eq!(
+        root_bytes(&["--include-dir-entry-size"]),
+        root_bytes(&[]) + dir_entry_bytes,
+        "Directory entry sizes should count toward disk usage"
+    );
+
+    Ok(())
+}
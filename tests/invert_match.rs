@@ -0,0 +1,85 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn invert_glob() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--glob",
+            "*.txt",
+            "--invert-match",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (143 B)
+            └─ the_yellow_king (143 B)
+               └─ cassildas_song.md (143 B)"
+        )
+    )
+}
+
+#[test]
+fn invert_filter() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--filter",
+            "size > 150",
+            "-v",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (326 B)
+            ├─ necronomicon.txt (83 B)
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king (143 B)
+               └─ cassildas_song.md (143 B)"
+        )
+    )
+}
+
+#[test]
+fn invert_glob_and_filter() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--glob",
+            "*.txt",
+            "--filter",
+            "size < 150",
+            "-v",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1.03 KiB)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.txt (308 B)
+            ├─ lipsum (446 B)
+            │  └─ lipsum.txt (446 B)
+            ├─ nemesis.txt (161 B)
+            └─ the_yellow_king (143 B)
+               └─ cassildas_song.md (143 B)"
+        )
+    )
+}
+
+#[test]
+fn invert_nothing() {
+    let output = std::process::Command::new("cargo")
+        .args(["run", "--", "--no-config", "-v", "tests/data"])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("'--invert-match' requires glob patterns or a --filter expression"));
+}
@@ -0,0 +1,84 @@
+mod utils;
+
+#[cfg(windows)]
+mod test {
+    use std::{fs, process::Command};
+    use tempfile::TempDir;
+
+    /// Creates a directory junction at `link` pointing to `target`.
+    fn junction(link: &std::path::Path, target: &std::path::Path) {
+        let status = Command::new("cmd")
+            .args(["/C", "mklink", "/J"])
+            .arg(link)
+            .arg(target)
+            .status()
+            .unwrap();
+
+        assert!(status.success());
+    }
+
+    #[test]
+    fn junction_annotated() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("carcosa");
+
+        fs::create_dir_all(root.join("hali"))?;
+        fs::write(root.join("hali").join("lake.txt"), "Hali")?;
+        junction(&root.join("lake"), &root.join("hali"));
+
+        let output = super::utils::run_cmd(&["--sort", "name", &root.to_string_lossy()]);
+
+        assert!(output
+            .lines()
+            .any(|line| line.contains("lake") && line.ends_with("[junction]")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn junction_cycle() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("carcosa");
+
+        fs::create_dir_all(&root)?;
+        fs::write(root.join("hastur.txt"), "Hastur")?;
+        junction(&root.join("again"), &root);
+
+        let output =
+            super::utils::run_cmd(&["--follow-links", "--sort", "name", &root.to_string_lossy()]);
+
+        assert_eq!(output.matches("hastur.txt").count(), 1);
+
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use indoc::indoc;
+    use std::{fs, os::unix::fs::symlink};
+    use tempfile::TempDir;
+
+    #[test]
+    fn link_cycle() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("carcosa");
+
+        fs::create_dir_all(root.join("hali"))?;
+        fs::write(root.join("hali").join("hastur.txt"), "Hastur")?;
+        symlink(&root, root.join("hali").join("again"))?;
+
+        assert_eq!(
+            super::utils::run_cmd(&["--follow-links", "--sort", "name", &root.to_string_lossy()]),
+            indoc!(
+                "
+                carcosa (6 B)
+                └─ hali (6 B)
+                   └─ hastur.txt (6 B)"
+            ),
+            "Failed to leave out the link leading back to the root"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use indoc::indoc;
+use std::process::{Command, Stdio};
+
+mod utils;
+
+#[test]
+fn lang() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--lang",
+            "fr_FR.UTF-8",
+            "--sort",
+            "size",
+            "--level",
+            "1",
+            "--count",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1.21 Kio)
+            ├─ necronomicon.txt (83 o)
+            ├─ nylarlathotep.txt (100 o)
+            ├─ the_yellow_king (143 o)
+            ├─ nemesis.txt (161 o)
+            ├─ dream_cycle (308 o)
+            └─ lipsum (446 o)
+
+            3 répertoires, 6 fichiers"
+        )
+    );
+}
+
+#[test]
+fn lang_stats() {
+    let out = utils::run_cmd(&["--lang", "de", "--stats", "--deterministic", "tests/data"]);
+
+    assert!(out.ends_with("10 Einträge"), "{out}");
+}
+
+#[test]
+fn lang_truncated() {
+    for (max_lines, marker) in [
+        ("8", "\u{2026} Ausgabe gekürzt (2 weitere Einträge)"),
+        ("9", "\u{2026} Ausgabe gekürzt (1 weiterer Eintrag)"),
+    ] {
+        let out = utils::run_cmd(&["--lang", "de", "--max-lines", max_lines, "tests/data"]);
+
+        assert!(out.ends_with(marker), "{out}");
+    }
+}
+
+#[test]
+fn lang_from_env() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--no-config",
+            "--patterns-from",
+            "tests/data/missing",
+            "tests/data",
+        ])
+        .env("LC_ALL", "")
+        .env("LC_MESSAGES", "es_ES.UTF-8")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("No se pudieron leer los patrones de tests/data/missing: "));
+}
+
+#[test]
+fn lang_overrides_env() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--no-config",
+            "--lang",
+            "C",
+            "--stats",
+            "tests/data",
+        ])
+        .env("LC_ALL", "fr_FR.UTF-8")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains(" entries/s, "));
+}
+
+#[test]
+fn lang_unsupported() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "--no-config", "--lang", "xx", "tests/data"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unsupported language 'xx'"));
+}
@@ -19,3 +19,57 @@ fn level() {
         "Failed to print at max level of 1."
     )
 }
+
+#[test]
+fn level_subtree() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--level",
+            "1",
+            "--level",
+            "1:dream_cycle",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1.21 KiB)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.txt (308 B)
+            ├─ lipsum (446 B)
+            ├─ necronomicon.txt (83 B)
+            ├─ nemesis.txt (161 B)
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king (143 B)"
+        ),
+        "Failed to print dream_cycle past the max level of 1."
+    )
+}
+
+#[test]
+fn level_subtree_shallower() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--level",
+            "0:./lipsum/",
+            "--level",
+            "0:the_yellow_king",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1.21 KiB)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.txt (308 B)
+            ├─ lipsum (446 B)
+            ├─ necronomicon.txt (83 B)
+            ├─ nemesis.txt (161 B)
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king (143 B)"
+        ),
+        "Failed to print lipsum and the_yellow_king without their contents."
+    )
+}
@@ -0,0 +1,83 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::{fs, os::unix::fs::symlink};
+    use tempfile::TempDir;
+
+    fn links() -> Result<TempDir, Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("links");
+
+        fs::create_dir_all(root.join("data"))?;
+        fs::write(root.join("data").join("blob"), [0_u8; 1000])?;
+        symlink("data/blob", root.join("to_blob"))?;
+        symlink("missing", root.join("to_missing"))?;
+
+        Ok(tmp)
+    }
+
+    fn run(tmp: &TempDir, link_sizes: &str) -> String {
+        let root = tmp.path().join("links");
+
+        super::utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--link-sizes",
+            link_sizes,
+            &root.to_string_lossy(),
+        ])
+    }
+
+    #[test]
+    fn link_sizes_none() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(
+            run(&links()?, "none"),
+            indoc!(
+                "
+                links (1000 B)
+                ├─ data (1000 B)
+                │  └─ blob (1000 B)
+                ├─ to_blob → blob
+                └─ to_missing → missing"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn link_sizes_self() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(
+            run(&links()?, "self"),
+            indoc!(
+                "
+                links (1016 B)
+                ├─ data (1000 B)
+                │  └─ blob (1000 B)
+                ├─ to_blob → blob (9 B)
+                └─ to_missing → missing (7 B)"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn link_sizes_target() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(
+            run(&links()?, "target"),
+            indoc!(
+                "
+                links (1.95 KiB)
+                ├─ data (1000 B)
+                │  └─ blob (1000 B)
+                ├─ to_blob → blob (1000 B)
+                └─ to_missing → missing"
+            )
+        );
+
+        Ok(())
+    }
+}
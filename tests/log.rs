@@ -0,0 +1,56 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn log_file() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let log_file = tmp.path().join("et.log");
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--log-file",
+            &log_file.to_string_lossy(),
+            "tests/data/the_yellow_king"
+        ]),
+        indoc!(
+            "
+            the_yellow_king (143 B)
+            └─ cassildas_song.md (143 B)"
+        )
+    );
+
+    let log = fs::read_to_string(log_file)?;
+
+    for span in ["walk{", "assemble{", "render:"] {
+        assert!(log.contains(span), "{span} missing from {log}");
+    }
+
+    assert!(!log.contains("sort{"));
+
+    Ok(())
+}
+
+#[test]
+fn log_level_trace() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let log_file = tmp.path().join("et.log");
+
+    utils::run_cmd(&[
+        "--sort",
+        "name",
+        "--log-level",
+        "trace",
+        "--log-file",
+        &log_file.to_string_lossy(),
+        "tests/data/the_yellow_king",
+    ]);
+
+    assert!(fs::read_to_string(log_file)?.contains("sort{children=1}"));
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+/// Nests directories deep enough to exceed `MAX_PATH` on Windows.
+fn deep_tree() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+
+    let mut leaf = tmp.path().join("deep");
+
+    for i in 0..8 {
+        leaf.push(format!("{i}{}", "x".repeat(50)));
+    }
+
+    fs::create_dir_all(&leaf)?;
+    fs::write(leaf.join("leaf.txt"), "leaf")?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn long_paths() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = deep_tree()?;
+    let root = tmp.path().join("deep");
+
+    let output = utils::run_cmd(&[&root.to_string_lossy()]);
+
+    assert!(output.starts_with("deep (4 B)"));
+    assert!(output.ends_with("leaf.txt (4 B)"));
+
+    Ok(())
+}
+
+#[test]
+fn long_paths_strip_verbatim() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = deep_tree()?;
+    let root = tmp.path().join("deep");
+
+    let output = utils::run_cmd(&[
+        "--output",
+        "prometheus",
+        "--strip-verbatim",
+        &root.to_string_lossy(),
+    ]);
+
+    assert!(!output.contains(r"\\\\?\\"));
+    assert!(output.contains(&format!("{}x", "x".repeat(49))));
+
+    Ok(())
+}
+
+#[cfg(windows)]
+#[test]
+fn strip_verbatim_root() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = deep_tree()?;
+    let root = fs::canonicalize(tmp.path().join("deep"))?;
+    let root = root.to_string_lossy();
+
+    let stripped = root
+        .strip_prefix(r"\\?\")
+        .expect("canonical paths are verbatim");
+
+    let output = utils::run_cmd(&["--relative", "--strip-verbatim", &root]);
+
+    assert!(output.starts_with(&format!("{stripped} (4 B)")));
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn max_lines() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let data = tmp.path().join("data");
+
+    fs::create_dir_all(data.join("a").join("b"))?;
+    fs::create_dir_all(data.join("c"))?;
+    fs::write(data.join("a").join("b").join("big"), "a".repeat(500))?;
+    fs::write(data.join("a").join("x"), "b".repeat(100))?;
+    fs::write(data.join("c").join("mid"), "c".repeat(300))?;
+    fs::write(data.join("small"), "d".repeat(10))?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--max-lines",
+            "4",
+            &data.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            data (910 B)
+            ├─ a (600 B)
+            │  ├─ b (500 B)
+            │  │  └─ big (500 B)
+            … output truncated (4 more entries)"
+        )
+    );
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--max-lines",
+            "3",
+            "--level",
+            "1",
+            &data.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            data (910 B)
+            ├─ a (600 B)
+            ├─ c (300 B)
+            … output truncated (1 more entry)"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn max_lines_summary() {
+    assert_eq!(
+        utils::run_cmd_raw(&[
+            "--sort",
+            "name",
+            "--deterministic",
+            "--count",
+            "--max-lines",
+            "2",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1.21 KiB)
+            ├─ dream_cycle (308 B)
+            … output truncated (8 more entries)
+            3 directories, 4 files"
+        ),
+        "The summary should follow the truncation marker without a blank line in between"
+    );
+}
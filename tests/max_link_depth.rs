@@ -0,0 +1,57 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    #[test]
+    fn max_link_depth() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("root");
+        let outer = tmp.path().join("outer");
+
+        fs::create_dir_all(&root)?;
+        fs::create_dir_all(outer.join("x"))?;
+        fs::create_dir_all(outer.join("y"))?;
+        fs::write(outer.join("y").join("file"), "a".repeat(100))?;
+        symlink("../y", outer.join("x").join("inner"))?;
+        symlink("../outer/x", root.join("link"))?;
+
+        let root = root.to_string_lossy();
+
+        assert_eq!(
+            super::utils::run_cmd(&["--sort", "name", "--follow-links", &root]),
+            indoc!(
+                "
+                root (100 B)
+                └─ link → x (100 B)
+                   └─ inner → y (100 B)
+                      └─ file (100 B)"
+            ),
+            "Failed to follow nested symlinks"
+        );
+
+        assert_eq!(
+            super::utils::run_cmd(&[
+                "--sort",
+                "name",
+                "--follow-links",
+                "--max-link-depth",
+                "1",
+                &root
+            ]),
+            indoc!(
+                "
+                root
+                └─ link → x
+                   └─ inner → y [link depth exceeded]"
+            ),
+            "Failed to stop at the nested symlink"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn max_open() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--max-open", "1", "tests/data"]),
+        indoc!(
+            "
+            data (1.21 KiB)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.txt (308 B)
+            ├─ lipsum (446 B)
+            │  └─ lipsum.txt (446 B)
+            ├─ necronomicon.txt (83 B)
+            ├─ nemesis.txt (161 B)
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king (143 B)
+               └─ cassildas_song.md (143 B)"
+        )
+    );
+}
+
+#[test]
+#[should_panic]
+fn max_open_zero() {
+    utils::run_cmd(&["--max-open", "0", "tests/data"]);
+}
@@ -0,0 +1,37 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn negation_overrides_config() {
+    assert_eq!(
+        utils::run_cmd_with_config(
+            "--suppress-size",
+            &["--no-suppress-size", "tests/data/the_yellow_king"]
+        ),
+        indoc!(
+            "
+            the_yellow_king (143 B)
+            └─ cassildas_song.md (143 B)"
+        )
+    );
+}
+
+#[test]
+fn last_of_flag_and_negation_wins() {
+    assert_eq!(
+        utils::run_cmd_with_config(
+            "",
+            &[
+                "--no-suppress-size",
+                "--suppress-size",
+                "tests/data/the_yellow_king"
+            ]
+        ),
+        indoc!(
+            "
+            the_yellow_king
+            └─ cassildas_song.md"
+        )
+    );
+}
@@ -0,0 +1,65 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::{env, os::unix::fs::symlink};
+    use tempfile::TempDir;
+
+    fn linked_data() -> Result<TempDir, Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let data = env::current_dir()?.join("tests/data/the_yellow_king");
+
+        symlink(data, tmp.path().join("carcosa"))?;
+
+        Ok(tmp)
+    }
+
+    #[test]
+    fn canonicalize() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = linked_data()?;
+        let root = tmp.path().join("carcosa");
+
+        assert_eq!(
+            super::utils::run_cmd(&["--report", &root.to_string_lossy()]),
+            indoc!(
+                "
+                d   143 B   the_yellow_king
+                -   143 B   cassildas_song.md"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn no_canonicalize() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = linked_data()?;
+        let root = tmp.path().join("carcosa");
+
+        assert_eq!(
+            super::utils::run_cmd(&["--report", "--no-canonicalize", &root.to_string_lossy()]),
+            indoc!(
+                "
+                d   143 B   carcosa
+                -   143 B   cassildas_song.md"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn no_canonicalize_du_compat() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = linked_data()?;
+        let root = tmp.path().join("carcosa");
+        let root = root.to_string_lossy();
+
+        assert_eq!(
+            super::utils::run_cmd_raw(&["--du-compat", "--no-canonicalize", &root]),
+            format!("143\t{root}/cassildas_song.md\n143\t{root}")
+        );
+
+        Ok(())
+    }
+}
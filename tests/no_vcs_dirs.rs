@@ -0,0 +1,42 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn no_vcs_dirs() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("repo");
+
+    for vcs in [".git", ".hg", ".svn"] {
+        fs::create_dir_all(root.join(vcs).join("objects"))?;
+        fs::write(
+            root.join(vcs).join("objects").join("pack"),
+            "x".repeat(1000),
+        )?;
+    }
+
+    fs::create_dir_all(root.join("src"))?;
+    fs::write(root.join("src").join("main.rs"), "fn main() {}")?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--hidden",
+            "--no-vcs-dirs",
+            &root.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            repo (12 B)
+            └─ src (12 B)
+               └─ main.rs (12 B)"
+        )
+    );
+
+    assert!(utils::run_cmd(&["--hidden", &root.to_string_lossy()]).contains(".hg"));
+
+    Ok(())
+}
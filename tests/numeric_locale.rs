@@ -0,0 +1,93 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn numeric_locale() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--report",
+            "--numeric-locale",
+            "en_US",
+            "--sort",
+            "name",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            d   1,241 B   data
+            d     308 B   dream_cycle
+            d     446 B   lipsum
+            -      83 B   necronomicon.txt
+            -     161 B   nemesis.txt
+            -     100 B   nylarlathotep.txt
+            d     143 B   the_yellow_king"
+        )
+    );
+
+    for (locale, total) in [
+        ("de_DE.UTF-8", "1.241"),
+        ("fr_FR", "1 241"),
+        ("de_CH", "1'241"),
+        ("C", "1241"),
+    ] {
+        assert_eq!(
+            utils::run_cmd(&[
+                "--report",
+                "--numeric-locale",
+                locale,
+                "--level",
+                "0",
+                "tests/data"
+            ]),
+            format!("d   {total} B   data")
+        );
+    }
+}
+
+#[test]
+fn numeric_locale_file_counts() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("inbox");
+
+    fs::create_dir_all(&root)?;
+
+    for i in 0..1234 {
+        fs::write(root.join(format!("{i}.eml")), "")?;
+    }
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--report=files",
+            "--numeric-locale",
+            "fr",
+            &root.to_string_lossy()
+        ]),
+        "1 234   inbox"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn numeric_locale_unrecognized() {
+    let output = std::process::Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--no-config",
+            "--numeric-locale",
+            "xx_XX",
+            "tests/data",
+        ])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unrecognized locale 'xx_XX'"));
+}
@@ -0,0 +1,80 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::{
+        fs::{self, Permissions},
+        os::unix::fs::PermissionsExt,
+    };
+    use tempfile::TempDir;
+
+    /// Creates a directory with files of which some have risky permissions. Everything belongs to
+    /// the user running the tests so no file is ever misplaced and root-owned.
+    fn audit() -> Result<TempDir, Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("audit");
+
+        fs::create_dir_all(root.join("bin"))?;
+        fs::create_dir_all(root.join("docs"))?;
+
+        for (path, mode) in [
+            ("bin/login", 0o4755),
+            ("bin/mail", 0o2755),
+            ("bin/ls", 0o755),
+            ("docs/shared.txt", 0o666),
+            ("docs/readme.md", 0o644),
+        ] {
+            let path = root.join(path);
+            fs::write(&path, "x")?;
+            fs::set_permissions(&path, Permissions::from_mode(mode))?;
+        }
+
+        Ok(tmp)
+    }
+
+    #[test]
+    fn permissions() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = audit()?;
+        let root = tmp.path().join("audit");
+
+        assert_eq!(
+            super::utils::run_cmd(&[
+                "--sort",
+                "name",
+                "--report=permissions",
+                &root.to_string_lossy()
+            ]),
+            indoc!(
+                "
+                audit (3 B)
+                ├─ bin (2 B)
+                │  ├─ login (1 B) [setuid]
+                │  └─ mail (1 B) [setgid]
+                └─ docs (1 B)
+                   └─ shared.txt (1 B) [world-writable]"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn permissions_json() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = audit()?;
+        let root = tmp.path().join("audit");
+
+        assert_eq!(
+            super::utils::run_cmd(&[
+                "--sort",
+                "name",
+                "--report=permissions",
+                "--json",
+                &root.to_string_lossy()
+            ]),
+            r#"{"schema_version":1,"root":"audit","entries":[{"path":"bin/login","issues":["setuid"]},{"path":"bin/mail","issues":["setgid"]},{"path":"docs/shared.txt","issues":["world-writable"]}]}"#
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::fs::{self, File};
+    use tempfile::TempDir;
+
+    #[test]
+    fn physical() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("vms");
+
+        fs::create_dir_all(root.join("images"))?;
+        File::create(root.join("images").join("disk.img"))?.set_len(10 * 1024 * 1024)?;
+
+        assert_eq!(
+            super::utils::run_cmd(&["--physical", &root.to_string_lossy()]),
+            indoc!(
+                "
+                vms
+                └─ images
+                   └─ disk.img (0 B) [sparse: 0 B allocated of 10.00 MiB]"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn logical_and_block() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("vms");
+
+        fs::create_dir_all(root.join("images"))?;
+        File::create(root.join("images").join("disk.img"))?.set_len(10 * 1024 * 1024)?;
+
+        assert_eq!(
+            super::utils::run_cmd(&["--disk-usage", "logical,block", &root.to_string_lossy()]),
+            indoc!(
+                "
+                vms (10.00 MiB, 0 B)
+                └─ images (10.00 MiB, 0 B)
+                   └─ disk.img (10.00 MiB, 0 B) [sparse: 0 B allocated of 10.00 MiB]"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn physical_conflicts_with_disk_usage() {
+        let output = std::process::Command::new("cargo")
+            .args([
+                "run",
+                "--",
+                "--no-config",
+                "--physical",
+                "--disk-usage",
+                "logical",
+            ])
+            .stdin(std::process::Stdio::null())
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success());
+    }
+}
@@ -0,0 +1,45 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn porcelain() {
+    assert_eq!(
+        utils::run_cmd_raw(&["--porcelain", "--sort", "name", "tests/data"]),
+        indoc!(
+            "
+            # erdtree porcelain v1
+            d\t0\t1241\t.
+            d\t1\t308\tdream_cycle
+            -\t2\t308\tdream_cycle/polaris.txt
+            d\t1\t446\tlipsum
+            -\t2\t446\tlipsum/lipsum.txt
+            -\t1\t83\tnecronomicon.txt
+            -\t1\t161\tnemesis.txt
+            -\t1\t100\tnylarlathotep.txt
+            d\t1\t143\tthe_yellow_king
+            -\t2\t143\tthe_yellow_king/cassildas_song.md"
+        )
+    )
+}
+
+#[test]
+fn porcelain_with_level() {
+    assert_eq!(
+        utils::run_cmd_raw(&[
+            "--porcelain",
+            "--level",
+            "1",
+            "--sort",
+            "name",
+            "--suppress-size",
+            "tests/data/the_yellow_king"
+        ]),
+        indoc!(
+            "
+            # erdtree porcelain v1
+            d\t0\t-\t.
+            -\t1\t-\tcassildas_song.md"
+        )
+    )
+}
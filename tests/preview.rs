@@ -0,0 +1,62 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn preview() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("conf");
+
+    fs::create_dir_all(root.join("env"))?;
+    fs::write(
+        root.join("app.toml"),
+        "[server]\nport = 8080\nhost = \"::\"\n",
+    )?;
+    fs::write(root.join("env").join("dev"), "DEBUG=1\r\n")?;
+    fs::write(root.join("logo.png"), b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")?;
+    fs::write(root.join("z.sh"), "#!/bin/sh\n\techo hi\n")?;
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--preview", "2", &root.to_string_lossy()]),
+        indoc!(
+            "
+            conf (77 B)
+            ├─ app.toml (33 B)
+            │    [server]
+            │    port = 8080
+            ├─ env (9 B)
+            │  └─ dev (9 B)
+            │       DEBUG=1
+            ├─ logo.png (16 B)
+            └─ z.sh (19 B)
+                 #!/bin/sh
+                     echo hi"
+        ),
+        "Failed to print the first lines of text files beneath them"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn preview_large() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("logs");
+
+    fs::create_dir_all(&root)?;
+    fs::write(root.join("big.log"), "line\n".repeat(20_000))?;
+
+    assert_eq!(
+        utils::run_cmd(&["--preview", "1", &root.to_string_lossy()]),
+        indoc!(
+            "
+            logs (97.66 KiB)
+            └─ big.log (97.66 KiB)"
+        ),
+        "Failed to leave out files too large to preview"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use indoc::indoc;
+
+mod utils;
+
+const CONFIG: &str = indoc!(
+    "
+    --sort name
+
+    [triage]
+    --sort size
+    --level 1
+
+    [browse]
+    --dirs-only
+    --suppress-size
+    "
+);
+
+#[test]
+fn profile() {
+    assert_eq!(
+        utils::run_cmd_with_config(CONFIG, &["--profile", "browse", "tests/data"]),
+        indoc!(
+            "
+            data
+            ├─ dream_cycle
+            ├─ lipsum
+            └─ the_yellow_king"
+        )
+    );
+}
+
+#[test]
+fn profile_overrides_defaults() {
+    assert_eq!(
+        utils::run_cmd_with_config(CONFIG, &["--profile", "triage", "tests/data"]),
+        indoc!(
+            "
+            data (1.21 KiB)
+            ├─ necronomicon.txt (83 B)
+            ├─ nylarlathotep.txt (100 B)
+            ├─ the_yellow_king (143 B)
+            ├─ nemesis.txt (161 B)
+            ├─ dream_cycle (308 B)
+            └─ lipsum (446 B)"
+        )
+    );
+}
+
+#[test]
+fn profiles_are_ignored_unless_selected() {
+    assert_eq!(
+        utils::run_cmd_with_config(CONFIG, &["tests/data/the_yellow_king"]),
+        indoc!(
+            "
+            the_yellow_king (143 B)
+            └─ cassildas_song.md (143 B)"
+        )
+    );
+}
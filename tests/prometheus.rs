@@ -0,0 +1,25 @@
+use indoc::formatdoc;
+use std::path::Path;
+
+mod utils;
+
+#[test]
+fn prometheus() -> Result<(), Box<dyn std::error::Error>> {
+    let data = Path::new("tests/data").canonicalize()?;
+    let data = data.to_string_lossy();
+
+    assert_eq!(
+        utils::run_cmd(&["--output", "prometheus", "--top", "3", "tests/data"]),
+        formatdoc!(
+            r#"
+            # HELP erdtree_directory_bytes Disk usage of a directory in bytes.
+            # TYPE erdtree_directory_bytes gauge
+            erdtree_directory_bytes{{path="{data}"}} 1241
+            erdtree_directory_bytes{{path="{data}/lipsum"}} 446
+            erdtree_directory_bytes{{path="{data}/dream_cycle"}} 308
+            # EOF"#
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn quota() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("projects");
+
+    for (project, bytes) in [("alpha", 800), ("beta", 100), ("gamma", 950)] {
+        fs::create_dir_all(root.join(project))?;
+        fs::write(root.join(project).join("data.bin"), vec![0_u8; bytes])?;
+    }
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--quota", "1KB", &root.to_string_lossy()]),
+        indoc!(
+            "
+            projects (1.81 KiB)
+            ├─ alpha (800 B) [80% of quota]
+            │  └─ data.bin (800 B)
+            ├─ beta (100 B) [10% of quota]
+            │  └─ data.bin (100 B)
+            └─ gamma (950 B) [95% of quota]
+               └─ data.bin (950 B)"
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn rank() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let data = tmp.path().join("data");
+
+    fs::create_dir_all(data.join("a").join("b"))?;
+    fs::create_dir_all(data.join("c"))?;
+    fs::write(data.join("a").join("b").join("big"), "a".repeat(500))?;
+    fs::write(data.join("a").join("x"), "b".repeat(100))?;
+    fs::write(data.join("c").join("mid"), "c".repeat(300))?;
+    fs::write(data.join("small"), "d".repeat(10))?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--rank",
+            "--top",
+            "2",
+            &data.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            data (910 B)
+            ├─ a (600 B)
+            │  ├─ b (500 B)
+            │  │  └─ #1 big (500 B)
+            │  └─ x (100 B)
+            ├─ c (300 B)
+            │  └─ #2 mid (300 B)
+            └─ small (10 B)"
+        )
+    );
+
+    Ok(())
+}
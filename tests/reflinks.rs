@@ -0,0 +1,27 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn reflinks_ignores_independent_copies() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("builds");
+
+    fs::create_dir_all(&root)?;
+    fs::write(root.join("a.o"), [0_u8; 100])?;
+    fs::write(root.join("b.o"), [0_u8; 100])?;
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--reflinks", &root.to_string_lossy()]),
+        indoc!(
+            "
+            builds (200 B)
+            ├─ a.o (100 B)
+            └─ b.o (100 B)"
+        )
+    );
+
+    Ok(())
+}
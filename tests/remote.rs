@@ -0,0 +1,147 @@
+#[cfg(target_os = "linux")]
+mod test {
+    use indoc::indoc;
+    use std::{
+        fs,
+        os::unix::fs::PermissionsExt,
+        process::{Command, Output, Stdio},
+    };
+    use strip_ansi_escapes::strip as strip_ansi_escapes;
+    use tempfile::TempDir;
+
+    /// Stands in for `ssh` by running the remote command locally.
+    const FAKE_SSH: &str = "#!/bin/sh\nshift 2\nexec sh -c \"$1\"\n";
+
+    fn run_remote(remote: &str) -> Output {
+        run_remote_with(FAKE_SSH, remote)
+    }
+
+    /// Runs erdtree against `remote` with `script` standing in for `ssh`.
+    fn run_remote_with(script: &str, remote: &str) -> Output {
+        let tmp = TempDir::new().unwrap();
+        let ssh = tmp.path().join("ssh");
+
+        fs::write(&ssh, script).unwrap();
+        fs::set_permissions(&ssh, fs::Permissions::from_mode(0o755)).unwrap();
+
+        Command::new("cargo")
+            .args([
+                "run",
+                "--",
+                "--threads",
+                "1",
+                "--no-config",
+                "--sort",
+                "name",
+            ])
+            .args(["--remote", remote])
+            .env("ERDTREE_SSH", &ssh)
+            .stdin(Stdio::null())
+            .output()
+            .unwrap()
+    }
+
+    #[test]
+    fn remote() {
+        let data = fs::canonicalize("tests/data").unwrap();
+        let remote = format!("me@box:{}", data.display());
+
+        let output = run_remote(&remote);
+
+        assert!(output.status.success());
+
+        let stdout = String::from_utf8(strip_ansi_escapes(output.stdout).unwrap()).unwrap();
+
+        assert_eq!(
+            stdout.trim().replace(&remote, "data"),
+            indoc!(
+                "
+                data (1.21 KiB)
+                ├─ dream_cycle (308 B)
+                │  └─ polaris.txt (308 B)
+                ├─ lipsum (446 B)
+                │  └─ lipsum.txt (446 B)
+                ├─ necronomicon.txt (83 B)
+                ├─ nemesis.txt (161 B)
+                ├─ nylarlathotep.txt (100 B)
+                └─ the_yellow_king (143 B)
+                   └─ cassildas_song.md (143 B)"
+            )
+        );
+    }
+
+    #[test]
+    fn remote_symlink_escape() {
+        let outside = TempDir::new().unwrap();
+
+        // A listing in which a symlink is followed by an entry beneath it.
+        let listing = format!(
+            "#!/bin/sh\nprintf 'l\\0000\\0{}\\0dir\\0f\\0005\\0\\0dir/f\\0'\n",
+            outside.path().display()
+        );
+
+        let output = run_remote_with(&listing, "me@box:/srv");
+
+        assert!(output.status.success());
+        assert!(
+            !outside.path().join("f").exists(),
+            "Failed to keep the listing from writing through a symlink"
+        );
+    }
+
+    #[test]
+    fn remote_missing() {
+        let output = run_remote("me@box:/does/not/exist");
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to list /does/not/exist"));
+    }
+
+    #[test]
+    fn remote_dash_path() {
+        let home = TempDir::new().unwrap();
+        let dir = home.path().join("-name");
+
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("file"), "hello").unwrap();
+
+        // Paths are relative to the home directory on the remote host.
+        let script = format!(
+            "#!/bin/sh\nshift 2\ncd '{}' && exec sh -c \"$1\"\n",
+            home.path().display()
+        );
+
+        let output = run_remote_with(&script, "me@box:-name");
+
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8(strip_ansi_escapes(output.stdout).unwrap()).unwrap();
+
+        assert!(
+            stdout.contains("file (5 B)"),
+            "Failed to list a path that starts with a dash: {stdout}"
+        );
+    }
+
+    #[test]
+    fn remote_conflicts_with_dir() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--",
+                "--no-config",
+                "--remote",
+                "me@box",
+                "tests/data",
+            ])
+            .stdin(Stdio::null())
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success());
+    }
+}
@@ -0,0 +1,10 @@
+mod utils;
+
+#[test]
+fn render_buffer() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--render-buffer", "1", "tests/data"]),
+        utils::run_cmd(&["--sort", "name", "tests/data"]),
+        "Output written in chunks should be the same as otherwise"
+    );
+}
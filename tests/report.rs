@@ -59,6 +59,80 @@ fn report_with_level() {
     )
 }
 
+#[test]
+fn report_json() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--report",
+            "--json",
+            "--sort",
+            "name",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        concat!(
+            r#"{"schema_version":1,"root":"data","total_bytes":1241,"#,
+            r#""counts":{"directories":3,"files":6,"links":0},"#,
+            r#""entries":["#,
+            r#"{"type":"d","path":"dream_cycle","bytes":308},"#,
+            r#"{"type":"d","path":"lipsum","bytes":446},"#,
+            r#"{"type":"-","path":"necronomicon.txt","bytes":83,"content":"text"},"#,
+            r#"{"type":"-","path":"nemesis.txt","bytes":161,"content":"text"},"#,
+            r#"{"type":"-","path":"nylarlathotep.txt","bytes":100,"content":"text"},"#,
+            r#"{"type":"d","path":"the_yellow_king","bytes":143}]}"#
+        )
+    );
+}
+
+#[test]
+fn report_files() {
+    assert_eq!(
+        utils::run_cmd(&["--report=files", "--sort", "name", "tests/data"]),
+        indoc!(
+            "
+            6   data
+            1   dream_cycle
+            1   lipsum
+            1   the_yellow_king"
+        )
+    );
+}
+
+#[test]
+fn report_files_top() {
+    assert_eq!(
+        utils::run_cmd(&["--report=files", "--top", "2", "--json", "tests/data"]),
+        concat!(
+            r#"{"schema_version":1,"directories":["#,
+            r#"{"path":"data","directories":3,"files":6,"links":0},"#,
+            r#"{"path":"dream_cycle","directories":0,"files":1,"links":0}]}"#
+        )
+    );
+}
+
+#[test]
+fn report_compressibility() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::TempDir::new()?;
+    let zeros = tmp.path().join("zeros");
+
+    std::fs::create_dir(&zeros)?;
+    std::fs::write(zeros.join("zeros.bin"), vec![0; 1024 * 1024])?;
+
+    let out = utils::run_cmd(&["--report=compressibility", &zeros.to_string_lossy()]);
+
+    let columns = out.split("   ").collect::<Vec<_>>();
+
+    assert_eq!(columns.len(), 4, "Expected size, estimate, ratio, and path");
+    assert_eq!(columns[0], "1048576 B");
+    assert_eq!(columns[3], "zeros");
+
+    let ratio = columns[2].trim_end_matches('x').parse::<f64>()?;
+    assert!(ratio > 100.0, "Zeros should compress well, not by {ratio}");
+
+    Ok(())
+}
+
 #[test]
 #[should_panic]
 fn report_requires_human() {
@@ -70,3 +144,9 @@ fn report_requires_human() {
 fn report_requires_file_name() {
     utils::run_cmd(&["--file-name"]);
 }
+
+#[test]
+#[should_panic]
+fn report_requires_json() {
+    utils::run_cmd(&["--json"]);
+}
@@ -0,0 +1,51 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn root_label() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--root-label",
+            "Carcosa",
+            "tests/data/the_yellow_king"
+        ]),
+        indoc!(
+            "
+            Carcosa (143 B)
+            └─ cassildas_song.md (143 B)"
+        )
+    );
+}
+
+#[test]
+fn relative() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--relative", "tests/data/the_yellow_king"]),
+        indoc!(
+            "
+            tests/data/the_yellow_king (143 B)
+            └─ cassildas_song.md (143 B)"
+        )
+    );
+}
+
+#[test]
+fn relative_report() {
+    assert_eq!(
+        utils::run_cmd(&["--report", "--relative", "tests/data/the_yellow_king"]),
+        indoc!(
+            "
+            d   143 B   tests/data/the_yellow_king
+            -   143 B   cassildas_song.md"
+        )
+    );
+}
+
+#[test]
+#[should_panic]
+fn root_label_conflicts_with_relative() {
+    utils::run_cmd(&["--root-label", "Carcosa", "--relative", "tests/data"]);
+}
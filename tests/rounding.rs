@@ -0,0 +1,75 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn rounding_up() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--precision",
+            "1",
+            "--rounding",
+            "up",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1.3 KiB)
+            ├─ dream_cycle (308 B)
+            ├─ lipsum (446 B)
+            ├─ necronomicon.txt (83 B)
+            ├─ nemesis.txt (161 B)
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king (143 B)"
+        )
+    );
+}
+
+#[test]
+fn rounding_down() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--scale",
+            "0",
+            "--rounding",
+            "down",
+            "--prefix",
+            "si",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1 KB)
+            ├─ dream_cycle (308 B)
+            ├─ lipsum (446 B)
+            ├─ necronomicon.txt (83 B)
+            ├─ nemesis.txt (161 B)
+            ├─ nylarlathotep.txt (100 B)
+            └─ the_yellow_king (143 B)"
+        )
+    );
+}
+
+#[test]
+fn rounding_nearest() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--precision",
+            "1",
+            "--level",
+            "0",
+            "tests/data"
+        ]),
+        "data (1.2 KiB)"
+    );
+}
@@ -0,0 +1,78 @@
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::{
+        fs,
+        os::unix::fs::PermissionsExt,
+        process::{Command, Output, Stdio},
+    };
+    use strip_ansi_escapes::strip as strip_ansi_escapes;
+    use tempfile::TempDir;
+
+    /// Stands in for the AWS CLI by printing a fixed listing.
+    const FAKE_AWS: &str = r#"#!/bin/sh
+cat <<'JSON'
+{"Contents": [
+    {"Key": "logs/", "Size": 0},
+    {"Key": "logs/2024/app.log", "Size": 1048576},
+    {"Key": "logs/2024/db.log", "Size": 2048},
+    {"Key": "logs/2025/", "Size": 0},
+    {"Key": "logs/latest.log", "Size": 512},
+    {"Key": "logs-old/app.log", "Size": 4096}
+]}
+JSON
+"#;
+
+    fn run_s3(args: &[&str]) -> Output {
+        let tmp = TempDir::new().unwrap();
+        let aws = tmp.path().join("aws");
+
+        fs::write(&aws, FAKE_AWS).unwrap();
+        fs::set_permissions(&aws, fs::Permissions::from_mode(0o755)).unwrap();
+
+        Command::new("cargo")
+            .args([
+                "run",
+                "--",
+                "--threads",
+                "1",
+                "--no-config",
+                "--sort",
+                "name",
+            ])
+            .args(args)
+            .env("ERDTREE_AWS", &aws)
+            .stdin(Stdio::null())
+            .output()
+            .unwrap()
+    }
+
+    #[test]
+    fn s3() {
+        let output = run_s3(&["s3://bucket/logs"]);
+
+        assert!(output.status.success());
+
+        assert_eq!(
+            String::from_utf8(strip_ansi_escapes(output.stdout).unwrap())
+                .unwrap()
+                .trim(),
+            indoc!(
+                "
+                s3://bucket/logs (1.00 MiB)
+                ├─ 2024 (1.00 MiB)
+                │  ├─ app.log (1.00 MiB)
+                │  └─ db.log (2.00 KiB)
+                ├─ 2025
+                └─ latest.log (512 B)"
+            )
+        );
+    }
+
+    #[test]
+    fn s3_conflicts_with_physical() {
+        let output = run_s3(&["--physical", "s3://bucket/logs"]);
+
+        assert!(!output.status.success());
+    }
+}
@@ -0,0 +1,44 @@
+use serde_json::Value;
+
+mod utils;
+
+#[test]
+fn schema() -> Result<(), Box<dyn std::error::Error>> {
+    let schema: Value = serde_json::from_str(&utils::run_cmd(&["--schema"]))?;
+
+    assert_eq!(schema["title"], "erdtree du report v1");
+    assert_eq!(schema["properties"]["schema_version"]["type"], "integer");
+    assert_eq!(schema["properties"]["entries"]["type"], "array");
+
+    let required = schema["required"].as_array().cloned().unwrap_or_default();
+
+    for field in ["schema_version", "root", "total_bytes", "counts", "entries"] {
+        assert!(
+            required.contains(&Value::from(field)),
+            "Failed to require {field}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn schema_of_report() -> Result<(), Box<dyn std::error::Error>> {
+    let schema: Value = serde_json::from_str(&utils::run_cmd(&["--report=files", "--schema"]))?;
+
+    assert_eq!(schema["title"], "erdtree files report v1");
+    assert_eq!(schema["properties"]["directories"]["type"], "array");
+    assert!(schema["properties"]["total_bytes"].is_null());
+
+    Ok(())
+}
+
+#[test]
+fn schema_version() -> Result<(), Box<dyn std::error::Error>> {
+    let report: Value =
+        serde_json::from_str(&utils::run_cmd(&["--report=ages", "--json", "tests/data"]))?;
+
+    assert_eq!(report["schema_version"], 1);
+
+    Ok(())
+}
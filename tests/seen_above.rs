@@ -0,0 +1,35 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    #[test]
+    fn seen_above() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("diamond");
+
+        fs::create_dir_all(root.join("data"))?;
+        fs::create_dir_all(root.join("mnt"))?;
+        fs::write(root.join("data").join("blob"), "a".repeat(100))?;
+        symlink("../data", root.join("mnt").join("data"))?;
+
+        assert_eq!(
+            super::utils::run_cmd(&["--sort", "name", "--follow-links", &root.to_string_lossy()]),
+            indoc!(
+                "
+                diamond (100 B)
+                ├─ data (100 B)
+                │  └─ blob (100 B)
+                └─ mnt
+                   └─ data → data [seen above]"
+            ),
+            "Failed to count the directory linked to twice only once"
+        );
+
+        Ok(())
+    }
+}
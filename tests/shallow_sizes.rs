@@ -0,0 +1,41 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn shallow_sizes() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let data = tmp.path().join("data");
+
+    fs::create_dir_all(data.join("a").join("b"))?;
+    fs::create_dir_all(data.join("c"))?;
+    fs::write(data.join("a").join("b").join("big"), "a".repeat(500))?;
+    fs::write(data.join("a").join("x"), "b".repeat(100))?;
+    fs::write(data.join("c").join("mid"), "c".repeat(300))?;
+    fs::write(data.join("small"), "d".repeat(10))?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--level",
+            "2",
+            "--shallow-sizes",
+            &data.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            data (410 B)
+            ├─ a (100 B)
+            │  ├─ b
+            │  └─ x (100 B)
+            ├─ c (300 B)
+            │  └─ mid (300 B)
+            └─ small (10 B)"
+        )
+    );
+
+    Ok(())
+}
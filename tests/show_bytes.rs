@@ -0,0 +1,76 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn show_bytes() {
+    assert_eq!(
+        utils::run_cmd(&["--show-bytes", "--sort", "name", "tests/data"]),
+        indoc!(
+            "
+            data (1.21 KiB (1241 B))
+            ├─ dream_cycle (308 B (308 B))
+            │  └─ polaris.txt (308 B (308 B))
+            ├─ lipsum (446 B (446 B))
+            │  └─ lipsum.txt (446 B (446 B))
+            ├─ necronomicon.txt (83 B (83 B))
+            ├─ nemesis.txt (161 B (161 B))
+            ├─ nylarlathotep.txt (100 B (100 B))
+            └─ the_yellow_king (143 B (143 B))
+               └─ cassildas_song.md (143 B (143 B))"
+        )
+    );
+}
+
+#[test]
+fn show_bytes_size_left() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--show-bytes",
+            "--size-left",
+            "--numeric-locale",
+            "en_US",
+            "--sort",
+            "name",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            1.21 KiB (1,241 B) data
+               308   B (  308 B) ├─ dream_cycle
+               446   B (  446 B) ├─ lipsum
+                83   B (   83 B) ├─ necronomicon.txt
+               161   B (  161 B) ├─ nemesis.txt
+               100   B (  100 B) ├─ nylarlathotep.txt
+               143   B (  143 B) └─ the_yellow_king"
+        )
+    );
+}
+
+#[test]
+fn show_bytes_unit() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--show-bytes",
+            "--unit",
+            "KiB",
+            "--sort",
+            "size",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data (1.21 KiB (1241 B))
+            ├─ necronomicon.txt (0.08 KiB (83 B))
+            ├─ nylarlathotep.txt (0.10 KiB (100 B))
+            ├─ the_yellow_king (0.14 KiB (143 B))
+            ├─ nemesis.txt (0.16 KiB (161 B))
+            ├─ dream_cycle (0.30 KiB (308 B))
+            └─ lipsum (0.44 KiB (446 B))"
+        )
+    );
+}
@@ -1,4 +1,5 @@
 use indoc::indoc;
+use std::fs;
 
 mod utils;
 
@@ -85,3 +86,29 @@ fn sort_size_dir_first() {
         "Failed to sort by directory and descending size"
     )
 }
+
+#[test]
+fn sort_multiple_keys() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::TempDir::new()?;
+    let root = tmp.path().join("ties");
+    fs::create_dir(&root)?;
+
+    for (name, len) in [("delta", 20), ("alpha", 10), ("charlie", 20), ("bravo", 20)] {
+        fs::write(root.join(name), "x".repeat(len))?;
+    }
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "size-rev,name", &root.to_string_lossy()]),
+        indoc!(
+            "
+            ties (70 B)
+            ├─ bravo (20 B)
+            ├─ charlie (20 B)
+            ├─ delta (20 B)
+            └─ alpha (10 B)"
+        ),
+        "Failed to break ties in size by file name"
+    );
+
+    Ok(())
+}
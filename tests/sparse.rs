@@ -0,0 +1,31 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::fs::{self, File};
+    use tempfile::TempDir;
+
+    #[test]
+    fn sparse_only() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("vms");
+
+        fs::create_dir_all(root.join("images"))?;
+        fs::create_dir_all(root.join("notes"))?;
+        fs::write(root.join("notes").join("todo.txt"), "defrag")?;
+        File::create(root.join("images").join("disk.img"))?.set_len(10 * 1024 * 1024)?;
+
+        assert_eq!(
+            super::utils::run_cmd(&["--sort", "name", "--sparse-only", &root.to_string_lossy()]),
+            indoc!(
+                "
+                vms (10.00 MiB)
+                └─ images (10.00 MiB)
+                   └─ disk.img (10.00 MiB) [sparse: 0 B allocated of 10.00 MiB]"
+            )
+        );
+
+        Ok(())
+    }
+}
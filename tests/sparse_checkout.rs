@@ -0,0 +1,88 @@
+use indoc::indoc;
+use std::{fs, path::Path, process::Command};
+use tempfile::TempDir;
+
+mod utils;
+
+fn git(repo: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["-c", "user.name=Carcosa"])
+        .args(["-c", "user.email=dev@example.com"])
+        .args(["-c", "commit.gpgsign=false"])
+        .args(args)
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+}
+
+/// A repository whose sparse-checkout leaves out `LICENSE` and all of `docs/api`.
+fn repo() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let repo = tmp.path();
+
+    git(repo, &["init", "--quiet"]);
+
+    fs::create_dir_all(repo.join("docs").join("api"))?;
+    fs::write(repo.join("README.md"), "a".repeat(40))?;
+    fs::write(repo.join("LICENSE"), "a".repeat(10))?;
+    fs::write(repo.join("docs").join("guide.md"), "a".repeat(100))?;
+    fs::write(repo.join("docs").join("api").join("index.md"), "a")?;
+    fs::write(repo.join("docs").join("api").join("types.md"), "a")?;
+    git(repo, &["add", "."]);
+    git(repo, &["commit", "--quiet", "-m", "Initial commit"]);
+
+    git(
+        repo,
+        &[
+            "sparse-checkout",
+            "set",
+            "--no-cone",
+            "/README.md",
+            "/docs/guide.md",
+        ],
+    );
+
+    Ok(tmp)
+}
+
+#[test]
+fn sparse_checkout() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = repo()?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sparse-checkout",
+            "--sort",
+            "name",
+            "--root-label",
+            "repo",
+            &tmp.path().to_string_lossy()
+        ]),
+        indoc!(
+            "
+            repo (140 B) [sparse: 1 file not checked out]
+            ├─ README.md (40 B)
+            └─ docs (100 B) [sparse: 2 files not checked out]
+               └─ guide.md (100 B)"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sparse_checkout_outside_repo() {
+    let tmp = TempDir::new().unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "--no-config", "--sparse-checkout"])
+        .arg(tmp.path())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to read git history"));
+}
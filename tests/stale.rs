@@ -0,0 +1,64 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn stale() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--stale",
+            "0",
+            "tests/data/the_yellow_king"
+        ]),
+        indoc!(
+            "
+            the_yellow_king (143 B) [stale]
+            └─ cassildas_song.md (143 B)"
+        )
+    );
+}
+
+#[test]
+fn stale_badge() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--stale",
+            "0",
+            "--stale-badge",
+            "zzz",
+            "tests/data/the_yellow_king"
+        ]),
+        indoc!(
+            "
+            the_yellow_king (143 B) zzz
+            └─ cassildas_song.md (143 B)"
+        )
+    );
+}
+
+#[test]
+fn not_stale() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("hastur");
+
+    fs::create_dir_all(root.join("fresh"))?;
+    fs::write(root.join("fresh").join("yhtill.txt"), "Carcosa")?;
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--stale", "1w", &root.to_string_lossy()]),
+        indoc!(
+            "
+            hastur (7 B)
+            └─ fresh (7 B)
+               └─ yhtill.txt (7 B)"
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+mod utils;
+
+#[test]
+fn stats() {
+    let out = utils::run_cmd(&["--stats", "tests/data"]);
+    let summary = out.lines().last().unwrap();
+
+    assert!(summary.starts_with("10 entries in "), "{summary}");
+    assert!(summary.contains(" entries/s, "), "{summary}");
+    assert!(summary.ends_with("/s"), "{summary}");
+}
+
+#[test]
+fn stats_with_count() {
+    let out = utils::run_cmd(&["--stats", "--count", "tests/data"]);
+    let mut summary = out.lines().rev();
+
+    assert!(summary.next().unwrap().starts_with("10 entries in "));
+    assert_eq!(summary.next().unwrap(), "3 directories, 6 files");
+}
+
+#[test]
+fn no_stats() {
+    let out = utils::run_cmd(&["tests/data"]);
+
+    assert!(!out.contains("entries/s"));
+}
+
+#[test]
+fn no_render() {
+    let out = utils::run_cmd(&["--no-render", "tests/data"]);
+
+    assert_eq!(out.lines().count(), 1, "{out}");
+    assert!(out.starts_with("10 entries in "), "{out}");
+    assert!(out.ends_with("/s"), "{out}");
+}
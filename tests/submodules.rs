@@ -0,0 +1,139 @@
+use indoc::indoc;
+use std::{fs, path::Path, process::Command};
+use tempfile::TempDir;
+
+mod utils;
+
+fn git(repo: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["-c", "user.name=Carcosa"])
+        .args(["-c", "user.email=dev@example.com"])
+        .args(["-c", "commit.gpgsign=false"])
+        .args(["-c", "protocol.file.allow=always"])
+        .args(args)
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+}
+
+/// A repository with a `README.md` of its own and a library added as the submodule `vendor/lib`.
+fn superproject() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let lib = tmp.path().join("lib");
+    let repo = tmp.path().join("repo");
+
+    fs::create_dir_all(lib.join("src"))?;
+    git(&lib, &["init", "--quiet"]);
+    fs::write(lib.join("src").join("lib.rs"), "a".repeat(100))?;
+    fs::write(lib.join("Cargo.toml"), "a".repeat(20))?;
+    git(&lib, &["add", "."]);
+    git(&lib, &["commit", "--quiet", "-m", "Initial commit"]);
+
+    fs::create_dir(&repo)?;
+    git(&repo, &["init", "--quiet"]);
+    fs::write(repo.join("README.md"), "a".repeat(40))?;
+    git(
+        &repo,
+        &[
+            "submodule",
+            "--quiet",
+            "add",
+            &lib.to_string_lossy(),
+            "vendor/lib",
+        ],
+    );
+
+    Ok(tmp)
+}
+
+#[test]
+fn submodules() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = superproject()?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--root-label",
+            "repo",
+            &tmp.path().join("repo").to_string_lossy()
+        ]),
+        indoc!(
+            "
+            repo (160 B)
+            ├─ README.md (40 B)
+            └─ vendor (120 B)
+               └─ lib (120 B) [submodule]
+                  ├─ Cargo.toml (20 B)
+                  └─ src (100 B)
+                     └─ lib.rs (100 B)"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn no_submodules() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = superproject()?;
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--no-submodules",
+            "--sort",
+            "name",
+            "--root-label",
+            "repo",
+            &tmp.path().join("repo").to_string_lossy()
+        ]),
+        indoc!(
+            "
+            repo (160 B)
+            ├─ README.md (40 B)
+            └─ vendor (120 B)
+               └─ lib (120 B) [submodule]"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn worktrees_are_not_submodules() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let repo = tmp.path().join("repo");
+
+    fs::create_dir(&repo)?;
+    git(&repo, &["init", "--quiet"]);
+    fs::write(repo.join("README.md"), "a".repeat(40))?;
+    git(&repo, &["add", "."]);
+    git(&repo, &["commit", "--quiet", "-m", "Initial commit"]);
+    git(
+        &repo,
+        &["worktree", "add", "--quiet", "-b", "feature", "wt"],
+    );
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--no-submodules",
+            "--sort",
+            "name",
+            "--root-label",
+            "repo",
+            &repo.to_string_lossy()
+        ]),
+        indoc!(
+            "
+            repo (80 B)
+            ├─ README.md (40 B)
+            └─ wt (40 B)
+               └─ README.md (40 B)"
+        ),
+        "Linked worktrees also have a .git file but aren't submodules"
+    );
+
+    Ok(())
+}
@@ -3,6 +3,7 @@ mod utils;
 #[cfg(unix)]
 mod test {
     use indoc::indoc;
+    use std::fs;
     use std::os::unix::fs::symlink;
     use std::path::Path;
     use tempfile::TempDir;
@@ -31,4 +32,28 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn link_icons() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("links");
+
+        fs::create_dir_all(root.join("dir"))?;
+        fs::write(root.join("file"), "")?;
+        symlink("dir", root.join("to_dir"))?;
+        symlink("file", root.join("to_file"))?;
+        symlink("missing", root.join("to_missing"))?;
+
+        assert_eq!(
+            super::utils::run_cmd(&["--sort", "name", "--icons", &root.to_string_lossy()]),
+            "\u{f413} links\n\
+             ├─ \u{f413} dir\n\
+             ├─ \u{f15b} file (0 B)\n\
+             ├─ \u{f482} to_dir → dir\n\
+             ├─ \u{f481} to_file → file\n\
+             └─ \u{f127} to_missing → missing"
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,79 @@
+use indoc::indoc;
+use std::{fs, path::Path};
+use tempfile::TempDir;
+
+mod utils;
+
+/// A minimal JPEG holding nothing but EXIF data saying that it was taken on 2019-07-14.
+fn write_photo(path: &Path) -> std::io::Result<()> {
+    let mut tiff = vec![];
+
+    // Little-endian header followed by IFD0 with a single entry pointing to the EXIF IFD.
+    tiff.extend_from_slice(b"II*\0");
+    tiff.extend_from_slice(&8_u32.to_le_bytes());
+    tiff.extend_from_slice(&1_u16.to_le_bytes());
+    tiff.extend_from_slice(&[0x69, 0x87, 4, 0, 1, 0, 0, 0]);
+    tiff.extend_from_slice(&26_u32.to_le_bytes());
+    tiff.extend_from_slice(&0_u32.to_le_bytes());
+
+    // EXIF IFD with DateTimeOriginal stored right after it.
+    tiff.extend_from_slice(&1_u16.to_le_bytes());
+    tiff.extend_from_slice(&[0x03, 0x90, 2, 0, 20, 0, 0, 0]);
+    tiff.extend_from_slice(&44_u32.to_le_bytes());
+    tiff.extend_from_slice(&0_u32.to_le_bytes());
+    tiff.extend_from_slice(b"2019:07:14 10:00:00\0");
+
+    let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+    jpeg.extend_from_slice(&u16::try_from(2 + 6 + tiff.len()).unwrap().to_be_bytes());
+    jpeg.extend_from_slice(b"Exif\0\0");
+    jpeg.extend_from_slice(&tiff);
+    jpeg.extend_from_slice(&[0xFF, 0xD9]);
+
+    fs::write(path, jpeg)
+}
+
+#[test]
+fn taken() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("photos");
+
+    fs::create_dir(&root)?;
+    write_photo(&root.join("photo.jpg"))?;
+    fs::write(root.join("plain.txt"), "hello")?;
+
+    let root = root.to_string_lossy();
+
+    assert_eq!(
+        utils::run_cmd(&["--taken-before", "2020-01-01", &root]),
+        indoc!(
+            "
+            photos (78 B)
+            └─ photo.jpg (78 B)"
+        ),
+        "Failed to filter by the EXIF date of the photo"
+    );
+
+    assert_eq!(
+        utils::run_cmd(&["--taken-after", "2020-01-01", &root]),
+        indoc!(
+            "
+            photos (5 B)
+            └─ plain.txt (5 B)"
+        ),
+        "Failed to fall back to when the file was modified"
+    );
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--taken-after",
+            "2019-07-14",
+            "--taken-before",
+            "2019-07-14T10:00",
+            &root
+        ]),
+        "photos",
+        "Failed to exclude photos taken at the end of the range"
+    );
+
+    Ok(())
+}
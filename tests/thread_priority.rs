@@ -0,0 +1,18 @@
+mod utils;
+
+#[test]
+fn thread_priority_low() {
+    assert_eq!(
+        utils::run_cmd(&["--thread-priority", "low", "--sort", "name", "tests/data"]),
+        utils::run_cmd(&["--sort", "name", "tests/data"])
+    );
+}
+
+#[cfg(any(target_os = "linux", windows))]
+#[test]
+fn cpu_affinity() {
+    assert_eq!(
+        utils::run_cmd(&["--cpu-affinity", "0", "--sort", "name", "tests/data"]),
+        utils::run_cmd(&["--sort", "name", "tests/data"])
+    );
+}
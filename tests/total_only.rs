@@ -0,0 +1,37 @@
+use indoc::indoc;
+use std::fs;
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn total_only() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let data = tmp.path().join("data");
+
+    fs::create_dir_all(data.join("a").join("b"))?;
+    fs::write(data.join("a").join("b").join("big"), "a".repeat(500))?;
+    fs::write(data.join("small"), "d".repeat(10))?;
+
+    let path = data.to_string_lossy();
+
+    assert_eq!(
+        utils::run_cmd_raw(&["--total-only", &path]),
+        format!("510 B\t{path}")
+    );
+
+    assert_eq!(
+        utils::run_cmd_raw(&["-q", "--count", &path]),
+        format!(
+            indoc!(
+                "
+                510 B\t{}
+
+                2 directories, 2 files"
+            ),
+            path
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+mod utils;
+
+#[test]
+fn tree_svg() {
+    let svg = utils::run_cmd_raw(&["--output", "tree-svg", "--sort", "name", "tests/data"]);
+    let lines = svg.lines().collect::<Vec<_>>();
+
+    assert!(lines[0]
+        .starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="292.4" height="232.0""#));
+    assert_eq!(
+        lines
+            .iter()
+            .filter(|line| line.starts_with("<text "))
+            .count(),
+        10
+    );
+    assert_eq!(
+        lines[3],
+        concat!(
+            r#"<text x="16" y="31.0" xml:space="preserve">"#,
+            r##"<tspan fill="#2472c8" font-weight="bold">data</tspan>"##,
+            r#"<tspan> (</tspan>"#,
+            r##"<tspan fill="#e5e510">1.21 KiB</tspan>"##,
+            r#"<tspan>)</tspan>"#,
+            r#"</text>"#
+        )
+    );
+    assert_eq!(
+        lines[5],
+        concat!(
+            r#"<text x="16" y="71.0" xml:space="preserve">"#,
+            r##"<tspan fill="#bc3fbc">│  </tspan>"##,
+            r##"<tspan fill="#bc3fbc">└─ </tspan>"##,
+            r#"<tspan>polaris.txt (</tspan>"#,
+            r##"<tspan fill="#11a8cd">308 B</tspan>"##,
+            r#"<tspan>)</tspan>"#,
+            r#"</text>"#
+        )
+    );
+    assert_eq!(lines.last(), Some(&"</svg>"));
+}
+
+#[test]
+fn tree_svg_deterministic() {
+    let svg = utils::run_cmd_raw(&[
+        "--output",
+        "tree-svg",
+        "--deterministic",
+        "--level",
+        "0",
+        "tests/data",
+    ]);
+
+    assert!(svg.contains(
+        r#"<text x="16" y="31.0" xml:space="preserve"><tspan>data (1.21 KiB)</tspan></text>"#
+    ));
+    assert!(!svg.contains("fill=\"#2472c8\""));
+}
@@ -0,0 +1,54 @@
+mod utils;
+
+#[test]
+fn treemap() {
+    assert_eq!(
+        utils::run_cmd(&["--output", "treemap", "--sort", "name", "tests/data"]),
+        concat!(
+            r#"{"name":"data","children":["#,
+            r#"{"name":"dream_cycle","children":[{"name":"polaris.txt","value":308}]},"#,
+            r#"{"name":"lipsum","children":[{"name":"lipsum.txt","value":446}]},"#,
+            r#"{"name":"necronomicon.txt","value":83},"#,
+            r#"{"name":"nemesis.txt","value":161},"#,
+            r#"{"name":"nylarlathotep.txt","value":100},"#,
+            r#"{"name":"the_yellow_king","children":[{"name":"cassildas_song.md","value":143}]}"#,
+            r#"]}"#
+        )
+    );
+}
+
+#[test]
+fn treemap_level() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--output",
+            "treemap",
+            "--sort",
+            "name",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        concat!(
+            r#"{"name":"data","children":["#,
+            r#"{"name":"dream_cycle","value":308,"children":[]},"#,
+            r#"{"name":"lipsum","value":446,"children":[]},"#,
+            r#"{"name":"necronomicon.txt","value":83},"#,
+            r#"{"name":"nemesis.txt","value":161},"#,
+            r#"{"name":"nylarlathotep.txt","value":100},"#,
+            r#"{"name":"the_yellow_king","value":143,"children":[]}"#,
+            r#"]}"#
+        )
+    );
+}
+
+#[test]
+fn svg() {
+    let svg = utils::run_cmd(&["--output", "svg", "tests/data"]);
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>"));
+    assert_eq!(svg.matches("<rect ").count(), 10);
+    assert!(svg.contains("<title>data/lipsum/lipsum.txt (446 B)</title>"));
+    assert!(svg.contains(r#"<rect x="2.0" y="16.0" width="458.6" height="702.0""#));
+}
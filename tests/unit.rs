@@ -0,0 +1,77 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn unit() {
+    assert_eq!(
+        utils::run_cmd(&["--unit", "KiB", "--sort", "name", "tests/data"]),
+        indoc!(
+            "
+            data (1.21 KiB)
+            ├─ dream_cycle (0.30 KiB)
+            │  └─ polaris.txt (0.30 KiB)
+            ├─ lipsum (0.44 KiB)
+            │  └─ lipsum.txt (0.44 KiB)
+            ├─ necronomicon.txt (0.08 KiB)
+            ├─ nemesis.txt (0.16 KiB)
+            ├─ nylarlathotep.txt (0.10 KiB)
+            └─ the_yellow_king (0.14 KiB)
+               └─ cassildas_song.md (0.14 KiB)"
+        )
+    );
+}
+
+#[test]
+fn unit_aligned() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--unit",
+            "b",
+            "--size-left",
+            "--sort",
+            "name",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            1241   B data
+             308   B ├─ dream_cycle
+             446   B ├─ lipsum
+              83   B ├─ necronomicon.txt
+             161   B ├─ nemesis.txt
+             100   B ├─ nylarlathotep.txt
+             143   B └─ the_yellow_king"
+        )
+    );
+}
+
+#[test]
+fn unit_scale() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--unit",
+            "MiB",
+            "--scale",
+            "4",
+            "--size-left",
+            "--sort",
+            "name",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            0.0012 MiB data
+            0.0003 MiB ├─ dream_cycle
+            0.0004 MiB ├─ lipsum
+            0.0001 MiB ├─ necronomicon.txt
+            0.0002 MiB ├─ nemesis.txt
+            0.0001 MiB ├─ nylarlathotep.txt
+            0.0001 MiB └─ the_yellow_king"
+        )
+    );
+}
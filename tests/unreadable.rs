@@ -0,0 +1,41 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::{fs, os::unix::fs::PermissionsExt};
+    use tempfile::TempDir;
+
+    #[test]
+    #[ignore = "permissions don't apply to the superuser; run with --ignored as any other user"]
+    fn unreadable() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("vault");
+        let locked = root.join("locked");
+
+        fs::create_dir_all(&locked)?;
+        fs::write(locked.join("secret"), "a".repeat(100))?;
+        fs::write(root.join("open"), "a".repeat(100))?;
+
+        // Without the execute bit the contents of the directory can be listed but not stat'ed.
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o644))?;
+
+        let output = super::utils::run_cmd(&["--sort", "name", &root.to_string_lossy()]);
+
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755))?;
+
+        assert_eq!(
+            output,
+            indoc!(
+                "
+                vault (100 B)
+                ├─ locked
+                │  └─ secret [⚠ permission denied]
+                └─ open (100 B)"
+            ),
+            "Failed to print the entry whose metadata couldn't be read in place"
+        );
+
+        Ok(())
+    }
+}
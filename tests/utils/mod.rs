@@ -2,10 +2,55 @@ use std::process::Command;
 use std::process::Stdio;
 use strip_ansi_escapes::strip as strip_ansi_escapes;
 
+#[allow(dead_code)]
 pub fn run_cmd(args: &[&str]) -> String {
+    String::from_utf8(strip_ansi_escapes(stdout_of(args)).unwrap())
+        .unwrap()
+        .trim()
+        .to_string()
+}
+
+/// Like [run_cmd] but leaves control characters such as tabs intact; meant for uncolored output.
+#[allow(dead_code)]
+pub fn run_cmd_raw(args: &[&str]) -> String {
+    String::from_utf8(stdout_of(args))
+        .unwrap()
+        .trim()
+        .to_string()
+}
+
+/// Like [run_cmd] but reads `config` as the configuration file rather than passing `--no-config`.
+#[allow(dead_code)]
+pub fn run_cmd_with_config(config: &str, args: &[&str]) -> String {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let config_path = tmp.path().join(".erdtreerc");
+
+    std::fs::write(&config_path, config).unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "--threads", "1"])
+        .args(args)
+        .env("ERDTREE_CONFIG_PATH", &config_path)
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    String::from_utf8(strip_ansi_escapes(output.stdout).unwrap())
+        .unwrap()
+        .trim()
+        .to_string()
+}
+
+fn stdout_of(args: &[&str]) -> Vec<u8> {
     let mut cmd = Command::new("cargo");
     cmd.args(["run", "--", "--threads", "1", "--no-config"]);
 
+    // Messages are in the language of the environment unless --lang is provided.
+    cmd.env("LC_ALL", "C");
+
     for arg in args {
         cmd.arg(arg);
     }
@@ -21,8 +66,5 @@ pub fn run_cmd(args: &[&str]) -> String {
 
     assert!(output.status.success());
 
-    String::from_utf8(strip_ansi_escapes(output.stdout).unwrap())
-        .unwrap()
-        .trim()
-        .to_string()
+    output.stdout
 }
@@ -0,0 +1,159 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn width_truncates_names() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--width", "24", "tests/data"]),
+        indoc!(
+            "
+            data (1.21 KiB)
+            ├─ dream_cycle (308 B)
+            │  └─ polaris.t… (308 B)
+            ├─ lipsum (446 B)
+            │  └─ lipsum.txt (446 B)
+            ├─ necronomicon.… (83 B)
+            ├─ nemesis.txt (161 B)
+            ├─ nylarlathote… (100 B)
+            └─ the_yellow_k… (143 B)
+               └─ cassildas… (143 B)"
+        )
+    )
+}
+
+#[test]
+fn width_drops_size() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--width", "14", "tests/data"]),
+        indoc!(
+            "
+            data
+            ├─ dream_cycle
+            │  └─ polaris…
+            ├─ lipsum
+            │  └─ lipsum.…
+            ├─ necronomic…
+            ├─ nemesis.txt
+            ├─ nylarlatho…
+            └─ the_yellow…
+               └─ cassild…"
+        )
+    )
+}
+
+#[test]
+fn width_size_left() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--size-left",
+            "--width",
+            "28",
+            "tests/data"
+        ]),
+        "1.21 KiB data
+   308   B ├─ dream_cycle
+   308   B │  └─ polaris.txt
+   446   B ├─ lipsum
+   446   B │  └─ lipsum.txt
+    83   B ├─ necronomicon.…
+   161   B ├─ nemesis.txt
+   100   B ├─ nylarlathotep…
+   143   B └─ the_yellow_ki…
+   143   B    └─ cassildas_…",
+    )
+}
+
+#[test]
+fn width_leaves_no_room_for_names() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--width",
+            "3",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        concat!(
+            "da…\n",
+            "├─ …\n",
+            "├─ …\n",
+            "├─ …\n",
+            "├─ …\n",
+            "├─ …\n",
+            "└─ …"
+        ),
+        "Names without any room left should still be marked as truncated"
+    )
+}
+
+#[test]
+fn width_counts_annotations() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--accessible",
+            "--width",
+            "20",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "
+            data [dir]
+               dream_cycle [dir]
+               lipsum [dir]
+               necronomi… (83 B)
+               nemesis.… (161 B)
+               nylarlat… (100 B)
+               the_yellow… [dir]"
+        )
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn width_truncates_link_targets() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::symlink;
+
+    let tmp = tempfile::TempDir::new()?;
+    let root = tmp.path().join("links");
+    std::fs::create_dir(&root)?;
+
+    symlink(
+        std::path::Path::new("tests/data/nemesis.txt").canonicalize()?,
+        root.join("a_long_symlink_name"),
+    )?;
+    symlink("nowhere", root.join("link"))?;
+
+    let root = root.to_string_lossy();
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--width", "14", &root]),
+        indoc!(
+            "
+            links
+            ├─ a_long_…
+            └─ link → now…"
+        ),
+        "Link targets should be truncated or dropped to fit within the width"
+    );
+
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--width", "20", &root]),
+        indoc!(
+            "
+            links
+            ├─ a_long_… → nemes…
+            └─ link → nowhere"
+        )
+    );
+
+    Ok(())
+}